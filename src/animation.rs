@@ -4,16 +4,59 @@
 //! the 'Animation' clip and the 'AnimationController', which advances 
 //! frames based on game time and handles looping/clamping logic.
 
+use crate::config::{AnimationConfig, AnimationEventConfig};
+use crate::ecs::resources::AnimationClock;
+use rand::Rng;
 use sdl3::rect::Rect;
 use std::collections::HashMap;
 
 /// A sequence of frames mapped to a single texture.
+///
+/// `frames` are always stored in sprite-sheet order; `reverse` is applied at
+/// playback time so frame indices (e.g. footstep foot-plants) keep referring
+/// to the same sheet cell.
 #[derive(Clone)]
 pub struct Animation {
     pub texture_name: String,
     pub frames: Vec<Rect>,
     pub frame_duration: u32,
     pub loops: bool,
+    /// Plays the frames last-to-first.
+    pub reverse: bool,
+    /// Events keyed by sheet frame index.
+    pub events: Vec<AnimationEventConfig>,
+}
+
+impl Animation {
+    /// Builds a clip from `[animation.*]`: resolved atlas frames if present, otherwise a horizontal grid strip.
+    pub fn from_config(config: &AnimationConfig) -> Self {
+        let padding = config.frame_padding.unwrap_or(0);
+        let frames = if !config.atlas_frames.is_empty() {
+            config.atlas_frames.clone()
+        } else {
+            (0..config.frame_count)
+                .map(|i| Rect::new(config.start_x + (i * (config.frame_width + padding)) as i32, config.start_y, config.frame_width, config.frame_height))
+                .collect()
+        };
+        Self {
+            texture_name: config.texture.clone(),
+            frames,
+            frame_duration: config.frame_duration,
+            loops: config.loops,
+            reverse: config.reverse.unwrap_or(false),
+            events: config.events.clone(),
+        }
+    }
+
+    /// Maps a playback step to the index of the sheet frame shown at that step.
+    fn sheet_index(&self, step: usize) -> usize {
+        if self.reverse { self.frames.len().saturating_sub(1 + step) } else { step }
+    }
+
+    /// Names of the events attached to sheet frame `frame`.
+    fn events_on(&self, frame: usize) -> impl Iterator<Item = String> + '_ {
+        self.events.iter().filter(move |e| e.frame == frame).map(|e| e.name.clone())
+    }
 }
 
 /// A stateful controller that tracks the temporal position of an animation.
@@ -21,8 +64,17 @@ pub struct Animation {
 pub struct AnimationController {
     animations: HashMap<String, Animation>,
     current_animation: Option<String>,
+    /// Playback step; index into `frames` only when not reversed.
     current_frame_index: usize,
     frame_timer: f32,
+    /// Sheet frame index reached during the most recent `update`/`set_animation`, if the frame changed.
+    entered_frame: Option<usize>,
+    /// Events fired since the last [Self::take_events].
+    fired_events: Vec<String>,
+    /// Follows the shared [AnimationClock] instead of `frame_timer`.
+    synced: bool,
+    /// Clock step last shown while synced; `None` until the clip has snapped to the clock.
+    clock_step: Option<usize>,
 }
 
 impl AnimationController {
@@ -35,7 +87,7 @@ impl AnimationController {
     pub fn current_frame_rect(&self) -> Option<&Rect> {
         self.current_animation.as_ref()
             .and_then(|name| self.animations.get(name))
-            .and_then(|anim| anim.frames.get(self.current_frame_index))
+            .and_then(|anim| anim.frames.get(anim.sheet_index(self.current_frame_index)))
     }
 
     pub fn current_texture_name(&self) -> Option<&str> {
@@ -48,24 +100,105 @@ impl AnimationController {
 
     pub fn has_animation(&self, name: &str) -> bool { self.animations.contains_key(name) }
 
+    /// The sheet frame that became current on the last advance, used for frame-timed effects like footsteps.
+    pub fn entered_frame(&self) -> Option<usize> { self.entered_frame }
+
+    /// Makes playback follow the shared clock so every synced controller shows the same frame.
+    pub fn set_synced(&mut self, synced: bool) {
+        self.synced = synced;
+        self.clock_step = None;
+    }
+
+    pub fn is_synced(&self) -> bool { self.synced }
+
+    /// Moves the active clip to a random step and sub-frame time, e.g. so coins don't spin in unison.
+    pub fn randomize_phase(&mut self, rng: &mut impl Rng) {
+        let Some(animation) = self.current_animation.as_ref().and_then(|name| self.animations.get(name)) else { return };
+        if animation.frames.is_empty() { return; }
+        self.current_frame_index = rng.random_range(0..animation.frames.len());
+        self.frame_timer = rng.random_range(0.0..1.0) * animation.frame_duration as f32 / 60.0;
+        let frame = animation.sheet_index(self.current_frame_index);
+        self.entered_frame = Some(frame);
+        // The start frame replaces frame 0, including its events.
+        self.fired_events.clear();
+        self.fired_events.extend(animation.events_on(frame));
+    }
+
+    /// Drains the frame events fired since the previous call, in the order they fired.
+    pub fn take_events(&mut self) -> Vec<String> { std::mem::take(&mut self.fired_events) }
+
+    /// Rebuilds every known clip from `configs` (hot reload), keeping the active clip and a valid frame.
+    pub fn reload_clips(&mut self, configs: &HashMap<String, AnimationConfig>) {
+        for (name, clip) in self.animations.iter_mut() {
+            if let Some(config) = configs.get(name) { *clip = Animation::from_config(config); }
+        }
+        if let Some(len) = self.current_animation.as_ref().and_then(|name| self.animations.get(name)).map(|a| a.frames.len()) {
+            self.current_frame_index = self.current_frame_index.min(len.saturating_sub(1));
+        }
+    }
+
+    /// Advances playback by one tick: synced controllers follow `clock`, others their own timer.
+    pub fn advance(&mut self, delta_time: f32, clock: &AnimationClock) {
+        if self.synced { self.follow_clock(clock.time); } else { self.update(delta_time); }
+    }
+
+    /// Shows the frame the shared clock is on, entering every frame passed since the last tick.
+    fn follow_clock(&mut self, time: f32) {
+        self.entered_frame = None;
+        let Some(animation) = self.current_animation.as_ref().and_then(|name| self.animations.get(name)) else { return };
+        let duration_seconds = animation.frame_duration as f32 / 60.0;
+        let len = animation.frames.len();
+        if len == 0 || duration_seconds <= 0.0 { return; }
+
+        let step = (time / duration_seconds) as usize;
+        let to_index = |s: usize| if animation.loops { s % len } else { s.min(len - 1) };
+        self.frame_timer = time - step as f32 * duration_seconds;
+
+        // 1. A freshly started clip snaps to the clock without replaying the frames it skipped.
+        let Some(last) = self.clock_step.filter(|last| *last <= step) else {
+            self.clock_step = Some(step);
+            self.current_frame_index = to_index(step);
+            return;
+        };
+
+        // 2. Enter each step since the last tick so a long tick never skips a frame's events.
+        for s in last + 1..=step {
+            let index = to_index(s);
+            // A finished one-shot clip holds its last frame.
+            if !animation.loops && index == self.current_frame_index { continue; }
+            self.current_frame_index = index;
+            let frame = animation.sheet_index(index);
+            self.entered_frame = Some(frame);
+            self.fired_events.extend(animation.events_on(frame));
+        }
+        self.clock_step = Some(step);
+    }
+
     /// Advances the internal timers and frame indices for the active clip.
+    ///
+    /// Every frame boundary crossed is processed, so a long tick never skips a frame's events.
     pub fn update(&mut self, delta_time: f32) {
-        if let Some(current_anim_name) = &self.current_animation
-            && let Some(animation) = self.animations.get(current_anim_name) {
-                
-                self.frame_timer += delta_time;
-                let duration_seconds = animation.frame_duration as f32 / 60.0;
-                
-                if self.frame_timer >= duration_seconds {
-                    self.frame_timer -= duration_seconds; 
-                    self.current_frame_index += 1;
-                    
-                    if self.current_frame_index >= animation.frames.len() {
-                        if animation.loops { self.current_frame_index = 0; } 
-                        else { self.current_frame_index = animation.frames.len() - 1; }
-                    }
-                }
+        self.entered_frame = None;
+        let Some(animation) = self.current_animation.as_ref().and_then(|name| self.animations.get(name)) else { return };
+
+        self.frame_timer += delta_time;
+        let duration_seconds = animation.frame_duration as f32 / 60.0;
+
+        while self.frame_timer >= duration_seconds {
+            self.frame_timer -= duration_seconds;
+            self.current_frame_index += 1;
+
+            if self.current_frame_index >= animation.frames.len() {
+                if animation.loops { self.current_frame_index = 0; }
+                else { self.current_frame_index = animation.frames.len().saturating_sub(1); return; }
             }
+            let frame = animation.sheet_index(self.current_frame_index);
+            self.entered_frame = Some(frame);
+            self.fired_events.extend(animation.events_on(frame));
+
+            // A zero-length frame advances once per tick instead of spinning.
+            if duration_seconds <= 0.0 { break; }
+        }
     }
 
     /// Sets the currently active animation, resetting the playback position.
@@ -75,6 +208,136 @@ impl AnimationController {
                 self.current_animation = Some(name.to_string());
                 self.current_frame_index = 0;
                 self.frame_timer = 0.0;
+                self.clock_step = None;
+                if let Some(animation) = self.animations.get(name) {
+                    let frame = animation.sheet_index(0);
+                    self.entered_frame = Some(frame);
+                    self.fired_events.extend(animation.events_on(frame));
+                }
             }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entered_frame_reports_each_new_frame_once() {
+        let mut controller = AnimationController::new();
+        let frames = vec![Rect::new(0, 0, 1, 1); 3];
+        controller.add_animation("walk".to_string(), Animation { texture_name: "t".to_string(), frames, frame_duration: 6, loops: true, reverse: false, events: Vec::new() });
+        controller.set_animation("walk");
+        assert_eq!(controller.entered_frame(), Some(0));
+
+        controller.update(0.06);
+        assert_eq!(controller.entered_frame(), None, "Still on frame 0");
+        controller.update(0.06);
+        assert_eq!(controller.entered_frame(), Some(1));
+        controller.update(0.1);
+        assert_eq!(controller.entered_frame(), Some(2));
+        controller.update(0.1);
+        assert_eq!(controller.entered_frame(), Some(0), "Looping re-enters the first frame");
+    }
+
+    #[test]
+    fn test_reversed_playback_reports_sheet_frames() {
+        let mut controller = AnimationController::new();
+        let frames: Vec<Rect> = (0..3).map(|i| Rect::new(i * 10, 0, 10, 10)).collect();
+        controller.add_animation("walk".to_string(), Animation { texture_name: "t".to_string(), frames, frame_duration: 6, loops: true, reverse: true, events: Vec::new() });
+        controller.set_animation("walk");
+        assert_eq!(controller.entered_frame(), Some(2), "Reversed playback starts on the last sheet frame");
+        assert_eq!(controller.current_frame_rect().map(|r| r.x()), Some(20));
+
+        controller.update(0.12);
+        assert_eq!(controller.entered_frame(), Some(1));
+        assert_eq!(controller.current_frame_rect().map(|r| r.x()), Some(10), "Events and drawn frame agree");
+        controller.update(0.1);
+        assert_eq!(controller.entered_frame(), Some(0));
+        controller.update(0.1);
+        assert_eq!(controller.entered_frame(), Some(2), "Looping wraps back to the last sheet frame");
+    }
+
+    fn clip_with_events(frame_duration: u32, loops: bool) -> Animation {
+        let events = vec![
+            AnimationEventConfig { frame: 0, name: "start".to_string() },
+            AnimationEventConfig { frame: 2, name: "step".to_string() },
+        ];
+        Animation { texture_name: "t".to_string(), frames: vec![Rect::new(0, 0, 1, 1); 4], frame_duration, loops, reverse: false, events }
+    }
+
+    #[test]
+    fn test_looping_events_fire_every_loop() {
+        let mut controller = AnimationController::new();
+        controller.add_animation("walk".to_string(), clip_with_events(6, true));
+        controller.set_animation("walk");
+        assert_eq!(controller.take_events(), vec!["start"]);
+
+        let mut fired = Vec::new();
+        for _ in 0..8 { controller.update(0.1); fired.extend(controller.take_events()); }
+        assert_eq!(fired, vec!["step", "start", "step", "start"], "Two full loops");
+    }
+
+    #[test]
+    fn test_reversed_events_fire_on_their_sheet_frames() {
+        let mut controller = AnimationController::new();
+        controller.add_animation("walk".to_string(), Animation { reverse: true, ..clip_with_events(6, true) });
+        controller.set_animation("walk");
+        assert!(controller.take_events().is_empty(), "Reversed playback starts on sheet frame 3, which has no event");
+
+        // Sheet frames 2, 1, 0, 3, 2, 1, 0.
+        let fired: Vec<Vec<String>> = (0..7).map(|_| { controller.update(0.1); controller.take_events() }).collect();
+        let expected: [&[&str]; 7] = [&["step"], &[], &["start"], &[], &["step"], &[], &["start"]];
+        assert_eq!(fired, expected);
+
+        let mut attack = AnimationController::new();
+        attack.add_animation("attack".to_string(), Animation { reverse: true, ..clip_with_events(6, false) });
+        attack.set_animation("attack");
+        let fired: Vec<String> = (0..20).flat_map(|_| { attack.update(0.1); attack.take_events() }).collect();
+        assert_eq!(fired, vec!["step", "start"], "A reversed one-shot ends on sheet frame 0 and fires it once");
+    }
+
+    #[test]
+    fn test_one_shot_events_fire_once() {
+        let mut controller = AnimationController::new();
+        controller.add_animation("attack".to_string(), clip_with_events(6, false));
+        controller.set_animation("attack");
+        controller.take_events();
+
+        let mut fired = Vec::new();
+        for _ in 0..20 { controller.update(0.1); fired.extend(controller.take_events()); }
+        assert_eq!(fired, vec!["step"]);
+    }
+
+    #[test]
+    fn test_lag_does_not_skip_events() {
+        let mut controller = AnimationController::new();
+        controller.add_animation("walk".to_string(), clip_with_events(1, true));
+        controller.set_animation("walk");
+        controller.take_events();
+
+        // One 5-frame stall crosses frame 2 and wraps back to frame 0.
+        controller.update(5.0 / 60.0 + 0.001);
+        assert_eq!(controller.take_events(), vec!["step", "start"]);
+        assert_eq!(controller.entered_frame(), Some(1));
+    }
+
+    #[test]
+    fn test_reload_clips_keeps_playback_in_range() {
+        let mut configs = crate::config::GameConfig::default().animation;
+        let mut controller = AnimationController::new();
+        controller.add_animation("walk_right".to_string(), Animation::from_config(&configs["walk_right"]));
+        controller.set_animation("walk_right");
+        for _ in 0..200 { controller.update(1.0 / 60.0); }
+
+        let walk = configs.get_mut("walk_right").unwrap();
+        walk.frame_count = 2;
+        walk.frame_duration = 1;
+        controller.reload_clips(&configs);
+
+        assert_eq!(controller.current_animation_name(), Some("walk_right"));
+        assert!(controller.current_frame_rect().is_some(), "Frame index is clamped to the shorter clip");
+        controller.update(1.0 / 60.0);
+        assert!(controller.entered_frame().is_some(), "The new frame duration applies immediately");
+    }
+}
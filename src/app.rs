@@ -5,21 +5,48 @@
 //! of the high-level application loop, and the temporal decoupling of 
 //! deterministic logic (120Hz) from variable-rate rendering.
 
-use crate::config::{Config, GameConfig, load_config, load_game_config};
-use crate::texture_manager::TextureManager;
+use crate::config::{Config, GameConfig, UserSettings, USER_SETTINGS_PATH, load_config, load_game_config, parse_game_config, prefabs_changed};
+use crate::texture_manager::{TextureManager, TextureScope};
 use crate::input::{InputHandler, InputState};
 use crate::audio::GameAudioManager;
 use crate::game_state_manager::GameStateManager;
 use crate::ecs::world::Entity;
 use crate::renderer::Renderer;
-use crate::ecs::resources::GameState;
+use crate::frame_capture::{FrameCapture, CAPTURE_DIR};
+use crate::bench::{BenchSummary, BENCH_LEVEL, BENCH_TICKS};
 use sdl3::EventPump;
 use sdl3::Sdl;
 
+/// Location of the game data file, re-read on `DebugAction::ReloadConfig`.
+const GAME_CONFIG_PATH: &str = "assets/game_config.toml";
+
+/// Command-line options of the engine binary.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct LaunchOptions {
+    /// Run the benchmark scene (see [crate::bench]) instead of the game, then exit.
+    pub bench: bool,
+}
+
+impl LaunchOptions {
+    /// Parses the program arguments (without the program name).
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut options = Self::default();
+        for arg in args {
+            match arg.as_str() {
+                "--bench" => options.bench = true,
+                other => return Err(format!("Unknown argument '{}' (supported: --bench)", other)),
+            }
+        }
+        Ok(options)
+    }
+}
+
 /// The root application controller holding the persistent hardware and engine state.
 pub struct App {
     config: Config,
     _game_config: GameConfig,
+    /// Raw TOML of the active game config, used to detect prefab edits on reload.
+    game_config_source: String,
     _virtual_width: u32,
     renderer: Renderer,
     event_pump: EventPump,
@@ -30,20 +57,34 @@ pub struct App {
     frame_count: u64,
     _sdl_context: Sdl,
     _virtual_height: u32,
-    show_debug_info: bool,
     fps: u32,
     last_frame_time: std::time::Instant,
     fps_last_update: std::time::Instant,
     frame_count_for_fps: u32,
     game_state_manager: GameStateManager,
+    /// Screenshot and GIF capture, encoded off the render thread.
+    frame_capture: FrameCapture,
+    /// Whether the OS cursor is currently shown (only in menus).
+    cursor_visible: bool,
 }
 
 impl App {
     /// Performs physical hardware initialization and pre-loads global assets.
-    pub fn new(sdl_context: Sdl) -> Result<App, String> {
+    pub fn new(sdl_context: Sdl, options: LaunchOptions) -> Result<App, String> {
         // 1. Load engine and game-specific configurations from the project root.
-        let config = load_config().map_err(|e| e.to_string())?;
-        let game_config = load_game_config("assets/game_config.toml").map_err(|e| e.to_string())?;
+        let mut config = load_config().map_err(|e| e.to_string())?;
+        // Options saved from the menu take precedence over the shipped defaults.
+        UserSettings::load(USER_SETTINGS_PATH).apply(&mut config);
+        // A benchmark measures the frame itself, never the wait for the display.
+        if options.bench { config.window.vsync = false; }
+        let mut game_config = load_game_config(GAME_CONFIG_PATH).map_err(|e| e.to_string())?;
+        let game_config_source = std::fs::read_to_string(GAME_CONFIG_PATH).map_err(|e| e.to_string())?;
+        // A typo in a reference would otherwise only show up as an invisible entity or a silent sound.
+        let problems = game_config.validate();
+        if !problems.is_empty() {
+            for problem in &problems { eprintln!("[Config] {}", problem); }
+            return Err(format!("{} has {} problem(s); see the messages above", GAME_CONFIG_PATH, problems.len()));
+        }
 
         let video_subsystem = sdl_context.video().map_err(|e| e.to_string())?;
         let mouse = sdl_context.mouse();
@@ -62,126 +103,325 @@ impl App {
         let texture_creator = canvas.texture_creator();
         let mut texture_manager = TextureManager::new();
 
-        // 4. Perform bulk loading of visual assets defined in the TOML registry.
+        // 4. Register the visual assets defined in the TOML registry; each is decoded on first draw.
         for anim_config in game_config.animation.values() {
-            texture_manager.load(&anim_config.texture, &anim_config.texture, &texture_creator)?;
+            if let Some(atlas) = &anim_config.atlas { texture_manager.load_atlas(atlas)?; }
+            texture_manager.register(&anim_config.texture, &anim_config.texture, TextureScope::Global);
         }
         for (name, path) in &game_config.textures {
-            texture_manager.load(path, name, &texture_creator)?;
+            texture_manager.register(name, path, TextureScope::Global);
         }
+        for layer in &game_config.parallax.layers {
+            // Parallax layers may also reference an image path directly instead of a texture key.
+            if !game_config.textures.contains_key(&layer.texture) {
+                texture_manager.register(&layer.texture, &layer.texture, TextureScope::Global);
+            }
+        }
+        game_config.resolve_atlas_frames(&texture_manager)?;
 
         // 5. Initialize high-level managers (State, Audio, Input).
         let game_state_manager = GameStateManager::new(&config, &game_config, &mut texture_manager, &texture_creator)?;
-        let renderer = Renderer::new(canvas)?;
+        // The virtual resolution defines the viewport; the window size only picks the scale and the bars around it.
+        let mut renderer = Renderer::new(canvas, &config.window)?;
+        let view = renderer.view;
+        println!("[Renderer] Virtual {}x{} at {}x, canvas {}x{} offset {:?}", config.window.virtual_width, config.window.virtual_height, view.scale, view.canvas_size.0, view.canvas_size.1, view.offset);
+        if config.window.crt_post_process {
+            renderer.enable_post_process(config.window.virtual_width, config.window.virtual_height, config.window.crt_scanline_strength)?;
+        }
         let event_pump = sdl_context.event_pump().map_err(|e| e.to_string())?;
-        let audio_manager = GameAudioManager::new(&game_config)?;
+        let audio_manager = GameAudioManager::new(&game_config, config.audio)?;
 
         // 6. Trigger the starting soundtrack and return the initialized controller.
         let _ = audio_manager.event_sender().send(crate::audio::AudioEvent::PlayMusic("soundtrack_01".to_string(), crate::audio::PlaySoundParams::default()));
         let input_handler = InputHandler::new(config.input.clone());
+        let frame_capture = FrameCapture::new(CAPTURE_DIR, &config.debug);
 
         Ok(Self {
-            config: config.clone(), _game_config: game_config.clone(), renderer, event_pump,
+            config: config.clone(), _game_config: game_config.clone(), game_config_source, renderer, event_pump,
             texture_manager, audio_manager, input_handler, input_state: InputState::default(),
             frame_count: 0, _sdl_context: sdl_context, _virtual_width: config.window.virtual_width,
-            _virtual_height: config.window.virtual_height, show_debug_info: config.debug.show_debug_info,
+            _virtual_height: config.window.virtual_height,
             fps: 0, last_frame_time: std::time::Instant::now(), fps_last_update: std::time::Instant::now(),
-            frame_count_for_fps: 0, game_state_manager,
+            frame_count_for_fps: 0, game_state_manager, frame_capture, cursor_visible: false,
         })
     }
 
+    /// Re-reads `config.toml` and the game config and applies them to the running session.
+    ///
+    /// Physics, gameplay and input values take effect on the next tick; animation clips are
+    /// rebuilt in place. Window and audio settings still require a restart.
+    ///
+    /// # Side Effects
+    /// * Keeps the previous configuration if either file fails to load or parse.
+    /// * Restarts the current level when `[prefabs]` changed.
+    fn reload_config(&mut self) {
+        // 1. Parse everything before touching the live session.
+        let parsed = load_config().and_then(|mut config| {
+            UserSettings::load(USER_SETTINGS_PATH).apply(&mut config);
+            let source = std::fs::read_to_string(GAME_CONFIG_PATH).map_err(|e| e.to_string())?;
+            let game_config = parse_game_config(&source)?;
+            Ok((config, game_config, source))
+        });
+        let (config, mut game_config, source) = match parsed {
+            Ok(parsed) => parsed,
+            Err(e) => { eprintln!("[Config] Reload failed, keeping the previous config: {}", e); return; }
+        };
+        let problems = game_config.validate();
+        if !problems.is_empty() {
+            eprintln!("[Config] Reload failed, keeping the previous config:\n  {}", problems.join("\n  "));
+            return;
+        }
+
+        // 2. Load atlases and register textures introduced by the new animation definitions.
+        let assets = game_config.animation.values().try_for_each(|anim| {
+            if let Some(atlas) = &anim.atlas { self.texture_manager.load_atlas(atlas)?; }
+            self.texture_manager.register(&anim.texture, &anim.texture, TextureScope::Global);
+            Ok::<(), String>(())
+        }).and_then(|_| game_config.resolve_atlas_frames(&self.texture_manager));
+        if let Err(e) = assets {
+            eprintln!("[Config] Reload failed, keeping the previous config: {}", e);
+            return;
+        }
+
+        // 3. Swap in the new values.
+        let respawn = prefabs_changed(&self.game_config_source, &source);
+        self.game_state_manager.apply_reloaded_config(&game_config, respawn);
+        self.input_handler = InputHandler::new(config.input.clone());
+        self.game_state_manager.apply_input_bindings(&config.input);
+        self.frame_capture.apply_config(&config.debug);
+        if config.audio != self.config.audio { self.game_state_manager.apply_audio_settings(config.audio, &self.audio_manager); }
+        self.config = config;
+        self._game_config = game_config;
+        self.game_config_source = source;
+        println!("[Config] Reloaded{}.", if respawn { "; prefabs changed, restarting the level" } else { "" });
+    }
+
+    /// Draws and presents one frame of the current state, interpolated by `alpha` between the
+    /// last two ticks, and closes the frame's telemetry.
+    ///
+    /// ⚠️ **Hotpath**: Called every frame at monitor refresh rate.
+    fn render_frame(&mut self, frame_time: f32, max_delta: f32, alpha: f32) -> Result<(), String> {
+        // 1. Draw the level, entities, particles and GUI back to front.
+        self.game_state_manager.benchmarker.push("Render.Clear");
+        self.renderer.clear(sdl3::pixels::Color::RGB(50, 50, 50));
+        self.game_state_manager.benchmarker.pop();
+
+        self.game_state_manager.benchmarker.push("Render.Level");
+        self.renderer.update_tile_animations(frame_time.min(max_delta));
+        // Shake only displaces what is drawn; culling below uses the steady gameplay camera.
+        let view = self.game_state_manager.camera.render_view();
+        self.renderer.draw_level(&self.game_state_manager.level, &mut self.texture_manager, &view, &self._game_config.parallax)?;
+        self.game_state_manager.benchmarker.pop();
+
+        self.game_state_manager.benchmarker.push("Render.Entities");
+        // Sprites are grouped by texture within each depth so whole runs go out as one batch.
+        self.game_state_manager.benchmarker.push("Render.Entities.Sort");
+        let world = &self.game_state_manager.world;
+        // The grid indexes collision boxes; the margin keeps sprites that overhang their box from popping at the edges.
+        let margin = 2 * crate::ecs::resources::SPATIAL_CELL_SIZE as i32;
+        let view_rect = self.game_state_manager.camera.view_rect();
+        let cull_rect = sdl3::rect::Rect::new(view_rect.x() - margin, view_rect.y() - margin, view_rect.width() + 2 * margin as u32, view_rect.height() + 2 * margin as u32);
+        let mut renderables_sorted: Vec<(u8, &str, Entity)> = Vec::new();
+        for entity in world.spatial_grid.query_near(cull_rect) {
+            if let (Some(renderable), Some(animation)) = (world.renderables.get(&entity), world.animations.get(&entity))
+                && world.positions.contains_key(&entity)
+                && let Some(texture_name) = animation.controller.current_texture_name() { renderables_sorted.push((renderable.z_index, texture_name, entity)); }
+        }
+        crate::renderer::sort_for_batching(&mut renderables_sorted);
+
+        let mut sprites = Vec::with_capacity(renderables_sorted.len());
+        for (_, texture_name, entity) in renderables_sorted {
+            let mut draw_pos = crate::math::Vector2D::default();
+            if let Some(curr_pos) = world.positions.get(&entity) {
+                draw_pos = curr_pos.0;
+                if let Some(prev_pos) = world.previous_positions.get(&entity) {
+                    draw_pos.x = prev_pos.0.x * (1.0 - alpha) + curr_pos.0.x * alpha;
+                    draw_pos.y = prev_pos.0.y * (1.0 - alpha) + curr_pos.0.y * alpha;
+                }
+            }
+            if let (Some(renderable), Some(frame_rect)) = (world.renderables.get(&entity), world.animations.get(&entity).and_then(|a| a.controller.current_frame_rect())) {
+                sprites.push(crate::renderer::SpriteDrawParams { pos: draw_pos, size: (renderable.width, renderable.height), offsets: (renderable.horizontal_offset, renderable.vertical_offset), texture_name, frame_rect, color_mod: (renderable.tint != sdl3::pixels::Color::WHITE).then_some(renderable.tint), alpha: renderable.alpha, rotation: renderable.rotation, flip_horizontal: renderable.flip_horizontal, flip_vertical: renderable.flip_vertical });
+            }
+        }
+        self.game_state_manager.benchmarker.pop();
+
+        self.game_state_manager.benchmarker.push("Render.Entities.Draw");
+        self.renderer.draw_sprites(&sprites, &mut self.texture_manager, &view).unwrap_or_else(|e| eprintln!("Failed to draw sprites: {}", e));
+        self.game_state_manager.benchmarker.pop();
+        self.game_state_manager.benchmarker.pop();
+
+        self.game_state_manager.benchmarker.push("Render.Particles");
+        self.renderer.draw_particles(&self.game_state_manager.world.particles, &view)?;
+        self.game_state_manager.benchmarker.pop();
+
+        self.game_state_manager.benchmarker.push("Render.Foreground");
+        self.renderer.draw_foreground(&self.game_state_manager.level, &mut self.texture_manager, &view, &self._game_config.parallax)?;
+        self.game_state_manager.benchmarker.pop();
+
+        self.game_state_manager.benchmarker.push("Render.GUI");
+        self.game_state_manager.draw(&mut self.renderer, &mut self.texture_manager, &self.config, &self._game_config, &self.input_state, self.frame_count, self.fps, alpha)?;
+        self.game_state_manager.benchmarker.pop();
+
+        // 2. Read back the finished frame for captures, then overlay their status (and the replay REC label) so it never ends up in them.
+        self.game_state_manager.benchmarker.push("Render.Capture");
+        if self.frame_capture.wants_frame(frame_time) {
+            match self.renderer.read_pixels() {
+                Ok(frame) => self.frame_capture.submit(frame),
+                Err(e) => self.frame_capture.report_error(&e),
+            }
+        }
+        self.frame_capture.poll();
+        if let Some(status) = self.frame_capture.status() {
+            let color = if status.is_error { sdl3::pixels::Color::RGB(255, 80, 80) } else { sdl3::pixels::Color::RGB(255, 220, 0) };
+            let (width, height) = self.renderer.output_size();
+            self.renderer.render_text(&self.game_state_manager.font_manager, crate::renderer::TextRenderParams {
+                text: &status.text, x: width as i32 - 20, y: height as i32 - 50, color, align: crate::renderer::TextAlign::Right, ..Default::default()
+            })?;
+        }
+        if let Some(label) = self.game_state_manager.recording_label() {
+            let (width, _) = self.renderer.output_size();
+            self.renderer.render_text(&self.game_state_manager.font_manager, crate::renderer::TextRenderParams {
+                text: &label, x: width as i32 - 20, y: 20, color: sdl3::pixels::Color::RGB(255, 40, 40), align: crate::renderer::TextAlign::Right, ..Default::default()
+            })?;
+        }
+        self.game_state_manager.benchmarker.pop();
+
+        // 3. Physical presentation to the GPU and finalize frame telemetry.
+        self.game_state_manager.benchmarker.push("Wait.VSync");
+        self.renderer.present();
+        self.game_state_manager.benchmarker.pop(); 
+        self.game_state_manager.benchmarker.end_frame(); 
+        self.frame_count += 1;
+        Ok(())
+    }
+
     /// Executes the persistent run loop using a Fixed Timestep Accumulator.
     ///
     /// ⚠️ **Hotpath**: The main loop runs continuously. The `update` phase runs at 120Hz, 
     /// and the `render` phase runs at the monitor's refresh rate.
     pub fn run(&mut self) -> Result<(), String> {
-        const FIXED_TIMESTEP: f32 = 1.0 / 120.0;
-        let mut accumulator: f32 = 0.0;
+        let mut timestep = crate::timestep::FixedTimestep::new(crate::timestep::FIXED_TIMESTEP, self.config.physics.max_delta_time);
 
         'running: loop {
             // 1. Calculate temporal delta and update frame-rate statistics.
             let now = std::time::Instant::now();
-            let mut frame_time = now.duration_since(self.last_frame_time).as_secs_f32();
+            let frame_time = now.duration_since(self.last_frame_time).as_secs_f32();
             self.last_frame_time = now;
-            if frame_time > 0.25 { frame_time = 0.25; }
-            accumulator += frame_time;
+            let steps = timestep.advance(frame_time);
 
             self.frame_count_for_fps += 1;
             if now.duration_since(self.fps_last_update).as_secs() >= 1 {
                 self.fps = self.frame_count_for_fps;
                 self.frame_count_for_fps = 0;
                 self.fps_last_update = now;
-                if self.game_state_manager.world.game_state == GameState::Playing { self.game_state_manager.benchmarker.update_fps(self.fps); }
+                self.game_state_manager.benchmarker.update_fps(self.fps);
             }
 
             // 2. Resolve hardware input and process debug/replay toggle facts.
             self.game_state_manager.benchmarker.push("Input");
-            if !self.input_handler.process_events(&mut self.event_pump, &mut self.input_state) { break 'running; }
-            if self.input_state.is_debug_action_just_pressed(crate::input::DebugAction::ToggleDebugInfo) { self.show_debug_info = !self.show_debug_info; }
+            self.input_handler.set_capturing(self.game_state_manager.is_capturing_key());
+            if !self.input_handler.process_events(&mut self.event_pump, &mut self.input_state, self.renderer.pointer_scale(), &self.renderer.view) { break 'running; }
+            if self.input_state.window_resized() { self.renderer.update_view(); }
+            if self.input_state.is_debug_action_just_pressed(crate::input::DebugAction::ExportBenchmarks) {
+                match self.game_state_manager.export_benchmarks() {
+                    Ok(path) => println!("[Benchmarker] Exported to {}", path),
+                    Err(e) => eprintln!("[Benchmarker] Export failed: {}", e),
+                }
+            }
+            if self.input_state.is_debug_action_just_pressed(crate::input::DebugAction::ReloadConfig) {
+                self.reload_config();
+                timestep.max_delta = self.config.physics.max_delta_time.max(timestep.step);
+            }
+            if self.input_state.is_debug_action_just_pressed(crate::input::DebugAction::Screenshot) { self.frame_capture.request_screenshot(); }
+            if self.input_state.is_debug_action_just_pressed(crate::input::DebugAction::ToggleGifCapture) { self.frame_capture.toggle_gif(); }
+            if self.input_state.is_debug_action_just_pressed(crate::input::DebugAction::ToggleRecording) {
+                let texture_creator = self.renderer.canvas.texture_creator();
+                if let Err(e) = self.game_state_manager.toggle_recording(&self.config, &self._game_config, &mut self.texture_manager, &texture_creator) {
+                    eprintln!("[Replay] {}", e);
+                }
+            }
+            let seek_ticks = self.config.debug.replay_seek_ticks;
+            let replay_tick = self.game_state_manager.replay_tick;
+            let seek_target = if self.input_state.is_debug_action_just_pressed(crate::input::DebugAction::ReplaySeekBack) {
+                Some(replay_tick.saturating_sub(seek_ticks))
+            } else if self.input_state.is_debug_action_just_pressed(crate::input::DebugAction::ReplaySeekForward) {
+                Some(replay_tick + seek_ticks)
+            } else { None };
+            if let Some(target) = seek_target {
+                let texture_creator = self.renderer.canvas.texture_creator();
+                if let Err(e) = self.game_state_manager.seek_replay(target, &self.config, &self._game_config, &mut self.texture_manager, &texture_creator) {
+                    eprintln!("[Replay] Seek failed: {}", e);
+                }
+            }
 
             // 3. Process high-level menu navigation once per frame.
-            if let Err(e) = self.game_state_manager.handle_input(&self.input_state, &mut self.audio_manager, &self.config, &self._game_config) {
+            if let Err(e) = self.game_state_manager.handle_input(&self.input_state, &mut self.audio_manager, &self.config, &self._game_config, frame_time.min(timestep.max_delta)) {
                 if e == "QUIT" { break 'running; } else { return Err(e); }
             }
+            if let Some(input) = self.game_state_manager.take_rebound_input() {
+                self.input_handler = InputHandler::new(input.clone());
+                self.config.input = input;
+            }
+            // The cursor is only useful (and only shown) while a mouse-enabled menu is up.
+            let wants_cursor = self._game_config.menu.mouse && matches!(self.game_state_manager.world.game_state, crate::ecs::resources::GameState::Menu(_));
+            if wants_cursor != self.cursor_visible {
+                self._sdl_context.mouse().show_cursor(wants_cursor);
+                self.cursor_visible = wants_cursor;
+            }
             self.game_state_manager.benchmarker.pop();
 
+            self.game_state_manager.pan_free_camera(&self.input_state, &self.config, frame_time.min(timestep.max_delta));
+
             // 4. Advance deterministic simulation steps (Logic/Physics) at a strict 120Hz.
+            // The debug time controls may hold ticks back (pause/frame-step) or shrink each tick's delta;
+            // the FPS counter and audio run on wall-clock time either way. No frame runs more ticks than the
+            // step cap, so a stall can never snowball into an ever longer catch-up frame.
             self.game_state_manager.benchmarker.push("Update");
             let texture_creator = self.renderer.canvas.texture_creator();
-            while accumulator >= FIXED_TIMESTEP {
-                self.game_state_manager.update(FIXED_TIMESTEP, &self.config, &self._game_config, &self.input_state, &mut self.audio_manager, &mut self.texture_manager, &texture_creator)?;
-                accumulator -= FIXED_TIMESTEP;
+            let ticks = self.game_state_manager.debug_overlays.simulation_ticks(steps).min(timestep.max_steps_per_frame());
+            let delta_time = crate::timestep::FIXED_TIMESTEP * self.game_state_manager.debug_overlays.time_scale;
+            for _ in 0..ticks {
+                self.game_state_manager.update(delta_time, &self.config, &self._game_config, &self.input_state, &mut self.audio_manager, &mut self.texture_manager, &texture_creator)?;
             }
             self.game_state_manager.benchmarker.pop();
 
             // 5. Execute variable-rate rendering pass with alpha interpolation.
-            let alpha = accumulator / FIXED_TIMESTEP;
-            self.game_state_manager.benchmarker.push("Render.Clear");
-            self.renderer.clear(sdl3::pixels::Color::RGB(50, 50, 50));
-            self.game_state_manager.benchmarker.pop();
-
-            self.game_state_manager.benchmarker.push("Render.Level");
-            self.renderer.draw_level(&self.game_state_manager.level, &self.texture_manager, &self.game_state_manager.camera, &self._game_config.parallax)?;
-            self.game_state_manager.benchmarker.pop();
+            // A paused simulation has no next tick to blend towards, so draw the latest one as is.
+            let alpha = if self.game_state_manager.debug_overlays.paused { 1.0 } else { timestep.alpha() };
+            self.render_frame(frame_time, timestep.max_delta, alpha)?;
+        }
+        
+        // 6. Session cleanup and write performance logs to disk.
+        Ok(())
+    }
 
-            self.game_state_manager.benchmarker.push("Render.Entities");
-            let visible_entities = self.game_state_manager.world.spatial_grid.query(self.game_state_manager.camera.view_rect());
-            let mut renderables_sorted: Vec<(u8, Entity)> = Vec::new();
-            for entity in visible_entities {
-                if let Some(renderable) = self.game_state_manager.world.renderables.get(&entity) 
-                    && self.game_state_manager.world.positions.contains_key(&entity) { renderables_sorted.push((renderable.z_index, entity)); }
-            }
-            renderables_sorted.sort_by(|a, b| b.0.cmp(&a.0));
-
-            for (_, entity) in renderables_sorted {
-                let mut draw_pos = crate::math::Vector2D::default();
-                if let Some(curr_pos) = self.game_state_manager.world.positions.get(&entity) {
-                    draw_pos = curr_pos.0;
-                    if let Some(prev_pos) = self.game_state_manager.world.previous_positions.get(&entity) {
-                        draw_pos.x = prev_pos.0.x * (1.0 - alpha) + curr_pos.0.x * alpha;
-                        draw_pos.y = prev_pos.0.y * (1.0 - alpha) + curr_pos.0.y * alpha;
-                    }
-                }
-                if let (Some(renderable), Some(animation)) = (self.game_state_manager.world.renderables.get(&entity), self.game_state_manager.world.animations.get(&entity))
-                    && let (Some(texture_name), Some(frame_rect)) = (animation.controller.current_texture_name(), animation.controller.current_frame_rect()) {
-                        self.renderer.draw_sprite(crate::renderer::SpriteDrawParams { pos: draw_pos, size: (renderable.width, renderable.height), offsets: (renderable.horizontal_offset, renderable.vertical_offset), texture_name, frame_rect, color_mod: None, rotation: renderable.rotation, flip_horizontal: renderable.flip_horizontal, flip_vertical: renderable.flip_vertical }, &mut self.texture_manager, &self.game_state_manager.camera).unwrap_or_else(|e| eprintln!("Failed to draw sprite: {}", e));
-                    }
-            }
-            self.game_state_manager.benchmarker.pop();
+    /// Plays the benchmark scene without any interaction: [BENCH_TICKS] ticks of scripted input on
+    /// [BENCH_LEVEL], then prints the summary to stdout, ending with one JSON line.
+    ///
+    /// Every frame simulates exactly one tick, so the sequence of simulated states does not depend on
+    /// how fast the machine renders; only the measured timings do.
+    pub fn run_benchmark(&mut self) -> Result<(), String> {
+        // 1. Go straight into the stress level, skipping menu and transitions.
+        let texture_creator = self.renderer.canvas.texture_creator();
+        self.game_state_manager.start_benchmark(BENCH_LEVEL, &self.config, &self._game_config, &mut self.texture_manager, &texture_creator)?;
+        println!("[Benchmarker] Running {} ticks on {}", BENCH_TICKS, BENCH_LEVEL);
 
-            self.game_state_manager.benchmarker.push("Render.GUI");
-            self.game_state_manager.draw(&mut self.renderer, &self.texture_manager, &self.config, &self._game_config, &self.input_state, self.frame_count, self.fps, self.show_debug_info, alpha)?;
+        // 2. One scripted tick and one rendered frame per iteration; events are pumped only to keep the window responsive.
+        let mut input = InputState::default();
+        for tick in 0..BENCH_TICKS {
+            self.event_pump.pump_events();
+            input = crate::bench::scripted_input(tick, &input);
+            self.game_state_manager.benchmarker.push("Update");
+            self.game_state_manager.update(crate::timestep::FIXED_TIMESTEP, &self.config, &self._game_config, &input, &mut self.audio_manager, &mut self.texture_manager, &texture_creator)?;
             self.game_state_manager.benchmarker.pop();
-
-            // 6. Physical presentation to the GPU and finalize frame telemetry.
-            self.game_state_manager.benchmarker.push("Wait.VSync");
-            self.renderer.present();
-            self.game_state_manager.benchmarker.pop(); 
-            self.game_state_manager.benchmarker.end_frame(); 
-            self.frame_count += 1;
+            self.render_frame(crate::timestep::FIXED_TIMESTEP, crate::timestep::FIXED_TIMESTEP, 1.0)?;
         }
-        
-        // 7. Session cleanup and write performance logs to disk.
+
+        // 3. Report the level's session: the table for people, the final JSON line for scripts.
+        let benchmarker = &self.game_state_manager.benchmarker;
+        let summary = BenchSummary::from_section(&benchmarker.section(&benchmarker.session_name), BENCH_TICKS);
+        println!("{}", summary.table());
+        println!("{}", summary.to_json_line()?);
         Ok(())
     }
 }
@@ -4,12 +4,14 @@
 //! hardware playback. It encapsulates the Kira audio backend, managing 
 //! pre-loaded SFX, streaming music tracks, and asynchronous beat detection.
 
-use crate::audio_analysis::{BeatDetector, DetectedBeat};
-use crate::config::{GameConfig, SoundtrackConfig};
+use crate::audio_analysis::{Band, BeatDetector, BeatMap, DetectedBeat};
+use crate::config::{AudioSettings, AudioSettingsConfig, GameConfig, SoundtrackConfig};
+use crate::math::Vector2D;
 use kira::{
+    clock::ClockHandle,
     manager::{AudioManager, AudioManagerSettings, backend::DefaultBackend},
     sound::{
-        static_sound::{StaticSoundData, StaticSoundSettings},
+        static_sound::{StaticSoundData, StaticSoundHandle, StaticSoundSettings},
         streaming::{StreamingSoundData, StreamingSoundSettings, StreamingSoundHandle},
         FromFileError,
     },
@@ -19,10 +21,146 @@ use std::path::Path;
 use std::sync::mpsc;
 use std::thread;
 
-#[derive(Clone, Copy, Debug)] pub struct PlaySoundParams { pub volume: f64 }
-impl Default for PlaySoundParams { fn default() -> Self { Self { volume: 1.0 } } }
+/// `position` is the sound's world position in **pixels**; `None` plays it unattenuated and centered (UI sounds).
+/// `max_instances` caps simultaneous copies of this sound; `None` uses `[gameplay.audio] max_sound_instances`.
+#[derive(Clone, Copy, Debug)] pub struct PlaySoundParams { pub volume: f64, pub position: Option<Vector2D>, pub max_instances: Option<usize> }
+impl Default for PlaySoundParams { fn default() -> Self { Self { volume: 1.0, position: None, max_instances: None } } }
 
-pub enum AudioEvent { PlaySound(String, PlaySoundParams), PlayMusic(String, PlaySoundParams), StopMusic, FadeOutMusic(f64) }
+impl PlaySoundParams {
+    /// Full-volume parameters for a sound emitted at `position`.
+    pub fn at(position: Vector2D) -> Self { Self { position: Some(position), ..Default::default() } }
+}
+
+/// Where positional sounds are heard from, refreshed every tick from the primary player and camera.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AudioListener {
+    /// World position in **pixels**.
+    pub position: Vector2D,
+    /// Horizontal distance in **pixels** at which a sound is panned fully to one side (half the view).
+    pub half_width: f32,
+}
+
+/// Gain and stereo panning for a positional sound. `panning` is `0.0` (left) to `1.0` (right).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpatialMix { pub gain: f64, pub panning: f64 }
+
+/// Attenuates a sound at `source` by its distance to the listener: `(1 - d / max) ^ power`.
+///
+/// Returns `None` beyond `max_hearing_distance` so the sound is skipped instead of played inaudibly.
+/// A non-positive `max_hearing_distance` disables attenuation.
+pub fn spatial_mix(source: Vector2D, listener: &AudioListener, falloff: &AudioSettingsConfig) -> Option<SpatialMix> {
+    let (dx, dy) = (source.x - listener.position.x, source.y - listener.position.y);
+    let gain = if falloff.max_hearing_distance > 0.0 {
+        let distance = (dx * dx + dy * dy).sqrt();
+        if distance >= falloff.max_hearing_distance { return None; }
+        (1.0 - distance / falloff.max_hearing_distance).powf(falloff.volume_falloff_power.max(0.0)) as f64
+    } else { 1.0 };
+    let pan = if listener.half_width > 0.0 { (dx / listener.half_width).clamp(-1.0, 1.0) } else { 0.0 };
+    Some(SpatialMix { gain, panning: 0.5 + 0.5 * pan as f64 })
+}
+
+/// # Concept: Voice Pooling
+///
+/// The playing copies ("voices") of one sound effect, oldest first. Limits how many can
+/// overlap and rejects retriggers that arrive within the debounce window, so a burst of
+/// identical events (two coins in one frame) does not stack into clipping.
+pub struct VoicePool<H> {
+    voices: std::collections::VecDeque<H>,
+    /// Time in **seconds** the last voice started.
+    last_started: Option<f64>,
+}
+
+impl<H> Default for VoicePool<H> {
+    fn default() -> Self { Self { voices: std::collections::VecDeque::new(), last_started: None } }
+}
+
+impl<H> VoicePool<H> {
+    /// True when a voice started less than `debounce` **seconds** before `now`.
+    pub fn is_debounced(&self, now: f64, debounce: f64) -> bool {
+        self.last_started.is_some_and(|t| now - t < debounce)
+    }
+
+    /// Forgets finished voices, then removes the oldest ones so that one more fits under `max`.
+    /// Returns the evicted voices so the caller can stop them. A `max` of `0` means unlimited.
+    pub fn make_room(&mut self, max: usize, is_playing: impl Fn(&H) -> bool) -> Vec<H> {
+        self.voices.retain(|v| is_playing(v));
+        if max == 0 { return Vec::new(); }
+        let excess = (self.voices.len() + 1).saturating_sub(max);
+        self.voices.drain(..excess).collect()
+    }
+
+    pub fn push(&mut self, voice: H, now: f64) {
+        self.voices.push_back(voice);
+        self.last_started = Some(now);
+    }
+
+    pub fn len(&self) -> usize { self.voices.len() }
+
+    pub fn is_empty(&self) -> bool { self.voices.is_empty() }
+}
+
+/// Requests sent to [GameAudioManager]. The `Set*Volume` variants take a linear volume in `[0.0, 1.0]`;
+/// master and music changes also apply to the track that is already playing.
+/// `CrossfadeMusic` fades the current track out while the new one fades in over the duration in **seconds**;
+/// it keeps a track that is already playing instead of restarting it.
+/// `SetLayerVolume(name, target, fade)` eases a [MusicLayer] to a linear `target` over `fade` **seconds**.
+pub enum AudioEvent { PlaySound(String, PlaySoundParams), PlayMusic(String, PlaySoundParams), CrossfadeMusic(String, f64), StopMusic, FadeOutMusic(f64), SetMasterVolume(f64), SetMusicVolume(f64), SetSfxVolume(f64), SetLayerVolume(String, f64, f64) }
+
+/// # Concept: Music Layers
+///
+/// A stem (e.g. extra percussion) playing alongside the current soundtrack. Every stem of a
+/// soundtrack starts on the same clock tick as the base track and loops with it, so the
+/// layers never drift apart; gameplay only ever changes their volume.
+pub struct MusicLayer {
+    handle: StreamingSoundHandle<FromFileError>,
+    /// Target volume before the music and master gains.
+    volume: f64,
+}
+
+/// # Concept: Crossfading
+///
+/// The soundtrack being faded out while its replacement fades in. It keeps its own beat maps
+/// so rhythm gameplay can stay on it until the incoming track becomes the louder of the two.
+struct OutgoingMusic {
+    handle: StreamingSoundHandle<FromFileError>,
+    beat_maps: HashMap<Band, Vec<DetectedBeat>>,
+    track: u64,
+    started: std::time::Instant,
+    /// Length of the fade in **seconds**.
+    duration: f64,
+}
+
+/// Fade-out of the replaced soundtrack in **seconds** when a new track is played without a crossfade.
+const MUSIC_CUT_FADE: f64 = 0.5;
+
+/// Fade lengths in **seconds** when a newly loaded soundtrack replaces the current one: `(fade_in, fade_out)`.
+///
+/// A crossfade uses its duration for both (with nothing playing it is just a fade-in); a plain
+/// `PlayMusic` starts at full volume and fades the old track out quickly.
+pub fn music_fades(crossfade: Option<f64>) -> (Option<f64>, f64) {
+    match crossfade {
+        Some(seconds) => (Some(seconds.max(0.0)), seconds.max(0.0)),
+        None => (None, MUSIC_CUT_FADE),
+    }
+}
+
+fn fade_tween(seconds: f64) -> kira::tween::Tween {
+    kira::tween::Tween { duration: std::time::Duration::from_secs_f64(seconds), ..Default::default() }
+}
+
+/// True once the incoming track of a linear crossfade is at least as loud as the outgoing one.
+pub fn is_incoming_dominant(elapsed: f64, duration: f64) -> bool {
+    duration <= 0.0 || elapsed >= duration / 2.0
+}
+
+/// The soundtrack rhythm gameplay should follow this tick.
+pub struct MusicPlayhead<'a> {
+    /// Changes whenever a different track (or a restart of the same one) takes over.
+    pub track: u64,
+    /// Playback position in **seconds**.
+    pub position: f64,
+    pub beat_maps: &'a HashMap<Band, Vec<DetectedBeat>>,
+}
 
 pub struct GameAudioManager {
     manager: AudioManager<DefaultBackend>,
@@ -31,14 +169,37 @@ pub struct GameAudioManager {
     pub event_sender: mpsc::Sender<AudioEvent>,
     loading_receiver: mpsc::Receiver<LoadedMusic>,
     loading_sender: mpsc::Sender<LoadedMusic>,
-    pub current_beat_map: Option<Vec<DetectedBeat>>,
+    /// Onsets of the current soundtrack per frequency band, once the analysis is ready.
+    pub current_beat_maps: HashMap<Band, Vec<DetectedBeat>>,
+    /// Estimated tempo of the current soundtrack in **BPM**, once its beat map is ready.
+    pub current_bpm: Option<f32>,
     pub current_music_handle: Option<StreamingSoundHandle<FromFileError>>,
     pub current_music_name: Option<String>,
+    /// Identifies the track behind `current_music_handle`; bumped every time a track starts.
+    current_track: u64,
+    /// The previous track while a crossfade is in progress.
+    outgoing_music: Option<OutgoingMusic>,
     soundtrack_properties: HashMap<String, SoundtrackConfig>,
+    volumes: AudioSettings,
+    /// Per-track volume requested by the `PlayMusic` event of the current music, before `volumes`.
+    music_volume: f64,
+    /// Distance falloff for positional sounds, from `[gameplay.audio]`.
+    falloff: AudioSettingsConfig,
+    listener: Option<AudioListener>,
+    /// Stems of the current soundtrack, keyed by layer name.
+    music_layers: HashMap<String, MusicLayer>,
+    /// Last requested volume per layer name; applied to stems as they start, silent when absent.
+    layer_targets: HashMap<String, f64>,
+    /// Clock that starts the base track and its stems on the same sample.
+    music_clock: Option<ClockHandle>,
+    /// Playing copies of each sound effect, keyed by sound name.
+    voices: HashMap<String, VoicePool<StaticSoundHandle>>,
+    /// Reference point for the debounce timestamps.
+    started: std::time::Instant,
 }
 
 impl GameAudioManager {
-    pub fn new(game_config: &GameConfig) -> Result<Self, String> {
+    pub fn new(game_config: &GameConfig, volumes: AudioSettings) -> Result<Self, String> {
         let mut settings = AudioManagerSettings::<DefaultBackend>::default();
         settings.capacities.command_capacity = 256;
         let manager = AudioManager::<DefaultBackend>::new(settings).map_err(|e| format!("Failed to create Kira audio manager: {}", e))?;
@@ -52,71 +213,266 @@ impl GameAudioManager {
                 sounds.insert(name.clone(), AudioAsset::Static(Box::new(sound_data)));
             }
         }
-        Ok(Self { manager, sounds, event_receiver, event_sender, loading_receiver, loading_sender, current_beat_map: None, current_music_handle: None, current_music_name: None, soundtrack_properties: game_config.soundtrack_properties.clone() })
+        Ok(Self { manager, sounds, event_receiver, event_sender, loading_receiver, loading_sender, current_beat_maps: HashMap::new(), current_bpm: None, current_music_handle: None, current_music_name: None, current_track: 0, outgoing_music: None, soundtrack_properties: game_config.soundtrack_properties.clone(), volumes, music_volume: 1.0, falloff: game_config.gameplay.audio.clone(), listener: None, music_layers: HashMap::new(), layer_targets: HashMap::new(), music_clock: None, voices: HashMap::new(), started: std::time::Instant::now() })
     }
 
     pub fn event_sender(&self) -> mpsc::Sender<AudioEvent> { self.event_sender.clone() }
 
+    /// Sets where positional sounds are heard from; until set, they play unattenuated.
+    pub fn set_listener(&mut self, listener: AudioListener) { self.listener = Some(listener); }
+
     fn play_sound(&mut self, name: &str, params: PlaySoundParams) -> Result<(), String> {
         if let Some(AudioAsset::Static(sound_data)) = self.sounds.get(name) {
+            let mix = match (params.position, &self.listener) {
+                (Some(position), Some(listener)) => match spatial_mix(position, listener, &self.falloff) {
+                    Some(mix) => mix,
+                    None => return Ok(()),
+                },
+                _ => SpatialMix { gain: 1.0, panning: 0.5 },
+            };
+
+            // Drop retriggers inside the debounce window and make room under the instance limit.
+            let now = self.started.elapsed().as_secs_f64();
+            let pool = self.voices.entry(name.to_string()).or_default();
+            if pool.is_debounced(now, self.falloff.sound_debounce) { return Ok(()); }
+            let max = params.max_instances.unwrap_or(self.falloff.max_sound_instances);
+            for mut oldest in pool.make_room(max, |h| h.state() != kira::sound::PlaybackState::Stopped) {
+                let _ = oldest.stop(kira::tween::Tween { duration: std::time::Duration::from_millis(10), ..Default::default() });
+            }
+
             let mut sound = *sound_data.clone();
-            sound.settings.volume = kira::tween::Value::Fixed(kira::Volume::Amplitude(params.volume));
-            self.manager.play(sound).map_err(|e| e.to_string())?;
+            sound.settings.volume = kira::tween::Value::Fixed(kira::Volume::Amplitude(params.volume * mix.gain * self.volumes.sfx_gain()));
+            sound.settings.panning = kira::tween::Value::Fixed(mix.panning);
+            let handle = self.manager.play(sound).map_err(|e| e.to_string())?;
+            pool.push(handle, now);
             return Ok(());
         }
         Err(format!("Sound '{}' not found", name))
     }
 
+    /// The dominant soundtrack: the outgoing track during the first half of a crossfade, the current one otherwise.
+    pub fn playhead(&self) -> Option<MusicPlayhead<'_>> {
+        if let Some(outgoing) = &self.outgoing_music && !is_incoming_dominant(outgoing.started.elapsed().as_secs_f64(), outgoing.duration) {
+            return Some(MusicPlayhead { track: outgoing.track, position: outgoing.handle.position(), beat_maps: &outgoing.beat_maps });
+        }
+        self.current_music_handle.as_ref().map(|handle| MusicPlayhead { track: self.current_track, position: handle.position(), beat_maps: &self.current_beat_maps })
+    }
+
     fn stop_music(&mut self) {
-        if let Some(mut handle) = self.current_music_handle.take() { let _ = handle.stop(kira::tween::Tween { duration: std::time::Duration::from_millis(100), ..Default::default() }); }
-        self.current_music_name = None; self.current_beat_map = None;
+        self.fade_out_music(0.1);
     }
 
     fn fade_out_music(&mut self, duration_seconds: f64) {
-        if let Some(mut handle) = self.current_music_handle.take() { let _ = handle.stop(kira::tween::Tween { duration: std::time::Duration::from_secs_f64(duration_seconds), ..Default::default() }); }
-        self.current_music_name = None; self.current_beat_map = None;
+        let tween = fade_tween(duration_seconds.max(0.0));
+        if let Some(mut handle) = self.current_music_handle.take() { let _ = handle.stop(tween); }
+        if let Some(mut outgoing) = self.outgoing_music.take() { let _ = outgoing.handle.stop(tween); }
+        self.stop_layers(tween);
+        self.current_music_name = None; self.current_beat_maps.clear(); self.current_bpm = None;
+    }
+
+    /// Stops every stem of the current soundtrack with the same fade as the base track.
+    fn stop_layers(&mut self, tween: kira::tween::Tween) {
+        for (_, mut layer) in self.music_layers.drain() { let _ = layer.handle.stop(tween); }
+    }
+
+    /// Applies the current music gain to the playing track without restarting it.
+    fn refresh_music_volume(&mut self) {
+        let amplitude = self.music_volume * self.volumes.music_gain();
+        if let Some(handle) = &mut self.current_music_handle { let _ = handle.set_volume(kira::Volume::Amplitude(amplitude), kira::tween::Tween::default()); }
+        for layer in self.music_layers.values_mut() {
+            let _ = layer.handle.set_volume(kira::Volume::Amplitude(layer.volume * amplitude), kira::tween::Tween::default());
+        }
+    }
+
+    /// Eases the stem `name` towards `target` over `fade_seconds`; remembered for stems that start later.
+    fn set_layer_volume(&mut self, name: &str, target: f64, fade_seconds: f64) {
+        let target = target.clamp(0.0, 1.0);
+        self.layer_targets.insert(name.to_string(), target);
+        let amplitude = target * self.music_volume * self.volumes.music_gain();
+        if let Some(layer) = self.music_layers.get_mut(name) {
+            layer.volume = target;
+            let tween = fade_tween(fade_seconds.max(0.0));
+            let _ = layer.handle.set_volume(kira::Volume::Amplitude(amplitude), tween);
+        }
     }
 
     fn play_music(&mut self, name: &str, params: PlaySoundParams) -> Result<(), String> {
         if self.current_music_name.as_deref() == Some(name) && let Some(handle) = &mut self.current_music_handle { let _ = handle.seek_to(0.0); return Ok(()); }
+        self.load_music(name, params, None)
+    }
+
+    fn crossfade_music(&mut self, name: &str, duration_seconds: f64) -> Result<(), String> {
+        if self.current_music_name.as_deref() == Some(name) && self.current_music_handle.is_some() { return Ok(()); }
+        self.load_music(name, PlaySoundParams::default(), Some(duration_seconds))
+    }
+
+    /// Streams `name` from disk on a worker thread; it starts in [Self::process_events] once ready.
+    /// With `crossfade`, the old track fades out and the new one fades in over that many **seconds**.
+    /// A later request supersedes one that is still loading.
+    fn load_music(&mut self, name: &str, params: PlaySoundParams, crossfade: Option<f64>) -> Result<(), String> {
         if let Some(AudioAsset::StreamingPath(path)) = self.sounds.get(name) {
             self.current_music_name = Some(name.to_string());
             let sender = self.loading_sender.clone();
             let path = path.clone(); let name = name.to_string();
             let target_bpm = self.soundtrack_properties.get(&name).and_then(|p| p.bpm);
+            let stems = self.soundtrack_properties.get(&name).map(|p| p.layers.clone()).unwrap_or_default();
             thread::spawn(move || {
                 let result = StreamingSoundData::from_file(Path::new(&path), StreamingSoundSettings::default()).map_err(|e| format!("Failed to load streaming sound '{}': {}", path, e));
-                let _ = sender.send(LoadedMusic::MusicReady { name: name.clone(), result: Box::new(result), params });
-                if path.ends_with(".wav") && let Ok(b) = BeatDetector::analyze_beats(&path, target_bpm) { let _ = sender.send(LoadedMusic::BeatsReady(b)); }
+                // Stems are opened up front so they can all start on the base track's first sample.
+                let layers = stems.into_iter().filter_map(|(layer, stem_path)| {
+                    StreamingSoundData::from_file(Path::new(&stem_path), StreamingSoundSettings::default())
+                        .map_err(|e| eprintln!("[Audio] Failed to load music layer '{}' ({}): {}", layer, stem_path, e))
+                        .ok().map(|data| (layer, data))
+                }).collect();
+                let _ = sender.send(LoadedMusic::MusicReady { name: name.clone(), result: Box::new(result), layers, params, crossfade });
+                if path.ends_with(".wav") && let Ok(maps) = BeatDetector::analyze_bands(&path, &Band::ALL, target_bpm) { let _ = sender.send(LoadedMusic::BeatsReady(name, maps)); }
             });
         }
         Ok(())
     }
 
     pub fn process_events(&mut self) {
+        // A finished crossfade releases the outgoing track; its stop tween has silenced it by now.
+        if self.outgoing_music.as_ref().is_some_and(|o| o.started.elapsed().as_secs_f64() >= o.duration) { self.outgoing_music = None; }
         while let Ok(event) = self.event_receiver.try_recv() {
             match event {
                 AudioEvent::PlaySound(name, p) => { let _ = self.play_sound(&name, p); }
                 AudioEvent::PlayMusic(name, p) => { let _ = self.play_music(&name, p); }
+                AudioEvent::CrossfadeMusic(name, d) => { let _ = self.crossfade_music(&name, d); }
                 AudioEvent::StopMusic => self.stop_music(),
                 AudioEvent::FadeOutMusic(d) => self.fade_out_music(d),
+                AudioEvent::SetMasterVolume(v) => { self.volumes.master_volume = v.clamp(0.0, 1.0); self.refresh_music_volume(); }
+                AudioEvent::SetMusicVolume(v) => { self.volumes.music_volume = v.clamp(0.0, 1.0); self.refresh_music_volume(); }
+                AudioEvent::SetSfxVolume(v) => self.volumes.sfx_volume = v.clamp(0.0, 1.0),
+                AudioEvent::SetLayerVolume(name, target, fade) => self.set_layer_volume(&name, target, fade),
             }
         }
         while let Ok(loaded) = self.loading_receiver.try_recv() {
             match loaded {
-                LoadedMusic::MusicReady { name: _, result, params } => {
+                LoadedMusic::MusicReady { name, result, layers, params, crossfade } => {
+                    if self.current_music_name.as_deref() != Some(name.as_str()) { continue; }
                     if let Ok(mut sound) = *result {
-                        if let Some(mut handle) = self.current_music_handle.take() { let _ = handle.stop(kira::tween::Tween { duration: std::time::Duration::from_millis(500), ..Default::default() }); }
-                        sound.settings.volume = kira::tween::Value::Fixed(kira::Volume::Amplitude(params.volume));
+                        let (fade_in, fade_out) = music_fades(crossfade);
+                        let (fade, fade_out) = (fade_in.map(fade_tween), fade_tween(fade_out));
+
+                        // 0. Retargeting: a track still fading out from an earlier crossfade makes way at once,
+                        //    and the half-faded-in track becomes the one leaving.
+                        if let Some(mut previous) = self.outgoing_music.take() {
+                            let _ = previous.handle.stop(fade_tween(0.05));
+                        }
+                        if let Some(mut handle) = self.current_music_handle.take() {
+                            let _ = handle.stop(fade_out);
+                            if let Some(seconds) = fade_in {
+                                let beat_maps = std::mem::take(&mut self.current_beat_maps);
+                                self.outgoing_music = Some(OutgoingMusic { handle, beat_maps, track: self.current_track, started: std::time::Instant::now(), duration: seconds });
+                            }
+                        }
+                        self.stop_layers(fade_out);
+                        self.music_volume = params.volume;
+                        let gain = params.volume * self.volumes.music_gain();
+
+                        // 1. Hold the base track and its stems on a stopped clock so they begin on the same tick.
+                        let clock = self.manager.add_clock(kira::clock::ClockSpeed::TicksPerSecond(1000.0)).ok();
+                        let start_time = clock.as_ref().map_or(kira::StartTime::Immediate, |c| kira::StartTime::ClockTime(c.time() + 1));
+                        sound.settings.volume = kira::tween::Value::Fixed(kira::Volume::Amplitude(gain));
+                        sound.settings.fade_in_tween = fade;
                         sound.settings.loop_region = Some(kira::sound::Region::from(..));
-                        if let Ok(handle) = self.manager.play(sound) { self.current_music_handle = Some(handle); self.current_beat_map = None; }
+                        sound.settings.start_time = start_time;
+                        if let Ok(handle) = self.manager.play(sound) { self.current_music_handle = Some(handle); self.current_track += 1; self.current_beat_maps.clear(); self.current_bpm = None; }
+
+                        // 2. Stems loop over the same region and start silent unless a volume was requested.
+                        for (layer, mut stem) in layers {
+                            let volume = self.layer_targets.get(&layer).copied().unwrap_or(0.0);
+                            stem.settings.volume = kira::tween::Value::Fixed(kira::Volume::Amplitude(volume * gain));
+                            stem.settings.fade_in_tween = fade;
+                            stem.settings.loop_region = Some(kira::sound::Region::from(..));
+                            stem.settings.start_time = start_time;
+                            if let Ok(handle) = self.manager.play(stem) { self.music_layers.insert(layer, MusicLayer { handle, volume }); }
+                        }
+
+                        // 3. Release them together.
+                        if let Some(mut clock) = clock { let _ = clock.start(); }
+                        self.music_clock = clock;
                     }
                 },
-                LoadedMusic::BeatsReady(beats) => self.current_beat_map = Some(beats),
+                LoadedMusic::BeatsReady(name, maps) => {
+                    // Soundtracks without a configured tempo adopt the default band's estimate for later analyses.
+                    let bpm = maps.get(&Band::default()).map(|map| map.bpm);
+                    let properties = self.soundtrack_properties.entry(name.clone()).or_insert_with(|| SoundtrackConfig { bpm: None, layers: HashMap::new() });
+                    if properties.bpm.is_none() { properties.bpm = bpm; }
+                    if self.current_music_name.as_deref() != Some(name.as_str()) { continue; }
+                    self.current_bpm = bpm;
+                    self.current_beat_maps = maps.into_iter().map(|(band, map)| (band, map.beats)).collect();
+                },
             }
         }
     }
 }
 
 enum AudioAsset { Static(Box<StaticSoundData>), StreamingPath(String) }
-enum LoadedMusic { MusicReady { #[allow(dead_code)] name: String, result: Box<Result<StreamingSoundData<FromFileError>, String>>, params: PlaySoundParams }, BeatsReady(Vec<DetectedBeat>) }
\ No newline at end of file
+enum LoadedMusic { MusicReady { name: String, result: Box<Result<StreamingSoundData<FromFileError>, String>>, layers: Vec<(String, StreamingSoundData<FromFileError>)>, params: PlaySoundParams, crossfade: Option<f64> }, BeatsReady(String, HashMap<Band, BeatMap>) }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FALLOFF: AudioSettingsConfig = AudioSettingsConfig { max_hearing_distance: 1000.0, volume_falloff_power: 2.0, music_crossfade: 0.0, danger_layer: None, danger_radius: 0.0, layer_fade: 0.0, max_sound_instances: 0, sound_debounce: 0.0 };
+    const LISTENER: AudioListener = AudioListener { position: Vector2D { x: 0.0, y: 0.0 }, half_width: 240.0 };
+
+    #[test]
+    fn test_gain_follows_falloff_curve() {
+        let near = spatial_mix(Vector2D::new(0.0, 0.0), &LISTENER, &FALLOFF).unwrap();
+        assert_eq!(near, SpatialMix { gain: 1.0, panning: 0.5 });
+        let halfway = spatial_mix(Vector2D::new(0.0, 500.0), &LISTENER, &FALLOFF).unwrap();
+        assert!((halfway.gain - 0.25).abs() < 1e-6, "(1 - 0.5)^2");
+    }
+
+    #[test]
+    fn test_sounds_beyond_hearing_distance_are_silent() {
+        assert_eq!(spatial_mix(Vector2D::new(1000.0, 0.0), &LISTENER, &FALLOFF), None);
+        assert_eq!(spatial_mix(Vector2D::new(0.0, -5000.0), &LISTENER, &FALLOFF), None);
+    }
+
+    #[test]
+    fn test_panning_follows_horizontal_offset() {
+        let left = spatial_mix(Vector2D::new(-120.0, 0.0), &LISTENER, &FALLOFF).unwrap();
+        let right = spatial_mix(Vector2D::new(600.0, 0.0), &LISTENER, &FALLOFF).unwrap();
+        assert_eq!(left.panning, 0.25);
+        assert_eq!(right.panning, 1.0, "Off-screen sounds are panned fully to that side");
+    }
+
+    #[test]
+    fn test_crossfade_fades_both_tracks_and_a_cut_only_the_old_one() {
+        assert_eq!(music_fades(Some(1.5)), (Some(1.5), 1.5));
+        assert_eq!(music_fades(None), (None, MUSIC_CUT_FADE), "PlayMusic starts the new track at full volume");
+        assert_eq!(music_fades(Some(-1.0)), (Some(0.0), 0.0), "A negative duration is a hard cut");
+    }
+
+    #[test]
+    fn test_crossfade_hands_over_at_the_midpoint() {
+        assert!(!is_incoming_dominant(0.0, 2.0));
+        assert!(!is_incoming_dominant(0.99, 2.0), "The outgoing track still leads");
+        assert!(is_incoming_dominant(1.0, 2.0));
+        assert!(is_incoming_dominant(0.0, 0.0), "A hard cut hands over immediately");
+    }
+
+    #[test]
+    fn test_voice_pool_evicts_oldest_and_skips_finished() {
+        let mut pool = VoicePool::default();
+        for (voice, time) in [(1, 0.0), (2, 0.1), (3, 0.2)] { pool.push(voice, time); }
+
+        assert_eq!(pool.make_room(3, |_| true), vec![1], "The oldest voice makes room for the new one");
+        assert_eq!(pool.make_room(3, |&v| v != 2), Vec::<i32>::new(), "Finished voices free their slot first");
+        assert_eq!(pool.len(), 1);
+        pool.push(4, 0.3);
+        assert!(pool.make_room(0, |_| true).is_empty(), "0 means unlimited");
+    }
+
+    #[test]
+    fn test_voice_pool_debounce_window() {
+        let mut pool = VoicePool::default();
+        assert!(!pool.is_debounced(0.0, 0.02));
+        pool.push((), 1.0);
+        assert!(pool.is_debounced(1.01, 0.02), "Same-frame retrigger is dropped");
+        assert!(!pool.is_debounced(1.03, 0.02));
+    }
+}
@@ -3,68 +3,178 @@
 //! This module provides the engine's rhythmic intelligence. It uses Fast 
 //! Fourier Transform (FFT) analysis to identify rhythmic onsets (beats) in 
 //! WAV files, enabling gameplay elements to synchronize with the music.
+//!
+//! Each [Band] (bass, mids, highs) is analyzed separately so different enemy
+//! behaviors can follow the kick, the snare or the hi-hats.
 
+use serde::Deserialize;
 use spectrum_analyzer::{samples_fft_to_spectrum, FrequencyLimit};
 use spectrum_analyzer::scaling::divide_by_N;
-use std::path::Path;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::fs;
 use std::io::Write;
 
 /// Represents a detected rhythmic onset in an audio track.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct DetectedBeat {
     pub time: f32,
     pub intensity: f32,
 }
 
+/// A frequency range with its own onset stream.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Band {
+    /// **40-200 Hz**: kick drum and bass. The default, and the only band analyzed before bands existed.
+    #[default]
+    Bass,
+    /// **200-2000 Hz**: snare, vocals and most melodic content.
+    Mid,
+    /// **2000-8000 Hz**: hi-hats and cymbals.
+    High,
+}
+
+impl Band {
+    pub const ALL: [Band; 3] = [Band::Bass, Band::Mid, Band::High];
+
+    /// The spectrum slice whose flux drives this band's onsets.
+    pub fn frequency_limit(self) -> FrequencyLimit {
+        match self {
+            Band::Bass => FrequencyLimit::Range(40.0, 200.0),
+            Band::Mid => FrequencyLimit::Range(200.0, 2000.0),
+            Band::High => FrequencyLimit::Range(2000.0, 8000.0),
+        }
+    }
+
+    /// Onsets expected per beat when tuning peak picking; hi-hats usually play eighth notes.
+    fn onsets_per_beat(self) -> f32 {
+        if self == Band::High { 2.0 } else { 1.0 }
+    }
+
+    /// The band's sidecar next to `file_path`: `song.beats` for bass (unchanged), `song.mid.beats` etc. otherwise.
+    pub fn cache_path(self, file_path: &str) -> PathBuf {
+        match self {
+            Band::Bass => Path::new(file_path).with_extension("beats"),
+            Band::Mid => Path::new(file_path).with_extension("mid.beats"),
+            Band::High => Path::new(file_path).with_extension("high.beats"),
+        }
+    }
+}
+
+/// Version of the `.beats` cache format and analysis; caches with any other version are recomputed.
+pub const BEAT_CACHE_VERSION: u32 = 2;
+
+/// The result of analyzing a track: its onsets plus the estimated global tempo.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BeatMap {
+    /// Estimated tempo in **beats per minute**.
+    pub bpm: f32,
+    pub beats: Vec<DetectedBeat>,
+}
+
+impl BeatMap {
+    /// Serializes the map as a `.beats` sidecar: a `#v2 bpm=116.4` header, then `time,intensity` lines.
+    pub fn to_cache_string(&self) -> String {
+        let mut out = format!("#v{} bpm={:.1}\n", BEAT_CACHE_VERSION, self.bpm);
+        for beat in &self.beats {
+            out.push_str(&format!("{},{}\n", beat.time, beat.intensity));
+        }
+        out
+    }
+
+    /// Parses a `.beats` sidecar. Headerless (legacy) files and other versions are rejected
+    /// rather than guessed at, so the caller re-runs the analysis.
+    pub fn from_cache_string(content: &str) -> Result<Self, String> {
+        let mut lines = content.lines();
+        let header = lines.next().unwrap_or_default();
+        let rest = header.strip_prefix("#v").ok_or("legacy beat cache without a version header")?;
+        let (version, bpm) = rest.split_once(" bpm=").ok_or_else(|| format!("malformed beat cache header '{}'", header))?;
+        if version.parse::<u32>().ok() != Some(BEAT_CACHE_VERSION) {
+            return Err(format!("beat cache version {} does not match {}", version, BEAT_CACHE_VERSION));
+        }
+        let bpm = bpm.trim().parse::<f32>().map_err(|e| format!("bad bpm '{}': {}", bpm, e))?;
+
+        let mut beats = Vec::new();
+        for line in lines.filter(|l| !l.trim().is_empty()) {
+            let (time, intensity) = line.split_once(',').ok_or_else(|| format!("bad beat line '{}'", line))?;
+            let time = time.trim().parse::<f32>().map_err(|e| e.to_string())?;
+            let intensity = intensity.trim().parse::<f32>().map_err(|e| e.to_string())?;
+            beats.push(DetectedBeat { time, intensity });
+        }
+        Ok(Self { bpm, beats })
+    }
+}
+
 /// A utility for analyzing audio files and extracting beat maps.
 pub struct BeatDetector;
 
 impl BeatDetector {
-    /// Performs an automated analysis of a WAV file to generate a beat map.
-    pub fn analyze_beats(file_path: &str, target_bpm_hint: Option<f32>) -> Result<Vec<DetectedBeat>, String> {
-        let path = Path::new(file_path);
-        let cache_path = path.with_extension("beats");
-
-        // 1. Check for a sidecar '.beats' cache file to skip expensive FFT analysis.
-        if cache_path.exists()
-            && let Ok(content) = fs::read_to_string(&cache_path) {
-                let mut cached_beats = Vec::new();
-                let mut valid = true;
-                for line in content.lines() {
-                    let parts: Vec<&str> = line.split(',').collect();
-                    if parts.len() == 2 {
-                        if let (Ok(time), Ok(intensity)) = (parts[0].parse::<f32>(), parts[1].parse::<f32>()) {
-                            cached_beats.push(DetectedBeat { time, intensity });
-                        } else {
-                            valid = false;
-                            break;
-                        }
+    /// Performs an automated analysis of a WAV file to generate a beat map of the default [Band].
+    ///
+    /// # Side Effects
+    /// * Reads or (re)writes the `.beats` sidecar next to the WAV file.
+    pub fn analyze_beats(file_path: &str, target_bpm_hint: Option<f32>) -> Result<BeatMap, String> {
+        Self::analyze_bands(file_path, &[Band::default()], target_bpm_hint)?
+            .remove(&Band::default())
+            .ok_or_else(|| format!("no beats analyzed for {}", file_path))
+    }
+
+    /// Analyzes each of `bands` into its own beat map; the WAV file is decoded at most once.
+    ///
+    /// # Side Effects
+    /// * Reads or (re)writes one sidecar per band (see [Band::cache_path]).
+    pub fn analyze_bands(file_path: &str, bands: &[Band], target_bpm_hint: Option<f32>) -> Result<HashMap<Band, BeatMap>, String> {
+        let mut maps = HashMap::new();
+        let mut decoded: Option<(Vec<f32>, u32)> = None;
+
+        for &band in bands {
+            let cache_path = band.cache_path(file_path);
+
+            // 1. Check for a sidecar cache file to skip expensive FFT analysis.
+            if let Ok(content) = fs::read_to_string(&cache_path) {
+                match BeatMap::from_cache_string(&content) {
+                    Ok(map) if !map.beats.is_empty() => {
+                        println!("[Analysis] Loaded {} {:?} beats ({:.1} BPM) from cache.", map.beats.len(), band, map.bpm);
+                        maps.insert(band, map);
+                        continue;
                     }
-                }
-                if valid && !cached_beats.is_empty() {
-                    println!("[Analysis] Loaded {} beats from cache.", cached_beats.len());
-                    return Ok(cached_beats);
+                    Ok(_) => {}
+                    Err(e) => println!("[Analysis] Recomputing {}: {}.", cache_path.display(), e),
                 }
             }
 
-        // 2. Perform raw spectral flux calculation if no valid cache is found.
-        let (spectral_flux, sample_rate) = Self::calculate_spectral_flux(file_path)?;
-        
-        // 3. Iteratively tune peak-picking sensitivity to match the target BPM.
+            // 2. Perform raw spectral flux calculation if no valid cache is found.
+            if decoded.is_none() { decoded = Some(Self::decode_mono(file_path)?); }
+            let Some((samples, sample_rate)) = &decoded else { continue };
+            let spectral_flux = Self::calculate_spectral_flux(samples, *sample_rate, band.frequency_limit());
+            let map = Self::tune_peaks(&spectral_flux, *sample_rate, band, target_bpm_hint);
+
+            // 3. Save the optimized beat map to a cache file for instant loading next time.
+            if let Ok(mut file) = fs::File::create(&cache_path) {
+                let _ = file.write_all(map.to_cache_string().as_bytes());
+            }
+            maps.insert(band, map);
+        }
+        Ok(maps)
+    }
+
+    /// Iteratively tunes peak-picking sensitivity so the onset rate matches the target BPM.
+    fn tune_peaks(spectral_flux: &[f32], sample_rate: u32, band: Band, target_bpm_hint: Option<f32>) -> BeatMap {
         let duration_minutes = (spectral_flux.len() as f32 * 1024.0 / sample_rate as f32) / 60.0;
         let target_bpm = target_bpm_hint.unwrap_or(116.0);
-        
+        let target_rate = target_bpm * band.onsets_per_beat();
+
         let mut best_beats = Vec::new();
         let mut best_diff = f32::MAX;
         let mut best_multiplier = 1.5;
 
         for m in 11..=100 {
             let multiplier = m as f32 / 10.0;
-            let beats = Self::pick_peaks(&spectral_flux, multiplier, sample_rate as f32);
+            let beats = Self::pick_peaks(spectral_flux, multiplier, sample_rate as f32);
             
-            let bpm = beats.len() as f32 / duration_minutes;
-            let diff = (bpm - target_bpm).abs();
+            let rate = beats.len() as f32 / duration_minutes;
+            let diff = (rate - target_rate).abs();
             
             if diff < best_diff {
                 best_diff = diff;
@@ -73,20 +183,25 @@ impl BeatDetector {
             }
         }
 
-        println!("[Analysis] Selected Multiplier {:.1} (Error: {:.1} BPM). Found {} beats.", best_multiplier, best_diff, best_beats.len());
-
-        // 4. Save the optimized beat map to a cache file for instant loading next time.
-        if let Ok(mut file) = fs::File::create(&cache_path) {
-            for beat in &best_beats {
-                let _ = writeln!(file, "{},{}", beat.time, beat.intensity);
-            }
-        }
+        let map = BeatMap { bpm: Self::estimate_bpm(&best_beats).unwrap_or(target_bpm), beats: best_beats };
+        println!("[Analysis] {:?}: Selected Multiplier {:.1} (Error: {:.1} onsets/min). Found {} beats, tempo {:.1} BPM.", band, best_multiplier, best_diff, map.beats.len(), map.bpm);
+        map
+    }
 
-        Ok(best_beats)
+    /// Estimates the tempo from the median gap between onsets, folded into **70-180 BPM**
+    /// (so a half- or double-time onset pattern still reports the felt tempo).
+    pub fn estimate_bpm(beats: &[DetectedBeat]) -> Option<f32> {
+        let mut gaps: Vec<f32> = beats.windows(2).map(|w| w[1].time - w[0].time).filter(|g| *g > 0.0).collect();
+        if gaps.is_empty() { return None; }
+        gaps.sort_by(f32::total_cmp);
+        let mut bpm = 60.0 / gaps[gaps.len() / 2];
+        while bpm < 70.0 { bpm *= 2.0; }
+        while bpm > 180.0 { bpm /= 2.0; }
+        Some((bpm * 10.0).round() / 10.0)
     }
 
-    /// Computes the change in frequency energy (flux) across overlapping FFT windows.
-    fn calculate_spectral_flux(file_path: &str) -> Result<(Vec<f32>, u32), String> {
+    /// Decodes a WAV file into normalized mono samples and its sample rate.
+    fn decode_mono(file_path: &str) -> Result<(Vec<f32>, u32), String> {
         let path = Path::new(file_path);
         let mut reader = hound::WavReader::open(path).map_err(|e| e.to_string())?;
         
@@ -114,13 +229,17 @@ impl BeatDetector {
         } else {
             samples
         };
+        Ok((mono_samples, spec.sample_rate))
+    }
 
+    /// Computes the change in frequency energy (flux) within `limit` across overlapping FFT windows.
+    fn calculate_spectral_flux(mono_samples: &[f32], sample_rate: u32, limit: FrequencyLimit) -> Vec<f32> {
         let window_size = 2048;
         let hop_size = 1024;
         let mut spectral_flux = Vec::new();
         let mut prev_spectrum: Option<Vec<f32>> = None;
 
-        // 3. Slide an overlapping window across the audio and perform FFT.
+        // 1. Slide an overlapping window across the audio and perform FFT.
         let mut start_idx = 0;
         while start_idx < mono_samples.len() {
             let end_idx = (start_idx + window_size).min(mono_samples.len());
@@ -133,15 +252,15 @@ impl BeatDetector {
 
             let spectrum_result = samples_fft_to_spectrum(
                 &padded_window,
-                spec.sample_rate,
-                FrequencyLimit::Range(40.0, 200.0), 
+                sample_rate,
+                limit,
                 Some(&divide_by_N),
             );
 
             if let Ok(spectrum) = spectrum_result {
                 let magnitudes: Vec<f32> = spectrum.data().iter().map(|(_, val)| val.val()).collect();
                 
-                // 4. Calculate the positive difference in energy between this frame and the last (Flux).
+                // 2. Calculate the positive difference in energy between this frame and the last (Flux).
                 if let Some(prev) = &prev_spectrum {
                     let mut flux = 0.0;
                     for (curr, old) in magnitudes.iter().zip(prev.iter()) {
@@ -157,7 +276,7 @@ impl BeatDetector {
             
             start_idx += hop_size;
         }
-        Ok((spectral_flux, spec.sample_rate))
+        spectral_flux
     }
 
     /// Identifies local flux maxima that exceed a dynamic sensitivity threshold.
@@ -198,3 +317,55 @@ impl BeatDetector {
         beats
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn beats_every(seconds: f32, count: usize) -> Vec<DetectedBeat> {
+        (0..count).map(|i| DetectedBeat { time: i as f32 * seconds, intensity: 1.0 }).collect()
+    }
+
+    #[test]
+    fn test_cache_round_trips_with_version_header() {
+        let map = BeatMap { bpm: 116.4, beats: vec![DetectedBeat { time: 0.5, intensity: 0.25 }, DetectedBeat { time: 1.0, intensity: 2.0 }] };
+        let text = map.to_cache_string();
+        assert!(text.starts_with("#v2 bpm=116.4\n"));
+        assert_eq!(BeatMap::from_cache_string(&text), Ok(map));
+    }
+
+    #[test]
+    fn test_legacy_and_stale_caches_are_rejected() {
+        assert!(BeatMap::from_cache_string("0.5,0.25\n1,2\n").is_err(), "Headerless v1 files are legacy");
+        assert!(BeatMap::from_cache_string("#v1 bpm=120.0\n0.5,0.25\n").is_err());
+        assert!(BeatMap::from_cache_string("#v2 bpm=120.0\n0.5;0.25\n").is_err(), "Malformed lines are not skipped");
+    }
+
+    #[test]
+    fn test_tempo_estimate_folds_into_range() {
+        assert_eq!(BeatDetector::estimate_bpm(&beats_every(0.5, 16)), Some(120.0));
+        assert_eq!(BeatDetector::estimate_bpm(&beats_every(1.0, 16)), Some(120.0), "60 BPM onsets are read as half-time");
+        assert_eq!(BeatDetector::estimate_bpm(&beats_every(0.25, 16)), Some(120.0), "240 BPM onsets are read as double-time");
+        assert_eq!(BeatDetector::estimate_bpm(&beats_every(0.5, 1)), None);
+    }
+
+    #[test]
+    fn test_bands_separate_low_and_high_onsets() {
+        // Short 5 kHz bursts every quarter second: hi-hat-like content with no bass.
+        let sample_rate = 44_100;
+        let samples: Vec<f32> = (0..sample_rate * 2).map(|i| {
+            let t = i as f32 / sample_rate as f32;
+            if t % 0.25 < 0.03 { (t * 5000.0 * std::f32::consts::TAU).sin() } else { 0.0 }
+        }).collect();
+
+        let energy = |band: Band| BeatDetector::calculate_spectral_flux(&samples, sample_rate, band.frequency_limit()).iter().sum::<f32>();
+        assert!(energy(Band::High) > 10.0 * energy(Band::Bass), "High-band flux dominates for hi-hat content");
+    }
+
+    #[test]
+    fn test_default_band_keeps_the_original_sidecar() {
+        assert_eq!(Band::default(), Band::Bass);
+        assert_eq!(Band::Bass.cache_path("music/song.wav"), Path::new("music/song.beats"));
+        assert_eq!(Band::High.cache_path("music/song.wav"), Path::new("music/song.high.beats"));
+    }
+}
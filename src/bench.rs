@@ -0,0 +1,130 @@
+//! # Concept: Benchmark Scene
+//!
+//! A reproducible performance scenario, launched with `--bench`: a dedicated stress level is
+//! played for [BENCH_TICKS] fixed ticks with scripted input, then a summary is printed. The
+//! input only depends on the tick number, so two runs simulate the same game and differ in
+//! timing alone. The last line of the summary is a single JSON object for tracking across commits.
+
+use serde::Serialize;
+use crate::benchmarker::BenchmarkSection;
+use crate::input::{InputAction, InputState};
+
+/// The stress level: rows of coins and patrolling enemies on platforms above a flat floor.
+pub const BENCH_LEVEL: &str = "assets/levels/bench_stress/level.tmx";
+/// Length of a run in fixed ticks (**30 seconds** of simulated time).
+pub const BENCH_TICKS: u64 = 3600;
+
+/// Ticks the player runs in one direction before turning around.
+const TURN_INTERVAL: u64 = 900;
+/// Ticks between two jumps, and how long each jump is held.
+const JUMP_INTERVAL: u64 = 60;
+const JUMP_HOLD: u64 = 10;
+/// Rows in the printed hotspot table; the JSON line always carries every scope.
+const TABLE_ROWS: usize = 15;
+
+/// The scripted input for `tick`: run right, then left, alternating every [TURN_INTERVAL] ticks,
+/// with a held jump every [JUMP_INTERVAL] ticks. `previous` is the input of the tick before,
+/// so presses register as "just pressed" exactly once.
+pub fn scripted_input(tick: u64, previous: &InputState) -> InputState {
+    let direction = if (tick / TURN_INTERVAL).is_multiple_of(2) { InputAction::MoveRight } else { InputAction::MoveLeft };
+    let mut actions = vec![direction];
+    if tick % JUMP_INTERVAL < JUMP_HOLD { actions.push(InputAction::Jump); }
+    let mut input = InputState::from_actions(actions);
+    input.calculate_deltas(previous);
+    input
+}
+
+/// Session-wide timing of one measured scope, in **microseconds**.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct Hotspot {
+    pub scope: String,
+    pub avg_us: f64,
+    pub max_us: u64,
+}
+
+/// The result of a benchmark run, printed as a table and as one JSON line.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct BenchSummary {
+    pub level: String,
+    pub ticks: u64,
+    pub frames: u64,
+    pub frame_avg_us: f64,
+    pub frame_min_us: u64,
+    pub frame_max_us: u64,
+    /// Every measured scope, slowest (by average) first.
+    pub hotspots: Vec<Hotspot>,
+}
+
+impl BenchSummary {
+    /// Condenses the benchmark session of the run into a summary.
+    pub fn from_section(section: &BenchmarkSection, ticks: u64) -> Self {
+        let mut hotspots: Vec<Hotspot> = section.scopes.iter()
+            .map(|(scope, s)| Hotspot { scope: scope.clone(), avg_us: s.avg_us, max_us: s.max_us })
+            .collect();
+        hotspots.sort_by(|a, b| b.avg_us.partial_cmp(&a.avg_us).unwrap_or(std::cmp::Ordering::Equal).then_with(|| a.scope.cmp(&b.scope)));
+        Self {
+            level: section.name.clone(),
+            ticks,
+            frames: section.frame.samples,
+            frame_avg_us: section.frame.avg_us,
+            frame_min_us: section.frame.min_us,
+            frame_max_us: section.frame.max_us,
+            hotspots,
+        }
+    }
+
+    /// A human-readable report: frame times followed by the slowest scopes.
+    pub fn table(&self) -> String {
+        let mut lines = vec![
+            format!("Benchmark: {} ({} ticks, {} frames)", self.level, self.ticks, self.frames),
+            format!("Frame time (us): avg {:.1}  min {}  max {}", self.frame_avg_us, self.frame_min_us, self.frame_max_us),
+            format!("{:<28} {:>10} {:>10}", "Scope", "avg (us)", "max (us)"),
+        ];
+        lines.extend(self.hotspots.iter().take(TABLE_ROWS).map(|h| format!("{:<28} {:>10.1} {:>10}", h.scope, h.avg_us, h.max_us)));
+        lines.join("\n")
+    }
+
+    /// The summary as a single line of JSON.
+    pub fn to_json_line(&self) -> Result<String, String> {
+        serde_json::to_string(self).map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmarker::ScopeSummary;
+
+    #[test]
+    fn test_scripted_input_presses_jump_once_per_interval() {
+        let mut previous = InputState::default();
+        let mut jumps = 0;
+        for tick in 0..BENCH_TICKS {
+            let input = scripted_input(tick, &previous);
+            if input.is_action_just_pressed(InputAction::Jump) { jumps += 1; }
+            if tick == TURN_INTERVAL { assert!(input.is_action_pressed(InputAction::MoveLeft)); }
+            previous = input;
+        }
+        assert_eq!(jumps, BENCH_TICKS / JUMP_INTERVAL);
+    }
+
+    #[test]
+    fn test_summary_is_one_json_line_with_the_slowest_scope_first() {
+        let scope = |avg_us| ScopeSummary { min_us: 1, max_us: 90, avg_us, samples: 10 };
+        let section = BenchmarkSection {
+            name: BENCH_LEVEL.to_string(),
+            frame: ScopeSummary { min_us: 900, max_us: 3000, avg_us: 1200.0, samples: 3599 },
+            scopes: [("Physics".to_string(), scope(40.0)), ("Render.Level".to_string(), scope(300.0))].into_iter().collect(),
+            ..Default::default()
+        };
+        let summary = BenchSummary::from_section(&section, BENCH_TICKS);
+        assert_eq!(summary.hotspots[0].scope, "Render.Level");
+
+        let line = summary.to_json_line().unwrap();
+        assert!(!line.contains('\n'));
+        let parsed: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(parsed["ticks"], 3600);
+        assert_eq!(parsed["frame_max_us"], 3000);
+        assert_eq!(parsed["hotspots"][1]["scope"], "Physics");
+    }
+}
@@ -2,10 +2,12 @@
 //! 
 //! This module provides the engine's observability tools. It implements a 
 //! stack-based hierarchical profiler and maintains rolling session statistics 
-//! to identify CPU bottlenecks and monitor frame rate stability.
+//! to identify CPU bottlenecks and monitor frame rate stability. Named sessions 
+//! (menu, attract mode, each level) keep unrelated profiles from mixing.
 
-use std::collections::{HashMap, VecDeque};
+use std::collections::{BTreeMap, HashMap, VecDeque};
 use std::time::Instant;
+use serde::{Serialize, Deserialize};
 
 const SMOOTHING_WINDOW: usize = 100;
 const FRAME_BUDGET_MICROS: f64 = 16666.6; 
@@ -48,9 +50,71 @@ impl RingBuffer {
     }
 }
 
+/// Session-wide aggregate timings for a single scope, in **microseconds**.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct ScopeSummary {
+    pub min_us: u64,
+    pub max_us: u64,
+    pub avg_us: f64,
+    pub samples: u64,
+}
+
+impl ScopeSummary {
+    fn record(&mut self, duration: u64) {
+        if self.samples == 0 || duration < self.min_us { self.min_us = duration; }
+        if duration > self.max_us { self.max_us = duration; }
+        self.avg_us += (duration as f64 - self.avg_us) / (self.samples + 1) as f64;
+        self.samples += 1;
+    }
+}
+
+/// A named, exportable snapshot of one benchmarking session (e.g. one level).
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct BenchmarkSection {
+    pub name: String,
+    pub min_fps: u32,
+    pub max_fps: u32,
+    pub avg_fps: u32,
+    /// Whole-frame timings measured between consecutive `end_frame` calls.
+    pub frame: ScopeSummary,
+    /// Per-scope timings, sorted by name so exports diff cleanly between commits.
+    pub scopes: BTreeMap<String, ScopeSummary>,
+}
+
+impl BenchmarkSection {
+    /// Average-time difference per scope against `baseline`, in **microseconds**
+    /// (positive = slower here). Sorted by largest absolute change first.
+    pub fn compare(&self, baseline: &BenchmarkSection) -> Vec<(String, f64)> {
+        let mut deltas: Vec<(String, f64)> = self.scopes.iter()
+            .map(|(name, s)| (name.clone(), s.avg_us - baseline.scopes.get(name).map_or(0.0, |b| b.avg_us)))
+            .collect();
+        deltas.sort_by(|a, b| b.1.abs().partial_cmp(&a.1.abs()).unwrap_or(std::cmp::Ordering::Equal));
+        deltas
+    }
+}
+
+/// The root of an exported benchmark file.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct BenchmarkReport {
+    pub version: u32,
+    pub sections: Vec<BenchmarkSection>,
+}
+
+/// Writes a set of benchmark sections to disk as pretty-printed JSON.
+pub fn export_report_json(path: &str, sections: Vec<BenchmarkSection>) -> Result<(), String> {
+    let report = BenchmarkReport { version: 1, sections };
+    if let Some(parent) = std::path::Path::new(path).parent() && !parent.as_os_str().is_empty() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(&report).map_err(|e| e.to_string())?;
+    std::fs::write(path, json).map_err(|e| e.to_string())
+}
+
 /// The primary manager for performance tracking and instrumentation.
 #[derive(Clone)]
 pub struct Benchmarker {
+    /// Name of the session currently being measured (e.g. "menu" or a level path).
+    pub session_name: String,
     pub min_fps: u32,
     pub max_fps: u32,
     pub avg_fps: u32,
@@ -59,11 +123,15 @@ pub struct Benchmarker {
     scope_stack: Vec<(String, Instant)>,
     current_frame_metrics: FrameMetrics,
     history: HashMap<String, RingBuffer>,
+    session_scopes: HashMap<String, ScopeSummary>,
+    session_frame: ScopeSummary,
+    last_frame_end: Option<Instant>,
 }
 
 impl Benchmarker {
     pub fn new() -> Self {
         Self {
+            session_name: "session".to_string(),
             min_fps: u32::MAX,
             max_fps: 0,
             avg_fps: 0,
@@ -72,6 +140,9 @@ impl Benchmarker {
             scope_stack: Vec::with_capacity(16),
             current_frame_metrics: FrameMetrics::default(),
             history: HashMap::new(),
+            session_scopes: HashMap::new(),
+            session_frame: ScopeSummary::default(),
+            last_frame_end: None,
         }
     }
 
@@ -83,6 +154,18 @@ impl Benchmarker {
         self.total_fps_sum = 0;
         self.samples = 0;
         self.history.clear();
+        self.session_scopes.clear();
+        self.session_frame = ScopeSummary::default();
+        self.last_frame_end = None;
+    }
+
+    /// Closes the current session and starts measuring a new one.
+    /// Returns the finished session's snapshot if it recorded any frames.
+    pub fn begin_session(&mut self, name: &str) -> Option<BenchmarkSection> {
+        let finished = (self.frame_count() > 0).then(|| self.section(&self.session_name));
+        self.reset();
+        self.session_name = name.to_string();
+        finished
     }
 
     pub fn update_fps(&mut self, current_fps: u32) {
@@ -106,7 +189,11 @@ impl Benchmarker {
     pub fn end_frame(&mut self) {
         for (name, duration) in &self.current_frame_metrics.scopes {
             self.history.entry(name.clone()).or_insert_with(|| RingBuffer::new(SMOOTHING_WINDOW)).push(*duration);
+            self.session_scopes.entry(name.clone()).or_default().record(*duration);
         }
+        let now = Instant::now();
+        if let Some(last) = self.last_frame_end { self.session_frame.record(now.duration_since(last).as_micros() as u64); }
+        self.last_frame_end = Some(now);
         self.current_frame_metrics.scopes.clear();
         self.scope_stack.clear();
     }
@@ -123,8 +210,88 @@ impl Benchmarker {
     }
 }
 
+impl Benchmarker {
+    /// Builds an exportable snapshot of the session-wide statistics.
+    pub fn section(&self, name: &str) -> BenchmarkSection {
+        BenchmarkSection {
+            name: name.to_string(),
+            min_fps: if self.samples == 0 { 0 } else { self.min_fps },
+            max_fps: self.max_fps,
+            avg_fps: self.avg_fps,
+            frame: self.session_frame,
+            scopes: self.session_scopes.iter().map(|(k, v)| (k.clone(), *v)).collect(),
+        }
+    }
+
+    /// The number of complete frames measured since the last reset.
+    pub fn frame_count(&self) -> u64 { self.session_frame.samples }
+
+    /// Dumps this session's per-scope min/max/avg timings (in microseconds) to a JSON file.
+    pub fn export_json(&self, path: &str) -> Result<(), String> {
+        export_report_json(path, vec![self.section(&self.session_name)])
+    }
+}
+
 impl Default for Benchmarker {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_export_json_round_trip() {
+        let mut benchmarker = Benchmarker::new();
+        for _ in 0..3 {
+            benchmarker.push("Physics");
+            benchmarker.pop();
+            benchmarker.end_frame();
+        }
+        benchmarker.update_fps(60);
+
+        let path = std::env::temp_dir().join("gfx_engine_benchmark_test.json");
+        let path = path.to_string_lossy().to_string();
+        benchmarker.export_json(&path).unwrap();
+
+        let parsed: BenchmarkReport = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(parsed.sections.len(), 1);
+        let section = &parsed.sections[0];
+        assert_eq!(section.avg_fps, 60);
+        assert_eq!(section.scopes["Physics"].samples, 3);
+        assert_eq!(section.frame.samples, 2, "Frame time is measured between consecutive frames");
+        assert_eq!(section.scopes, benchmarker.section("session").scopes);
+    }
+
+    #[test]
+    fn test_sessions_do_not_mix() {
+        let mut benchmarker = Benchmarker::new();
+        benchmarker.begin_session("menu");
+        for _ in 0..3 {
+            benchmarker.push("MenuRender");
+            benchmarker.pop();
+            benchmarker.end_frame();
+        }
+
+        let menu = benchmarker.begin_session("level_01").expect("Menu session recorded frames");
+        assert_eq!(menu.name, "menu");
+        assert_eq!(menu.scopes["MenuRender"].samples, 3);
+
+        benchmarker.push("Physics");
+        benchmarker.pop();
+        benchmarker.end_frame();
+        let level = benchmarker.section(&benchmarker.session_name);
+        assert_eq!(level.name, "level_01");
+        assert!(!level.scopes.contains_key("MenuRender"), "Menu scopes must not leak into gameplay");
+        assert!(benchmarker.begin_session("empty").is_some());
+        assert!(benchmarker.begin_session("other").is_none(), "Sessions without frames are dropped");
+
+        let deltas = level.compare(&menu);
+        assert_eq!(deltas.len(), 1);
+        assert_eq!(deltas[0].0, "Physics");
+    }
+}
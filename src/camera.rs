@@ -2,12 +2,31 @@
 //! 
 //! This module is the authority for the game's "Eye". It smoothly follows 
 //! a target (the Player) using advanced cinematic features like lookahead 
-//! bias, platform snapping, and dynamic trauma-based shake effects.
+//! bias, platform snapping, and dynamic trauma-based shake effects. In local 
+//! co-op it frames every player at once, zooming out to keep them on screen.
+//! Inside a `CameraZone` (or a `CameraBounds` layer rectangle) the view is
+//! confined to that region instead of the map.
+//! A single target is followed according to the [CameraMode].
 
 use crate::math::Vector2D;
 use crate::ecs::component::Direction;
+use crate::level::Region;
+use serde::Deserialize;
+
+/// How the camera follows a single target.
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub enum CameraMode {
+    /// Horizontal slow/fast zones with lookahead; the vertical position locks to the last ground height.
+    #[default] SlowZone,
+    /// A "camera window": the target moves freely inside a box centered in the view, and the
+    /// view scrolls only as far as the target pushes past the box edge, the same on both axes.
+    Deadzone,
+}
 
 /// Holds the state and logic for the cinematic viewport.
+/// All smoothing values (`tightness`, `vertical_tightness`, `camera_falling_tightness`, `smoothing_speed`)
+/// are exponential rates **per second**, so the camera feels the same at any frame rate.
+#[derive(Clone)]
 pub struct Camera {
     pub position: Vector2D,
     pub velocity: Vector2D,
@@ -27,55 +46,328 @@ pub struct Camera {
     pub lookahead_distance: f32,
     pub current_lookahead_offset: f32,
     pub smoothing_speed: f32,
+    /// Render-only displacement in **pixels** set by the shake system; never part of `position`.
     pub shake_offset: Vector2D,
+    /// Magnification relative to the virtual resolution (1.0 = native, < 1.0 = zoomed out).
+    pub zoom: f32,
+    /// Region the view is currently confined to, blended between the map and the active zone.
+    pub bounds: Region,
+    bounds_from: Region,
+    bounds_to: Region,
+    bounds_blend_elapsed: f32,
+    /// Time in **seconds** to ease between two clamp regions.
+    pub bounds_blend_time: f32,
+    pub mode: CameraMode,
+    /// Size of the [CameraMode::Deadzone] box as a fraction of the view on each axis.
+    pub deadzone: Vector2D,
 }
 
 impl Camera {
     #[allow(clippy::too_many_arguments)]
     pub fn new(x: f32, y: f32, tightness: f32, virtual_width: f32, virtual_height: f32, map_width: f32, map_height: f32, slow_zone: f32, fast_zone: f32, vertical_snap_threshold: f32, vertical_tightness: f32, camera_falling_tightness: f32, camera_falling_velocity_threshold: f32, entity_max_fall_speed: f32, lookahead_distance: f32, smoothing_speed: f32) -> Self {
+        let map_bounds = Region { x: 0.0, y: 0.0, width: map_width, height: map_height };
         Self {
             position: Vector2D::new(x, y), velocity: Vector2D::default(), tightness, virtual_width, virtual_height,
             map_width, map_height, slow_zone, fast_zone, locked_y_position: y, _vertical_snap_threshold: vertical_snap_threshold,
             vertical_tightness, camera_falling_tightness, camera_falling_velocity_threshold, entity_max_fall_speed,
-            lookahead_distance, current_lookahead_offset: 0.0, smoothing_speed, shake_offset: Vector2D::default(),
+            lookahead_distance, current_lookahead_offset: 0.0, smoothing_speed, shake_offset: Vector2D::default(), zoom: 1.0,
+            bounds: map_bounds, bounds_from: map_bounds, bounds_to: map_bounds, bounds_blend_elapsed: 0.0, bounds_blend_time: 0.0,
+            mode: CameraMode::default(), deadzone: Vector2D::new(0.25, 0.35),
         }
     }
 
+    /// Confines the view to `zone`, or to the whole map when `None`.
+    /// Changing region starts a smooth blend over `bounds_blend_time`.
+    pub fn set_zone(&mut self, zone: Option<Region>) {
+        let target = zone.unwrap_or(Region { x: 0.0, y: 0.0, width: self.map_width, height: self.map_height });
+        if target == self.bounds_to { return; }
+        self.bounds_from = self.bounds;
+        self.bounds_to = target;
+        self.bounds_blend_elapsed = 0.0;
+    }
+
+    /// Confines the view to an integer rectangle (in **pixels**), e.g. one authored on a `CameraBounds` layer.
+    pub fn set_bounds(&mut self, rect: sdl3::rect::Rect) {
+        self.set_zone(Some(Region { x: rect.x() as f32, y: rect.y() as f32, width: rect.width() as f32, height: rect.height() as f32 }));
+    }
+
+    /// Advances the clamp-region blend by `dt` **seconds** using a smoothstep curve.
+    pub fn update_bounds(&mut self, dt: f32) {
+        self.bounds_blend_elapsed += dt;
+        let t = if self.bounds_blend_time > 0.0 { (self.bounds_blend_elapsed / self.bounds_blend_time).min(1.0) } else { 1.0 };
+        let t = t * t * (3.0 - 2.0 * t);
+        let lerp = |a: f32, b: f32| a + (b - a) * t;
+        let (from, to) = (self.bounds_from, self.bounds_to);
+        self.bounds = Region { x: lerp(from.x, to.x), y: lerp(from.y, to.y), width: lerp(from.width, to.width), height: lerp(from.height, to.height) };
+    }
+
+    /// Clamps a view origin to the active region on one axis.
+    /// Regions smaller than the view center the camera on them; the map edge is never crossed.
+    fn clamp_axis(value: f32, start: f32, extent: f32, view: f32) -> f32 {
+        let clamped = if extent <= view { start + (extent - view) / 2.0 } else { value.clamp(start, start + extent - view) };
+        clamped.max(0.0)
+    }
+
+    fn clamp_x(&self, x: f32) -> f32 { Self::clamp_axis(x, self.bounds.x, self.bounds.width, self.view_width()) }
+
+    fn clamp_y(&self, y: f32) -> f32 { Self::clamp_axis(y, self.bounds.y, self.bounds.height, self.view_height()) }
+
     pub fn view_rect(&self) -> sdl3::rect::Rect {
-        sdl3::rect::Rect::new(self.position.x as i32, self.position.y as i32, self.virtual_width as u32, self.virtual_height as u32)
+        sdl3::rect::Rect::new(self.position.x as i32, self.position.y as i32, self.view_width() as u32, self.view_height() as u32)
     }
 
+    /// Width of the visible world area in **world units**, accounting for zoom (see [crate::units]).
+    pub fn view_width(&self) -> f32 { self.virtual_width / self.zoom }
+
+    /// Height of the visible world area in **world units**, accounting for zoom.
+    pub fn view_height(&self) -> f32 { self.virtual_height / self.zoom }
+
     /// Smoothly interpolates the camera position to track a target with cinematic logic.
-    pub fn update(&mut self, target: Vector2D, is_grounded: bool, player_vel_y: f32, player_direction: Direction) {
+    /// `delta_time` is the time in **seconds** since the last update.
+    pub fn update(&mut self, target: Vector2D, is_grounded: bool, player_vel_y: f32, player_direction: Direction, delta_time: f32) {
+        // A lone target relaxes any co-op zoom back to the native view.
+        self.zoom += (1.0 - self.zoom) * smoothing(self.smoothing_speed, delta_time);
+        if self.mode == CameraMode::Deadzone { return self.follow_deadzone(target); }
         let slow_zone_x = self.virtual_width * self.slow_zone;
         let fast_zone_x = self.virtual_width * self.fast_zone;
         let target_lookahead_offset = if player_direction == Direction::Right { self.lookahead_distance } else { -self.lookahead_distance };
-        self.current_lookahead_offset += (target_lookahead_offset - self.current_lookahead_offset) * self.smoothing_speed;
-        let base_x = self.position.x - self.shake_offset.x;
-        let base_y = self.position.y - self.shake_offset.y;
-        let camera_center_x = base_x + self.virtual_width / 2.0;
+        self.current_lookahead_offset += (target_lookahead_offset - self.current_lookahead_offset) * smoothing(self.smoothing_speed, delta_time);
+        let (base_x, base_y) = (self.position.x, self.position.y);
+        let camera_center_x = base_x + self.view_width() / 2.0;
         let delta_x = (target.x + self.current_lookahead_offset) - camera_center_x;
         let mut move_x = 0.0;
         if delta_x.abs() > slow_zone_x {
             let speed_factor = if delta_x.abs() > fast_zone_x { 1.0 } else { ((delta_x.abs() - slow_zone_x) / (fast_zone_x - slow_zone_x)).powi(3) };
-            move_x = delta_x * speed_factor * self.tightness;
+            move_x = delta_x * speed_factor * smoothing(self.tightness, delta_time);
         }
-        if is_grounded { self.locked_y_position = target.y - (self.virtual_height / 2.0); }
+        if is_grounded { self.locked_y_position = target.y - (self.view_height() / 2.0); }
         let vertical_tightness = if player_vel_y > self.camera_falling_velocity_threshold {
             let t = ((player_vel_y - self.camera_falling_velocity_threshold) / (self.entity_max_fall_speed - self.camera_falling_velocity_threshold)).clamp(0.0, 1.0);
             self.vertical_tightness + t * (self.camera_falling_tightness - self.vertical_tightness)
         } else { self.vertical_tightness };
         let delta_y = self.locked_y_position - base_y;
-        let move_y = delta_y * vertical_tightness;
-        let new_base_x = (base_x + move_x).clamp(0.0, self.map_width - self.virtual_width);
-        let new_base_y = (base_y + move_y).clamp(0.0, self.map_height - self.virtual_height);
-        self.position.x = new_base_x + self.shake_offset.x;
-        self.position.y = new_base_y + self.shake_offset.y;
+        let move_y = delta_y * smoothing(vertical_tightness, delta_time);
+        let new_base_x = self.clamp_x(base_x + move_x);
+        let new_base_y = self.clamp_y(base_y + move_y);
+        self.position = Vector2D::new(new_base_x, new_base_y);
+    }
+
+    /// Scrolls just far enough to keep `target` inside the deadzone box; no smoothing is applied.
+    fn follow_deadzone(&mut self, target: Vector2D) {
+        let (base_x, base_y) = (self.position.x, self.position.y);
+        let half_width = self.view_width() * self.deadzone.x / 2.0;
+        let half_height = self.view_height() * self.deadzone.y / 2.0;
+
+        // How far the target sticks out of the box on one axis (0 while inside it).
+        let overshoot = |target: f32, center: f32, half: f32| (target - (center + half)).max(0.0) + (target - (center - half)).min(0.0);
+        let move_x = overshoot(target.x, base_x + self.view_width() / 2.0, half_width);
+        let move_y = overshoot(target.y, base_y + self.view_height() / 2.0, half_height);
+
+        let new_base_x = self.clamp_x(base_x + move_x);
+        let new_base_y = self.clamp_y(base_y + move_y);
+        self.locked_y_position = new_base_y;
+        self.position = Vector2D::new(new_base_x, new_base_y);
     }
 
+    /// Frames several targets at once (local co-op): centers on their average position
+    /// and zooms out just enough to keep every target plus `padding` on screen.
+    pub fn frame_group(&mut self, targets: &[Vector2D], padding: f32, min_zoom: f32, delta_time: f32) {
+        if targets.is_empty() { return; }
+
+        // 1. Measure the bounding box of all targets.
+        let (mut min_x, mut min_y, mut max_x, mut max_y) = (f32::MAX, f32::MAX, f32::MIN, f32::MIN);
+        for t in targets {
+            min_x = min_x.min(t.x); max_x = max_x.max(t.x);
+            min_y = min_y.min(t.y); max_y = max_y.max(t.y);
+        }
+
+        // 2. Zoom to fit the padded box, never magnifying beyond the native view.
+        let fit_x = self.virtual_width / (max_x - min_x + padding * 2.0);
+        let fit_y = self.virtual_height / (max_y - min_y + padding * 2.0);
+        let target_zoom = fit_x.min(fit_y).clamp(min_zoom.min(1.0), 1.0);
+        self.zoom += (target_zoom - self.zoom) * smoothing(self.smoothing_speed, delta_time);
+
+        // 3. Ease the view towards the targets' average position.
+        let n = targets.len() as f32;
+        let center_x = targets.iter().map(|t| t.x).sum::<f32>() / n;
+        let center_y = targets.iter().map(|t| t.y).sum::<f32>() / n;
+        let (base_x, base_y) = (self.position.x, self.position.y);
+        let desired_x = center_x - self.view_width() / 2.0;
+        let desired_y = center_y - self.view_height() / 2.0;
+        let new_base_x = self.clamp_x(base_x + (desired_x - base_x) * smoothing(self.tightness, delta_time));
+        let new_base_y = self.clamp_y(base_y + (desired_y - base_y) * smoothing(self.vertical_tightness, delta_time));
+        self.locked_y_position = new_base_y;
+        self.position = Vector2D::new(new_base_x, new_base_y);
+    }
+
+    /// Jumps straight to `target`, finishing any pending clamp-region blend.
     pub fn snap_to(&mut self, target: Vector2D) {
-        self.position.x = (target.x - self.virtual_width / 2.0).clamp(0.0, self.map_width - self.virtual_width);
-        self.position.y = (target.y - self.virtual_height / 2.0).clamp(0.0, self.map_height - self.virtual_height);
+        self.bounds = self.bounds_to;
+        self.bounds_blend_elapsed = self.bounds_blend_time;
+        self.position.x = self.clamp_x(target.x - self.view_width() / 2.0);
+        self.position.y = self.clamp_y(target.y - self.view_height() / 2.0);
+        self.locked_y_position = self.position.y;
         self.velocity = Vector2D::default();
     }
-}
\ No newline at end of file
+
+    /// Moves the view by `delta` **pixels** without any smoothing (debug free camera).
+    /// Camera zones are ignored; only the map edges limit the view.
+    pub fn pan(&mut self, delta: Vector2D) {
+        self.position.x = Self::clamp_axis(self.position.x + delta.x, 0.0, self.map_width, self.view_width());
+        self.position.y = Self::clamp_axis(self.position.y + delta.y, 0.0, self.map_height, self.view_height());
+    }
+
+    /// The camera as it should be drawn: `position` plus the shake offset, kept inside the map.
+    /// Gameplay (culling, dormancy, listeners) keeps using the unshaken camera.
+    pub fn render_view(&self) -> Camera {
+        let mut view = self.clone();
+        view.position.x = Self::clamp_axis(self.position.x + self.shake_offset.x, 0.0, self.map_width, self.view_width());
+        view.position.y = Self::clamp_axis(self.position.y + self.shake_offset.y, 0.0, self.map_height, self.view_height());
+        view.shake_offset = Vector2D::default();
+        view
+    }
+
+    /// The world point under the middle of the screen.
+    pub fn view_center(&self) -> Vector2D {
+        Vector2D::new(self.position.x + self.view_width() / 2.0, self.position.y + self.view_height() / 2.0)
+    }
+}
+
+/// Fraction of the remaining distance an exponential follower with `rate` **per second** covers in `dt` **seconds**.
+fn smoothing(rate: f32, dt: f32) -> f32 {
+    1.0 - (-rate * dt).exp()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Rates high enough that one 1-second update reaches the target.
+    fn make_camera() -> Camera {
+        Camera::new(0.0, 0.0, 1000.0, 480.0, 270.0, 4000.0, 2000.0, 0.1, 0.4, 0.1, 1000.0, 1000.0, 1.0, 600.0, 100.0, 1000.0)
+    }
+
+    #[test]
+    fn test_smoothing_is_frame_rate_independent() {
+        let run = |dt: f32, steps: usize| {
+            let mut camera = Camera::new(0.0, 0.0, 3.0, 480.0, 270.0, 4000.0, 2000.0, 0.0, 0.0, 0.1, 3.0, 3.0, 1.0, 600.0, 100.0, 5.0);
+            for _ in 0..steps { camera.update(Vector2D::new(2000.0, 1000.0), true, 0.0, Direction::Right, dt); }
+            camera.position
+        };
+        let at_60 = run(1.0 / 60.0, 60);
+        let at_120 = run(1.0 / 120.0, 120);
+        assert!(at_60.x > 1000.0 && at_60.x < 2000.0, "Still easing towards the target after one second");
+        assert!((at_60.x - at_120.x).abs() < 0.5 && (at_60.y - at_120.y).abs() < 0.5, "{:?} vs {:?}", at_60, at_120);
+    }
+
+    #[test]
+    fn test_frame_group_zooms_out_to_fit_players() {
+        let mut camera = make_camera();
+        let targets = [Vector2D::new(1000.0, 500.0), Vector2D::new(1600.0, 500.0)];
+
+        camera.frame_group(&targets, 50.0, 0.5, 1.0);
+
+        // 600px apart plus 2 * 50px padding must fit into the zoomed view.
+        assert!(camera.zoom < 1.0);
+        assert!(camera.view_width() >= 700.0 - 0.01);
+        let center_x = camera.position.x + camera.view_width() / 2.0;
+        assert!((center_x - 1300.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_group_zoom_eases_at_the_same_rate_at_any_frame_rate() {
+        let run = |dt: f32, steps: usize| {
+            let mut camera = Camera::new(0.0, 0.0, 3.0, 480.0, 270.0, 4000.0, 2000.0, 0.0, 0.0, 0.1, 3.0, 3.0, 1.0, 600.0, 100.0, 5.0);
+            let targets = [Vector2D::new(100.0, 500.0), Vector2D::new(3900.0, 500.0)];
+            for _ in 0..steps { camera.frame_group(&targets, 50.0, 0.5, dt); }
+            camera.zoom
+        };
+        let (at_30, at_144) = (run(1.0 / 30.0, 6), run(1.0 / 144.0, 29));
+        assert!(at_30 > 0.5 && at_30 < 1.0, "Still zooming out after 0.2 seconds");
+        assert!((at_30 - run(1.0 / 60.0, 12)).abs() < 1e-4);
+        assert!((at_144 - run(1.0 / 288.0, 58)).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_frame_group_respects_min_zoom_and_native_scale() {
+        let mut camera = make_camera();
+
+        camera.frame_group(&[Vector2D::new(100.0, 500.0), Vector2D::new(3900.0, 500.0)], 50.0, 0.5, 1.0);
+        assert_eq!(camera.zoom, 0.5);
+
+        camera.frame_group(&[Vector2D::new(1000.0, 500.0), Vector2D::new(1010.0, 500.0)], 50.0, 0.5, 1.0);
+        assert_eq!(camera.zoom, 1.0);
+    }
+
+    #[test]
+    fn test_zone_clamp_blends_and_centers_small_zones() {
+        let mut camera = make_camera();
+        camera.bounds_blend_time = 1.0;
+        let room = Region { x: 1000.0, y: 500.0, width: 300.0, height: 600.0 };
+
+        camera.set_zone(Some(room));
+        camera.update_bounds(0.5);
+        assert!(camera.bounds.x > 0.0 && camera.bounds.x < 1000.0, "Halfway through the blend the clamp is in between");
+
+        camera.update_bounds(0.5);
+        assert_eq!(camera.bounds, room);
+
+        // The room is narrower than the 480px view, so the camera centers on it horizontally.
+        camera.snap_to(Vector2D::new(0.0, 0.0));
+        assert_eq!(camera.position.x, 1000.0 + (300.0 - 480.0) / 2.0);
+        assert_eq!(camera.position.y, 500.0, "Taller zones clamp normally");
+
+        camera.set_bounds(sdl3::rect::Rect::new(1000, 500, 300, 600));
+        camera.update_bounds(0.0);
+        assert_eq!(camera.bounds, room, "The same region as a pixel rect does not restart the blend");
+        camera.set_bounds(sdl3::rect::Rect::new(0, 0, 4000, 2000));
+        camera.update_bounds(0.5);
+        assert_ne!(camera.bounds, room, "A new rect blends away from the room");
+    }
+
+    #[test]
+    fn test_pan_ignores_zones_and_stops_at_map_edges() {
+        let mut camera = make_camera();
+        camera.set_zone(Some(Region { x: 0.0, y: 0.0, width: 500.0, height: 300.0 }));
+        camera.update_bounds(10.0);
+
+        camera.pan(Vector2D::new(1000.0, 200.0));
+        assert_eq!((camera.position.x, camera.position.y), (1000.0, 200.0), "The free camera leaves the zone");
+        assert_eq!(camera.view_center(), Vector2D::new(1240.0, 335.0));
+
+        camera.pan(Vector2D::new(10000.0, -1000.0));
+        assert_eq!((camera.position.x, camera.position.y), (4000.0 - 480.0, 0.0));
+    }
+
+    #[test]
+    fn test_shake_is_render_only_and_stays_on_the_map() {
+        let mut camera = make_camera();
+        camera.snap_to(Vector2D::new(240.0, 1000.0));
+        camera.shake_offset = Vector2D::new(-15.0, 12.0);
+
+        let view = camera.render_view();
+        assert_eq!(view.position, Vector2D::new(0.0, 877.0), "The left map edge swallows the horizontal shake");
+        assert_eq!(camera.position, Vector2D::new(0.0, 865.0), "The gameplay camera is untouched");
+
+        camera.update(Vector2D::new(240.0, 1000.0), true, 0.0, Direction::Right, 1.0);
+        assert_eq!(camera.position.y, 865.0, "Shake never feeds back into the follow logic");
+    }
+
+    #[test]
+    fn test_deadzone_holds_still_until_the_target_pushes_an_edge() {
+        let mut camera = make_camera();
+        camera.mode = CameraMode::Deadzone;
+        camera.snap_to(Vector2D::new(1000.0, 1000.0));
+        let start = camera.position;
+
+        // The box is 120x94.5 around the view center; wander inside it on both axes.
+        for target in [Vector2D::new(1050.0, 1000.0), Vector2D::new(950.0, 1040.0), Vector2D::new(1000.0, 960.0)] {
+            camera.update(target, false, 300.0, Direction::Right, 1.0);
+            assert_eq!(camera.position, start, "Target at {:?} is inside the deadzone", target);
+        }
+
+        // Pushing 10px past the right edge and 5px past the top scrolls by exactly that much.
+        camera.update(Vector2D::new(1070.0, 1000.0 - 47.25 - 5.0), true, 0.0, Direction::Left, 1.0);
+        assert_eq!(camera.position, Vector2D::new(start.x + 10.0, start.y - 5.0));
+    }
+}
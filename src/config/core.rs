@@ -4,7 +4,8 @@
 //! It is responsible for decoding 'config.toml' and applying global 
 //! resolution-independent scaling to all physical and spatial constants.
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use crate::input::InputAction;
 use std::fs;
 
 /// The root structure for the global application configuration.
@@ -15,6 +16,7 @@ pub struct Config {
     pub physics: PhysicsConfig,
     pub debug: DebugConfig,
     pub game: GameSettings,
+    #[serde(default)] pub audio: AudioSettings,
 }
 
 #[derive(Deserialize, Clone)]
@@ -27,27 +29,79 @@ pub struct WindowConfig {
     pub fullscreen: bool,
     pub vsync: bool,
     pub scaling_quality: String,
+    /// Horizontal follow rate **per second** (exponential smoothing).
     pub camera_tightness: f32,
     pub camera_slow_zone: f32,
     pub camera_fast_zone: f32,
     pub camera_vertical_snap_threshold: f32,
+    /// Vertical follow rate **per second** towards the last ground height.
     pub camera_vertical_tightness: f32,
+    /// Vertical follow rate **per second** used at the maximum fall speed.
     pub camera_falling_tightness: f32,
     pub camera_falling_velocity_threshold: f32,
     pub camera_lookahead_distance: f32,
+    /// Rate **per second** at which the lookahead offset and zoom settle.
     pub camera_smoothing_speed: f32,
+    /// Margin in **pixels** kept around all players when framing local co-op.
+    #[serde(default = "default_camera_group_padding")] pub camera_group_padding: f32,
+    /// Lowest zoom factor the camera may use to fit every player on screen.
+    #[serde(default = "default_camera_min_zoom")] pub camera_min_zoom: f32,
+    /// Time in **seconds** to ease between the map clamp and a `CameraZone` clamp.
+    #[serde(default = "default_camera_zone_blend_time")] pub camera_zone_blend_time: f32,
+    /// How a single player is followed; `SlowZone` keeps the original behavior.
+    #[serde(default)] pub camera_mode: crate::camera::CameraMode,
+    /// Deadzone box size as a fraction of the view width/height (only used by `Deadzone`).
+    #[serde(default = "default_camera_deadzone_width")] pub camera_deadzone_width: f32,
+    #[serde(default = "default_camera_deadzone_height")] pub camera_deadzone_height: f32,
+    /// Draw the frame at the virtual resolution into an offscreen texture, then stretch it to the window.
+    #[serde(default)] pub crt_post_process: bool,
+    /// Darkening of every other scanline in the stretched frame (0 = none, 1 = black); needs `crt_post_process`.
+    #[serde(default = "default_crt_scanline_strength")] pub crt_scanline_strength: f32,
+    /// Scale the virtual screen by whole numbers only, letterboxing the rest of the window.
+    /// When false, the canvas stretches to the largest fractional scale that fits instead.
+    #[serde(default = "default_true")] pub integer_scaling: bool,
 }
 
+fn default_camera_group_padding() -> f32 { 64.0 }
+fn default_camera_min_zoom() -> f32 { 0.5 }
+fn default_camera_zone_blend_time() -> f32 { 0.6 }
+fn default_camera_deadzone_width() -> f32 { 0.25 }
+fn default_camera_deadzone_height() -> f32 { 0.35 }
+fn default_crt_scanline_strength() -> f32 { 0.35 }
+
 #[derive(Deserialize, Clone)]
 pub struct DebugConfig {
     pub show_debug_info: bool,
     pub debug_draw_collision_boxes: bool,
+    /// Show the per-scope frame budget list while the debug overlay is on.
+    #[serde(default = "default_true")] pub show_hotspots: bool,
+    /// Outline every tile in view while the debug overlay is on.
+    #[serde(default)] pub show_tile_grid: bool,
+    /// Free camera pan speed in **pixels per second**.
+    #[serde(default = "default_free_camera_speed")] pub free_camera_speed: f32,
+    /// Pan speed multiplier while the `free_camera_fast` key is held.
+    #[serde(default = "default_free_camera_fast_multiplier")] pub free_camera_fast_multiplier: f32,
+    /// Length of the rolling GIF buffer in **seconds**.
+    #[serde(default = "default_gif_buffer_seconds")] pub gif_buffer_seconds: f32,
+    /// Frames per second kept while GIF capture is on.
+    #[serde(default = "default_gif_frame_rate")] pub gif_frame_rate: u32,
+    /// GIF frames keep every n-th window pixel on both axes.
+    #[serde(default = "default_gif_downscale")] pub gif_downscale: u32,
+    /// How far the replay seek keys jump, in **ticks**.
+    #[serde(default = "default_replay_seek_ticks")] pub replay_seek_ticks: u64,
     pub text_start_x: i32,
     #[allow(dead_code)]
     pub text_start_y: i32,
     pub text_line_spacing: i32,
 }
 
+fn default_free_camera_speed() -> f32 { 600.0 }
+fn default_free_camera_fast_multiplier() -> f32 { 4.0 }
+fn default_gif_buffer_seconds() -> f32 { 5.0 }
+fn default_gif_frame_rate() -> u32 { 15 }
+fn default_gif_downscale() -> u32 { 4 }
+fn default_replay_seek_ticks() -> u64 { 600 }
+
 #[derive(Deserialize, Clone)]
 pub struct GameSettings {
     pub start_level: String,
@@ -64,29 +118,267 @@ pub struct PhysicsConfig {
     pub jump_hold_force: f32,
     pub air_control_factor: f32,
     pub velocity_threshold: f32,
+    /// Grace period in **seconds** to still jump after walking off a ledge.
+    #[serde(default = "default_coyote_time")] pub coyote_time: f32,
+    /// Window in **seconds** during which an early jump press fires on landing.
+    #[serde(default = "default_jump_buffer_time")] pub jump_buffer_time: f32,
+    /// Jumps available per airtime, counting the one from the ground (`2` = double jump).
+    #[serde(default = "default_max_jumps")] pub max_jumps: u32,
+    /// Longest frame in **seconds** fed to the simulation; stalls beyond it are dropped.
+    #[serde(default = "default_max_delta_time")] pub max_delta_time: f32,
+    /// Fraction of gravity cancelled while submerged (`1.0` = neutrally buoyant).
+    #[serde(default = "default_buoyancy")] pub buoyancy: f32,
+    /// Terminal sink speed in **pixels per second** while submerged.
+    #[serde(default = "default_water_max_speed")] pub water_max_speed: f32,
+    /// Upward velocity in **pixels per second** of a swim stroke (negative is up).
+    #[serde(default = "default_stroke_strength")] pub stroke_strength: f32,
+    /// Multiplier applied to horizontal acceleration while submerged.
+    #[serde(default = "default_water_control_factor")] pub water_control_factor: f32,
 }
 
-#[derive(Deserialize, Clone)]
+fn default_max_jumps() -> u32 { 1 }
+fn default_buoyancy() -> f32 { 0.8 }
+fn default_water_max_speed() -> f32 { 90.0 }
+fn default_stroke_strength() -> f32 { -220.0 }
+fn default_water_control_factor() -> f32 { 0.5 }
+
+fn default_max_delta_time() -> f32 { 1.0 / 30.0 }
+
+fn default_coyote_time() -> f32 { 0.1 }
+fn default_jump_buffer_time() -> f32 { 0.12 }
+
+/// # Concept: Key Bindings
+/// Keys by SDL key name (e.g. "Space", "Left Shift"). Player-one gameplay keys can be rebound
+/// from the Controls menu; the result is saved to [USER_SETTINGS_PATH].
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
 pub struct InputConfig {
     pub left: String,
     pub right: String,
     pub jump: String,
+    #[serde(default = "default_key_x")] pub fire: String,
+    #[serde(default = "default_key_c")] pub attack: String,
     #[serde(default = "default_key_up")] pub up: String,
     #[serde(default = "default_key_down")] pub down: String,
     pub quit: String,
+    #[serde(default = "default_key_r")] pub restart_level: String,
     pub debug_toggle: String,
+    #[serde(default = "default_key_f2")] pub toggle_collision_boxes: String,
+    #[serde(default = "default_key_f3")] pub toggle_hotspots: String,
+    #[serde(default = "default_key_f4")] pub toggle_tile_grid: String,
     #[serde(default = "default_key_f5")] pub record_toggle: String,
     #[serde(default = "default_key_f6")] pub save_replay: String,
+    #[serde(default = "default_key_f7")] pub export_benchmarks: String,
+    #[serde(default = "default_key_f8")] pub reload_config: String,
+    #[serde(default = "default_key_f9")] pub toggle_free_camera: String,
+    #[serde(default = "default_key_f10")] pub inspect_entity: String,
+    #[serde(default = "default_key_f11")] pub toggle_gif_capture: String,
+    #[serde(default = "default_key_f12")] pub screenshot: String,
+    #[serde(default = "default_key_p")] pub pause_simulation: String,
+    #[serde(default = "default_key_n")] pub step_frame: String,
+    #[serde(default = "default_key_t")] pub cycle_time_scale: String,
+    #[serde(default = "default_key_comma")] pub replay_seek_back: String,
+    #[serde(default = "default_key_period")] pub replay_seek_forward: String,
+    /// Held to pan the free camera faster.
+    #[serde(default = "default_key_left_shift")] pub free_camera_fast: String,
+    #[serde(default = "default_key_a")] pub p2_left: String,
+    #[serde(default = "default_key_d")] pub p2_right: String,
+    #[serde(default = "default_key_w")] pub p2_jump: String,
+    #[serde(default = "default_key_s")] pub p2_fire: String,
+    #[serde(default = "default_key_e")] pub p2_attack: String,
+}
+
+impl InputConfig {
+    /// The key bound to a player-one `action`.
+    pub fn key_for(&self, action: InputAction) -> &str {
+        match action {
+            InputAction::MoveLeft => &self.left,
+            InputAction::MoveRight => &self.right,
+            InputAction::Jump => &self.jump,
+            InputAction::Fire => &self.fire,
+            InputAction::Attack => &self.attack,
+            InputAction::Up => &self.up,
+            InputAction::Down => &self.down,
+            InputAction::Quit => &self.quit,
+            InputAction::RestartLevel => &self.restart_level,
+        }
+    }
+
+    fn key_for_mut(&mut self, action: InputAction) -> &mut String {
+        match action {
+            InputAction::MoveLeft => &mut self.left,
+            InputAction::MoveRight => &mut self.right,
+            InputAction::Jump => &mut self.jump,
+            InputAction::Fire => &mut self.fire,
+            InputAction::Attack => &mut self.attack,
+            InputAction::Up => &mut self.up,
+            InputAction::Down => &mut self.down,
+            InputAction::Quit => &mut self.quit,
+            InputAction::RestartLevel => &mut self.restart_level,
+        }
+    }
+
+    /// Binds `key` to `action`. A player-one action that already used `key` takes over
+    /// `action`'s previous key, so no two actions ever share one; that action is returned.
+    pub fn rebind(&mut self, action: InputAction, key: &str) -> Option<InputAction> {
+        let previous = self.key_for(action).to_string();
+        let owner = InputAction::ALL.into_iter().find(|&other| other != action && self.key_for(other).eq_ignore_ascii_case(key));
+        if let Some(owner) = owner { *self.key_for_mut(owner) = previous; }
+        *self.key_for_mut(action) = key.to_string();
+        owner
+    }
+
+    /// The second-player or debug binding that also uses `key`, by config name; those are not swapped.
+    pub fn shared_with(&self, key: &str) -> Option<&'static str> {
+        [
+            ("p2_left", &self.p2_left), ("p2_right", &self.p2_right), ("p2_jump", &self.p2_jump),
+            ("p2_fire", &self.p2_fire), ("p2_attack", &self.p2_attack),
+            ("debug_toggle", &self.debug_toggle), ("toggle_collision_boxes", &self.toggle_collision_boxes),
+            ("toggle_hotspots", &self.toggle_hotspots), ("toggle_tile_grid", &self.toggle_tile_grid),
+            ("record_toggle", &self.record_toggle), ("save_replay", &self.save_replay),
+            ("export_benchmarks", &self.export_benchmarks), ("reload_config", &self.reload_config),
+            ("toggle_free_camera", &self.toggle_free_camera), ("inspect_entity", &self.inspect_entity),
+            ("toggle_gif_capture", &self.toggle_gif_capture), ("screenshot", &self.screenshot),
+            ("pause_simulation", &self.pause_simulation), ("step_frame", &self.step_frame),
+            ("cycle_time_scale", &self.cycle_time_scale), ("free_camera_fast", &self.free_camera_fast),
+            ("replay_seek_back", &self.replay_seek_back), ("replay_seek_forward", &self.replay_seek_forward),
+        ].into_iter().find(|(_, bound)| bound.eq_ignore_ascii_case(key)).map(|(name, _)| name)
+    }
 }
 
 fn default_key_up() -> String { "Up".to_string() }
 fn default_key_down() -> String { "Down".to_string() }
+fn default_key_r() -> String { "R".to_string() }
+fn default_true() -> bool { true }
+
+fn default_key_f2() -> String { "F2".to_string() }
+fn default_key_f3() -> String { "F3".to_string() }
+fn default_key_f4() -> String { "F4".to_string() }
 fn default_key_f5() -> String { "F5".to_string() }
 fn default_key_f6() -> String { "F6".to_string() }
+fn default_key_f7() -> String { "F7".to_string() }
+fn default_key_f8() -> String { "F8".to_string() }
+fn default_key_f9() -> String { "F9".to_string() }
+fn default_key_f10() -> String { "F10".to_string() }
+fn default_key_f11() -> String { "F11".to_string() }
+fn default_key_f12() -> String { "F12".to_string() }
+fn default_key_left_shift() -> String { "Left Shift".to_string() }
+fn default_key_a() -> String { "A".to_string() }
+fn default_key_d() -> String { "D".to_string() }
+fn default_key_w() -> String { "W".to_string() }
+fn default_key_x() -> String { "X".to_string() }
+fn default_key_s() -> String { "S".to_string() }
+fn default_key_c() -> String { "C".to_string() }
+fn default_key_e() -> String { "E".to_string() }
+fn default_key_p() -> String { "P".to_string() }
+fn default_key_n() -> String { "N".to_string() }
+fn default_key_t() -> String { "T".to_string() }
+fn default_key_comma() -> String { ",".to_string() }
+fn default_key_period() -> String { ".".to_string() }
+
+/// # Concept: Volume Levels
+/// Linear amplitudes in the range `[0.0, 1.0]`; music and effects are each scaled by `master_volume`.
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq)]
+pub struct AudioSettings {
+    #[serde(default = "default_volume")] pub master_volume: f64,
+    #[serde(default = "default_volume")] pub music_volume: f64,
+    #[serde(default = "default_volume")] pub sfx_volume: f64,
+}
+
+fn default_volume() -> f64 { 1.0 }
+
+impl Default for AudioSettings {
+    fn default() -> Self { Self { master_volume: 1.0, music_volume: 1.0, sfx_volume: 1.0 } }
+}
+
+impl AudioSettings {
+    /// Combined gain applied to music tracks.
+    pub fn music_gain(&self) -> f64 { self.master_volume * self.music_volume }
+    /// Combined gain applied to sound effects.
+    pub fn sfx_gain(&self) -> f64 { self.master_volume * self.sfx_volume }
+}
+
+/// Where options chosen in the menu are stored between sessions.
+pub const USER_SETTINGS_PATH: &str = "settings.toml";
+
+/// # Concept: User Settings
+/// Options changed at runtime (e.g. from the Options menu). Only sections that were
+/// changed are written; everything else keeps the value from `config.toml`.
+#[derive(Deserialize, Serialize, Clone, Default, Debug, PartialEq)]
+pub struct UserSettings {
+    #[serde(default, skip_serializing_if = "Option::is_none")] pub audio: Option<AudioSettings>,
+    #[serde(default, skip_serializing_if = "Option::is_none")] pub input: Option<InputConfig>,
+}
+
+impl UserSettings {
+    /// Reads the settings file; a missing or unreadable file yields empty settings.
+    pub fn load(path: &str) -> Self {
+        let Ok(source) = fs::read_to_string(path) else { return Self::default() };
+        toml::from_str(&source).unwrap_or_else(|e| {
+            eprintln!("[Settings] Ignoring malformed '{}': {}", path, e);
+            Self::default()
+        })
+    }
+
+    /// Writes the settings file, replacing its previous contents.
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        let source = toml::to_string(self).map_err(|e| e.to_string())?;
+        fs::write(path, source).map_err(|e| e.to_string())
+    }
+
+    /// Overrides the matching sections of `config` with the stored values.
+    pub fn apply(&self, config: &mut Config) {
+        if let Some(audio) = self.audio { config.audio = audio; }
+        if let Some(input) = &self.input { config.input = input.clone(); }
+    }
+}
 
 /// Decodes the engine configuration from disk.
 pub fn load_config() -> Result<Config, String> {
     let config_str = fs::read_to_string("config.toml").map_err(|e| e.to_string())?;
-    let config: Config = toml::from_str(&config_str).map_err(|e| e.to_string())?;
-    Ok(config)
+    parse_config(&config_str)
+}
+
+/// Decodes the engine configuration from TOML text already in memory.
+pub fn parse_config(toml_str: &str) -> Result<Config, String> {
+    toml::from_str(toml_str).map_err(|e| e.to_string())
+}
+
+/// The shipped `config.toml`, compiled in so tests and tools need no working directory.
+impl Default for Config {
+    fn default() -> Self {
+        parse_config(include_str!("../../config.toml")).expect("bundled config.toml is valid")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rebinding_a_taken_key_swaps_the_two_actions() {
+        let mut input = Config::default().input;
+        let (jump, left) = (input.jump.clone(), input.left.clone());
+
+        assert_eq!(input.rebind(InputAction::Jump, &left.to_uppercase()), Some(InputAction::MoveLeft), "Key names match regardless of case");
+        assert!(input.key_for(InputAction::Jump).eq_ignore_ascii_case(&left));
+        assert_eq!(input.key_for(InputAction::MoveLeft), jump, "The old owner takes the freed key");
+
+        assert_eq!(input.rebind(InputAction::Attack, "V"), None);
+        assert_eq!(input.key_for(InputAction::Attack), "V");
+        assert_eq!(input.shared_with("F1"), Some("debug_toggle"));
+        assert_eq!(input.shared_with("V"), None);
+    }
+
+    #[test]
+    fn test_saved_bindings_override_the_config() {
+        let mut input = Config::default().input;
+        input.rebind(InputAction::Fire, "Z");
+        let settings = UserSettings { input: Some(input), ..Default::default() };
+        let reloaded: UserSettings = toml::from_str(&toml::to_string(&settings).unwrap()).unwrap();
+        assert_eq!(reloaded, settings);
+
+        let mut config = Config::default();
+        reloaded.apply(&mut config);
+        assert_eq!(config.input.fire, "Z");
+    }
 }
@@ -5,6 +5,7 @@
 //! high-level metadata for players, enemies, animations, and the UI.
 
 use crate::math::Vector2D;
+use super::particles::ParticleConfig;
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::fs;
@@ -20,11 +21,17 @@ pub struct GameConfig {
     #[serde(default)] pub sound_events: HashMap<String, String>,
     #[serde(default)] pub soundtrack_properties: HashMap<String, SoundtrackConfig>,
     #[serde(default)] pub textures: HashMap<String, String>,
+    /// Extra fonts by name (`name = "path.ttf"`), usable alongside the built-in "debug" font.
+    #[serde(default)] pub fonts: HashMap<String, String>,
     #[serde(default)] pub prefabs: HashMap<String, PrefabConfig>,
     #[serde(default)] pub enemy_behavior: HashMap<String, EnemyBehaviorConfig>,
+    /// Boss tunings by key, referenced from `Boss` prefab components.
+    #[serde(default)] pub bosses: HashMap<String, BossConfig>,
     #[serde(default)] pub menu: MenuConfig,
     #[serde(default)] pub parallax: ParallaxConfig,
     #[serde(default)] pub ui: UIConfig,
+    #[serde(default)] pub particles: ParticleConfig,
+    #[serde(default)] pub footsteps: FootstepConfig,
 }
 
 /// # Concept: HUD Configuration
@@ -32,6 +39,13 @@ pub struct GameConfig {
 pub struct UIConfig {
     #[serde(default = "default_hearts_pos")] pub hearts_pos: UIPosition,
     #[serde(default = "default_coins_pos")] pub coins_pos: UIPosition,
+    #[serde(default = "default_score_pos")] pub score_pos: UIPosition,
+    /// Top-left of the first per-player health bar (co-op) or of the health hearts (single player).
+    #[serde(default = "default_health_bars_pos")] pub health_bars_pos: UIPosition,
+    /// Where the level name is shown when a level starts.
+    #[serde(default = "default_level_name_pos")] pub level_name_pos: UIPosition,
+    /// How long the level name stays up in **seconds**, including its final one-second fade.
+    #[serde(default = "default_level_name_duration")] pub level_name_duration: f32,
 }
 
 impl Default for UIConfig {
@@ -39,33 +53,108 @@ impl Default for UIConfig {
         Self {
             hearts_pos: default_hearts_pos(),
             coins_pos: default_coins_pos(),
+            score_pos: default_score_pos(),
+            health_bars_pos: default_health_bars_pos(),
+            level_name_pos: default_level_name_pos(),
+            level_name_duration: default_level_name_duration(),
         }
     }
 }
 
-#[derive(Deserialize, Clone, Copy)] pub struct UIPosition { pub x: i32, pub y: i32 }
+/// The screen edge or corner a HUD widget is pinned to.
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum UIAnchor { #[default] TopLeft, TopCenter, TopRight, CenterLeft, Center, CenterRight, BottomLeft, BottomCenter, BottomRight }
 
-fn default_hearts_pos() -> UIPosition { UIPosition { x: 20, y: 20 } }
-fn default_coins_pos() -> UIPosition { UIPosition { x: 20, y: 100 } }
+/// A HUD widget placement. `x`/`y` are screen **pixels** measured inward from the `anchor`
+/// (leftward from right anchors, upward from bottom ones); on centered axes they are added as is.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)] pub struct UIPosition { pub x: i32, pub y: i32, #[serde(default)] pub anchor: UIAnchor }
+
+impl UIPosition {
+    /// Top-left corner of a widget `size` **pixels** large on a screen `screen` **pixels** large.
+    /// Resolved every frame, so widgets follow window resizes and aspect ratio changes.
+    pub fn resolve(self, screen: (u32, u32), size: (u32, u32)) -> (i32, i32) {
+        use UIAnchor::*;
+        // 0 = start edge, 1 = centered, 2 = end edge.
+        let (horizontal, vertical) = match self.anchor {
+            TopLeft => (0, 0), TopCenter => (1, 0), TopRight => (2, 0),
+            CenterLeft => (0, 1), Center => (1, 1), CenterRight => (2, 1),
+            BottomLeft => (0, 2), BottomCenter => (1, 2), BottomRight => (2, 2),
+        };
+        let place = |edge, offset: i32, screen: u32, size: u32| match edge {
+            0 => offset,
+            1 => (screen as i32 - size as i32) / 2 + offset,
+            _ => screen as i32 - size as i32 - offset,
+        };
+        (place(horizontal, self.x, screen.0, size.0), place(vertical, self.y, screen.1, size.1))
+    }
+}
+
+fn default_hearts_pos() -> UIPosition { UIPosition { x: 20, y: 20, anchor: UIAnchor::TopLeft } }
+fn default_coins_pos() -> UIPosition { UIPosition { x: 20, y: 100, anchor: UIAnchor::TopLeft } }
+fn default_health_bars_pos() -> UIPosition { UIPosition { x: 20, y: 170, anchor: UIAnchor::TopLeft } }
+fn default_score_pos() -> UIPosition { UIPosition { x: 20, y: 20, anchor: UIAnchor::TopRight } }
+fn default_level_name_pos() -> UIPosition { UIPosition { x: 0, y: 160, anchor: UIAnchor::TopCenter } }
+fn default_level_name_duration() -> f32 { 3.0 }
+
+/// # Concept: Footstep Audio
+/// Foot-plant frames per walk animation and the sound (an `[audio]` key) for each tile surface.
+#[derive(Deserialize, Clone, Default)]
+pub struct FootstepConfig {
+    /// Animation name -> frame indices where a foot touches the ground.
+    #[serde(default)] pub foot_plants: HashMap<String, Vec<usize>>,
+    /// Sound for tiles without a known `surface` property.
+    #[serde(default)] pub default_sound: Option<String>,
+    /// TSX `surface` property value (e.g. "grass") -> sound.
+    #[serde(default)] pub surfaces: HashMap<String, String>,
+    /// Playback volume in the range `[0.0, 1.0]`.
+    #[serde(default = "default_footstep_volume")] pub volume: f64,
+}
+
+fn default_footstep_volume() -> f64 { 0.4 }
 
 /// # Concept: Parallax Configuration
-#[derive(Deserialize, Clone, Default)] pub struct ParallaxConfig { pub layers: Vec<ParallaxLayerConfig> }
-#[derive(Deserialize, Clone)] pub struct ParallaxLayerConfig { pub texture: String, pub z_index: u8, pub scroll_speed_x: f32, pub scroll_speed_y: f32 }
+/// Layers with a `z_index` above `tile_z_index` are drawn behind the tile map, the rest in front of it.
+#[derive(Deserialize, Clone)]
+pub struct ParallaxConfig {
+    #[serde(default)] pub layers: Vec<ParallaxLayerConfig>,
+    #[serde(default = "default_tile_z_index")] pub tile_z_index: u8,
+}
+#[derive(Deserialize, Clone)]
+pub struct ParallaxLayerConfig {
+    /// A key from `[textures]` or a direct image path.
+    pub texture: String, pub z_index: u8, pub scroll_speed_x: f32, pub scroll_speed_y: f32,
+    /// Whether the layer also wraps vertically (useful for tall maps).
+    #[serde(default)] pub repeat_y: bool,
+}
+
+impl Default for ParallaxConfig { fn default() -> Self { Self { layers: Vec::new(), tile_z_index: default_tile_z_index() } } }
+fn default_tile_z_index() -> u8 { 200 }
 
 /// # Concept: Soundtrack Metadata
-#[derive(Deserialize, Clone)] pub struct SoundtrackConfig { pub bpm: Option<f32> }
+/// `layers` maps a [crate::audio::MusicLayer] name to a stem file that plays in sync with the soundtrack.
+#[derive(Deserialize, Clone)] pub struct SoundtrackConfig { pub bpm: Option<f32>, #[serde(default)] pub layers: HashMap<String, String> }
 
 /// # Concept: Menu Configuration
 #[derive(Deserialize, Clone, Default, Debug)]
 pub struct MenuConfig {
     pub font_size: u32,
+    /// Font for menu items, by `[fonts]` name.
+    #[serde(default = "default_menu_font")] pub font: String,
+    /// Font and size of screen titles; the size falls back to `font_size`.
+    #[serde(default = "default_menu_font")] pub title_font: String,
+    #[serde(default)] pub title_font_size: Option<u32>,
     #[allow(dead_code)]
     pub item_spacing: i32,
     pub selected_color: [u8; 3],
     pub unselected_color: [u8; 3],
+    /// Shows the cursor in menus; hovering an item selects it and clicking activates it.
+    #[serde(default = "default_true")] pub mouse: bool,
     #[serde(default)] pub screens: HashMap<String, MenuScreenConfig>,
 }
 
+fn default_menu_font() -> String { "debug".to_string() }
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct MenuScreenConfig {
     pub title: String, #[allow(dead_code)] pub title_x: i32, pub title_y: i32,
@@ -78,18 +167,43 @@ pub struct MenuItemConfig { pub label: String, #[serde(flatten)] pub item_type:
 
 #[derive(Deserialize, Clone, Debug)]
 #[serde(tag = "type")]
-pub enum MenuItemType { Action { action: String }, Selector { options: Vec<String>, variable: String } }
+pub enum MenuItemType {
+    Action { action: String },
+    Selector { options: Vec<String>, variable: String },
+    /// A continuous value in `[min, max]`, nudged by `step` with left/right.
+    Slider { variable: String, min: f32, max: f32, step: f32 },
+    /// Shows the key bound to `action`; selecting it waits for a new key.
+    Binding { action: crate::input::InputAction },
+}
 
 /// # Concept: Enemy Behavior Logic
+/// Rhythm jumping and chasing; a behavior may tune either and leave the other at its defaults.
 #[derive(Deserialize, Clone)]
 pub struct EnemyBehaviorConfig {
-    #[allow(dead_code)] pub jump_interval: f32, pub jump_strength: f32,
-    pub active_beats: u32, pub rest_beats: u32,
-    #[allow(dead_code)] pub jump_sound: String, #[allow(dead_code)] pub rhythm_offset: f32,
+    #[serde(default)] #[allow(dead_code)] pub jump_interval: f32, #[serde(default)] pub jump_strength: f32,
+    #[serde(default)] pub active_beats: u32, #[serde(default)] pub rest_beats: u32,
+    #[serde(default)] #[allow(dead_code)] pub jump_sound: String, #[serde(default)] #[allow(dead_code)] pub rhythm_offset: f32,
     #[serde(default = "default_beats_per_jump")] pub beats_per_jump: u32,
+    /// The onset stream ("bass", "mid" or "high") this behavior counts beats on.
+    #[serde(default)] pub band: crate::audio_analysis::Band,
+    /// Distance in **pixels** at which a visible player is chased; `0` never chases.
+    #[serde(default)] pub detection_radius: f32,
+    /// Top speed in **pixels per second** while chasing.
+    #[serde(default = "default_chase_speed")] pub chase_speed: f32,
+    /// Keep chasing over platform edges instead of stopping at them.
+    #[serde(default)] pub reckless: bool,
+    /// Seconds between shots at a detected player; `0` chases instead of shooting.
+    #[serde(default)] pub fire_interval: f32,
+    /// Shot speed in **pixels per second**, overriding the prefab's.
+    #[serde(default = "default_enemy_projectile_speed")] pub projectile_speed: f32,
+    /// Prefab (sprite, hitbox, damage, lifetime) of the fired shots.
+    #[serde(default = "default_enemy_projectile_prefab")] pub projectile_prefab: String,
 }
 
 fn default_beats_per_jump() -> u32 { 1 }
+fn default_chase_speed() -> f32 { 100.0 }
+fn default_enemy_projectile_speed() -> f32 { 160.0 }
+fn default_enemy_projectile_prefab() -> String { "EnemyBullet".to_string() }
 
 /// # Concept: Entity Prefabs
 #[derive(Deserialize, Clone)] pub struct PrefabConfig { pub components: Vec<ComponentConfig> }
@@ -99,13 +213,101 @@ fn default_beats_per_jump() -> u32 { 1 }
 pub enum ComponentConfig {
     Position, Velocity { x: f32, y: f32 }, Acceleration { x: f32, y: f32 },
     Renderable { draw_width: u32, draw_height: u32, z_index: u8, #[serde(default)] horizontal_offset: i32, #[serde(default)] vertical_offset: i32 },
-    Animation { animations: Vec<String>, initial_animation: String },
-    Collision { width: u32, height: u32 }, Gravity,
-    Patrol { speed: f32, #[serde(default = "default_anim_prefix")] anim_prefix: String },
-    EnemyTag, GoldCoin, Goal, StateComponent { initial_state: String },
+    /// `phase` decides whether instances animate in lockstep, from a random frame, or from their spawn time.
+    Animation { animations: Vec<String>, initial_animation: String, #[serde(default)] phase: AnimationPhase },
+    /// `use_object_size` sizes the box from the rectangle drawn in Tiled, falling back to `width`/`height`.
+    Collision { width: u32, height: u32, #[serde(default)] use_object_size: bool }, Gravity,
+    /// The initial heading comes from `direction` ("left"/"right"), else from the sign of `speed`.
+    /// `behavior` names the `[enemy_behavior]` entry that lets it chase; a TMX `behavior` property overrides it.
+    Patrol { speed: f32, #[serde(default = "default_anim_prefix")] anim_prefix: String, #[serde(default)] direction: Option<String>, #[serde(default = "default_true")] turn_at_ledge: bool, #[serde(default)] behavior: Option<String> },
+    EnemyTag, GoldCoin, Goal, Checkpoint, StateComponent { initial_state: String },
+    /// Restores `amount` health on touch; a TMX `amount` property overrides it.
+    HealthPickup { #[serde(default = "default_health_pickup_amount")] amount: u32 },
+    /// TMX properties `event`/`event_name`, `once`, `delay`, `shake`, `spawn` and `spawn_at` override these.
+    Trigger { #[serde(default)] event_name: String, #[serde(default)] once: bool, #[serde(default)] delay: f32, #[serde(default)] shake: f32 },
+    /// A shot; `speed` in pixels per second, `lifetime` in seconds. `gravity` makes it arc.
+    Projectile { #[serde(default = "default_projectile_damage")] damage: u32, speed: f32, lifetime: f32, #[serde(default)] gravity: bool },
+    /// Continuously emits particles from `[particles.presets]` at `rate` per second.
+    ParticleEmitter { preset: String, rate: f32 },
+    /// A boss tuned by `[bosses.<config_key>]`; adds its Health and state machine. A TMX `boss` property overrides the key.
+    Boss { config_key: String },
+}
+
+/// # Concept: Boss Tuning
+/// The base behavior of a boss; `phases` take over as its health drops.
+#[derive(Deserialize, Clone, Debug)]
+pub struct BossConfig {
+    pub max_health: u32,
+    /// Time in **seconds** spent winding up between charges.
+    pub idle_time: f32,
+    /// Charge speed in **pixels per second**.
+    pub charge_speed: f32,
+    /// Longest charge in **seconds**; hitting a wall ends it early with a stun.
+    pub charge_time: f32,
+    /// Time in **seconds** the boss stays stunned (and vulnerable) after crashing into a wall.
+    pub stun_time: f32,
+    /// Level loaded once the boss is defeated; a TMX `next_level` property on the boss overrides it.
+    #[serde(default)] pub next_level: Option<String>,
+    /// Charges play `<anim_prefix>_walk_left` / `_walk_right`; without it the animation is left alone.
+    #[serde(default)] pub anim_prefix: Option<String>,
+    /// Faster, angrier tunings, each active once health is at or below its threshold.
+    #[serde(default)] pub phases: Vec<BossPhaseConfig>,
+}
+
+/// Overrides for one boss phase; unset values keep the previous tuning.
+#[derive(Deserialize, Clone, Debug)]
+pub struct BossPhaseConfig {
+    pub health_at_most: u32,
+    #[serde(default)] pub idle_time: Option<f32>,
+    #[serde(default)] pub charge_speed: Option<f32>,
+    #[serde(default)] pub charge_time: Option<f32>,
+    #[serde(default)] pub stun_time: Option<f32>,
+}
+
+/// The tuning in effect for one phase.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BossTuning {
+    pub idle_time: f32,
+    pub charge_speed: f32,
+    pub charge_time: f32,
+    pub stun_time: f32,
+}
+
+impl BossConfig {
+    /// Phase for `health`: the number of thresholds it has fallen to, so phases only ever advance.
+    pub fn phase_for(&self, health: u32) -> usize {
+        self.phases.iter().filter(|p| health <= p.health_at_most).count()
+    }
+
+    /// The base tuning with the overrides of phases `1..=phase` applied, strongest threshold last.
+    pub fn tuning(&self, phase: usize) -> BossTuning {
+        let mut tuning = BossTuning { idle_time: self.idle_time, charge_speed: self.charge_speed, charge_time: self.charge_time, stun_time: self.stun_time };
+        let mut phases: Vec<_> = self.phases.iter().collect();
+        phases.sort_by_key(|p| std::cmp::Reverse(p.health_at_most));
+        for p in phases.into_iter().take(phase) {
+            tuning.idle_time = p.idle_time.unwrap_or(tuning.idle_time);
+            tuning.charge_speed = p.charge_speed.unwrap_or(tuning.charge_speed);
+            tuning.charge_time = p.charge_time.unwrap_or(tuning.charge_time);
+            tuning.stun_time = p.stun_time.unwrap_or(tuning.stun_time);
+        }
+        tuning
+    }
+}
+
+/// Where an animated prefab's playback starts relative to other instances.
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub enum AnimationPhase {
+    /// Each instance starts on frame 0 when spawned.
+    #[default] Independent,
+    /// Every instance follows the world's animation clock and shows the same frame.
+    Synced,
+    /// Each instance starts at a random point of its clip, drawn from the world's seeded RNG.
+    Random,
 }
 
 fn default_anim_prefix() -> String { "enemy".to_string() }
+fn default_projectile_damage() -> u32 { 1 }
+fn default_health_pickup_amount() -> u32 { 1 }
 
 /// # Concept: Player Parameters
 #[derive(Deserialize, Clone)]
@@ -114,8 +316,43 @@ pub struct PlayerConfig {
     pub draw_width: u32, pub draw_height: u32,
     pub horizontal_draw_offset: i32, pub vertical_draw_offset: i32,
     pub respawn_pos: Vector2D, pub lives: u32, pub max_health: u32,
+    /// Number of local players sharing the screen (1 or 2).
+    #[serde(default = "default_local_players")] pub local_players: usize,
+    /// Spawn offset in **pixels** of each additional player relative to `start_pos`.
+    #[serde(default = "default_coop_spawn_offset")] pub coop_spawn_offset: Vector2D,
+    /// Sprite depth of the player (higher = further back).
+    #[serde(default = "default_player_z_index")] pub z_index: u8,
+    /// Prefab spawned when the player fires.
+    #[serde(default = "default_projectile_prefab")] pub projectile_prefab: String,
+    /// Minimum time in **seconds** between two shots.
+    #[serde(default = "default_fire_cooldown")] pub fire_cooldown: f32,
+    /// How long a melee swing lasts (in **seconds**); its hitbox is live for the whole swing.
+    #[serde(default = "default_attack_duration")] pub attack_duration: f32,
+    /// Time in **seconds** from the start of one swing until the next may begin.
+    #[serde(default = "default_attack_cooldown")] pub attack_cooldown: f32,
+    /// Size in **pixels** of the hitbox placed in front of the player.
+    #[serde(default = "default_attack_width")] pub attack_width: u32,
+    #[serde(default = "default_attack_height")] pub attack_height: u32,
+    /// Collision height in **pixels** while crouching; the box shrinks from the top so the feet stay put.
+    #[serde(default = "default_crouch_height")] pub crouch_height: u32,
+    /// Fraction of the normal top speed available while crouching.
+    #[serde(default = "default_crouch_speed_factor")] pub crouch_speed_factor: f32,
 }
 
+fn default_projectile_prefab() -> String { "PlayerBullet".to_string() }
+fn default_fire_cooldown() -> f32 { 0.3 }
+fn default_attack_duration() -> f32 { 0.2 }
+fn default_attack_cooldown() -> f32 { 0.4 }
+fn default_attack_width() -> u32 { 28 }
+fn default_attack_height() -> u32 { 24 }
+fn default_crouch_height() -> u32 { 16 }
+fn default_crouch_speed_factor() -> f32 { 0.4 }
+
+fn default_player_z_index() -> u8 { 100 }
+
+fn default_local_players() -> usize { 1 }
+fn default_coop_spawn_offset() -> Vector2D { Vector2D::new(32.0, 0.0) }
+
 /// # Concept: World Parameters
 #[derive(Deserialize, Clone)] pub struct WorldConfig { pub width: f32, pub death_plane_y: f32 }
 
@@ -126,8 +363,134 @@ pub struct GameplayConfig {
     pub damage_invincibility_duration: f32, pub respawn_invincibility_duration: f32,
     pub game_over_duration: f32, pub game_over_texture: String,
     pub explosion: ExplosionConfig, pub audio: AudioSettingsConfig,
+    /// Keep collected coins when a life is lost (otherwise the count resets to zero on respawn).
+    #[serde(default = "default_true")] pub carry_coins_on_death: bool,
+    /// Keep collected coins when entering a new level (otherwise each level starts at zero).
+    #[serde(default = "default_true")] pub carry_coins_on_level: bool,
+    /// Hazard tiles drain all health at once instead of a single heart.
+    #[serde(default)] pub hazard_instant_kill: bool,
+    /// Blinks per second while a player is invincible.
+    #[serde(default = "default_flash_frequency")] pub invincibility_flash_frequency: f32,
+    /// Player opacity during the "off" half of each blink (0 = invisible).
+    #[serde(default = "default_flash_alpha")] pub invincibility_flash_alpha: u8,
+    /// Which outcome wins when a player reaches a goal and dies in the same moment.
+    #[serde(default)] pub goal_death_precedence: GoalDeathPrecedence,
+    /// Time in **seconds** after a hit during which horizontal input is ignored, so the knockback lands.
+    #[serde(default = "default_knockback_control_lock")] pub knockback_control_lock: f32,
+    #[serde(default)] pub camera_shake: CameraShakeConfig,
+    #[serde(default)] pub landing: LandingConfig,
+    #[serde(default)] pub score: ScoreConfig,
+    /// Distance beyond the camera view, in multiples of the view size, at which entities stop simulating.
+    #[serde(default = "default_dormancy_margin")] pub dormancy_margin: f32,
+}
+
+/// Trauma-based screen shake: events add trauma, the offset is `trauma²` times `max_offset`.
+#[derive(Deserialize, Clone)]
+pub struct CameraShakeConfig {
+    /// Largest displacement in **pixels**, reached at full trauma.
+    #[serde(default = "default_shake_max_offset")] pub max_offset: f32,
+    /// Trauma lost per **second**.
+    #[serde(default = "default_shake_decay")] pub decay: f32,
+    /// Noise samples per **second**; higher values rattle, lower values sway.
+    #[serde(default = "default_shake_frequency")] pub frequency: f32,
+    /// Trauma added when a player takes damage.
+    #[serde(default = "default_damage_trauma")] pub damage_trauma: f32,
+    /// Trauma added when a player stomps an enemy.
+    #[serde(default = "default_stomp_trauma")] pub stomp_trauma: f32,
+}
+
+impl Default for CameraShakeConfig {
+    fn default() -> Self {
+        Self { max_offset: default_shake_max_offset(), decay: default_shake_decay(), frequency: default_shake_frequency(), damage_trauma: default_damage_trauma(), stomp_trauma: default_stomp_trauma() }
+    }
+}
+
+/// How hard a player lands decides how loud the landing sounds and whether the screen shakes.
+/// Speeds are downward velocities in **pixels per second** at the moment of touchdown.
+#[derive(Deserialize, Clone)]
+pub struct LandingConfig {
+    /// Landings slower than this are silent (small steps, respawns settling onto the floor).
+    #[serde(default = "default_landing_min_speed")] pub min_speed: f32,
+    /// Speed at which the landing sound reaches full volume; it fades in linearly from `min_speed`.
+    #[serde(default = "default_landing_full_volume_speed")] pub full_volume_speed: f32,
+    /// Landings at least this fast count as heavy and shake the camera.
+    #[serde(default = "default_landing_heavy_speed")] pub heavy_speed: f32,
+    /// Trauma added by a heavy landing.
+    #[serde(default = "default_landing_heavy_trauma")] pub heavy_trauma: f32,
+}
+
+impl Default for LandingConfig {
+    fn default() -> Self {
+        Self { min_speed: default_landing_min_speed(), full_volume_speed: default_landing_full_volume_speed(), heavy_speed: default_landing_heavy_speed(), heavy_trauma: default_landing_heavy_trauma() }
+    }
+}
+
+impl LandingConfig {
+    /// Landing sound volume in `[0.0, 1.0]` for an `impact_speed` in **pixels per second**; 0 means silent.
+    pub fn volume(&self, impact_speed: f32) -> f64 {
+        if impact_speed < self.min_speed { return 0.0; }
+        let range = self.full_volume_speed - self.min_speed;
+        if range <= 0.0 { return 1.0; }
+        ((impact_speed - self.min_speed) / range).clamp(0.1, 1.0) as f64
+    }
+}
+
+fn default_landing_min_speed() -> f32 { 120.0 }
+fn default_landing_full_volume_speed() -> f32 { 600.0 }
+fn default_landing_heavy_speed() -> f32 { 560.0 }
+fn default_landing_heavy_trauma() -> f32 { 0.35 }
+
+fn default_shake_max_offset() -> f32 { 20.0 }
+fn default_shake_decay() -> f32 { 1.5 }
+fn default_shake_frequency() -> f32 { 25.0 }
+fn default_damage_trauma() -> f32 { 0.8 }
+fn default_stomp_trauma() -> f32 { 0.3 }
+
+/// Points awarded during play and the end-of-level tally.
+#[derive(Deserialize, Clone)]
+pub struct ScoreConfig {
+    /// Points per collected coin.
+    #[serde(default = "default_coin_points")] pub coin_points: u32,
+    /// Points for the first stomp of a jump; each further stomp before landing multiplies them.
+    #[serde(default = "default_stomp_points")] pub stomp_points: u32,
+    /// Highest stomp multiplier a combo can reach.
+    #[serde(default = "default_max_combo")] pub max_combo: u32,
+    /// Level time in **seconds** under which a time bonus is paid.
+    #[serde(default = "default_par_time")] pub par_time: f32,
+    /// Bonus points per **second** finished under `par_time`.
+    #[serde(default = "default_time_bonus_per_second")] pub time_bonus_per_second: u32,
+    /// How long the tally stays up in **seconds** unless a button skips it.
+    #[serde(default = "default_tally_duration")] pub tally_duration: f32,
+}
+
+impl Default for ScoreConfig {
+    fn default() -> Self {
+        Self { coin_points: default_coin_points(), stomp_points: default_stomp_points(), max_combo: default_max_combo(), par_time: default_par_time(), time_bonus_per_second: default_time_bonus_per_second(), tally_duration: default_tally_duration() }
+    }
 }
 
+fn default_coin_points() -> u32 { 10 }
+fn default_stomp_points() -> u32 { 100 }
+fn default_max_combo() -> u32 { 8 }
+fn default_par_time() -> f32 { 120.0 }
+fn default_time_bonus_per_second() -> u32 { 10 }
+fn default_tally_duration() -> f32 { 6.0 }
+
+/// Resolves a goal reached over a pit: either the level is completed or the life is lost, never both.
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub enum GoalDeathPrecedence {
+    /// Deaths are ignored once the goal is touched; the level transition runs normally.
+    #[default] Goal,
+    /// Dead players cannot complete the level, and a death cancels a pending level transition.
+    Death,
+}
+
+fn default_true() -> bool { true }
+fn default_flash_frequency() -> f32 { 5.0 }
+fn default_flash_alpha() -> u8 { 64 }
+fn default_knockback_control_lock() -> f32 { 0.1 }
+fn default_dormancy_margin() -> f32 { 1.5 }
+
 impl Default for GameplayConfig {
     fn default() -> Self {
         Self {
@@ -139,11 +502,44 @@ impl Default for GameplayConfig {
             game_over_texture: "game_over_3".to_string(),
             explosion: ExplosionConfig::default(),
             audio: AudioSettingsConfig::default(),
+            carry_coins_on_death: true,
+            carry_coins_on_level: true,
+            hazard_instant_kill: false,
+            invincibility_flash_frequency: default_flash_frequency(),
+            invincibility_flash_alpha: default_flash_alpha(),
+            goal_death_precedence: GoalDeathPrecedence::default(),
+            knockback_control_lock: default_knockback_control_lock(),
+            camera_shake: CameraShakeConfig::default(),
+            landing: LandingConfig::default(),
+            score: ScoreConfig::default(),
+            dormancy_margin: default_dormancy_margin(),
         }
     }
 }
 
-#[derive(Deserialize, Clone, Default)] pub struct AudioSettingsConfig { pub max_hearing_distance: f32, pub volume_falloff_power: f32 }
+#[derive(Deserialize, Clone, Default)]
+pub struct AudioSettingsConfig {
+    pub max_hearing_distance: f32, pub volume_falloff_power: f32,
+    /// Time in **seconds** the old soundtrack fades out while the next fades in on level and character changes.
+    #[serde(default = "default_music_crossfade")] pub music_crossfade: f64,
+    /// Music layer faded in while an enemy is within `danger_radius` of the player; `None` disables it.
+    #[serde(default)] pub danger_layer: Option<String>,
+    /// Distance in **pixels** at which an enemy counts as near.
+    #[serde(default = "default_danger_radius")] pub danger_radius: f32,
+    /// Time in **seconds** a music layer takes to fade in or out.
+    #[serde(default = "default_layer_fade")] pub layer_fade: f64,
+    /// Copies of one sound effect that may play at once; the oldest is cut off beyond that. `0` is unlimited.
+    #[serde(default = "default_max_sound_instances")] pub max_sound_instances: usize,
+    /// Time in **seconds** during which a repeat of the same sound effect is ignored.
+    #[serde(default = "default_sound_debounce")] pub sound_debounce: f64,
+}
+
+fn default_music_crossfade() -> f64 { 1.5 }
+fn default_danger_radius() -> f32 { 320.0 }
+fn default_layer_fade() -> f64 { 0.75 }
+fn default_max_sound_instances() -> usize { 4 }
+fn default_sound_debounce() -> f64 { 0.02 }
+/// Hit/death effect. `z_index` above the player's draws it behind the player, below draws it in front.
 #[derive(Deserialize, Clone)] pub struct ExplosionConfig { pub width: u32, pub height: u32, pub horizontal_offset: i32, pub vertical_offset: i32, pub z_index: u8, pub animation_name: String }
 
 impl Default for ExplosionConfig {
@@ -160,19 +556,152 @@ impl Default for ExplosionConfig {
 }
 
 /// # Concept: Animation Metadata
+/// Frames come either from a grid (`start_x`, `frame_width`, `frame_count`, ...) or,
+/// when `frames` is set, from named rects in the JSON `atlas`.
 #[derive(Deserialize, Clone)]
 pub struct AnimationConfig {
-    pub texture: String, pub start_x: i32, pub start_y: i32,
-    pub frame_width: u32, pub frame_height: u32, pub frame_count: u32,
+    pub texture: String,
+    #[serde(default)] pub start_x: i32, #[serde(default)] pub start_y: i32,
+    #[serde(default)] pub frame_width: u32, #[serde(default)] pub frame_height: u32, #[serde(default)] pub frame_count: u32,
     pub frame_duration: u32, pub loops: bool, pub frame_padding: Option<u32>,
     pub reverse: Option<bool>,
+    /// TexturePacker JSON file describing the named frames of `texture`.
+    #[serde(default)] pub atlas: Option<String>,
+    /// Atlas frame names in playback order; overrides the grid when non-empty.
+    #[serde(default)] pub frames: Vec<String>,
+    /// Rects for `frames`, filled in by [GameConfig::resolve_atlas_frames].
+    #[serde(skip)] pub atlas_frames: Vec<sdl3::rect::Rect>,
+    /// Named events fired when playback enters a frame, e.g. `{ frame = 3, name = "attack_hit" }`.
+    #[serde(default)] pub events: Vec<AnimationEventConfig>,
+}
+
+/// A named event attached to one sprite-sheet frame of an animation.
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct AnimationEventConfig {
+    /// Sprite-sheet frame index; `reverse` playback does not change its meaning.
+    pub frame: usize,
+    pub name: String,
+}
+
+impl GameConfig {
+    /// Replaces atlas frame names with rects from the loaded atlases.
+    ///
+    /// # Side Effects
+    /// * Fills `AnimationConfig::atlas_frames`; fails on the first unknown frame name.
+    pub fn resolve_atlas_frames(&mut self, textures: &crate::texture_manager::TextureManager) -> Result<(), String> {
+        for (name, anim) in self.animation.iter_mut().filter(|(_, a)| !a.frames.is_empty()) {
+            anim.atlas_frames = anim.frames.iter()
+                .map(|frame| textures.atlas_frame(frame).ok_or_else(|| format!("Animation '{}' references unknown atlas frame '{}'", name, frame)))
+                .collect::<Result<_, _>>()?;
+        }
+        Ok(())
+    }
+
+    /// Checks cross-references between sections that serde cannot see.
+    /// Returns one human-readable line per problem; an empty list means the config is usable.
+    pub fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        let mut names: Vec<_> = self.prefabs.keys().collect();
+        names.sort();
+
+        // 1. Prefabs: referenced assets exist and component combinations make sense.
+        for name in names {
+            let components = &self.prefabs[name].components;
+            let has = |f: fn(&ComponentConfig) -> bool| components.iter().any(f);
+            for component in components {
+                match component {
+                    ComponentConfig::Animation { animations, initial_animation, .. } => {
+                        for anim in animations.iter().filter(|a| !self.animation.contains_key(*a)) {
+                            problems.push(format!("Prefab '{}' references unknown animation '{}'", name, anim));
+                        }
+                        if !animations.contains(initial_animation) {
+                            problems.push(format!("Prefab '{}' starts with '{}', which is not in its animations list", name, initial_animation));
+                        }
+                    }
+                    ComponentConfig::ParticleEmitter { preset, .. } if !self.particles.presets.contains_key(preset) => {
+                        problems.push(format!("Prefab '{}' emits unknown particle preset '{}'", name, preset));
+                    }
+                    ComponentConfig::Boss { config_key } if !self.bosses.contains_key(config_key) => {
+                        problems.push(format!("Prefab '{}' references unknown boss '{}'", name, config_key));
+                    }
+                    _ => {}
+                }
+            }
+            // A patrolling entity without Acceleration never moves.
+            if has(|c| matches!(c, ComponentConfig::Patrol { .. })) && !has(|c| matches!(c, ComponentConfig::Acceleration { .. })) {
+                problems.push(format!("Prefab '{}' has [Patrol] but is missing [Acceleration]; it will be stuck", name));
+            }
+            // Animations without a Renderable are never drawn.
+            if has(|c| matches!(c, ComponentConfig::Animation { .. })) && !has(|c| matches!(c, ComponentConfig::Renderable { .. })) {
+                problems.push(format!("Prefab '{}' has [Animation] but is missing [Renderable]; it will be invisible", name));
+            }
+        }
+
+        // 2. Animations: the texture is a registered key or an image on disk.
+        let mut anims: Vec<_> = self.animation.iter().collect();
+        anims.sort_by_key(|(name, _)| *name);
+        for (name, anim) in anims {
+            if !self.textures.contains_key(&anim.texture) && !std::path::Path::new(&anim.texture).exists() {
+                problems.push(format!("Animation '{}' uses texture '{}', which is neither in [textures] nor a file", name, anim.texture));
+            }
+            let frame_count = if anim.frames.is_empty() { anim.frame_count as usize } else { anim.frames.len() };
+            for event in anim.events.iter().filter(|e| e.frame >= frame_count) {
+                problems.push(format!("Animation '{}' fires '{}' on frame {}, but it only has {} frames", name, event.name, event.frame, frame_count));
+            }
+        }
+        if !self.animation.contains_key(&self.gameplay.explosion.animation_name) {
+            problems.push(format!("[gameplay.explosion] references unknown animation '{}'", self.gameplay.explosion.animation_name));
+        }
+
+        // 3. Sounds: every event name maps to a clip in [audio].
+        let mut events: Vec<_> = self.sound_events.iter().collect();
+        events.sort();
+        for (event, sound) in events.into_iter().filter(|(_, s)| !self.audio.contains_key(*s)) {
+            problems.push(format!("[sound_events] '{}' plays unknown sound '{}'", event, sound));
+        }
+        let footstep_sounds = self.footsteps.default_sound.iter().chain(self.footsteps.surfaces.values());
+        for sound in footstep_sounds.filter(|s| !self.audio.contains_key(*s)) {
+            problems.push(format!("[footsteps] plays unknown sound '{}'", sound));
+        }
+
+        // 4. Menus: slider ranges are non-empty and their steps move.
+        let mut screens: Vec<_> = self.menu.screens.iter().collect();
+        screens.sort_by_key(|(name, _)| *name);
+        for (screen, config) in screens {
+            for item in &config.items {
+                if let MenuItemType::Slider { min, max, step, .. } = &item.item_type
+                    && (min.partial_cmp(max) != Some(std::cmp::Ordering::Less) || *step <= 0.0) {
+                    problems.push(format!("[menu.screens.{}] slider '{}' needs min < max and step > 0", screen, item.label));
+                }
+            }
+        }
+        problems
+    }
 }
 
 /// Decodes the game configuration from assets.
 pub fn load_game_config(path: &str) -> Result<GameConfig, String> {
     let config_str = fs::read_to_string(path).map_err(|e| e.to_string())?;
-    let game_config: GameConfig = toml::from_str(&config_str).map_err(|e| e.to_string())?;
-    Ok(game_config)
+    parse_game_config(&config_str)
+}
+
+/// Compares the `[prefabs]` tables of two `game_config.toml` sources.
+/// Prefab edits only take effect once entities are respawned.
+pub fn prefabs_changed(old_source: &str, new_source: &str) -> bool {
+    let prefabs = |source: &str| toml::from_str::<toml::Table>(source).ok().and_then(|t| t.get("prefabs").cloned());
+    prefabs(old_source) != prefabs(new_source)
+}
+
+/// Decodes the game configuration from TOML text already in memory.
+pub fn parse_game_config(toml_str: &str) -> Result<GameConfig, String> {
+    toml::from_str(toml_str).map_err(|e| e.to_string())
+}
+
+/// The shipped `assets/game_config.toml`, compiled in so tests and tools need no working directory.
+impl Default for GameConfig {
+    fn default() -> Self {
+        parse_game_config(include_str!("../../assets/game_config.toml")).expect("bundled game_config.toml is valid")
+    }
 }
 
 #[cfg(test)]
@@ -183,34 +712,55 @@ mod tests {
     fn validate_prefab_integrity() {
         // Load the actual game config
         let config = load_game_config("assets/game_config.toml").expect("Failed to load game_config.toml for validation");
+        let problems = config.validate();
+        assert!(problems.is_empty(), "Shipped config has problems:\n{}", problems.join("\n"));
+    }
 
-        for (name, prefab) in config.prefabs {
-            let mut has_patrol = false;
-            let mut has_accel = false;
-            let mut has_animation = false;
-            let mut has_renderable = false;
+    #[test]
+    fn test_ui_anchors_resolve_against_the_screen_size() {
+        let widget = (100, 50);
+        let at = |anchor| UIPosition { x: 20, y: 10, anchor };
+        for (screen, right, bottom) in [((1920, 1080), 1800, 1020), ((3440, 1440), 3320, 1380)] {
+            assert_eq!(at(UIAnchor::TopLeft).resolve(screen, widget), (20, 10));
+            assert_eq!(at(UIAnchor::TopRight).resolve(screen, widget), (right, 10));
+            assert_eq!(at(UIAnchor::BottomLeft).resolve(screen, widget), (20, bottom));
+            assert_eq!(at(UIAnchor::BottomRight).resolve(screen, widget), (right, bottom));
+        }
+        assert_eq!(at(UIAnchor::BottomCenter).resolve((1920, 1080), widget), (930, 1020));
+        assert_eq!(at(UIAnchor::Center).resolve((1920, 1080), widget), (930, 525));
 
-            for component in prefab.components {
-                match component {
-                    ComponentConfig::Patrol { .. } => has_patrol = true,
-                    ComponentConfig::Acceleration { .. } => has_accel = true,
-                    ComponentConfig::Animation { .. } => has_animation = true,
-                    ComponentConfig::Renderable { .. } => has_renderable = true,
-                    _ => {}
-                }
-            }
+        let parsed: UIConfig = toml::from_str("hearts_pos = { x = 5, y = 6 }").unwrap();
+        assert_eq!(parsed.hearts_pos.anchor, UIAnchor::TopLeft, "Configs without anchors keep absolute positions");
+    }
 
-            // Rule 1: Physics Dependency
-            // If an entity is configured to Patrol, it MUST have Acceleration to physically move.
-            if has_patrol && !has_accel {
-                panic!("Prefab Integrity Error: '{}' has a [Patrol] component but is missing [Acceleration]. It will be stuck!", name);
-            }
+    #[test]
+    fn test_validate_reports_broken_references() {
+        let mut config = load_game_config("assets/game_config.toml").unwrap();
+        config.sound_events.insert("player_jump".to_string(), "jmup".to_string());
+        config.prefabs.insert("Ghost".to_string(), PrefabConfig { components: vec![
+            ComponentConfig::Animation { animations: vec!["ghost_idle".to_string()], initial_animation: "ghost_idle".to_string(), phase: AnimationPhase::Independent },
+            ComponentConfig::Patrol { speed: 10.0, anim_prefix: "ghost".to_string(), direction: None, turn_at_ledge: true, behavior: None },
+        ] });
 
-            // Rule 2: Visual Dependency
-            // If an entity has Animations, it MUST have a Renderable component to display them.
-            if has_animation && !has_renderable {
-                 panic!("Prefab Integrity Error: '{}' has [Animation] but is missing [Renderable]. It will be invisible!", name);
-            }
-        }
+        let problems = config.validate();
+        assert_eq!(problems.len(), 4, "{:?}", problems);
+        assert!(problems.iter().any(|p| p.contains("unknown animation 'ghost_idle'")));
+        assert!(problems.iter().any(|p| p.contains("missing [Acceleration]")));
+        assert!(problems.iter().any(|p| p.contains("missing [Renderable]")));
+        assert!(problems.iter().any(|p| p.contains("unknown sound 'jmup'")));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_boss_phases_stack_their_overrides() {
+        let config = load_game_config("assets/game_config.toml").unwrap();
+        let boss = &config.bosses["king_spider"];
+        assert_eq!((boss.phase_for(6), boss.phase_for(4), boss.phase_for(3), boss.phase_for(1)), (0, 1, 1, 2));
+
+        let base = boss.tuning(0);
+        let enraged = boss.tuning(2);
+        assert_eq!(base.charge_speed, boss.charge_speed);
+        assert!(enraged.charge_speed > boss.tuning(1).charge_speed && enraged.idle_time < base.idle_time);
+        assert_eq!(boss.tuning(1).stun_time, base.stun_time, "Phase 1 keeps the stun it does not override");
+        assert_eq!(enraged.charge_time, base.charge_time);
+    }
+}
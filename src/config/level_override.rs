@@ -0,0 +1,144 @@
+//! # Concept: Per-Level Overrides
+//!
+//! A level at `world_1_level_2/level.tmx` may ship a sibling `level.toml` that
+//! changes a handful of global values while that level is loaded:
+//!
+//! ```toml
+//! soundtrack = "soundtrack_03"
+//!
+//! [physics]
+//! gravity = 600.0
+//!
+//! [world]
+//! death_plane_y = 900.0
+//! ```
+//!
+//! Every field is optional; anything left out keeps the value from
+//! `config.toml` / `game_config.toml`. A level without an override file plays
+//! exactly like before.
+
+use serde::Deserialize;
+use std::path::Path;
+use super::{Config, GameConfig, PhysicsConfig, WorldConfig};
+
+/// Copies each `Some` field of `$src` onto the same field of `$dst`.
+macro_rules! merge_fields {
+    ($src:expr, $dst:expr, $($field:ident),+ $(,)?) => {
+        $( if let Some(value) = $src.$field { $dst.$field = value; } )+
+    };
+}
+
+/// The parsed contents of a level's override file.
+#[derive(Deserialize, Clone, Default, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct LevelOverride {
+    #[serde(default)] pub physics: PhysicsOverride,
+    #[serde(default)] pub world: WorldOverride,
+    /// Key in `[audio]` played instead of the character's soundtrack.
+    #[serde(default)] pub soundtrack: Option<String>,
+}
+
+/// Partial [PhysicsConfig]. `max_delta_time` is a frame-loop setting and cannot be overridden.
+#[derive(Deserialize, Clone, Copy, Default, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct PhysicsOverride {
+    pub gravity: Option<f32>,
+    pub max_speed: Option<f32>,
+    pub entity_max_fall_speed: Option<f32>,
+    pub acceleration: Option<f32>,
+    pub deceleration: Option<f32>,
+    pub jump_strength: Option<f32>,
+    pub jump_hold_force: Option<f32>,
+    pub air_control_factor: Option<f32>,
+    pub velocity_threshold: Option<f32>,
+    pub coyote_time: Option<f32>,
+    pub jump_buffer_time: Option<f32>,
+    pub buoyancy: Option<f32>,
+    pub water_max_speed: Option<f32>,
+    pub stroke_strength: Option<f32>,
+    pub water_control_factor: Option<f32>,
+}
+
+/// Partial [WorldConfig].
+#[derive(Deserialize, Clone, Copy, Default, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct WorldOverride {
+    pub width: Option<f32>,
+    pub death_plane_y: Option<f32>,
+}
+
+impl PhysicsOverride {
+    pub fn apply(&self, physics: &mut PhysicsConfig) {
+        merge_fields!(self, physics,
+            gravity, max_speed, entity_max_fall_speed, acceleration, deceleration,
+            jump_strength, jump_hold_force, air_control_factor, velocity_threshold,
+            coyote_time, jump_buffer_time, buoyancy, water_max_speed, stroke_strength,
+            water_control_factor,
+        );
+    }
+}
+
+impl WorldOverride {
+    pub fn apply(&self, world: &mut WorldConfig) {
+        merge_fields!(self, world, width, death_plane_y);
+    }
+}
+
+impl LevelOverride {
+    /// Returns the override file path for a level: the TMX path with a `.toml` extension.
+    pub fn path_for(level_path: &str) -> std::path::PathBuf {
+        Path::new(level_path).with_extension("toml")
+    }
+
+    /// Loads the override next to `level_path`, or an empty override when there is none.
+    pub fn load_for(level_path: &str) -> Result<Self, String> {
+        let path = Self::path_for(level_path);
+        if !path.exists() { return Ok(Self::default()); }
+        let source = std::fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        toml::from_str(&source).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    /// True when the override changes nothing, so the global configs can be used as-is.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Returns copies of the global configs with this override merged on top.
+    pub fn apply(&self, config: &Config, game_config: &GameConfig) -> (Config, GameConfig) {
+        let mut config = config.clone();
+        let mut game_config = game_config.clone();
+        self.physics.apply(&mut config.physics);
+        self.world.apply(&mut game_config.world);
+        (config, game_config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_fields_keep_global_values() {
+        let (config, game_config) = (Config::default(), GameConfig::default());
+        let level: LevelOverride = toml::from_str("[physics]\ngravity = 123.0\n[world]\ndeath_plane_y = 456.0").unwrap();
+
+        let (merged, merged_game) = level.apply(&config, &game_config);
+        assert_eq!(merged.physics.gravity, 123.0);
+        assert_eq!(merged_game.world.death_plane_y, 456.0);
+        assert_eq!(merged.physics.jump_strength, config.physics.jump_strength);
+        assert_eq!(merged.physics.max_speed, config.physics.max_speed);
+        assert_eq!(merged_game.world.width, game_config.world.width);
+    }
+
+    #[test]
+    fn test_level_without_override_file_is_empty() {
+        let level = LevelOverride::load_for("assets/levels/does_not_exist/level.tmx").unwrap();
+        assert!(level.is_empty());
+        assert_eq!(LevelOverride::path_for("assets/levels/a/level.tmx"), Path::new("assets/levels/a/level.toml"));
+    }
+
+    #[test]
+    fn test_unknown_keys_are_rejected() {
+        assert!(toml::from_str::<LevelOverride>("[physics]\ngravty = 1.0").is_err(), "Typos must not be silently ignored");
+    }
+}
@@ -1,17 +1,22 @@
 //! # Concept: Configuration Infrastructure
 //! 
 //! This module provides the data-driven foundation for the engine.
-//! It handles the loading of TOML settings and defines the global 
-//! scaling constants required for the 1:1 pixel coordinate system.
-
-/// The multiplier used to project the logical game world (Retro) to the screen (HD).
-pub const RENDER_SCALE_FACTOR: f32 = 4.0;
+//! It handles the loading of TOML settings. The screen scale is derived from
+//! them at runtime by [crate::units::ViewTransform].
 
 pub mod core;
 pub mod game;
+pub mod level_override;
+pub mod particles;
 
 // Re-export core configs
 pub use self::core::*;
 
 // Re-export game configs
-pub use self::game::*;
\ No newline at end of file
+pub use self::game::*;
+
+// Re-export per-level overrides
+pub use self::level_override::*;
+
+// Re-export particle configs
+pub use self::particles::*;
@@ -0,0 +1,46 @@
+//! # Concept: Particle Configuration
+//!
+//! Named presets used by emitters and one-shot bursts (coin pickup, stomp, landing dust),
+//! decoded from the `[particles]` table of 'game_config.toml'.
+
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// The `[particles]` table: a live-particle cap and the presets keyed by name.
+#[derive(Deserialize, Clone)]
+pub struct ParticleConfig {
+    /// Hard cap on live particles; spawns beyond it are dropped.
+    #[serde(default = "default_max_particles")] pub max_particles: usize,
+    #[serde(default)] pub presets: HashMap<String, ParticlePresetConfig>,
+}
+
+impl Default for ParticleConfig {
+    fn default() -> Self {
+        Self { max_particles: default_max_particles(), presets: HashMap::new() }
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct ParticlePresetConfig {
+    /// Particles emitted per burst.
+    #[serde(default = "default_burst_count")] pub count: u32,
+    /// Lifetime range in **seconds**.
+    pub lifetime: [f32; 2],
+    /// Initial speed range in **pixels per second**.
+    pub speed: [f32; 2],
+    /// Emission direction in **degrees** (0 = right, -90 = up).
+    #[serde(default)] pub angle: f32,
+    /// Width of the emission cone around `angle` in **degrees**.
+    #[serde(default = "default_spread")] pub spread: f32,
+    /// Downward acceleration in **pixels per second squared**.
+    #[serde(default)] pub gravity: f32,
+    pub start_color: [u8; 4],
+    pub end_color: [u8; 4],
+    /// Edge length of the square particle in **pixels**.
+    #[serde(default = "default_particle_size")] pub size: f32,
+}
+
+fn default_max_particles() -> usize { 512 }
+fn default_burst_count() -> u32 { 8 }
+fn default_spread() -> f32 { 360.0 }
+fn default_particle_size() -> f32 { 2.0 }
@@ -7,7 +7,7 @@
 use crate::animation::AnimationController;
 use crate::math::Vector2D;
 use sdl3::rect::Rect;
-use crate::audio_analysis::DetectedBeat;
+use crate::audio_analysis::{Band, DetectedBeat};
 
 /// Marker trait for all data containers in the ECS.
 #[allow(dead_code)]
@@ -19,8 +19,10 @@ pub trait Component {}
 pub struct MusicState {
     /// The current playback position in **seconds**.
     pub current_time: f64,
-    /// The last rhythmic onset detected by the audio analysis system.
-    pub last_beat: Option<DetectedBeat>,
+    /// The audio manager's id for the track being followed; a change restarts beat tracking.
+    pub track: u64,
+    /// The last rhythmic onset announced in each frequency band.
+    pub last_beats: std::collections::HashMap<Band, DetectedBeat>,
 }
 
 /// # Concept: Position
@@ -47,12 +49,16 @@ pub struct Renderable {
     pub horizontal_offset: i32,
     /// Vertical offset from the physical position in **pixels**.
     pub vertical_offset: i32,
-    /// Draw order (higher values are drawn first/behind).
+    /// Draw order (higher values are drawn first/behind; see the renderer's depth convention).
     pub z_index: u8,
     /// Rotation angle in **degrees**.
     pub rotation: f64,
     pub flip_horizontal: bool,
     pub flip_vertical: bool,
+    /// Color multiplied into the sprite (white = unchanged).
+    pub tint: sdl3::pixels::Color,
+    /// Sprite opacity (255 = opaque).
+    pub alpha: u8,
 }
 impl Component for Renderable {}
 
@@ -82,6 +88,13 @@ pub struct MovementIntention {
     /// * `1.0`: Right
     /// * `0.0`: None
     pub x: f32, 
+    /// Whether the entity is holding its jump input (extends the jump arc while rising).
+    pub jump_held: bool,
+    /// Whether the entity is holding its crouch (Down) input.
+    pub crouch_held: bool,
+    /// Top speed in **pixels per second** for this intention (e.g. a chase); `None` uses the
+    /// entity's patrol speed or the physics max speed.
+    pub max_speed: Option<f32>,
 }
 impl Component for MovementIntention {}
 
@@ -95,12 +108,24 @@ impl Component for EnemyTag {}
 #[derive(Debug, Clone, Copy)] pub struct GoldCoin;
 impl Component for GoldCoin {}
 
+/// # Concept: Health Pickup
+/// A collectible that restores `amount` health to the player who touches it.
+#[derive(Debug, Clone, Copy)]
+pub struct HealthPickup {
+    pub amount: u32,
+}
+impl Component for HealthPickup {}
+
 #[derive(Debug, Clone, Copy)] pub struct Gravity;
 impl Component for Gravity {}
 
 #[derive(Debug, Clone, Copy)] pub struct Grounded;
 impl Component for Grounded {}
 
+/// Marks an entity whose center is inside water; maintained by `SystemWater`.
+#[derive(Debug, Clone, Copy)] pub struct Submerged;
+impl Component for Submerged {}
+
 #[derive(Debug, Clone, Copy)] pub struct DeadTag;
 impl Component for DeadTag {}
 
@@ -124,6 +149,10 @@ pub struct Patrol {
     pub anim_prefix: String,
     /// Current direction of movement (`-1.0` or `1.0`).
     pub direction: f32,
+    /// Reverse before walking off a platform edge instead of falling.
+    pub turn_at_ledge: bool,
+    /// Key into `[enemy_behavior]` for chase tuning; `None` only ever patrols.
+    pub behavior: Option<String>,
 }
 impl Component for Patrol {}
 
@@ -136,6 +165,33 @@ pub struct Invincibility {
 }
 impl Component for Invincibility {}
 
+/// # Concept: Knockback
+/// A short window after a hit during which the player's horizontal input is ignored.
+#[derive(Debug, Clone, Copy)]
+pub struct Knockback { 
+    /// Remaining time in **seconds**.
+    pub timer: f32 
+}
+impl Component for Knockback {}
+
+/// # Concept: Melee Hitbox
+/// A short-lived damage zone placed in front of an attacking entity.
+#[derive(Debug, Clone, Copy)]
+pub struct MeleeHitbox {
+    /// The attacker; the hitbox never affects it.
+    pub owner: crate::ecs::world::Entity,
+}
+impl Component for MeleeHitbox {}
+
+/// # Concept: Attack Cooldown
+/// Blocks new attacks until the timer runs out; removed once it does.
+#[derive(Debug, Clone, Copy)]
+pub struct AttackCooldown {
+    /// Remaining time in **seconds**.
+    pub timer: f32,
+}
+impl Component for AttackCooldown {}
+
 /// # Concept: Lifetime
 /// A timer for ephemeral entities (particles, projectiles) that self-terminate.
 #[derive(Debug, Clone, Copy)]
@@ -161,18 +217,142 @@ pub struct RespawnTimer {
     /// Countdown in **seconds**.
     pub timer: f32,
     pub transition_started: bool,
+    /// Set once the iris-out started for this respawn has closed.
+    pub transition_complete: bool,
 }
 impl Component for RespawnTimer {}
 
+/// # Concept: Jump Assist
+/// Forgiveness windows that make jumping feel responsive (coyote time and input buffering).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JumpAssist {
+    /// Remaining time in **seconds** during which a jump is still allowed after leaving the ground.
+    pub coyote_timer: f32,
+    /// Remaining time in **seconds** during which a buffered jump press is still honored.
+    pub buffer_timer: f32,
+    /// Jumps left before landing again; refilled to `physics.max_jumps` on the ground.
+    pub jumps_remaining: u32,
+}
+impl Component for JumpAssist {}
+
+/// # Concept: Crouching
+/// Present while the player crouches with a shortened collision box; remembers the height to restore.
+#[derive(Debug, Clone, Copy)]
+pub struct Crouching {
+    /// Collision height in **pixels** to grow back to when standing up.
+    pub standing_height: u32,
+}
+impl Component for Crouching {}
+
+/// # Concept: Stomp Combo
+/// Counts the enemies a player has stomped since last standing on the ground; removed on landing.
+#[derive(Debug, Clone, Copy)]
+pub struct StompCombo {
+    pub chain: u32,
+}
+impl Component for StompCombo {}
+
+/// Identifies which local input device drives a player (0 = primary, 1 = second player).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PlayerSlot(pub usize);
+impl Component for PlayerSlot {}
+
 #[derive(Debug, Clone, Copy)] pub struct RespawnTag;
 impl Component for RespawnTag {}
 
 #[derive(Debug, Clone, Copy)] pub struct Goal;
 impl Component for Goal {}
 
+/// # Concept: Checkpoint
+/// A mid-level save point that becomes the player's respawn location once touched.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Checkpoint {
+    /// Whether the player has already reached this checkpoint (fires only once).
+    pub activated: bool,
+}
+impl Component for Checkpoint {}
+
+/// # Concept: Trigger Volume
+/// An invisible zone (sized by its `Collision` rect) that announces a named fact when a player enters it.
+#[derive(Debug, Clone, Default)]
+pub struct Trigger {
+    /// The name published with [crate::ecs::event::EventTriggerActivated].
+    pub event_name: String,
+    /// Disables the trigger after its first activation.
+    pub once: bool,
+    /// Time between entry and the announcement (in **seconds**).
+    pub delay: f32,
+    /// Trauma added to the camera shake when the trigger fires (0 = none).
+    pub shake: f32,
+    /// A prefab spawned at a named marker object when the trigger fires.
+    pub spawn: Option<TriggerSpawn>,
+    /// Set after a `once` trigger has fired; consumed triggers ignore players until the level reloads.
+    pub consumed: bool,
+    /// Remaining delay (in **seconds**) and the entering player while an activation is pending.
+    pub pending: Option<(f32, crate::ecs::world::Entity)>,
+    /// Players that overlapped the zone last tick; used to fire on entry only.
+    pub occupants: Vec<crate::ecs::world::Entity>,
+}
+impl Component for Trigger {}
+
+/// # Concept: Boss
+/// A multi-phase enemy tuned by `[bosses.<config_key>]`; it can only be hurt while stunned.
+#[derive(Debug, Clone, Default)]
+pub struct Boss {
+    pub config_key: String,
+    /// Index of the active entry of the config's `phases` (0 = the base tuning).
+    pub phase: usize,
+    /// Set by a hit during the stun, so the boss recovers at once instead of soaking a chain of stomps.
+    pub hit_while_stunned: bool,
+}
+impl Component for Boss {}
+
+/// What a [Trigger] spawns: an instance of `prefab` at the level object named `marker`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TriggerSpawn {
+    pub prefab: String,
+    pub marker: String,
+}
+
+/// # Concept: Projectile
+/// A shot fired by a player or enemy. It damages the opposing side and is destroyed on impact.
+#[derive(Debug, Clone, Copy)]
+pub struct Projectile {
+    /// The entity that fired the shot; it never collides with its own projectiles.
+    pub owner: Option<crate::ecs::world::Entity>,
+    /// Player shots hit enemies; all other shots hit players.
+    pub from_player: bool,
+    /// Health removed from a player on hit.
+    pub damage: u32,
+    /// Travel speed in **pixels per second**.
+    pub speed: f32,
+    /// Time in **seconds** before the shot expires.
+    pub lifetime: f32,
+    /// Whether the shot arcs under gravity.
+    pub gravity: bool,
+}
+impl Component for Projectile {}
+
+/// # Concept: Particle Emitter
+/// Continuously spawns particles at the entity's position (torches, waterfalls, smoke).
+#[derive(Debug, Clone)]
+pub struct ParticleEmitter {
+    /// Lifetime range, speed spread, gravity and colors of each particle.
+    pub spec: crate::config::ParticlePresetConfig,
+    /// Emission rate in **particles per second**.
+    pub rate: f32,
+    /// Fractional particles carried over between ticks.
+    pub accumulator: f32,
+}
+impl Component for ParticleEmitter {}
+
 #[derive(Debug, Clone)] pub struct NextLevel(pub String);
 impl Component for NextLevel {}
 
+/// Names the `SpawnPoint` object in the destination level that a Goal exit leads to.
+#[derive(Debug, Clone)] pub struct ExitSpawnPoint(pub String);
+impl Component for ExitSpawnPoint {}
+
 #[derive(Debug, Clone, Copy, PartialEq)] pub enum Direction { Left, Right }
 #[derive(Debug, Clone, Copy)] pub struct Directional { pub direction: Direction }
 impl Component for Directional {}
@@ -5,36 +5,74 @@
 //! without direct coupling, enabling the WYSIWID behavioral rule layer.
 
 use crate::ecs::world::Entity;
+use crate::ecs::component::Direction;
 use crate::math::Vector2D;
 use std::any::{Any, TypeId};
 use std::collections::HashMap;
 
 // --- Gameplay Facts (Events) ---
 #[derive(Clone, Copy)] pub struct EventCoinCollected { pub coin: Entity }
+#[derive(Clone, Copy)] pub struct EventEnemyHit { pub attacker: Entity, pub hitbox: Entity, pub enemy: Entity }
+#[derive(Clone, Copy)] pub struct EventHealthCollected { pub player: Entity, pub pickup: Entity, pub amount: u32 }
+#[derive(Clone, Copy)] pub struct EventCheckpointReached { pub player: Entity, pub checkpoint: Entity }
+#[derive(Clone)] pub struct EventTriggerActivated { pub name: String, pub trigger: Entity, pub player: Entity }
+#[derive(Clone)] pub struct EventParticleBurst { pub preset: String, pub position: Vector2D }
 #[derive(Clone, Copy)] pub struct EventPlayerEnemyStomped { pub player: Entity, pub enemy: Entity }
-#[derive(Clone, Copy)] pub struct EventPlayerDamaged { pub player: Entity, pub knockback_x: f32, pub position: Vector2D }
+#[derive(Clone, Copy)] pub struct EventProjectileHit { pub projectile: Entity, pub target: Entity }
+/// `direction` is the way the player is pushed; `knockback_x` is the matching signed speed.
+#[derive(Clone, Copy)] pub struct EventPlayerDamaged { pub player: Entity, pub knockback_x: f32, pub direction: Direction, pub position: Vector2D, pub amount: u32 }
 #[derive(Clone, Copy)] pub struct EventEntityJumped { pub entity: Entity }
+/// A player touched down after being airborne. `impact_speed` is the downward speed in **pixels per second**
+/// just before the landing zeroed it; `position` is the bottom-center of its collision box.
+#[derive(Clone, Copy)] pub struct EventPlayerLanded { pub player: Entity, pub impact_speed: f32, pub position: Vector2D }
+/// A named frame event from `[animation.*] events`, fired when playback enters that frame.
+#[derive(Clone)] pub struct EventAnimationFrame { pub entity: Entity, pub name: String }
+#[derive(Clone, Copy)] pub struct EventWaterSplash { pub entity: Entity, pub position: Vector2D, pub entered: bool }
 #[derive(Clone, Copy)] pub struct EventCollision { pub entity_a: Entity, pub entity_b: Entity, pub intersection: sdl3::rect::Rect }
 #[derive(Clone, Copy)] pub struct EventGameOver;
 #[derive(Clone, Copy)] pub struct EventRespawnStarted { pub player: Entity }
-#[derive(Clone, Copy)] pub struct EventMusicBeat { #[allow(dead_code)] pub beat_number: u32, #[allow(dead_code)] pub intensity: f32 }
-#[derive(Clone, Copy)] pub struct EventScreenShake { pub duration: f32, pub intensity: f32 }
+#[derive(Clone, Copy)] pub struct EventMusicBeat { #[allow(dead_code)] pub beat_number: u32, #[allow(dead_code)] pub intensity: f32, pub band: crate::audio_analysis::Band }
+#[derive(Clone, Copy)] pub struct EventScreenShake { pub trauma: f32 }
+/// A boss's health reached zero; `next_level` is where its defeat leads, if anywhere.
+#[derive(Clone)] pub struct EventBossDefeated { pub boss: Entity, pub next_level: Option<String> }
+/// Asks the level-transition system to close the iris and load `next_level`, as if a goal was reached.
+#[derive(Clone)] pub struct EventLevelExitRequested { pub next_level: String }
+/// Asks the level-transition system to close the iris and reload `level` from the start. Unlike an
+/// exit it completes nothing and carries no health over, even if it interrupts a goal's iris-out.
+#[derive(Clone)] pub struct EventLevelRestartRequested { pub level: String }
+/// The iris closed on a finished level; `next_level` loads next (after the tally, in a live game).
+#[derive(Clone)] pub struct EventLevelCompleted { pub next_level: String }
 
 #[derive(Clone, Copy, Debug, PartialEq)] pub enum PlayerDeathReason { HealthDepleted, FellOutOfBounds }
 #[derive(Clone, Copy)] pub struct EventPlayerDied { pub player: Entity, pub reason: PlayerDeathReason }
 
 #[derive(Clone, Copy, PartialEq, Debug)] pub enum TransitionType { IrisIn, IrisOut }
 #[derive(Clone, Copy)] pub struct EventStartTransition { pub transition_type: TransitionType, pub duration: f32, pub center: Option<(i32, i32)> }
-#[derive(Clone, Copy)] pub struct EventTransitionComplete { #[allow(dead_code)] pub transition_type: TransitionType }
+/// Published deferred by the transition system, so earlier-ordered systems react on the next frame.
+#[derive(Clone, Copy)] pub struct EventTransitionComplete { pub transition_type: TransitionType }
 
 // --- Semantic Intents (Commands) ---
 #[derive(Clone, Copy)] pub struct CommandJump { pub entity: Entity }
+#[derive(Clone, Copy)] pub struct CommandFire { pub entity: Entity }
+#[derive(Clone, Copy)] pub struct CommandAttack { pub entity: Entity }
 
 /// A central bus for managing strongly-typed fact and intent queues.
+///
+/// Events travel on one of two channels:
+/// * [EventBus::publish] is immediate: systems later in the same frame see the event.
+/// * [EventBus::publish_deferred] holds the event back until [EventBus::clear_events] ends the
+///   frame; it is then readable by every system, whatever its order, for the whole next frame.
+///
+/// [EventBus::read] yields last frame's deferred events first, then this frame's immediate
+/// ones, each in publish order.
 #[derive(Default)]
 pub struct EventBus {
-    /// Storage for all pending events, indexed by their specific Rust TypeId.
+    /// Immediate events published this frame, indexed by their specific Rust TypeId.
     queues: HashMap<TypeId, Vec<Box<dyn Any>>>,
+    /// Events deferred during the previous frame, readable for this whole frame.
+    delivered: HashMap<TypeId, Vec<Box<dyn Any>>>,
+    /// Events deferred during this frame, held back until the next swap.
+    pending: HashMap<TypeId, Vec<Box<dyn Any>>>,
 }
 
 impl EventBus {
@@ -47,23 +85,80 @@ impl EventBus {
         queue.push(Box::new(event));
     }
 
-    /// Provides an iterator over all facts of a specific type published this frame.
+    /// Publishes a fact that becomes readable next frame, for systems that run before the publisher.
+    pub fn publish_deferred<T: Any + 'static>(&mut self, event: T) {
+        self.pending.entry(TypeId::of::<T>()).or_default().push(Box::new(event));
+    }
+
+    /// Provides an iterator over all facts of a specific type readable this frame:
+    /// those deferred last frame, followed by those published this frame.
     pub fn read<T: Any + 'static>(&self) -> impl Iterator<Item = &T> {
-        // 1. Look up the specific queue for the requested type.
+        // 1. Look up the specific queues for the requested type.
         let type_id = TypeId::of::<T>();
         // 2. Provide a filtered iterator that safely downcasts back to the concrete type.
-        self.queues
-            .get(&type_id)
-            .map_or_else(
-                || [].iter(), 
-                |queue| queue.iter(),
-            )
+        Self::queue(&self.delivered, type_id).iter()
+            .chain(Self::queue(&self.queues, type_id))
             .filter_map(|event| event.downcast_ref::<T>())
     }
 
-    /// Wipes all queues to prepare for the next simulation tick.
+    fn queue(queues: &HashMap<TypeId, Vec<Box<dyn Any>>>, type_id: TypeId) -> &[Box<dyn Any>] {
+        queues.get(&type_id).map_or(&[][..], |queue| queue.as_slice())
+    }
+
+    /// Ends the frame: flushes this frame's immediate and delivered events and
+    /// delivers the deferred ones to the next simulation tick.
     pub fn clear_events(&mut self) {
-        // 1. Clear all mappings to flush every event from memory.
+        // 1. Clear all immediate mappings to flush every same-frame event from memory.
         self.queues.clear();
+        // 2. Swap the buffers so deferred events become readable and the old ones are dropped.
+        std::mem::swap(&mut self.delivered, &mut self.pending);
+        self.pending.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Copy, Debug, PartialEq)] struct Ping(u32);
+
+    fn pings(bus: &EventBus) -> Vec<u32> { bus.read::<Ping>().map(|p| p.0).collect() }
+
+    #[test]
+    fn test_immediate_events_last_one_frame() {
+        let mut bus = EventBus::default();
+        bus.publish(Ping(1));
+        bus.publish(Ping(2));
+        assert_eq!(pings(&bus), vec![1, 2], "Readable in publish order the same frame");
+        bus.clear_events();
+        assert!(pings(&bus).is_empty());
+    }
+
+    #[test]
+    fn test_deferred_events_are_readable_for_exactly_the_next_frame() {
+        let mut bus = EventBus::default();
+        bus.publish_deferred(Ping(1));
+        assert!(pings(&bus).is_empty(), "Not visible in the frame it was published");
+
+        bus.clear_events();
+        assert_eq!(pings(&bus), vec![1]);
+        assert_eq!(pings(&bus), vec![1], "Reading does not consume, so every system sees it");
+
+        bus.clear_events();
+        assert!(pings(&bus).is_empty());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_deferred_events_come_before_immediate_ones() {
+        let mut bus = EventBus::default();
+        bus.publish_deferred(Ping(1));
+        bus.publish_deferred(Ping(2));
+        bus.clear_events();
+        bus.publish(Ping(3));
+        bus.publish_deferred(Ping(4));
+        assert_eq!(pings(&bus), vec![1, 2, 3]);
+
+        bus.clear_events();
+        assert_eq!(pings(&bus), vec![4]);
+    }
+}
@@ -5,11 +5,13 @@
 //! - **component:** Pure data structures (the 'What').
 //! - **system:** Domain-specific logic processors (the 'How').
 //! - **world:** The central data container and entity manager.
+//! - **query:** Joins over component storages.
 //! - **event:** The decoupled communication bus.
 
 pub mod component;
 pub mod event;
 pub mod systems;
 pub mod world;
+pub mod query;
 pub mod system_manager;
 pub mod resources;
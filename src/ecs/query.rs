@@ -0,0 +1,62 @@
+//! # Concept: Component Queries
+//!
+//! Joins over component storages: iterate the entities that have every listed component,
+//! instead of looping over one `HashMap` and probing the others by hand. The first storage
+//! drives the iteration, so pass the sparsest one first (e.g. a tag before `positions`).
+//! The `_mut` variants hand out the first storage mutably; since each storage is a separate
+//! [crate::ecs::world::World] field, the borrow checker rules out aliasing.
+
+use std::collections::HashMap;
+use crate::ecs::world::Entity;
+
+/// Entities with both components.
+pub fn query2<'a, A, B>(a: &'a HashMap<Entity, A>, b: &'a HashMap<Entity, B>) -> impl Iterator<Item = (Entity, &'a A, &'a B)> {
+    a.iter().filter_map(move |(&entity, a)| Some((entity, a, b.get(&entity)?)))
+}
+
+/// Entities with all three components.
+pub fn query3<'a, A, B, C>(a: &'a HashMap<Entity, A>, b: &'a HashMap<Entity, B>, c: &'a HashMap<Entity, C>) -> impl Iterator<Item = (Entity, &'a A, &'a B, &'a C)> {
+    a.iter().filter_map(move |(&entity, a)| Some((entity, a, b.get(&entity)?, c.get(&entity)?)))
+}
+
+/// Entities with both components, the first one writable.
+pub fn query2_mut<'a, A, B>(a: &'a mut HashMap<Entity, A>, b: &'a HashMap<Entity, B>) -> impl Iterator<Item = (Entity, &'a mut A, &'a B)> {
+    a.iter_mut().filter_map(move |(&entity, a)| Some((entity, a, b.get(&entity)?)))
+}
+
+/// Entities with all three components, the first one writable.
+pub fn query3_mut<'a, A, B, C>(a: &'a mut HashMap<Entity, A>, b: &'a HashMap<Entity, B>, c: &'a HashMap<Entity, C>) -> impl Iterator<Item = (Entity, &'a mut A, &'a B, &'a C)> {
+    a.iter_mut().filter_map(move |(&entity, a)| Some((entity, a, b.get(&entity)?, c.get(&entity)?)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::component::{Acceleration, Gravity, Position, Velocity};
+    use crate::ecs::world::World;
+    use crate::math::Vector2D;
+
+    #[test]
+    fn test_queries_only_yield_entities_with_every_component() {
+        let mut world = World::new();
+        let falling = world.create_entity();
+        world.add_position(falling, Position(Vector2D::new(1.0, 2.0)));
+        world.add_velocity(falling, Velocity(Vector2D::default()));
+        world.add_gravity(falling, Gravity);
+        let floating = world.create_entity();
+        world.add_position(floating, Position(Vector2D::default()));
+        world.add_velocity(floating, Velocity(Vector2D::default()));
+
+        let found: Vec<Entity> = query3(&world.gravity_tags, &world.positions, &world.velocities).map(|(e, ..)| e).collect();
+        assert_eq!(found, vec![falling]);
+        assert_eq!(query2(&world.positions, &world.velocities).count(), 2);
+        assert_eq!(query2(&world.positions, &world.accelerations).count(), 0);
+
+        world.add_acceleration(floating, Acceleration(Vector2D::new(0.0, -1.0)));
+        for (_, velocity, acceleration) in query2_mut(&mut world.velocities, &world.accelerations) {
+            velocity.0.y += acceleration.0.y;
+        }
+        assert_eq!(world.velocities[&floating].0.y, -1.0);
+        assert_eq!(world.velocities[&falling].0.y, 0.0);
+    }
+}
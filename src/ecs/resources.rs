@@ -6,13 +6,17 @@
 
 use crate::math::Vector2D;
 
+use rand::rngs::StdRng;
+
+use rand::SeedableRng;
+
 
 
 /// The high-level state of the game application, driving the main system scheduler.
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 
-pub enum GameState { Menu(Screen), Playing, Paused, GameOver, Cinematic }
+pub enum GameState { Menu(Screen), Playing, Paused, GameOver, Cinematic, LevelComplete }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 
@@ -26,33 +30,114 @@ impl Default for GameState { fn default() -> Self { GameState::Menu(Screen::Main
 
 /// A spatial hash grid for accelerating collision detection and visibility queries.
 
-#[derive(Default)]
+#[derive(Debug, Clone)]
 
 pub struct SpatialGrid {
 
-    entities: Vec<crate::ecs::world::Entity>,
+    /// Edge length of a square cell in **pixels**.
+
+    cell_size: f32,
+
+    /// Entities overlapping each (column, row) cell. Buckets are kept between rebuilds to reuse their memory.
+
+    cells: std::collections::HashMap<(i32, i32), Vec<crate::ecs::world::Entity>>,
+
+    /// The inclusive cell range (min column, min row, max column, max row) each entity was inserted with.
+
+    extents: std::collections::HashMap<crate::ecs::world::Entity, (i32, i32, i32, i32)>,
 
 }
 
 
+/// Cell size matching the 32 px tiles, so a typical sprite touches one to four cells.
+
+pub const SPATIAL_CELL_SIZE: f32 = 32.0;
+
+
+impl Default for SpatialGrid { fn default() -> Self { Self::new(SPATIAL_CELL_SIZE) } }
+
 
 impl SpatialGrid {
 
-    pub fn new(_cell_size: f32) -> Self { Self::default() }
+    pub fn new(cell_size: f32) -> Self { Self { cell_size: cell_size.max(1.0), cells: Default::default(), extents: Default::default() } }
+
+
+    /// Empties every cell; call once per rebuild before re-inserting.
+
+    pub fn clear(&mut self) {
+
+        for bucket in self.cells.values_mut() { bucket.clear(); }
+
+        self.extents.clear();
+
+    }
+
+
+    /// The number of entities inserted since the last [SpatialGrid::clear].
+
+    pub fn len(&self) -> usize { self.extents.len() }
+
 
-    pub fn clear(&mut self) { self.entities.clear(); }
+    pub fn is_empty(&self) -> bool { self.extents.is_empty() }
 
-    pub fn insert(&mut self, entity: crate::ecs::world::Entity, _rect: sdl3::rect::Rect) {
 
-        self.entities.push(entity);
+    /// The inclusive cell range covered by `rect` (in **pixels**).
+
+    fn cell_range(&self, rect: sdl3::rect::Rect) -> (i32, i32, i32, i32) {
+
+        let cell = |v: i32| (v as f32 / self.cell_size).floor() as i32;
+
+        // `right()`/`bottom()` are exclusive, so a rect ending exactly on a cell edge stays out of the next cell.
+
+        (cell(rect.x()), cell(rect.y()), cell(rect.right() - 1), cell(rect.bottom() - 1))
 
     }
 
-    // TEMPORARY FIX: Return all entities, bypassing spatial query logic.
 
-    pub fn query(&self, _rect: sdl3::rect::Rect) -> Vec<crate::ecs::world::Entity> { 
+    /// Files `entity` under every cell its `rect` overlaps.
+
+    pub fn insert(&mut self, entity: crate::ecs::world::Entity, rect: sdl3::rect::Rect) {
+
+        let (min_x, min_y, max_x, max_y) = self.cell_range(rect);
 
-        self.entities.clone()
+        for row in min_y..=max_y {
+
+            for col in min_x..=max_x { self.cells.entry((col, row)).or_default().push(entity); }
+
+        }
+
+        self.extents.insert(entity, (min_x, min_y, max_x, max_y));
+
+    }
+
+
+    /// Broad phase: every entity sharing a cell with `rect`, each exactly once.
+
+    /// Candidates may not actually overlap `rect`; callers still run their exact test.
+
+    ///
+
+    /// ⚠️ **Hotpath**: Called per collidable entity every tick. Allocation-free: an entity spanning
+
+    /// several of the visited cells is only reported from the first of them.
+
+    pub fn query_near(&self, rect: sdl3::rect::Rect) -> impl Iterator<Item = crate::ecs::world::Entity> + '_ {
+
+        let (min_x, min_y, max_x, max_y) = self.cell_range(rect);
+
+        (min_y..=max_y)
+
+            .flat_map(move |row| (min_x..=max_x).map(move |col| (col, row)))
+
+            .filter_map(move |cell| self.cells.get(&cell).map(|bucket| (cell, bucket)))
+
+            .flat_map(move |((col, row), bucket)| bucket.iter().copied().filter(move |entity| {
+
+                let (entity_x, entity_y, _, _) = self.extents[entity];
+
+                col == entity_x.max(min_x) && row == entity_y.max(min_y)
+
+            }))
 
     }
 
@@ -70,6 +155,96 @@ pub struct GameStats {
 
     pub gold_coin_count: u32,
 
+    /// The position of the most recently activated checkpoint in the current level.
+
+    pub last_checkpoint: Option<Vector2D>,
+
+    /// Points scored this game, across levels.
+
+    pub score: u32,
+
+    /// The score when the current level was entered, restored on restart.
+
+    pub level_start_score: u32,
+
+    /// Coins collected in the current level, for the tally.
+
+    pub level_coins: u32,
+
+    /// Enemies defeated in the current level, for the tally.
+
+    pub level_kills: u32,
+
+    /// Simulated **seconds** spent in the current level.
+
+    pub level_time: f32,
+
+}
+
+
+
+impl GameStats {
+
+    /// Applies the level coin policy when a level is loaded and returns the count to roll back to on restart.
+
+    /// A restart always restores `restart_from`; a new level keeps or clears coins per `carry_coins_on_level`.
+
+    /// The level counters start over either way, and a restart also takes back the points scored in the level.
+
+    pub fn enter_level(&mut self, carry_coins_on_level: bool, restart_from: Option<u32>) -> u32 {
+
+        if restart_from.is_some() { self.score = self.level_start_score; }
+
+        self.level_start_score = self.score;
+
+        self.level_coins = 0;
+
+        self.level_kills = 0;
+
+        self.level_time = 0.0;
+
+        match restart_from {
+
+            Some(count) => self.gold_coin_count = count,
+
+            None if !carry_coins_on_level => self.gold_coin_count = 0,
+
+            None => {}
+
+        }
+
+        self.gold_coin_count
+
+    }
+
+
+
+    /// Applies the life coin policy after the player respawns.
+
+    pub fn lose_life_coins(&mut self, carry_coins_on_death: bool) {
+
+        if !carry_coins_on_death {
+
+            self.gold_coin_count = 0;
+
+        }
+
+    }
+
+}
+
+
+
+/// How the player left the previous level through a Goal exit, consumed by the level swap.
+
+#[derive(Debug, Clone, Default)]
+
+pub struct LevelExit {
+
+    /// Name of the `SpawnPoint` object to place the player at in the destination level.
+
+    pub spawn_point: Option<String>,
+
 }
 
 
@@ -84,6 +259,22 @@ pub struct UIState {
 
     pub display_coin_count: u32,
 
+    pub display_score: u32,
+
+    /// Health of the primary player, shown as hearts next to the lives.
+
+    pub display_health: u32,
+
+    pub display_max_health: u32,
+
+    /// Title of the level being played, shown briefly when it starts; empty outside levels.
+
+    pub level_name: String,
+
+    /// Simulated **seconds** since `level_name` was set.
+
+    pub level_name_age: f32,
+
 }
 
 
@@ -106,4 +297,111 @@ pub struct FrameDebugInfo {
 
     pub renderable_count: usize,
 
+    /// Positioned entities frozen by `SystemDormancy` this tick.
+
+    pub dormant_count: usize,
+
+    /// Positioned entities still simulated this tick.
+
+    pub active_count: usize,
+
+}
+
+
+
+/// A single short-lived visual particle, simulated on the CPU by `SystemParticles`.
+
+#[derive(Debug, Clone, Copy, Default)]
+
+pub struct Particle {
+
+    /// World-space position in **pixels**.
+
+    pub position: Vector2D,
+
+    /// Velocity in **pixels per second**.
+
+    pub velocity: Vector2D,
+
+    /// Time alive in **seconds**.
+
+    pub age: f32,
+
+    /// Total lifetime in **seconds**.
+
+    pub lifetime: f32,
+
+    /// Downward acceleration in **pixels per second squared**.
+
+    pub gravity: f32,
+
+    pub start_color: [u8; 4],
+
+    pub end_color: [u8; 4],
+
+    /// Edge length in **pixels**.
+
+    pub size: f32,
+
+}
+
+
+
+impl Particle {
+
+    /// Interpolates the RGBA color over the particle's life.
+
+    pub fn color(&self) -> [u8; 4] {
+
+        let t = if self.lifetime > 0.0 { (self.age / self.lifetime).clamp(0.0, 1.0) } else { 1.0 };
+
+        let mut out = [0u8; 4];
+
+        for (i, c) in out.iter_mut().enumerate() {
+
+            *c = (self.start_color[i] as f32 + (self.end_color[i] as f32 - self.start_color[i] as f32) * t) as u8;
+
+        }
+
+        out
+
+    }
+
+}
+
+
+/// A shared time base for animations that must stay in phase (e.g. every coin spinning together).
+
+#[derive(Debug, Clone, Copy, Default)]
+
+pub struct AnimationClock {
+
+    /// Simulated time in **seconds** since the world was created.
+
+    pub time: f32,
+
+}
+
+
+
+/// The seed every new world starts from, so replays and tests spawn identically.
+
+pub const GAME_RNG_SEED: u64 = 0x6766_785f_656e_67;
+
+
+
+/// # Resource: Deterministic RNG
+
+/// Gameplay randomness (spawn variation, animation phase) must come from here, never from `rand::rng()`.
+
+#[derive(Debug, Clone)]
+
+pub struct GameRng(pub StdRng);
+
+
+
+impl Default for GameRng {
+
+    fn default() -> Self { Self(StdRng::seed_from_u64(GAME_RNG_SEED)) }
+
 }
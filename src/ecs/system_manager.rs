@@ -9,6 +9,7 @@ use crate::ecs::systems::{
     physics::SystemPhysics,
     movement::SystemMovement,
     animation_update::SystemAnimationUpdate,
+    animation_events::SystemAnimationEvents,
     input::SystemInput,
     tile_collision::SystemTileCollision,
     gui_render::SystemGUIRender,
@@ -20,6 +21,7 @@ use crate::ecs::systems::{
     concept_vitality::ConceptVitality,
     rule_player_death::RulePlayerDeath,
     rule_respawn::RuleRespawn,
+    rule_landing::RuleLanding,
     game_resolution::SystemGameResolution,
     audio_synchronization::SystemAudioSynchronization,
     level_transition::SystemWorldLevelTransition,
@@ -29,10 +31,18 @@ use crate::ecs::systems::{
     audio::SystemAudio,
     camera_shake::SystemCameraShake,
     animation_synchronization::SystemAnimationSynchronization,
+    trigger::SystemTrigger,
+    hazard::SystemHazard,
+    particles::SystemParticles,
+    invincibility_flash::SystemInvincibilityFlash,
+    projectile::SystemProjectile,
+    water::SystemWater,
+    music_layers::SystemMusicLayers,
     EnemyRhythmContext,
 };
 use crate::ecs::world::World;
-use crate::audio::GameAudioManager;
+use crate::audio::{AudioListener, GameAudioManager};
+use crate::math::Vector2D;
 use crate::ecs::resources::GameState;
 
 /// The container for all engine systems, responsible for the main update pass.
@@ -43,8 +53,16 @@ pub struct SystemManager {
     spatial_update_system: SystemSpatialUpdate,
     tile_collision_system: SystemTileCollision,
     interaction_system: SystemInteraction,
+    trigger_system: SystemTrigger,
+    hazard_system: SystemHazard,
+    particle_system: SystemParticles,
+    invincibility_flash_system: SystemInvincibilityFlash,
+    projectile_system: SystemProjectile,
+    water_system: SystemWater,
+    music_layers_system: SystemMusicLayers,
     animation_synchronization_system: SystemAnimationSynchronization,
     animation_update_system: SystemAnimationUpdate,
+    animation_events_system: SystemAnimationEvents,
     state_machine_system: SystemStateMachine,
     synchronization_system: crate::ecs::systems::synchronization::SystemSynchronization,
     audio_system: SystemAudio,
@@ -58,6 +76,7 @@ pub struct SystemManager {
     concept_vitality: ConceptVitality,
     rule_player_death: RulePlayerDeath,
     rule_respawn: RuleRespawn,
+    rule_landing: RuleLanding,
     game_resolution_system: SystemGameResolution,
     pub debug_render_system: SystemDebugRender,
     pub gui_render_system: SystemGUIRender,
@@ -70,15 +89,16 @@ impl SystemManager {
             input_system: SystemInput, movement_system: SystemMovement,
             physics_system: SystemPhysics, spatial_update_system: SystemSpatialUpdate,
             tile_collision_system: SystemTileCollision, interaction_system: SystemInteraction,
-            animation_synchronization_system: SystemAnimationSynchronization,
-            animation_update_system: SystemAnimationUpdate, state_machine_system: SystemStateMachine,
+            trigger_system: SystemTrigger, hazard_system: SystemHazard, particle_system: SystemParticles::new(), invincibility_flash_system: SystemInvincibilityFlash,
+            projectile_system: SystemProjectile::new(), water_system: SystemWater, music_layers_system: SystemMusicLayers::new(), animation_synchronization_system: SystemAnimationSynchronization,
+            animation_update_system: SystemAnimationUpdate, animation_events_system: SystemAnimationEvents, state_machine_system: SystemStateMachine,
             synchronization_system: crate::ecs::systems::synchronization::SystemSynchronization,
             audio_system: SystemAudio, audio_synchronization_system: SystemAudioSynchronization,
             game_flow_system: SystemGameFlow, level_transition_system: SystemWorldLevelTransition::new(),
             enemy_rhythm_system: SystemEnemyRhythm::new(), camera_shake_system: SystemCameraShake::new(),
             dormancy_system: crate::ecs::systems::dormancy::SystemDormancy, 
             concept_health: ConceptHealth, concept_vitality: ConceptVitality,
-            rule_player_death: RulePlayerDeath, rule_respawn: RuleRespawn,
+            rule_player_death: RulePlayerDeath, rule_respawn: RuleRespawn, rule_landing: RuleLanding,
             game_resolution_system: SystemGameResolution,
             debug_render_system: SystemDebugRender, gui_render_system: SystemGUIRender, transition_system: SystemTransition::new(),
         }
@@ -91,28 +111,49 @@ impl SystemManager {
         context: &mut SystemContext,
         audio_manager: &mut GameAudioManager,
     ) {
-        world.snapshot_positions();
+        audio_manager.set_listener(Self::audio_listener(world, context.camera));
         audio_manager.process_events(); 
 
-        if let Some(handle) = &audio_manager.current_music_handle {
-             let position = handle.position();
-             if position < world.music_state.current_time { world.music_state.last_beat = None; }
+        // During a crossfade the beats follow whichever track is louder, so rhythm enemies never hear the quiet one.
+        if let Some(playhead) = audio_manager.playhead() {
+             let position = playhead.position;
+             if playhead.track != world.music_state.track || position < world.music_state.current_time { world.music_state.last_beats.clear(); }
+             world.music_state.track = playhead.track;
              world.music_state.current_time = position;
-             if let Some(beat_map) = &audio_manager.current_beat_map {
-                 let last_beat_time = world.music_state.last_beat.map(|b| b.time).unwrap_or(-1.0);
+             for band in crate::audio_analysis::Band::ALL {
+                 let Some(beat_map) = playhead.beat_maps.get(&band) else { continue };
+                 let last_beat_time = world.music_state.last_beats.get(&band).map(|b| b.time).unwrap_or(-1.0);
                  let mut best_beat = None;
                  for beat in beat_map {
                      if beat.time as f64 <= position && beat.time as f64 > last_beat_time as f64 { best_beat = Some(*beat); }
                      if beat.time as f64 > position { break; }
                  }
                  if let Some(new_beat) = best_beat {
-                      world.music_state.last_beat = Some(new_beat);
+                      world.music_state.last_beats.insert(band, new_beat);
                       use crate::ecs::event::EventMusicBeat;
-                      world.event_bus.publish(EventMusicBeat { beat_number: 0, intensity: new_beat.intensity });
+                      world.event_bus.publish(EventMusicBeat { beat_number: 0, intensity: new_beat.intensity, band });
                  }
              }
         }
 
+        self.step(world, context);
+        self.audio_system.update(world, audio_manager);
+    }
+
+    /// Positional sounds are heard from the primary player (slot 0), or the view center without one.
+    fn audio_listener(world: &World, camera: &crate::camera::Camera) -> AudioListener {
+        let player = world.player_tags.keys().copied().min_by_key(|e| world.player_slots.get(e).map_or(0, |s| s.0));
+        let view_center = Vector2D::new(camera.position.x + camera.view_width() / 2.0, camera.position.y + camera.view_height() / 2.0);
+        let position = player.and_then(|p| world.positions.get(&p)).map_or(view_center, |p| p.0);
+        AudioListener { position, half_width: camera.view_width() / 2.0 }
+    }
+
+    /// Runs one tick of input, gameplay and presentation-state systems without touching the audio device.
+    ///
+    /// Used by [Self::update] and directly by headless harnesses; sounds are only sent through
+    /// `context.audio_sender`.
+    pub fn step(&mut self, world: &mut World, context: &mut SystemContext) {
+        world.snapshot_positions();
         context.benchmarker.push("Input"); self.input_system.update(world, context); context.benchmarker.pop();
 
         let should_run_gameplay = match &world.game_state {
@@ -123,10 +164,13 @@ impl SystemManager {
 
         if should_run_gameplay {
             context.benchmarker.push("Dormancy"); self.dormancy_system.update(world, context); context.benchmarker.pop();
+            context.benchmarker.push("Water"); self.water_system.update(world, context); context.benchmarker.pop();
             context.benchmarker.push("Movement"); self.movement_system.update(world, context); context.benchmarker.pop();
             context.benchmarker.push("Physics"); self.physics_system.update(world, context); context.benchmarker.pop();
+            // SpatialUpdate owns the Collision.rect <- Position sync; it must precede Interaction and TileCollision.
             context.benchmarker.push("SpatialUpdate"); self.spatial_update_system.update(world, context); context.benchmarker.pop();
             context.benchmarker.push("Interaction"); self.interaction_system.update(world, context); context.benchmarker.pop();
+            context.benchmarker.push("Trigger"); self.trigger_system.update(world, context); context.benchmarker.pop();
             
             // New Decomposed Lifecycle Systems
             context.benchmarker.push("ConceptVitality"); self.concept_vitality.update(world, context); context.benchmarker.pop();
@@ -135,8 +179,11 @@ impl SystemManager {
             context.benchmarker.push("RuleRespawn"); self.rule_respawn.update(world, context); context.benchmarker.pop();
             
             context.benchmarker.push("Synchronization"); self.synchronization_system.update(world, context); context.benchmarker.pop();
+            context.benchmarker.push("Hazard"); self.hazard_system.update(world, context); context.benchmarker.pop();
             context.benchmarker.push("GameResolution"); self.game_resolution_system.update(world, context); context.benchmarker.pop();
             context.benchmarker.push("TileCollision"); self.tile_collision_system.update(world, context); context.benchmarker.pop();
+            context.benchmarker.push("RuleLanding"); self.rule_landing.update(world, context); context.benchmarker.pop();
+            context.benchmarker.push("Projectile"); self.projectile_system.update(world, context); context.benchmarker.pop();
             context.benchmarker.push("GameFlow"); self.game_flow_system.update(world, context); context.benchmarker.pop();
             context.benchmarker.push("LevelTransition"); self.level_transition_system.update(world, context); context.benchmarker.pop();
             { let mut erc = EnemyRhythmContext { game_config: context.game_config, delta_time: context.delta_time, camera: context.camera };
@@ -144,6 +191,10 @@ impl SystemManager {
             context.benchmarker.push("StateMachine"); self.state_machine_system.update(world, context); context.benchmarker.pop();
             context.benchmarker.push("AnimationSync"); self.animation_synchronization_system.update(world, context); context.benchmarker.pop();
             context.benchmarker.push("AnimationUpdate"); self.animation_update_system.update(world, context); context.benchmarker.pop();
+            context.benchmarker.push("AnimationEvents"); self.animation_events_system.update(world, context); context.benchmarker.pop();
+            context.benchmarker.push("InvincibilityFlash"); self.invincibility_flash_system.update(world, context); context.benchmarker.pop();
+            context.benchmarker.push("Particles"); self.particle_system.update(world, context); context.benchmarker.pop();
+            context.benchmarker.push("MusicLayers"); self.music_layers_system.update(world, context); context.benchmarker.pop();
         }
 
         match &world.game_state {
@@ -158,7 +209,6 @@ impl SystemManager {
             },
             _ => {}
         }
-        self.audio_system.update(world, audio_manager);
     }
 }
 
@@ -0,0 +1,27 @@
+//! # Concept: Animation Frame Events
+//!
+//! This module turns the frame events configured on `[animation.*]` clips into
+//! facts on the event bus, so gameplay (hitboxes, sounds) can be timed to the
+//! exact frame an animator chose instead of to a separate timer.
+
+use crate::ecs::systems::{System, SystemContext};
+use crate::ecs::event::EventAnimationFrame;
+
+/// A system that drains the events fired by each entity's animation controller.
+pub struct SystemAnimationEvents;
+
+impl System<SystemContext<'_>> for SystemAnimationEvents {
+    /// Publishes the frame events fired during this tick's animation update.
+    ///
+    /// ⚠️ **Hotpath**: Called 120x per second.
+    ///
+    /// # Side Effects
+    /// * Publishes one [EventAnimationFrame] per fired event, in firing order.
+    fn update(&mut self, world: &mut crate::ecs::world::World, _context: &mut SystemContext<'_>) {
+        for (&entity, animation) in world.animations.iter_mut() {
+            for name in animation.controller.take_events() {
+                world.event_bus.publish(EventAnimationFrame { entity, name });
+            }
+        }
+    }
+}
@@ -36,6 +36,10 @@ impl SystemAnimationSynchronization {
             if state_name == "DyingState" || state_name == "DeadState" {
                 return None; 
             }
+            if state_name == "AttackingState" {
+                let dir = world.directions.get(&entity).map(|d| d.direction).unwrap_or(Direction::Right);
+                return Some(if dir == Direction::Left { "attack_left" } else { "attack_right" }.to_string());
+            }
         }
 
         let is_grounded = world.is_grounded(entity);
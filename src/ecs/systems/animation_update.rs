@@ -14,10 +14,15 @@ impl System<SystemContext<'_>> for SystemAnimationUpdate {
     ///
     /// ⚠️ **Hotpath**: Called 120x per second.
     fn update(&mut self, world: &mut crate::ecs::world::World, context: &mut SystemContext<'_>) {
-        // 1. Iterate over every entity currently playing an animation.
-        for animation in world.animations.values_mut() {
-            // 2. Advance the controller by the frame's elapsed time.
-            animation.controller.update(context.delta_time);
+        // 1. Advance the shared clock that synced animations read their frame from.
+        world.animation_clock.time += context.delta_time;
+        let clock = world.animation_clock;
+
+        // 2. Iterate over every awake entity currently playing an animation.
+        for (entity, animation) in world.animations.iter_mut() {
+            if world.dormant_tags.contains_key(entity) { continue; }
+            // 3. Advance the controller by the frame's elapsed time (or to the clock's frame).
+            animation.controller.advance(context.delta_time, &clock);
         }
     }
 }
@@ -1,72 +1,72 @@
 //! # Concept: Screen Shake
-//! 
-//! This module manages the visual impact of high-energy events. 
-//! it tracks 'Trauma' levels and translates them into random camera offsets 
-//! to create a physical sense of impact and intensity.
+//!
+//! This module manages the visual impact of high-energy events.
+//! Events add 'Trauma' (0..1), which decays over time; the shake amplitude
+//! grows with the square of the trauma and follows smooth noise, so small
+//! knocks barely register while big hits rattle the screen. The result is a
+//! render-only offset on the camera: gameplay never sees it.
 
 use crate::ecs::systems::SystemContext;
-use crate::ecs::event::{EventScreenShake, EventPlayerDamaged, EventPlayerEnemyStomped};
+use crate::ecs::event::{EventScreenShake, EventPlayerDamaged, EventPlayerEnemyStomped, EventTriggerActivated};
 use crate::math::Vector2D;
-use rand::Rng;
 
-/// A system that manages camera trauma and calculates random screen shake offsets.
+/// A system that manages camera trauma and calculates noise-driven screen shake offsets.
 pub struct SystemCameraShake {
+    /// Current trauma in `[0.0, 1.0]`.
     pub trauma: f32,
-    pub shake_timer: f32,
+    /// Time in **seconds** the noise has advanced.
+    time: f32,
 }
 
 impl SystemCameraShake {
     pub fn new() -> Self {
         Self {
             trauma: 0.0,
-            shake_timer: 0.0,
+            time: 0.0,
         }
     }
 
+    /// Adds `amount` of trauma; the total saturates at 1.0.
+    pub fn add_trauma(&mut self, amount: f32) {
+        self.trauma = (self.trauma + amount.max(0.0)).min(1.0);
+    }
+
+    /// Shake displacement in **pixels** for the current trauma: `trauma²` scaled by `max_offset`,
+    /// steered by noise sampled `frequency` times per second.
+    pub fn offset(&self, max_offset: f32, frequency: f32) -> Vector2D {
+        let amplitude = self.trauma * self.trauma * max_offset;
+        let t = self.time * frequency;
+        Vector2D::new(amplitude * value_noise(t, 0), amplitude * value_noise(t, 1))
+    }
+
     /// Updates trauma levels and calculates the current frame's camera offset.
     ///
     /// ⚠️ **Hotpath**: Called 120x per second.
+    ///
+    /// # Side Effects
+    /// * Writes `context.camera.shake_offset`; the camera position itself is never moved.
     pub fn update(&mut self, world: &mut crate::ecs::world::World, context: &mut SystemContext<'_>) {
+        let config = &context.game_config.gameplay.camera_shake;
+
         // 1. Process explicit ScreenShake facts to increase trauma levels.
         for event in world.event_bus.read::<EventScreenShake>() {
-            self.trauma = (self.trauma + event.intensity).min(1.0);
-            self.shake_timer = event.duration.max(self.shake_timer);
+            self.add_trauma(event.trauma);
         }
 
         // 2. Automatically trigger trauma based on significant gameplay facts.
-        // Player Damaged -> Heavy Impact
-        if world.event_bus.read::<EventPlayerDamaged>().count() > 0 {
-             self.trauma = (self.trauma + 0.8).min(1.0);
-             self.shake_timer = 0.5f32.max(self.shake_timer);
-        }
-        
-        // Enemy Stomped -> Light Impact
-        if world.event_bus.read::<EventPlayerEnemyStomped>().count() > 0 {
-             self.trauma = (self.trauma + 0.3).min(1.0);
-             self.shake_timer = 0.2f32.max(self.shake_timer);
-        }
+        if world.event_bus.read::<EventPlayerDamaged>().count() > 0 { self.add_trauma(config.damage_trauma); }
+        if world.event_bus.read::<EventPlayerEnemyStomped>().count() > 0 { self.add_trauma(config.stomp_trauma); }
 
-        // 3. Update the trauma decay and calculate the resulting offset.
-        if self.shake_timer > 0.0 {
-            self.shake_timer -= context.delta_time;
-            
-            // Linear decay of trauma over time.
-            self.trauma = (self.trauma - context.delta_time).max(0.0);
-
-            // Calculate magnitude using squared trauma for a more natural 'impact' feel.
-            let shake_magnitude = self.trauma * self.trauma * 20.0;
-
-            let mut rng = rand::rng();
-            let offset_x = rng.random_range(-shake_magnitude..shake_magnitude);
-            let offset_y = rng.random_range(-shake_magnitude..shake_magnitude);
-
-            // 4. Apply the calculated displacement to the camera context.
-            context.camera.shake_offset = Vector2D::new(offset_x, offset_y);
-        } else {
-            // Reset to identity when trauma is depleted.
-            self.trauma = 0.0;
-            context.camera.shake_offset = Vector2D::default();
+        // Scripted Trigger -> Trauma from the zone's `shake` property
+        for event in world.event_bus.read::<EventTriggerActivated>() {
+            if let Some(trigger) = world.triggers.get(&event.trigger) { self.add_trauma(trigger.shake); }
         }
+
+        // 3. Sample the offset, then let the trauma decay linearly.
+        self.time += context.delta_time;
+        context.camera.shake_offset = self.offset(config.max_offset, config.frequency);
+        self.trauma = (self.trauma - config.decay * context.delta_time).max(0.0);
+        if self.trauma == 0.0 { self.time = 0.0; }
     }
 }
 
@@ -75,3 +75,55 @@ impl Default for SystemCameraShake {
         Self::new()
     }
 }
+
+/// Smooth 1D value noise in `[-1.0, 1.0]`; each `seed` gives an independent curve.
+fn value_noise(x: f32, seed: u32) -> f32 {
+    let lattice = |i: i32| {
+        let mut h = (i as u32).wrapping_mul(0x9E37_79B1) ^ seed.wrapping_mul(0x85EB_CA77);
+        h ^= h >> 15;
+        h = h.wrapping_mul(0x2C1B_3C6D);
+        h ^= h >> 12;
+        (h as f32 / u32::MAX as f32) * 2.0 - 1.0
+    };
+    let i = x.floor();
+    let t = x - i;
+    let t = t * t * (3.0 - 2.0 * t);
+    let (a, b) = (lattice(i as i32), lattice(i as i32 + 1));
+    a + (b - a) * t
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trauma_saturates_and_scales_quadratically() {
+        let mut shake = SystemCameraShake::new();
+        assert_eq!(shake.offset(20.0, 25.0), Vector2D::default(), "No trauma, no shake");
+
+        shake.add_trauma(0.7);
+        shake.add_trauma(0.7);
+        assert_eq!(shake.trauma, 1.0);
+
+        for step in 0..100 {
+            shake.time = step as f32 * 0.013;
+            shake.trauma = 1.0;
+            let full = shake.offset(20.0, 25.0);
+            shake.trauma = 0.5;
+            let half = shake.offset(20.0, 25.0);
+            assert!(full.x.abs() <= 20.0 && full.y.abs() <= 20.0);
+            assert!((half.x - full.x / 4.0).abs() < 1e-4, "Half the trauma is a quarter of the shake");
+        }
+    }
+
+    #[test]
+    fn test_noise_is_continuous() {
+        let mut previous = value_noise(0.0, 0);
+        for step in 1..1000 {
+            let sample = value_noise(step as f32 * 0.01, 0);
+            assert!((-1.0..=1.0).contains(&sample));
+            assert!((sample - previous).abs() < 0.1, "No jumps between neighbouring samples");
+            previous = sample;
+        }
+    }
+}
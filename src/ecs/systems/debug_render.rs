@@ -5,9 +5,124 @@
 //! performance metrics, and application state for observability.
 
 use crate::ecs::systems::RenderContext;
-use crate::renderer::{Renderer, TextRenderParams};
+use crate::ecs::world::{Entity, World};
+use crate::config::DebugConfig;
+use crate::input::{DebugAction, InputState};
+use crate::renderer::{Renderer, TextAlign, TextRenderParams};
+use crate::math::Vector2D;
 use sdl3::pixels::Color;
 
+/// Which developer overlays are visible.
+///
+/// Owned by the [crate::game_state_manager::GameStateManager], so the choice survives level
+/// loads, restarts and trips through the menu.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DebugOverlays {
+    /// Master switch; the other layers are only drawn while this is on.
+    pub enabled: bool,
+    pub collision_boxes: bool,
+    pub hotspots: bool,
+    pub tile_grid: bool,
+    /// The camera is panned by hand instead of following the player.
+    pub free_camera: bool,
+    /// Entity picked with [DebugAction::InspectEntity] while the free camera is active.
+    pub inspected: Option<Entity>,
+    /// Gameplay systems are frozen; [DebugAction::StepFrame] advances them a tick at a time.
+    pub paused: bool,
+    /// Ticks requested with [DebugAction::StepFrame] that have not run yet.
+    pub pending_steps: u32,
+    /// Multiplier on the delta time handed to the simulation, cycled through [TIME_SCALES]; held at 1x while recording.
+    pub time_scale: f32,
+}
+
+/// Simulation speeds offered by [DebugAction::CycleTimeScale], in cycling order.
+pub const TIME_SCALES: [f32; 3] = [1.0, 0.5, 0.25];
+
+impl DebugOverlays {
+    pub fn from_config(config: &DebugConfig) -> Self {
+        Self {
+            enabled: config.show_debug_info, collision_boxes: config.debug_draw_collision_boxes, hotspots: config.show_hotspots, tile_grid: config.show_tile_grid,
+            free_camera: false, inspected: None, paused: false, pending_steps: 0, time_scale: 1.0,
+        }
+    }
+
+    /// Flips every overlay whose toggle key was pressed this frame.
+    pub fn apply_input(&mut self, input_state: &InputState) {
+        let pressed = |action| input_state.is_debug_action_just_pressed(action);
+        if pressed(DebugAction::ToggleDebugInfo) { self.enabled = !self.enabled; }
+        if pressed(DebugAction::ToggleCollisionBoxes) { self.collision_boxes = !self.collision_boxes; }
+        if pressed(DebugAction::ToggleHotspots) { self.hotspots = !self.hotspots; }
+        if pressed(DebugAction::ToggleTileGrid) { self.tile_grid = !self.tile_grid; }
+        if pressed(DebugAction::ToggleFreeCamera) {
+            self.free_camera = !self.free_camera;
+            self.inspected = None;
+        }
+        if pressed(DebugAction::PauseSimulation) {
+            self.paused = !self.paused;
+            self.pending_steps = 0;
+        }
+        if pressed(DebugAction::StepFrame) && self.paused { self.pending_steps += 1; }
+        if pressed(DebugAction::CycleTimeScale) {
+            let current = TIME_SCALES.iter().position(|&s| s == self.time_scale).unwrap_or(0);
+            self.time_scale = TIME_SCALES[(current + 1) % TIME_SCALES.len()];
+        }
+    }
+
+    /// How many of the `due` fixed ticks actually run: all of them, or only the requested steps while paused.
+    pub fn simulation_ticks(&mut self, due: u32) -> u32 {
+        if !self.paused { return due; }
+        std::mem::take(&mut self.pending_steps)
+    }
+
+    /// "PAUSED", "0.5x" or both; `None` when the simulation runs normally.
+    pub fn time_label(&self) -> Option<String> {
+        let scale = (self.time_scale != 1.0).then(|| format!("{}x", self.time_scale));
+        match (self.paused, scale) {
+            (true, Some(scale)) => Some(format!("PAUSED {}", scale)),
+            (true, None) => Some("PAUSED".to_string()),
+            (false, scale) => scale,
+        }
+    }
+}
+
+/// Finds the entity whose collision box (or sprite, when it has none) is closest to `point`.
+/// Points inside a box have distance 0; ties go to the lowest entity id.
+pub fn nearest_entity(world: &World, point: Vector2D) -> Option<Entity> {
+    let distance = |x: f32, y: f32, w: f32, h: f32| {
+        let dx = (x - point.x).max(0.0).max(point.x - (x + w));
+        let dy = (y - point.y).max(0.0).max(point.y - (y + h));
+        dx * dx + dy * dy
+    };
+    let mut candidates: Vec<(Entity, f32)> = world.collisions.iter()
+        .map(|(e, c)| (*e, distance(c.rect.x as f32, c.rect.y as f32, c.rect.width() as f32, c.rect.height() as f32)))
+        .collect();
+    candidates.extend(world.renderables.iter()
+        .filter(|(e, _)| !world.collisions.contains_key(e))
+        .filter_map(|(e, r)| {
+            let pos = world.positions.get(e)?.0;
+            Some((*e, distance(pos.x + r.horizontal_offset as f32, pos.y + r.vertical_offset as f32, r.width as f32, r.height as f32)))
+        }));
+    candidates.into_iter()
+        .min_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)))
+        .map(|(entity, _)| entity)
+}
+
+/// Describes an inspected entity, one component per line; absent components are skipped.
+pub fn inspect_lines(world: &World, entity: Entity) -> Vec<String> {
+    let mut lines = vec![format!("Entity #{}", entity)];
+    if let Some(pos) = world.positions.get(&entity) { lines.push(format!("Pos: ({:.1}, {:.1})", pos.0.x, pos.0.y)); }
+    if let Some(vel) = world.velocities.get(&entity) { lines.push(format!("Vel: ({:.1}, {:.1})", vel.0.x, vel.0.y)); }
+    if let Some(state) = world.state_components.get(&entity) {
+        lines.push(format!("State: {}", state.state_machine.current_state.as_ref().map_or("None", |s| s.get_name())));
+    }
+    if let Some(health) = world.healths.get(&entity) { lines.push(format!("Health: {}/{}", health.current, health.max)); }
+    if let Some(animation) = world.animations.get(&entity).and_then(|a| a.controller.current_animation_name()) {
+        lines.push(format!("Anim: {}", animation));
+    }
+    if world.positions.get(&entity).is_none() { lines.push("(despawned)".to_string()); }
+    lines
+}
+
 /// A system that renders hitboxes and engine metrics to the screen.
 pub struct SystemDebugRender;
 
@@ -25,11 +140,53 @@ impl SystemDebugRender {
         font_manager: &crate::font_manager::FontManager,
         frame_count: u64,
         fps: u32,
+        overlays: DebugOverlays,
+        level: &crate::level::Level,
     ) -> Result<(), String> {
-        
-        // 1. Visualize Collision Boundaries.
-        if context.config.debug.debug_draw_collision_boxes {
-            let scale = crate::config::RENDER_SCALE_FACTOR;
+        // 0. Mark the free camera's crosshair and describe what lies under it.
+        if overlays.free_camera {
+            let scale = renderer.view.screen_scale(camera.zoom);
+            let center = camera.view_center();
+            let (cx, cy) = (((center.x - camera.position.x) * scale) as i32, ((center.y - camera.position.y) * scale) as i32);
+            let crosshair = Color::RGB(0, 255, 255);
+            renderer.fill_rect(&sdl3::rect::Rect::new(cx - 12, cy - 1, 24, 2), crosshair)?;
+            renderer.fill_rect(&sdl3::rect::Rect::new(cx - 1, cy - 12, 2, 24), crosshair)?;
+
+            let tile_x = (center.x / level.tileset.tile_width.max(1) as f32).floor() as i32;
+            let tile_y = (center.y / level.tileset.tile_height.max(1) as f32).floor() as i32;
+            let mut lines = vec![format!("Free camera - tile ({}, {})", tile_x, tile_y)];
+            if let Some(entity) = overlays.inspected { lines.extend(inspect_lines(world, entity)); }
+            renderer.render_text(font_manager, TextRenderParams { text: &lines.join("\n"), x: cx + 16, y: cy + 16, font_size: 24.0, scale: 1.0, color: crosshair, line_height: Some(context.config.debug.text_line_spacing), ..Default::default() })?;
+        }
+
+        // 0b. Time control stays visible with the overlay hidden, so a frozen game never looks hung.
+        if let Some(label) = overlays.time_label() {
+            renderer.render_text(font_manager, TextRenderParams { text: &label, x: renderer.output_size().0 as i32 / 2, y: 40, font_size: 48.0, scale: 1.0, color: Color::RGB(255, 220, 0), align: TextAlign::Center, ..Default::default() })?;
+        }
+
+        if !overlays.enabled { return Ok(()); }
+
+        // 1. Outline the tiles in view.
+        if overlays.tile_grid {
+            let scale = renderer.view.screen_scale(camera.zoom);
+            let (tile_w, tile_h) = (level.tileset.tile_width, level.tileset.tile_height);
+            let (rows, cols) = crate::renderer::visible_tile_range(camera, tile_w, tile_h, level.map.height, level.map.width);
+            for row in rows {
+                for col in cols.clone() {
+                    let screen_rect = sdl3::rect::Rect::new(
+                        (((col as u32 * tile_w) as f32 - camera.position.x) * scale) as i32,
+                        (((row as u32 * tile_h) as f32 - camera.position.y) * scale) as i32,
+                        (tile_w as f32 * scale) as u32,
+                        (tile_h as f32 * scale) as u32,
+                    );
+                    renderer.draw_rect(&screen_rect, Color::RGB(60, 60, 90))?;
+                }
+            }
+        }
+
+        // 2. Visualize Collision Boundaries.
+        if overlays.collision_boxes {
+            let scale = renderer.view.screen_scale(camera.zoom);
             for collision in world.collisions.values() {
                 // Transform world-space collision rect to screen-space for drawing.
                 let screen_rect = sdl3::rect::Rect::new(
@@ -42,7 +199,7 @@ impl SystemDebugRender {
             }
         }
 
-        // 2. Render Left-side Debug Text (Player state and core metrics).
+        // 3. Render Left-side Debug Text (Player state and core metrics).
         if let Some(player_entity) = context.player_entity
             && let (Some(_pos), Some(_vel), Some(state_comp), Some(_collision)) = (
                 world.positions.get(&player_entity),
@@ -66,6 +223,7 @@ impl SystemDebugRender {
                         font_size: 24.0,
                         scale: 1.0,
                         color: debug_text_color,
+                        ..Default::default()
                     })?;
                     current_y += line_height;
                     Ok(())
@@ -86,20 +244,31 @@ impl SystemDebugRender {
                 let pp_pos = info.player_prev_pos.unwrap_or_default();
                 let c_pos = info.camera_pos.unwrap_or_default();
 
-                renderer.render_text(font_manager, TextRenderParams { text: &format!("[Player] Pos: ({:.1}, {:.1})", p_pos.x, p_pos.y), x: start_x, y: current_y, font_size: 24.0, scale: 1.0, color: debug_text_color })?;
+                renderer.render_text(font_manager, TextRenderParams { text: &format!("[Player] Pos: ({:.1}, {:.1})", p_pos.x, p_pos.y), x: start_x, y: current_y, font_size: 24.0, scale: 1.0, color: debug_text_color, ..Default::default() })?;
+                current_y += line_height;
+                renderer.render_text(font_manager, TextRenderParams { text: &format!("[Player] Prev Pos: ({:.1}, {:.1})", pp_pos.x, pp_pos.y), x: start_x, y: current_y, font_size: 24.0, scale: 1.0, color: debug_text_color, ..Default::default() })?;
+                current_y += line_height;
+                renderer.render_text(font_manager, TextRenderParams { text: &format!("[Player] Render W/H: ({}, {})", info.player_render_w, info.player_render_h), x: start_x, y: current_y, font_size: 24.0, scale: 1.0, color: debug_text_color, ..Default::default() })?;
                 current_y += line_height;
-                renderer.render_text(font_manager, TextRenderParams { text: &format!("[Player] Prev Pos: ({:.1}, {:.1})", pp_pos.x, pp_pos.y), x: start_x, y: current_y, font_size: 24.0, scale: 1.0, color: debug_text_color })?;
+                renderer.render_text(font_manager, TextRenderParams { text: &format!("[Camera] Pos: ({:.1}, {:.1})", c_pos.x, c_pos.y), x: start_x, y: current_y, font_size: 24.0, scale: 1.0, color: debug_text_color, ..Default::default() })?;
                 current_y += line_height;
-                renderer.render_text(font_manager, TextRenderParams { text: &format!("[Player] Render W/H: ({}, {})", info.player_render_w, info.player_render_h), x: start_x, y: current_y, font_size: 24.0, scale: 1.0, color: debug_text_color })?;
+                renderer.render_text(font_manager, TextRenderParams { text: &format!("[World] Renderables: {}", info.renderable_count), x: start_x, y: current_y, font_size: 24.0, scale: 1.0, color: debug_text_color, ..Default::default() })?;
                 current_y += line_height;
-                renderer.render_text(font_manager, TextRenderParams { text: &format!("[Camera] Pos: ({:.1}, {:.1})", c_pos.x, c_pos.y), x: start_x, y: current_y, font_size: 24.0, scale: 1.0, color: debug_text_color })?;
+                renderer.render_text(font_manager, TextRenderParams { text: &format!("[World] Active: {} / Dormant: {}", info.active_count, info.dormant_count), x: start_x, y: current_y, font_size: 24.0, scale: 1.0, color: debug_text_color, ..Default::default() })?;
                 current_y += line_height;
-                renderer.render_text(font_manager, TextRenderParams { text: &format!("[World] Renderables: {}", info.renderable_count), x: start_x, y: current_y, font_size: 24.0, scale: 1.0, color: debug_text_color })?;
+                let stats = renderer.draw_stats;
+                renderer.render_text(font_manager, TextRenderParams { text: &format!("[Render] Tiles: {} in {} batches", stats.tiles, stats.tile_batches), x: start_x, y: current_y, font_size: 24.0, scale: 1.0, color: debug_text_color, ..Default::default() })?;
+                current_y += line_height;
+                renderer.render_text(font_manager, TextRenderParams { text: &format!("[Render] Sprites: {} in {} batches", stats.sprites, stats.sprite_batches), x: start_x, y: current_y, font_size: 24.0, scale: 1.0, color: debug_text_color, ..Default::default() })?;
+                current_y += line_height;
+                renderer.render_text(font_manager, TextRenderParams { text: &format!("[Render] Text: {} cached, {} rasterized", stats.text_cache_hits, stats.text_rasterized), x: start_x, y: current_y, font_size: 24.0, scale: 1.0, color: debug_text_color, ..Default::default() })?;
         }
 
-        // 3. Render Right-side Performance Hotspots.
+        // 4. Render Right-side Performance Hotspots.
         // Displays a sorted list of systems consuming the most frame budget.
-        let start_x_right = 1000; 
+        if !overlays.hotspots { return Ok(()); }
+        // Anchored to the right edge of the window, whatever its width.
+        let start_x_right = renderer.output_size().0 as i32 - 40;
         let mut current_y_right = 150; 
         let line_height = context.config.debug.text_line_spacing;
 
@@ -112,11 +281,13 @@ impl SystemDebugRender {
             font_size: 24.0,
             scale: 1.0,
             color: Color::RGB(255, 255, 0),
+            align: TextAlign::Right,
+            ..Default::default()
         })?;
         current_y_right += line_height;
 
         for (name, percent) in sorted_metrics {
-             // 4. Highlight expensive systems with warning colors.
+             // 5. Highlight expensive systems with warning colors.
              if percent < 1.0 { continue; }
 
              let color = if percent > 25.0 { Color::RGB(255, 50, 50) } // High Alert (Critical)
@@ -132,6 +303,8 @@ impl SystemDebugRender {
                 font_size: 24.0,
                 scale: 1.0,
                 color,
+                align: TextAlign::Right,
+                ..Default::default()
             })?;
             current_y_right += line_height;
         }
@@ -139,3 +312,47 @@ impl SystemDebugRender {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::component::{Collision, Position};
+
+    #[test]
+    fn test_nearest_entity_prefers_the_box_under_the_point() {
+        let mut world = World::new();
+        let far = world.create_entity();
+        world.add_position(far, Position(Vector2D::new(300.0, 0.0)));
+        world.add_collision(far, Collision { rect: sdl3::rect::Rect::new(300, 0, 32, 32) });
+        let under = world.create_entity();
+        world.add_position(under, Position(Vector2D::new(90.0, 90.0)));
+        world.add_collision(under, Collision { rect: sdl3::rect::Rect::new(90, 90, 32, 32) });
+
+        assert_eq!(nearest_entity(&world, Vector2D::new(100.0, 100.0)), Some(under));
+        assert_eq!(nearest_entity(&world, Vector2D::new(280.0, 10.0)), Some(far));
+        assert!(inspect_lines(&world, under).iter().any(|l| l == "Pos: (90.0, 90.0)"));
+        assert_eq!(nearest_entity(&World::new(), Vector2D::default()), None);
+    }
+
+    #[test]
+    fn test_paused_simulation_only_runs_requested_steps() {
+        let mut overlays = DebugOverlays {
+            enabled: false, collision_boxes: false, hotspots: false, tile_grid: false,
+            free_camera: false, inspected: None, paused: false, pending_steps: 0, time_scale: 1.0,
+        };
+        assert_eq!(overlays.simulation_ticks(3), 3);
+        assert_eq!(overlays.time_label(), None);
+
+        overlays.paused = true;
+        assert_eq!(overlays.simulation_ticks(3), 0);
+        overlays.pending_steps = 1;
+        assert_eq!(overlays.simulation_ticks(0), 1, "A step runs even on a frame with no tick due");
+        assert_eq!(overlays.simulation_ticks(3), 0, "Each press runs exactly one tick");
+        assert_eq!(overlays.time_label().as_deref(), Some("PAUSED"));
+
+        overlays.time_scale = TIME_SCALES[1];
+        assert_eq!(overlays.time_label().as_deref(), Some("PAUSED 0.5x"));
+        overlays.paused = false;
+        assert_eq!(overlays.time_label().as_deref(), Some("0.5x"));
+    }
+}
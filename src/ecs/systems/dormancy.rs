@@ -1,50 +1,61 @@
 //! # Concept: Simulation Culling (Dormancy)
 //!
-//! This module implements the "Macro-Culling" optimization. It tags entities 
-//! outside the camera's active range as 'Dormant', excluding them from 
-//! expensive simulation systems like Physics, AI, and Animation.
+//! This module implements the "Macro-Culling" optimization. It tags entities
+//! farther than `[gameplay] dormancy_margin` view sizes outside the camera as
+//! 'Dormant', excluding them from expensive simulation systems like Movement,
+//! Physics, State Machines and Animation. A dormant entity is simply frozen in
+//! place and resumes where it left off once the camera comes near again.
 
 use crate::ecs::systems::{System, SystemContext};
 use crate::ecs::component::DormantTag;
+use crate::ecs::world::{Entity, World};
 
 /// A system that manages entity dormancy based on distance from the camera.
 pub struct SystemDormancy;
 
+impl SystemDormancy {
+    /// Entities that must keep simulating wherever they are: players, goals, triggers,
+    /// and short-lived projectiles whose lifetime would otherwise freeze.
+    pub fn is_always_active(world: &World, entity: Entity) -> bool {
+        world.player_tags.contains_key(&entity)
+            || world.goals.contains_key(&entity)
+            || world.triggers.contains_key(&entity)
+            || world.projectiles.contains_key(&entity)
+    }
+}
+
 impl System<SystemContext<'_>> for SystemDormancy {
     /// Updates the dormancy state of entities based on camera distance.
     ///
     /// ⚠️ **Hotpath**: Called 120x per second.
-    fn update(&mut self, world: &mut crate::ecs::world::World, context: &mut SystemContext<'_>) {
-        // 1. Define the active simulation zone (e.g., 2 screens wide).
-        let camera_x = context.camera.position.x;
-        let viewport_width = context.config.window.virtual_width as f32;
-        let active_margin = viewport_width * 1.5;
-        
-        let min_x = camera_x - active_margin;
-        let max_x = camera_x + viewport_width + active_margin;
+    ///
+    /// # Side Effects
+    /// * Adds or removes [DormantTag] on every positioned entity.
+    fn update(&mut self, world: &mut World, context: &mut SystemContext<'_>) {
+        // 1. Grow the zoom-aware view by the margin on every side, so zooming out (co-op) widens the active zone too.
+        let margin = context.game_config.gameplay.dormancy_margin;
+        let (view_width, view_height) = (context.camera.view_width(), context.camera.view_height());
+        let min_x = context.camera.position.x - view_width * margin;
+        let min_y = context.camera.position.y - view_height * margin;
+        let max_x = context.camera.position.x + view_width * (1.0 + margin);
+        let max_y = context.camera.position.y + view_height * (1.0 + margin);
 
-        // 2. Iterate over all entities with positions.
-        // We collect to avoid borrowing conflicts, though iterating query is better if possible.
+        // 2. Toggle the tag for every entity with a position.
         let entities: Vec<_> = world.positions.keys().copied().collect();
-
-        for entity in entities {
-            // We generally don't cull the player.
-            if world.player_tags.contains_key(&entity) { continue; }
-
-            if let Some(pos) = world.positions.get(&entity) {
-                let x = pos.0.x;
-                
-                // 3. Check bounds and toggle DormantTag.
-                if x < min_x || x > max_x {
-                    if !world.dormant_tags.contains_key(&entity) {
-                        world.add_dormant_tag(entity, crate::ecs::component::DormantTag);
-                    }
-                } else {
-                    if world.dormant_tags.contains_tag(&entity) {
-                        world.dormant_tags.remove(&entity);
-                    }
-                }
+        let mut dormant_count = 0;
+        for &entity in &entities {
+            let position = world.positions[&entity].0;
+            let outside = position.x < min_x || position.x > max_x || position.y < min_y || position.y > max_y;
+            if outside && !Self::is_always_active(world, entity) {
+                if !world.is_dormant(entity) { world.add_dormant_tag(entity, DormantTag); }
+                dormant_count += 1;
+            } else if world.is_dormant(entity) {
+                world.remove_dormant_tag(entity);
             }
         }
+
+        // 3. Publish the split for the debug overlay.
+        world.frame_debug_info.dormant_count = dormant_count;
+        world.frame_debug_info.active_count = entities.len() - dormant_count;
     }
-}
\ No newline at end of file
+}
@@ -4,10 +4,15 @@
 //! It acts as the "Rules Engine" for meta-progression, managing player lives,
 //! death sequences, and level-wide state transitions.
 
-use crate::ecs::event::EventPlayerDied;
+use crate::ecs::event::{EventPlayerDied, EventBossDefeated, EventLevelExitRequested};
 use crate::ecs::systems::{System, SystemContext};
+use crate::ecs::systems::level_transition::goal_touched_by;
+use crate::config::GoalDeathPrecedence;
 
-/// A system that manages lives, respawn timing, and game over triggers.
+/// A system that manages lives, respawn timing, game over triggers and boss-defeat exits.
+///
+/// Runs before [crate::ecs::systems::level_transition::SystemWorldLevelTransition]; together
+/// they apply `gameplay.goal_death_precedence` when a goal and a death coincide.
 pub struct SystemGameFlow;
 
 impl System<SystemContext<'_>> for SystemGameFlow {
@@ -19,12 +24,18 @@ impl System<SystemContext<'_>> for SystemGameFlow {
         let events: Vec<EventPlayerDied> = world.event_bus.read::<EventPlayerDied>().cloned().collect();
 
         for event in events {
-            // 2. Only decrement lives if we have a positive balance.
+            // 2. A player who reached the goal this tick (or whose level is already ending) keeps the life.
+            if context.game_config.gameplay.goal_death_precedence == GoalDeathPrecedence::Goal
+                && (world.level_exit.is_some() || goal_touched_by(world, event.player).is_some()) {
+                continue;
+            }
+
+            // 3. Only decrement lives if we have a positive balance.
             if world.stats.lives > 0 {
                 world.stats.lives -= 1;
                 println!("[GameFlow] Player died (Reason: {:?}). Lives remaining: {}", event.reason, world.stats.lives);
                 
-                // 3. Determine the duration of the respawn sequence based on how the player died.
+                // 4. Determine the duration of the respawn sequence based on how the player died.
                 // Out-of-bounds falls transition faster than health depletion.
                 let duration = if event.reason == crate::ecs::event::PlayerDeathReason::FellOutOfBounds {
                     3.0 
@@ -32,19 +43,27 @@ impl System<SystemContext<'_>> for SystemGameFlow {
                     context.game_config.gameplay.respawn_invincibility_duration
                 };
 
-                // 4. Schedule the respawn by adding a RespawnTimer to the entity.
+                // 5. Schedule the respawn by adding a RespawnTimer to the entity.
                 println!("[GameFlow] Adding RespawnTimer for entity {:?}. Timer: {}", event.player, duration);
                 world.add_respawn_timer(event.player, crate::ecs::component::RespawnTimer {
                     timer: duration, 
                     transition_started: false,
+                    transition_complete: false,
                 });
             }
 
-            // 5. Trigger the global Game Over state if no lives remain.
+            // 6. Trigger the global Game Over state if no lives remain.
             if world.stats.lives == 0 {
                 println!("[GameFlow] Game Over!");
                 // Future: world.event_bus.publish(EventGameOver);
             }
         }
+
+        // 7. A defeated boss that leads somewhere ends the level like a goal would.
+        let exits: Vec<String> = world.event_bus.read::<EventBossDefeated>().filter_map(|e| e.next_level.clone()).collect();
+        for next_level in exits {
+            println!("[GameFlow] Boss defeated! Leaving for {}", next_level);
+            world.event_bus.publish(EventLevelExitRequested { next_level });
+        }
     }
 }
\ No newline at end of file
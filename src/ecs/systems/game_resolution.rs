@@ -4,9 +4,13 @@
 //! events (from Synchronization) and applies the consequences to the world 
 //! (Death, Score, Health). It also triggers sensory feedback (Audio/UI).
 
+use crate::ecs::query::query2;
 use crate::ecs::systems::{System, SystemContext};
-use crate::ecs::component::{DeadTag, Invincibility, Position, Renderable, Lifetime, Animation};
-use crate::ecs::event::{EventCoinCollected, EventPlayerDamaged, EventPlayerEnemyStomped};
+use crate::ecs::component::{DeadTag, Invincibility, Knockback, Position, Renderable, Lifetime, Animation, StompCombo};
+use crate::ecs::resources::GameState;
+use crate::score;
+use crate::ecs::event::{EventCoinCollected, EventHealthCollected, EventEnemyHit, EventPlayerDamaged, EventPlayerEnemyStomped, EventCheckpointReached, EventParticleBurst, EventProjectileHit, EventWaterSplash, EventTriggerActivated, EventBossDefeated};
+use crate::enemy::boss;
 use crate::animation::AnimationController;
 use crate::audio::{AudioEvent, PlaySoundParams};
 
@@ -17,82 +21,138 @@ impl System<SystemContext<'_>> for SystemGameResolution {
     ///
     /// ⚠️ **Hotpath**: Called 120x per second.
     fn update(&mut self, world: &mut crate::ecs::world::World, context: &mut SystemContext<'_>) {
+        // 0. Run the level clock and end the stomp combos of players back on the ground.
+        if world.game_state == GameState::Playing { world.stats.level_time += context.delta_time; }
+        let grounded = &world.grounded_tags;
+        world.stomp_combos.retain(|player, _| !grounded.contains_key(player));
+
+        // 0b. Apply the consequences of this tick's semantic facts.
+        self.handle_checkpoint_reached(world, context);
+        self.handle_coin_collection(world, context);
+        self.handle_health_collection(world, context);
+        self.handle_enemy_stomp(world, context);
+        self.handle_enemy_hits(world, context);
+        self.handle_projectile_hits(world, context);
+        self.handle_player_damage(world, context);
+        self.handle_footsteps(world, context);
+        self.handle_water_splashes(world, context);
+        self.handle_trigger_spawns(world, context);
+
         // 1. Process entities marked with DeadTag.
         let dead_entities: Vec<_> = world.dead_tags.keys().copied().collect();
 
         for entity in dead_entities {
-            // 2. Check if this entity has a death animation configured (e.g., Enemy).
-            if let Some(pos) = world.positions.get(&entity) {
-                // If it's an enemy (or has a specific death effect), spawn the effect.
-                // For now, we hardcode a "puff" or "explosion" effect for enemies.
-                if world.enemy_tags.contains_key(&entity) {
-                    let explosion_config = &context.game_config.gameplay.explosion;
-                    
-                    let explosion_entity = world.create_entity();
-                    world.add_position(explosion_entity, crate::ecs::component::Position(*pos.0));
-                    world.add_renderable(explosion_entity, crate::ecs::component::Renderable {
-                        width: explosion_config.width,
-                        height: explosion_config.height,
-                        horizontal_offset: explosion_config.horizontal_offset,
-                        vertical_offset: explosion_config.vertical_offset,
-                        z_index: explosion_config.z_index,
-                        rotation: 0.0,
-                        flip_horizontal: false,
-                        flip_vertical: false,
-                    });
-                    
-                    if let Some(anim_config) = context.game_config.animation.get(&explosion_config.animation_name) {
-                        let mut controller = crate::animation::AnimationController::new();
-                        controller.add_animation("default", anim_config);
-                        controller.set_animation("default");
-                        world.add_animation(explosion_entity, crate::ecs::component::Animation { controller });
-                        
-                        // Lifetime matches the animation duration.
-                        world.add_lifetime(explosion_entity, Lifetime { timer: (anim_config.frame_count * anim_config.frame_duration) as f32 / 60.0 });
-                    }
+            // 2. Enemies go out with an explosion and count towards the level tally.
+            if world.enemy_tags.contains_key(&entity) {
+                world.stats.level_kills += 1;
+                if let Some(pos) = world.positions.get(&entity) {
+                    let position = pos.0;
+                    self.spawn_explosion(world, context, position);
                 }
             }
 
-            // 3. Remove the entity from the world.
-            // Note: In a real ECS, we'd probably soft-delete or recycle IDs.
-            // For now, we just remove components to "kill" it.
-            // world.delete_entity(entity); // Assuming we had a delete method.
-            
-            // Minimal cleanup for now: remove visual/physical presence.
-            world.positions.remove(&entity);
-            world.velocities.remove(&entity);
-            world.accelerations.remove(&entity);
-            world.collisions.remove(&entity);
-            world.renderables.remove(&entity);
-            world.dead_tags.remove(&entity); // Remove tag so we don't process again.
-            
-            // If it was an enemy, remove the tag.
-            world.enemy_tags.remove(&entity);
+            // 3. Remove the entity from every component storage (including its DeadTag).
+            world.despawn(entity);
         }
     }
 }
 
 impl SystemGameResolution {
+    /// Spawns the configured explosion effect at `position`. It despawns itself once the
+    /// animation has played out.
+    ///
+    /// # Side Effects
+    /// * Creates one entity with a `Position`, `Renderable` and, if the animation exists, `Animation` and `Lifetime`.
+    fn spawn_explosion(&mut self, world: &mut crate::ecs::world::World, context: &SystemContext<'_>, position: crate::math::Vector2D) {
+        let explosion_config = &context.game_config.gameplay.explosion;
+        let explosion_entity = world.create_entity();
+        world.add_position(explosion_entity, Position(position));
+        world.add_renderable(explosion_entity, Renderable { width: explosion_config.width, height: explosion_config.height, horizontal_offset: explosion_config.horizontal_offset, vertical_offset: explosion_config.vertical_offset, z_index: explosion_config.z_index, rotation: 0.0, flip_horizontal: false, flip_vertical: false, tint: sdl3::pixels::Color::WHITE, alpha: 255 });
+
+        if let Some(anim_config) = context.game_config.animation.get(&explosion_config.animation_name) {
+            let animation = crate::animation::Animation::from_config(anim_config);
+            // Lifetime matches the animation duration.
+            let duration = (animation.frames.len() as u32 * anim_config.frame_duration) as f32 / 60.0;
+            let mut controller = AnimationController::new();
+            controller.add_animation(explosion_config.animation_name.clone(), animation);
+            controller.set_animation(&explosion_config.animation_name);
+            world.add_animation(explosion_entity, Animation { controller });
+            world.add_lifetime(explosion_entity, Lifetime { timer: duration });
+        }
+    }
+
+    fn handle_checkpoint_reached(&mut self, world: &mut crate::ecs::world::World, context: &mut SystemContext<'_>) {
+        let events: Vec<EventCheckpointReached> = world.event_bus.read::<EventCheckpointReached>().copied().collect();
+        for event in events {
+            // 1. Mutate State (a checkpoint may only fire once).
+            let Some(checkpoint) = world.checkpoints.get_mut(&event.checkpoint) else { continue };
+            if checkpoint.activated { continue; }
+            checkpoint.activated = true;
+            if let Some(pos) = world.positions.get(&event.checkpoint) {
+                world.stats.last_checkpoint = Some(pos.0);
+            }
+
+            // 2. Trigger Feedback
+            if let Some(sound_name) = context.game_config.sound_events.get("checkpoint_reached") {
+                let _ = context.audio_sender.send(AudioEvent::PlaySound(sound_name.clone(), PlaySoundParams::default()));
+            }
+        }
+    }
+
     fn handle_coin_collection(&mut self, world: &mut crate::ecs::world::World, context: &mut SystemContext<'_>) {
         let events: Vec<crate::ecs::world::Entity> = world.event_bus.read::<EventCoinCollected>().map(|e| e.coin).collect();
         for coin_entity in events { 
+            // Both co-op players may overlap the coin in the same tick; only the first one takes it.
+            if world.dead_tags.contains_key(&coin_entity) { continue; }
+
             // 1. Mutate State
             world.add_dead_tag(coin_entity, DeadTag); 
             world.stats.gold_coin_count += 1; 
+            world.stats.level_coins += 1;
+            world.stats.score += context.game_config.gameplay.score.coin_points;
             
             // 2. Trigger Feedback
+            if let Some(pos) = world.positions.get(&coin_entity) {
+                let position = pos.0;
+                world.event_bus.publish(EventParticleBurst { preset: "coin".to_string(), position });
+            }
             if let Some(sound_name) = context.game_config.sound_events.get("coin_pickup") {
                 let _ = context.audio_sender.send(AudioEvent::PlaySound(sound_name.clone(), PlaySoundParams::default()));
             }
         }
     }
 
+    fn handle_health_collection(&mut self, world: &mut crate::ecs::world::World, context: &mut SystemContext<'_>) {
+        let events: Vec<EventHealthCollected> = world.event_bus.read::<EventHealthCollected>().copied().collect();
+        if events.is_empty() { return; }
+        for event in events {
+            // 1. A full-health player leaves the pickup in place for later; so does one already taken this tick.
+            if world.dead_tags.contains_key(&event.pickup) { continue; }
+            let Some(health) = world.healths.get_mut(&event.player) else { continue };
+            if health.current >= health.max { continue; }
+
+            // 2. Mutate State
+            health.current = (health.current + event.amount).min(health.max);
+            world.add_dead_tag(event.pickup, DeadTag);
+
+            // 3. Trigger Feedback
+            if let Some(sound_name) = context.game_config.sound_events.get("health_pickup") {
+                let _ = context.audio_sender.send(AudioEvent::PlaySound(sound_name.clone(), PlaySoundParams::default()));
+            }
+        }
+
+        // 4. Show the refilled hearts this frame instead of after the next Synchronization pass.
+        crate::ecs::systems::synchronization::SystemSynchronization::sync_ui_state(world);
+    }
+
     fn handle_player_damage(&mut self, world: &mut crate::ecs::world::World, context: &mut SystemContext<'_>) {
         let events: Vec<EventPlayerDamaged> = world.event_bus.read::<EventPlayerDamaged>().copied().collect();
         for event in events {
+             // Invincibility granted by an earlier hit (even this tick) absorbs further damage.
+             if world.invincibilities.contains_key(&event.player) { continue; }
              if let Some(health) = world.healths.get_mut(&event.player) && health.current > 0 {
                     // 1. Mutate State
-                    health.current -= 1;
+                    health.current = health.current.saturating_sub(event.amount);
                     world.add_invincibility(event.player, Invincibility { timer: context.game_config.gameplay.damage_invincibility_duration });
                     
                     // Knockback
@@ -100,25 +160,11 @@ impl SystemGameResolution {
                         player_vel.0.x = event.knockback_x; 
                         player_vel.0.y = -event.knockback_x.abs(); 
                     }
+                    // Ignore held movement briefly so steering toward the enemy doesn't cancel the push.
+                    world.add_knockback(event.player, Knockback { timer: context.game_config.gameplay.knockback_control_lock });
                     
                     // Spawn Effect (Explosion)
-                    let explosion_config = &context.game_config.gameplay.explosion;
-                    let explosion_entity = world.create_entity();
-                    world.add_position(explosion_entity, Position(event.position));
-                    world.add_renderable(explosion_entity, Renderable { width: explosion_config.width, height: explosion_config.height, horizontal_offset: explosion_config.horizontal_offset, vertical_offset: explosion_config.vertical_offset, z_index: explosion_config.z_index, rotation: 0.0, flip_horizontal: false, flip_vertical: false });
-                    
-                    if let Some(anim_config) = context.game_config.animation.get(&explosion_config.animation_name) {
-                        let mut frames = Vec::new();
-                        for i in 0..anim_config.frame_count {
-                            let padding = anim_config.frame_padding.unwrap_or(0);
-                            frames.push(sdl3::rect::Rect::new(anim_config.start_x + (i * (anim_config.frame_width + padding)) as i32, anim_config.start_y, anim_config.frame_width, anim_config.frame_height));
-                        }
-                        let mut explosion_anim_controller = AnimationController::new();
-                        explosion_anim_controller.add_animation(explosion_config.animation_name.clone(), crate::animation::Animation { texture_name: anim_config.texture.clone(), frames, frame_duration: anim_config.frame_duration, loops: anim_config.loops });
-                        explosion_anim_controller.set_animation(&explosion_config.animation_name);
-                        world.add_animation(explosion_entity, Animation { controller: explosion_anim_controller });
-                        world.add_lifetime(explosion_entity, Lifetime { timer: (anim_config.frame_count * anim_config.frame_duration) as f32 / 60.0 });
-                    }
+                    self.spawn_explosion(world, context, event.position);
 
                     // 2. Trigger Feedback
                     if let Some(sound_name) = context.game_config.sound_events.get("player_hit") {
@@ -131,23 +177,150 @@ impl SystemGameResolution {
     fn handle_enemy_stomp(&mut self, world: &mut crate::ecs::world::World, context: &mut SystemContext<'_>) {
         let events: Vec<EventPlayerEnemyStomped> = world.event_bus.read::<EventPlayerEnemyStomped>().copied().collect();
         for event in events { 
-            // 1. Mutate State
-            world.add_dead_tag(event.enemy, DeadTag); 
+            // Both co-op players may land on the enemy in the same tick; only the first stomp counts.
+            if world.dead_tags.contains_key(&event.enemy) { continue; }
+
+            // 1. Mutate State (a boss only takes the hit while stunned, but the player bounces either way)
+            if world.bosses.contains_key(&event.enemy) {
+                self.hit_boss(world, context, event.enemy);
+            } else {
+                world.add_dead_tag(event.enemy, DeadTag);
+                // Every further stomp before landing is worth more.
+                let chain = world.stomp_combos.get(&event.player).map_or(0, |combo| combo.chain) + 1;
+                world.add_stomp_combo(event.player, StompCombo { chain });
+                world.stats.score += score::stomp_points(chain, &context.game_config.gameplay.score);
+            }
             if let Some(player_vel) = world.velocities.get_mut(&event.player) { 
                 player_vel.0.y = context.game_config.gameplay.stomp_bounce_velocity; 
             }
             
             // 2. Trigger Feedback
+            let position = world.positions.get(&event.enemy).map(|pos| pos.0);
+            if let Some(position) = position {
+                world.event_bus.publish(EventParticleBurst { preset: "stomp".to_string(), position });
+            }
             if let Some(sound_name) = context.game_config.sound_events.get("enemy_stomp") {
-                let _ = context.audio_sender.send(AudioEvent::PlaySound(sound_name.clone(), PlaySoundParams::default()));
+                let _ = context.audio_sender.send(AudioEvent::PlaySound(sound_name.clone(), PlaySoundParams { position, ..Default::default() }));
             }
         }
     }
 
-    fn cleanup_dead(&mut self, world: &mut crate::ecs::world::World) {
-        let to_remove: Vec<_> = world.dead_tags.keys().copied().collect();
-        for entity in to_remove {
-            world.positions.remove(&entity); world.velocities.remove(&entity); world.accelerations.remove(&entity); world.renderables.remove(&entity); world.animations.remove(&entity); world.player_tags.remove(&entity); world.gold_coins.remove(&entity); world.enemy_tags.remove(&entity); world.dead_tags.remove(&entity); world.patrols.remove(&entity); world.gravity_tags.remove(&entity); world.collisions.remove(&entity); world.grounded_tags.remove(&entity); world.state_components.remove(&entity); world.respawn_tags.remove(&entity); world.respawn_timers.remove(&entity); world.healths.remove(&entity); world.invincibilities.remove(&entity); world.lifetimes.remove(&entity); world.directions.remove(&entity); world.goals.remove(&entity); world.next_levels.remove(&entity); world.movement_intentions.remove(&entity); world.dormant_tags.remove(&entity); world.wall_hits.remove(&entity);
+    fn handle_enemy_hits(&mut self, world: &mut crate::ecs::world::World, context: &mut SystemContext<'_>) {
+        let events: Vec<EventEnemyHit> = world.event_bus.read::<EventEnemyHit>().copied().collect();
+        for event in events {
+            // A stomp or a second swing may already have finished this enemy.
+            if world.dead_tags.contains_key(&event.enemy) { continue; }
+
+            // 1. Mutate State
+            if world.bosses.contains_key(&event.enemy) {
+                self.hit_boss(world, context, event.enemy);
+            } else {
+                world.add_dead_tag(event.enemy, DeadTag);
+            }
+
+            // 2. Trigger Feedback
+            let position = world.positions.get(&event.enemy).map(|pos| pos.0);
+            if let Some(position) = position {
+                world.event_bus.publish(EventParticleBurst { preset: "stomp".to_string(), position });
+            }
+            if let Some(sound_name) = context.game_config.sound_events.get("player_attack") {
+                let _ = context.audio_sender.send(AudioEvent::PlaySound(sound_name.clone(), PlaySoundParams { position, ..Default::default() }));
+            }
+        }
+    }
+
+    fn handle_projectile_hits(&mut self, world: &mut crate::ecs::world::World, context: &mut SystemContext<'_>) {
+        let events: Vec<EventProjectileHit> = world.event_bus.read::<EventProjectileHit>().copied().collect();
+        for event in events {
+            // A projectile is spent on its first hit, even if it overlapped several targets this tick.
+            if world.dead_tags.contains_key(&event.projectile) { continue; }
+
+            // 1. Mutate State (player damage arrives separately as EventPlayerDamaged)
+            world.add_dead_tag(event.projectile, DeadTag);
+            let position = world.positions.get(&event.target).map(|pos| pos.0);
+            if world.bosses.contains_key(&event.target) {
+                self.hit_boss(world, context, event.target);
+            } else if world.enemy_tags.contains_key(&event.target) {
+                world.add_dead_tag(event.target, DeadTag);
+                if let Some(pos) = world.positions.get(&event.target) {
+                    world.event_bus.publish(EventParticleBurst { preset: "explosion".to_string(), position: pos.0 });
+                }
+            }
+
+            // 2. Trigger Feedback
+            if let Some(sound_name) = context.game_config.sound_events.get("projectile_hit") {
+                let _ = context.audio_sender.send(AudioEvent::PlaySound(sound_name.clone(), PlaySoundParams { position, ..Default::default() }));
+            }
+        }
+    }
+
+    /// Applies one hit to a boss. Hits outside its stun are shrugged off; the last one defeats it.
+    ///
+    /// # Side Effects
+    /// * Publishes [EventBossDefeated] with the level its defeat leads to.
+    fn hit_boss(&mut self, world: &mut crate::ecs::world::World, context: &mut SystemContext<'_>, entity: crate::ecs::world::Entity) {
+        if world.dead_tags.contains_key(&entity) || !boss::is_vulnerable(world, entity) { return; }
+        if !boss::damage_boss(world, context.game_config, entity, 1) { return; }
+
+        // A TMX `next_level` on the boss wins over the tuning's default exit.
+        let next_level = world.next_levels.get(&entity).map(|n| n.0.clone())
+            .or_else(|| world.bosses.get(&entity).and_then(|b| context.game_config.bosses.get(&b.config_key)).and_then(|c| c.next_level.clone()));
+        world.add_dead_tag(entity, DeadTag);
+        if let Some(pos) = world.positions.get(&entity) {
+            world.event_bus.publish(EventParticleBurst { preset: "explosion".to_string(), position: pos.0 });
+        }
+        world.event_bus.publish(EventBossDefeated { boss: entity, next_level });
+    }
+
+    fn handle_water_splashes(&mut self, world: &mut crate::ecs::world::World, context: &mut SystemContext<'_>) {
+        let splashes: Vec<EventWaterSplash> = world.event_bus.read::<EventWaterSplash>().copied().collect();
+        for splash in splashes {
+            // Feedback only: buoyancy and swimming are driven by the Submerged tag itself.
+            world.event_bus.publish(EventParticleBurst { preset: "splash".to_string(), position: splash.position });
+            if let Some(sound_name) = context.game_config.sound_events.get("water_splash") {
+                let _ = context.audio_sender.send(AudioEvent::PlaySound(sound_name.clone(), PlaySoundParams::at(splash.position)));
+            }
+        }
+    }
+
+    fn handle_trigger_spawns(&mut self, world: &mut crate::ecs::world::World, context: &mut SystemContext<'_>) {
+        let spawns: Vec<_> = world.event_bus.read::<EventTriggerActivated>()
+            .filter_map(|event| world.triggers.get(&event.trigger)?.spawn.clone())
+            .collect();
+        for spawn in spawns {
+            // Markers are plain Tiled objects; the prefab appears at the marker's top-left corner.
+            let Some(position) = context.level.marker(&spawn.marker) else {
+                eprintln!("[Trigger] Unknown spawn marker '{}'", spawn.marker);
+                continue;
+            };
+            let template = crate::level::Entity { r#type: spawn.prefab, position, ..Default::default() };
+            crate::entity_spawner::spawn_entity(world, &template, context.game_config);
+        }
+    }
+
+    fn handle_footsteps(&mut self, world: &mut crate::ecs::world::World, context: &mut SystemContext<'_>) {
+        let footsteps = &context.game_config.footsteps;
+        if footsteps.foot_plants.is_empty() { return; }
+        let (tile_w, tile_h) = (context.level.tileset.tile_width.max(1) as i32, context.level.tileset.tile_height.max(1) as i32);
+        for (entity, _, animation) in query2(&world.player_tags, &world.animations) {
+            // 1. Only grounded players that just reached a foot-plant frame make a sound.
+            if !world.is_grounded(entity) || world.is_submerged(entity) { continue; }
+            let controller = &animation.controller;
+            let (Some(name), Some(frame)) = (controller.current_animation_name(), controller.entered_frame()) else { continue };
+            if !footsteps.foot_plants.get(name).is_some_and(|plants| plants.contains(&frame)) { continue; }
+
+            // 2. Choose the variant for the tile directly below the feet.
+            let surface = world.collisions.get(&entity).and_then(|c| {
+                let (x, y) = ((c.rect.x() + c.rect.width() as i32 / 2) / tile_w, (c.rect.y() + c.rect.height() as i32) / tile_h);
+                if x < 0 || y < 0 { return None; }
+                context.level.surface_at(x as usize, y as usize)
+            });
+            let sound = surface.and_then(|s| footsteps.surfaces.get(s)).or(footsteps.default_sound.as_ref());
+
+            // 3. Trigger Feedback
+            if let Some(sound_name) = sound {
+                let _ = context.audio_sender.send(AudioEvent::PlaySound(sound_name.clone(), PlaySoundParams { volume: footsteps.volume, ..Default::default() }));
+            }
         }
     }
 }
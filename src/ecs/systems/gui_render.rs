@@ -5,11 +5,15 @@
 //! ensuring the player has clear feedback on their health and progress.
 
 use crate::ecs::systems::RenderContext;
-use crate::renderer::{Renderer, TextRenderParams};
+use crate::renderer::{Renderer, TextAlign, TextRenderParams};
 use crate::texture_manager::TextureManager;
 use crate::font_manager::FontManager;
 use sdl3::rect::Rect;
 use sdl3::pixels::Color;
+use crate::camera::Camera;
+use crate::ecs::world::{Entity, World};
+use crate::level::Region;
+use crate::score::LevelTally;
 
 /// A system that renders hearts, coin counters, and interface overlays.
 pub struct SystemGUIRender;
@@ -28,27 +32,35 @@ impl SystemGUIRender {
 
         renderer: &mut Renderer,
 
-        texture_manager: &TextureManager,
+        texture_manager: &mut TextureManager,
 
         font_manager: &FontManager,
 
         world: &crate::ecs::world::World,
 
+        camera: &Camera,
+
         context: &RenderContext<'_>,
 
     ) -> Result<(), String> {
 
+        let ui = &context.game_config.ui;
+
+        let screen = renderer.output_size();
+
         
 
         // 1. Render Player Health (Hearts).
 
-        if let Some(heart_texture) = texture_manager.get("heart") {
+        if let Some(heart_texture) = texture_manager.get_or_load("heart", renderer.texture_creator()) {
 
             let heart_size = 64;
 
             let padding = 10;
 
-            let start_pos = context.game_config.ui.hearts_pos;
+            let row_width = row_length(world.ui_state.display_lives, heart_size, padding);
+
+            let (start_x, start_y) = ui.hearts_pos.resolve(screen, (row_width, heart_size as u32));
 
             
 
@@ -56,9 +68,9 @@ impl SystemGUIRender {
 
                 let dest_rect = Rect::new(
 
-                    start_pos.x + (i as i32 * (heart_size + padding)),
+                    start_x + (i as i32 * (heart_size + padding)),
 
-                    start_pos.y,
+                    start_y,
 
                     heart_size as u32,
 
@@ -76,15 +88,13 @@ impl SystemGUIRender {
 
         // 2. Render Gold Coin Counter.
 
-        let coin_pos = context.game_config.ui.coins_pos;
-
         
 
         // Reuse the visual definition from the gold coin prefab for consistency.
 
         if let Some(anim_config) = context.game_config.animation.get("gold_coin_spin")
 
-            && let Some(texture) = texture_manager.get(&anim_config.texture)
+            && let Some(texture) = texture_manager.get_or_load(&anim_config.texture, renderer.texture_creator())
 
         {
 
@@ -104,7 +114,7 @@ impl SystemGUIRender {
 
                 
 
-                let icon_scale = crate::config::RENDER_SCALE_FACTOR; 
+                let icon_scale = renderer.view.scale;
 
                 let dest_w = (anim_config.frame_width as f32 * icon_scale) as u32;
 
@@ -112,11 +122,19 @@ impl SystemGUIRender {
 
                 
 
+                // The widget is the icon plus the count, so right-anchored counters grow leftward.
+
+                let text = format!("x {:02}", world.ui_state.display_coin_count);
+
+                let text_w = font_manager.measure_text("debug", &text, 32.0).map_or(0, |(w, _)| w);
+
+                let (coin_x, coin_y) = ui.coins_pos.resolve(screen, (dest_w + 10 + text_w, dest_h));
+
                 let dest_rect = Rect::new(
 
-                    coin_pos.x,
+                    coin_x,
 
-                    coin_pos.y,
+                    coin_y,
 
                     dest_w,
 
@@ -132,8 +150,6 @@ impl SystemGUIRender {
 
                 // Draw the count text with a simple shadow for legibility against bright backgrounds.
 
-                let text = format!("x {:02}", world.ui_state.display_coin_count);
-
                 
 
                 // Drop Shadow
@@ -142,9 +158,9 @@ impl SystemGUIRender {
 
                     text: &text,
 
-                    x: coin_pos.x + dest_w as i32 + 10 + 2,
+                    x: coin_x + dest_w as i32 + 10 + 2,
 
-                    y: coin_pos.y + (dest_h as i32 / 2) - 8 + 2,
+                    y: coin_y + (dest_h as i32 / 2) - 8 + 2,
 
                     font_size: 32.0,
 
@@ -152,6 +168,8 @@ impl SystemGUIRender {
 
                     color: Color::RGB(0, 0, 0),
 
+                    ..Default::default()
+
                 })?;
 
 
@@ -162,9 +180,9 @@ impl SystemGUIRender {
 
                     text: &text,
 
-                    x: coin_pos.x + dest_w as i32 + 10,
+                    x: coin_x + dest_w as i32 + 10,
 
-                    y: coin_pos.y + (dest_h as i32 / 2) - 8,
+                    y: coin_y + (dest_h as i32 / 2) - 8,
 
                     font_size: 32.0,
 
@@ -172,18 +190,197 @@ impl SystemGUIRender {
 
                     color: Color::RGB(255, 255, 255),
 
+                    ..Default::default()
+
+                })?;
+
+            }
+
+
+
+        // 3. Render one health bar per player during local co-op, ordered by player slot.
+
+        if world.player_tags.len() > 1 {
+
+            let (bar_width, bar_height, spacing) = (200u32, 20u32, 30i32);
+
+            let mut players: Vec<_> = world.player_tags.keys().copied().collect();
+
+            players.sort_by_key(|e| world.player_slots.get(e).map_or(0, |s| s.0));
+
+            // Bars plus their "P1" labels, stacked one row per player.
+
+            let block_height = (players.len() as i32 - 1) * spacing + bar_height as i32;
+
+            let (bar_x, bar_y) = ui.health_bars_pos.resolve(screen, (bar_width + 40, block_height as u32));
+
+
+
+            for (row, entity) in players.iter().enumerate() {
+
+                let Some(health) = world.healths.get(entity) else { continue };
+
+                let y = bar_y + row as i32 * spacing;
+
+                let fill = if health.max == 0 { 0.0 } else { health.current as f32 / health.max as f32 };
+
+
+
+                // Track, then the remaining health on top of it.
+
+                renderer.fill_rect(&Rect::new(bar_x, y, bar_width, bar_height), Color::RGBA(40, 40, 40, 200))?;
+
+                let fill_width = (bar_width as f32 * fill.clamp(0.0, 1.0)) as u32;
+
+                if fill_width > 0 {
+
+                    renderer.fill_rect(&Rect::new(bar_x, y, fill_width, bar_height), Color::RGB(200, 40, 40))?;
+
+                }
+
+                renderer.render_text(font_manager, TextRenderParams {
+
+                    text: &format!("P{}", row + 1),
+
+                    x: bar_x + bar_width as i32 + 10,
+
+                    y: y + 2,
+
+                    font_size: 16.0,
+
+                    scale: 1.0,
+
+                    color: Color::RGB(255, 255, 255),
+
+                    ..Default::default()
+
                 })?;
 
             }
 
+        } else if world.ui_state.display_max_health > 0 && let Some(heart_texture) = texture_manager.get_or_load("heart", renderer.texture_creator()) {
+
+            // 4. Single-player: one small heart per point of health, dark slots for what is missing.
+
+            let (heart_size, padding) = (32i32, 6i32);
+
+            let row_width = row_length(world.ui_state.display_max_health, heart_size, padding);
+
+            let (bar_x, bar_y) = ui.health_bars_pos.resolve(screen, (row_width, heart_size as u32));
+
+            for i in 0..world.ui_state.display_max_health {
+
+                let dest_rect = Rect::new(bar_x + i as i32 * (heart_size + padding), bar_y, heart_size as u32, heart_size as u32);
+
+                if i < world.ui_state.display_health {
+
+                    renderer.copy(heart_texture, None, Some(dest_rect))?;
+
+                } else {
+
+                    renderer.fill_rect(&dest_rect, Color::RGBA(40, 40, 40, 200))?;
+
+                }
+
+            }
+
+        }
+
+
+
+        // 5. Render the health of the boss whose arena the camera is confined to.
+
+        if let Some(boss) = active_boss(world, &camera.bounds)
+
+            && let (Some(health), Some(tuning)) = (world.healths.get(&boss), world.bosses.get(&boss)) {
+
+            let (bar_width, bar_height, top) = (600u32, 24u32, 40i32);
+
+            let x = (screen.0 as i32 - bar_width as i32) / 2;
+
+            let fill = if health.max == 0 { 0.0 } else { health.current as f32 / health.max as f32 };
+
+
+
+            renderer.fill_rect(&Rect::new(x, top, bar_width, bar_height), Color::RGBA(40, 40, 40, 200))?;
+
+            let fill_width = (bar_width as f32 * fill.clamp(0.0, 1.0)) as u32;
+
+            if fill_width > 0 {
+
+                renderer.fill_rect(&Rect::new(x, top, fill_width, bar_height), Color::RGB(160, 40, 200))?;
+
+            }
+
+            renderer.render_text(font_manager, TextRenderParams {
+
+                text: &tuning.config_key.replace('_', " ").to_uppercase(),
+
+                x: x + bar_width as i32 / 2,
+
+                y: top + bar_height as i32 + 6,
+
+                font_size: 16.0,
+
+                scale: 1.0,
+
+                color: Color::RGB(255, 255, 255),
+
+                align: crate::renderer::TextAlign::Center,
+
+                ..Default::default()
+
+            })?;
+
+        }
+
+
+
+        // 6. Announce the level by name as it starts, then fade the title out.
+
+        let name_alpha = level_name_alpha(world.ui_state.level_name_age, ui.level_name_duration);
+
+        if !world.ui_state.level_name.is_empty() && name_alpha > 0 {
+
+            let name = &world.ui_state.level_name;
+
+            let size = font_manager.measure_text("debug", name, 48.0).unwrap_or((0, 0));
+
+            let (x, y) = ui.level_name_pos.resolve(screen, size);
+
+            renderer.render_text(font_manager, TextRenderParams { text: name, x: x + 2, y: y + 2, font_size: 48.0, scale: 1.0, color: Color::RGBA(0, 0, 0, name_alpha), ..Default::default() })?;
+
+            renderer.render_text(font_manager, TextRenderParams { text: name, x, y, font_size: 48.0, scale: 1.0, color: Color::RGBA(255, 255, 255, name_alpha), ..Default::default() })?;
+
+        }
+
+
+
+
+
+        // 7. Render the score, right-aligned by default so it grows away from the screen edge.
+
+        let score_text = format!("SCORE {:06}", world.ui_state.display_score);
+
+        let score_size = font_manager.measure_text("debug", &score_text, 32.0).unwrap_or((0, 0));
+
+        let (score_x, score_y) = ui.score_pos.resolve(screen, score_size);
+
+        renderer.render_text(font_manager, TextRenderParams { text: &score_text, x: score_x + 2, y: score_y + 2, font_size: 32.0, scale: 1.0, color: Color::RGB(0, 0, 0), ..Default::default() })?;
+
+        renderer.render_text(font_manager, TextRenderParams { text: &score_text, x: score_x, y: score_y, font_size: 32.0, scale: 1.0, color: Color::RGB(255, 255, 255), ..Default::default() })?;
+
 
 
         Ok(())
 
+
+
     }
 
 
 
+
     /// Renders the full-screen Game Over overlay sequence.
 
     ///
@@ -196,7 +393,7 @@ impl SystemGUIRender {
 
         renderer: &mut Renderer,
 
-        texture_manager: &TextureManager,
+        texture_manager: &mut TextureManager,
 
         context: &RenderContext<'_>,
 
@@ -228,7 +425,7 @@ impl SystemGUIRender {
 
         
 
-        if let Some(texture) = texture_manager.get(&context.game_config.gameplay.game_over_texture) {
+        if let Some(texture) = texture_manager.get_or_load(&context.game_config.gameplay.game_over_texture, renderer.texture_creator()) {
 
              renderer.copy(texture, None, Some(game_over_rect))?;
 
@@ -240,4 +437,94 @@ impl SystemGUIRender {
 
     }
 
+
+
+
+    /// Renders the end-of-level tally over the closed iris: one centered row per line, then the skip hint.
+
+    ///
+
+    /// ⚠️ **Hotpath**: Called every frame while a level is complete.
+
+    pub fn render_level_tally(
+
+        &mut self,
+
+        renderer: &mut Renderer,
+
+        font_manager: &FontManager,
+
+        tally: &LevelTally,
+
+    ) -> Result<(), String> {
+
+        // 1. Black out whatever the iris left visible.
+
+        let (w, h) = renderer.output_size();
+
+        renderer.fill_rect(&Rect::new(0, 0, w, h), Color::RGB(0, 0, 0))?;
+
+
+
+        // 2. Title, the tally rows and the hint, stacked around the screen center.
+
+        let center_x = w as i32 / 2;
+
+        let lines = tally.lines();
+
+        let top = h as i32 / 2 - (lines.len() as i32 + 2) * 28;
+
+        renderer.render_text(font_manager, TextRenderParams { text: "LEVEL COMPLETE", x: center_x, y: top, font_size: 64.0, scale: 1.0, color: Color::RGB(255, 220, 0), align: TextAlign::Center, ..Default::default() })?;
+
+        for (row, line) in lines.iter().enumerate() {
+
+            let color = if tally.new_high_score && line.starts_with("NEW") { Color::RGB(255, 220, 0) } else { Color::RGB(255, 255, 255) };
+
+            renderer.render_text(font_manager, TextRenderParams { text: line, x: center_x, y: top + 100 + row as i32 * 48, font_size: 36.0, scale: 1.0, color, align: TextAlign::Center, ..Default::default() })?;
+
+        }
+
+        renderer.render_text(font_manager, TextRenderParams { text: "Press Jump to continue", x: center_x, y: top + 124 + lines.len() as i32 * 48, font_size: 24.0, scale: 1.0, color: Color::RGB(160, 160, 160), align: TextAlign::Center, ..Default::default() })?;
+
+
+
+        Ok(())
+
+    }
+
+
+}
+
+/// Width in **pixels** of `count` icons `size` wide with `padding` between them.
+fn row_length(count: u32, size: i32, padding: i32) -> u32 {
+    (count as i32 * (size + padding) - padding).max(0) as u32
+}
+
+/// Opacity of the level title `age` **seconds** after the level started: opaque, then fading
+/// out over the last second of `duration`.
+pub fn level_name_alpha(age: f32, duration: f32) -> u8 {
+    let fade = duration.min(1.0);
+    if fade <= 0.0 { return 0; }
+    (((duration - age) / fade).clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+/// The living boss standing inside `zone` (the camera's clamp region), if any.
+pub fn active_boss(world: &World, zone: &Region) -> Option<Entity> {
+    world.bosses.keys().copied()
+        .filter(|boss| !world.dead_tags.contains_key(boss))
+        .find(|boss| world.positions.get(boss).is_some_and(|p| zone.contains(p.0)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_level_name_fades_out_over_the_last_second() {
+        assert_eq!(level_name_alpha(0.0, 3.0), 255);
+        assert_eq!(level_name_alpha(2.0, 3.0), 255);
+        assert_eq!(level_name_alpha(2.5, 3.0), 128);
+        assert_eq!(level_name_alpha(3.0, 3.0), 0);
+        assert_eq!(level_name_alpha(0.0, 0.0), 0, "A zero duration turns the title off");
+    }
 }
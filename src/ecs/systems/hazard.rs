@@ -0,0 +1,113 @@
+//! # Synchronization: Hazard Tiles
+//!
+//! This module implements the rule that touching a hazard tile (spikes, lava) 
+//! hurts the player. It only publishes [EventPlayerDamaged]; health, 
+//! invincibility and knockback are applied by `SystemGameResolution`, exactly 
+//! as for enemy contact.
+
+use crate::ecs::systems::{System, SystemContext};
+use crate::ecs::event::EventPlayerDamaged;
+use crate::ecs::component::Direction;
+
+/// A system that converts player/hazard-tile overlaps into damage facts.
+pub struct SystemHazard;
+
+impl System<SystemContext<'_>> for SystemHazard {
+    /// Publishes at most one damage fact per player touching a hazard tile this tick.
+    ///
+    /// ⚠️ **Hotpath**: Called 120x per second. Scans only the tiles under each player.
+    fn update(&mut self, world: &mut crate::ecs::world::World, context: &mut SystemContext<'_>) {
+        let tile_width = context.level.tileset.tile_width as f32;
+        let tile_height = context.level.tileset.tile_height as f32;
+        if tile_width <= 0.0 || tile_height <= 0.0 || context.level.hazards.tiles.is_empty() { return; }
+
+        let gameplay = &context.game_config.gameplay;
+        let players: Vec<_> = world.player_tags.keys().copied().collect();
+        for player in players {
+            // 1. Skip players who cannot be hurt right now (invincible, dead or respawning).
+            if world.invincibilities.contains_key(&player)
+                || world.respawn_timers.contains_key(&player)
+                || world.healths.get(&player).is_none_or(|h| h.current == 0) {
+                continue;
+            }
+            let (Some(collision), Some(pos)) = (world.collisions.get(&player), world.positions.get(&player)) else { continue };
+            let rect = collision.rect;
+
+            // 2. Find the first hazard tile under the player's bounds.
+            let min_col = (rect.x() as f32 / tile_width).floor().max(0.0) as usize;
+            let max_col = ((rect.x() + rect.width() as i32 - 1) as f32 / tile_width).floor().max(0.0) as usize;
+            let min_row = (rect.y() as f32 / tile_height).floor().max(0.0) as usize;
+            let max_row = ((rect.y() + rect.height() as i32 - 1) as f32 / tile_height).floor().max(0.0) as usize;
+            let hit = (min_row..=max_row)
+                .flat_map(|row| (min_col..=max_col).map(move |col| (col, row)))
+                .find(|&(col, row)| context.level.is_hazard(col, row));
+
+            // 3. Publish damage with knockback pointing away from the hazard tile center.
+            if let Some((col, _row)) = hit {
+                let tile_center_x = (col as f32 + 0.5) * tile_width;
+                let player_center_x = rect.x() as f32 + rect.width() as f32 / 2.0;
+                let knockback_x = if player_center_x < tile_center_x { -gameplay.damage_knockback_force } else { gameplay.damage_knockback_force };
+                let amount = if gameplay.hazard_instant_kill { u32::MAX } else { 1 };
+                let direction = if knockback_x < 0.0 { Direction::Left } else { Direction::Right };
+                world.event_bus.publish(EventPlayerDamaged { player, knockback_x, direction, position: pos.0, amount });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::world::World;
+    use crate::ecs::component::{Collision, Health, Invincibility, PlayerTag, Position};
+    use crate::ecs::systems::test_context::TestContext;
+    use crate::level::{Level, Tileset};
+    use crate::math::Vector2D;
+
+    fn run(world: &mut World, instant_kill: bool) -> Vec<EventPlayerDamaged> {
+        let mut ctx = TestContext::default();
+        ctx.game_config.gameplay.hazard_instant_kill = instant_kill;
+        // A 4x2 map with a single spike tile at column 2, row 1.
+        ctx.level = Level { tileset: Tileset { tile_width: 32, tile_height: 32, ..Default::default() }, ..Default::default() };
+        ctx.level.hazards.tiles = vec![vec![0, 0, 0, 0], vec![0, 0, 1, 0]];
+
+        world.clear_events();
+        ctx.run(&mut SystemHazard, world);
+        world.event_bus.read::<EventPlayerDamaged>().copied().collect()
+    }
+
+    fn spawn_player(world: &mut World, x: i32, y: i32) -> crate::ecs::world::Entity {
+        let player = world.create_entity();
+        world.add_player_tag(player, PlayerTag);
+        world.add_position(player, Position(Vector2D::new(x as f32, y as f32)));
+        world.add_collision(player, Collision { rect: sdl3::rect::Rect::new(x, y, 24, 26) });
+        world.add_health(player, Health { current: 2, max: 2 });
+        player
+    }
+
+    #[test]
+    fn test_hazard_damages_and_pushes_away_from_tile() {
+        let mut world = World::new();
+        // Player overlaps the left edge of the spike tile (x 64..96, y 32..64).
+        spawn_player(&mut world, 50, 36);
+
+        let events = run(&mut world, false);
+        assert_eq!(events.len(), 1);
+        assert!(events[0].knockback_x < 0.0, "Knockback points away from the tile center");
+        assert_eq!(events[0].amount, 1);
+
+        assert_eq!(run(&mut world, true)[0].amount, u32::MAX, "Instant-kill hazards drain all health");
+    }
+
+    #[test]
+    fn test_hazard_respects_invincibility_and_safe_tiles() {
+        let mut world = World::new();
+        let player = spawn_player(&mut world, 70, 36);
+        world.add_invincibility(player, Invincibility { timer: 1.0 });
+        assert!(run(&mut world, false).is_empty(), "Invincible players ignore hazards");
+
+        let mut world = World::new();
+        spawn_player(&mut world, 0, 36);
+        assert!(run(&mut world, false).is_empty(), "Safe tiles do not hurt");
+    }
+}
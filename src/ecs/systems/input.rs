@@ -7,7 +7,7 @@
 use crate::ecs::world::World;
 use crate::ecs::systems::SystemContext;
 use crate::input::InputAction;
-use crate::ecs::event::CommandJump;
+use crate::ecs::event::{CommandJump, CommandFire, CommandAttack};
 use crate::ecs::component::MovementIntention;
 
 /// A system that maps input state to entity intentions and commands.
@@ -15,7 +15,7 @@ pub struct SystemInput;
 
 impl crate::ecs::systems::System<SystemContext<'_>> for SystemInput {
 
-    /// Translates hardware input into movement intentions and jump commands for players.
+    /// Translates hardware input into movement intentions and jump/fire commands for players.
 
     ///
 
@@ -61,7 +61,7 @@ impl crate::ecs::systems::System<SystemContext<'_>> for SystemInput {
 
                 // Force intention to zero to stop movement upon death.
 
-                world.add_movement_intention(entity, MovementIntention { x: 0.0 });
+                world.add_movement_intention(entity, MovementIntention::default());
 
                 continue;
 
@@ -69,17 +69,19 @@ impl crate::ecs::systems::System<SystemContext<'_>> for SystemInput {
 
 
 
-            // 3. Resolve horizontal movement intentions from the current input state.
+            // 3. Resolve horizontal movement intentions from the device assigned to this player.
+
+            let slot = world.player_slots.get(&entity).map_or(0, |s| s.0);
 
             let mut move_dir = 0.0;
 
-            if context.input_state.is_action_pressed(InputAction::MoveLeft) {
+            if context.input_state.is_player_action_pressed(slot, InputAction::MoveLeft) {
 
                 move_dir -= 1.0;
 
             }
 
-            if context.input_state.is_action_pressed(InputAction::MoveRight) {
+            if context.input_state.is_player_action_pressed(slot, InputAction::MoveRight) {
 
                 move_dir += 1.0;
 
@@ -87,20 +89,57 @@ impl crate::ecs::systems::System<SystemContext<'_>> for SystemInput {
 
 
 
+            // A recent hit suppresses horizontal control until its knockback has played out.
+
+            if let Some(knockback) = world.knockbacks.get_mut(&entity) {
+
+                knockback.timer -= context.delta_time;
+
+                if knockback.timer > 0.0 { move_dir = 0.0; } else { world.knockbacks.remove(&entity); }
+
+            }
+
+
+
             // 4. Update the entity's movement intention component.
 
-            world.add_movement_intention(entity, MovementIntention { x: move_dir });
+            world.add_movement_intention(entity, MovementIntention {
+                x: move_dir,
+                jump_held: context.input_state.is_player_action_pressed(slot, InputAction::Jump),
+                crouch_held: context.input_state.is_player_action_pressed(slot, InputAction::Down),
+                ..Default::default()
+            });
 
 
 
             // 5. Publish a 'Jump Command' intent if the jump action was triggered this frame.
 
-            if context.input_state.is_action_just_pressed(InputAction::Jump) {
+            if context.input_state.is_player_action_just_pressed(slot, InputAction::Jump) {
 
                 world.event_bus.publish(CommandJump { entity });
 
             }
 
+
+
+            // 6. Holding fire requests a shot every tick; the projectile system enforces the fire rate.
+
+            if context.input_state.is_player_action_pressed(slot, InputAction::Fire) {
+
+                world.event_bus.publish(CommandFire { entity });
+
+            }
+
+
+
+            // 7. Each press requests one melee swing; the player's state machine decides whether it starts.
+
+            if context.input_state.is_player_action_just_pressed(slot, InputAction::Attack) {
+
+                world.event_bus.publish(CommandAttack { entity });
+
+            }
+
         }
 
     }
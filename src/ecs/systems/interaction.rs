@@ -4,6 +4,9 @@
 //! physical overlaps. It identifies when two bounding boxes intersect and 
 //! publishes a raw 'Collision' fact, leaving the interpretation of that fact 
 //! to synchronization systems.
+//!
+//! It never mutates gameplay state: damage, knockback and effects are applied
+//! once, by [crate::ecs::systems::game_resolution::SystemGameResolution].
 
 use crate::ecs::event::EventCollision;
 use crate::ecs::systems::{System, SystemContext};
@@ -26,9 +29,7 @@ impl System<SystemContext<'_>> for SystemInteraction {
             if let Some(coll_a) = world.collisions.get(&entity_a) {
                 
                 // 2. Query the Spatial Grid for nearby entities to avoid O(N^2) complexity.
-                let neighbors = world.spatial_grid.query(coll_a.rect);
-
-                for entity_b in neighbors {
+                for entity_b in world.spatial_grid.query_near(coll_a.rect) {
                     // 3. Ensure we don't check an entity against itself or re-check the same pair.
                     if entity_a >= entity_b { continue; }
 
@@ -0,0 +1,88 @@
+//! # Synchronization: Invincibility Flash
+//!
+//! This module makes i-frames visible. While a player holds an
+//! [crate::ecs::component::Invincibility] timer, their sprite blinks between
+//! opaque and translucent; once the timer is gone, full opacity is restored.
+
+use crate::ecs::systems::{System, SystemContext};
+
+/// A system that maps the invincibility timer onto the player's [crate::ecs::component::Renderable] alpha.
+pub struct SystemInvincibilityFlash;
+
+impl System<SystemContext<'_>> for SystemInvincibilityFlash {
+    /// Updates the blink phase of every player.
+    ///
+    /// ⚠️ **Hotpath**: Called 120x per second.
+    ///
+    /// # Side Effects
+    /// * Writes `Renderable::alpha` for all players, resetting it to 255 when not flashing.
+    fn update(&mut self, world: &mut crate::ecs::world::World, context: &mut SystemContext<'_>) {
+        let gameplay = &context.game_config.gameplay;
+        for (entity, renderable) in world.renderables.iter_mut() {
+            if !world.player_tags.contains_key(entity) { continue; }
+
+            // 1. Dead players waiting to respawn hold a long invincibility but must not blink.
+            let flashing = !world.respawn_timers.contains_key(entity) && gameplay.invincibility_flash_frequency > 0.0;
+
+            // 2. Alternate halves of each blink period based on the remaining timer.
+            renderable.alpha = match world.invincibilities.get(entity) {
+                Some(inv) if flashing => {
+                    let half_period = (inv.timer * gameplay.invincibility_flash_frequency * 2.0) as u32;
+                    if half_period % 2 == 0 { 255 } else { gameplay.invincibility_flash_alpha }
+                }
+                _ => 255,
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::world::World;
+    use crate::ecs::component::{Invincibility, PlayerTag, Renderable, RespawnTimer};
+    use crate::ecs::systems::test_context::TestContext;
+
+    fn run(world: &mut World) {
+        let mut ctx = TestContext::default();
+        ctx.game_config.gameplay.invincibility_flash_frequency = 5.0;
+        ctx.game_config.gameplay.invincibility_flash_alpha = 64;
+        ctx.run(&mut SystemInvincibilityFlash, world);
+    }
+
+    fn spawn_player(world: &mut World) -> crate::ecs::world::Entity {
+        let player = world.create_entity();
+        world.add_player_tag(player, PlayerTag);
+        world.add_renderable(player, Renderable { width: 16, height: 16, horizontal_offset: 0, vertical_offset: 0, z_index: 100, rotation: 0.0, flip_horizontal: false, flip_vertical: false, tint: sdl3::pixels::Color::WHITE, alpha: 255 });
+        player
+    }
+
+    #[test]
+    fn test_flash_toggles_every_100ms_and_restores_opacity() {
+        let mut world = World::new();
+        let player = spawn_player(&mut world);
+
+        world.add_invincibility(player, Invincibility { timer: 1.05 });
+        run(&mut world);
+        let first = world.renderables[&player].alpha;
+
+        world.invincibilities.get_mut(&player).unwrap().timer = 0.95;
+        run(&mut world);
+        assert_ne!(world.renderables[&player].alpha, first, "Phase flips after 100ms");
+
+        world.invincibilities.remove(&player);
+        run(&mut world);
+        assert_eq!(world.renderables[&player].alpha, 255, "Expired invincibility restores full opacity");
+    }
+
+    #[test]
+    fn test_dead_player_does_not_flash() {
+        let mut world = World::new();
+        let player = spawn_player(&mut world);
+        world.add_invincibility(player, Invincibility { timer: 998.95 });
+        world.add_respawn_timer(player, RespawnTimer { timer: 3.0, transition_started: true, transition_complete: false });
+
+        run(&mut world);
+        assert_eq!(world.renderables[&player].alpha, 255);
+    }
+}
@@ -5,19 +5,36 @@
 //! bridging gameplay logic with the level loading engine.
 
 use crate::ecs::systems::{System, SystemContext};
-use crate::ecs::event::{EventStartTransition, TransitionType};
+use crate::ecs::event::{EventPlayerDied, EventStartTransition, EventTransitionComplete, EventLevelExitRequested, EventLevelRestartRequested, EventLevelCompleted, TransitionType};
+use crate::ecs::resources::LevelExit;
+use crate::ecs::world::{Entity, World};
+use crate::config::GoalDeathPrecedence;
 
 /// A system that monitors goal collisions and triggers the level transition sequence.
 pub struct SystemWorldLevelTransition {
     pending_level: Option<String>,
+    /// Whether `pending_level` is a restart of the current level rather than an exit.
+    restarting: bool,
 }
 
 impl SystemWorldLevelTransition {
     pub fn new() -> Self {
         Self {
             pending_level: None,
+            restarting: false,
         }
     }
+
+    /// Orchestrates the start of the sequence: Shutter close + Music fade.
+    fn begin_exit(&mut self, world: &mut World, level_path: String, spawn_point: Option<String>) {
+        self.pending_level = Some(level_path);
+        world.level_exit = Some(LevelExit { spawn_point });
+        world.event_bus.publish(EventStartTransition {
+            transition_type: TransitionType::IrisOut,
+            duration: 1.0,
+            center: None,
+        });
+    }
 }
 
 impl Default for SystemWorldLevelTransition {
@@ -27,58 +44,84 @@ impl Default for SystemWorldLevelTransition {
 }
 
 impl System<SystemContext<'_>> for SystemWorldLevelTransition {
-    /// Monitors goal proximity and coordinates the visual/auditory transition sequence.
+    /// Monitors goal proximity and exit requests, and coordinates the visual/auditory transition sequence.
     ///
     /// ⚠️ **Hotpath**: Called 120x per second.
     ///
     /// # Side Effects
     /// * Modifies `context.next_level` to trigger a scene load.
     /// * Publishes [crate::ecs::event::EventStartTransition].
-    /// * Triggers audio fade via [crate::audio::AudioEvent::FadeOutMusic].
+    /// * Publishes [EventLevelCompleted] once the iris has closed on a finished level.
+    /// * Leaves the music playing; the next level crossfades into its own soundtrack.
     fn update(&mut self, world: &mut crate::ecs::world::World, context: &mut SystemContext<'_>) {
         // 1. Check if a previously triggered visual transition has completed.
-        if world.transition_finished
-            && let Some(level_path) = &self.pending_level {
+        let iris_closed = world.event_bus.read::<EventTransitionComplete>().any(|e| e.transition_type == TransitionType::IrisOut);
+        if iris_closed
+            && let Some(level_path) = self.pending_level.take() {
                 // If complete, signal the GameStateManager to load the new level data.
-                println!("[LevelTransition] Transition Complete flag detected! Switching to level: {}", level_path);
-                *context.next_level = Some(level_path.clone());
-                self.pending_level = None;
-                world.transition_finished = false;
+                println!("[LevelTransition] Transition complete! Switching to level: {}", level_path);
+                if !std::mem::take(&mut self.restarting) {
+                    world.event_bus.publish(EventLevelCompleted { next_level: level_path.clone() });
+                }
+                *context.next_level = Some(level_path);
                 return; 
             }
 
-        // 2. Prevent overlapping transition triggers.
+        // 2. A restart takes over any exit in progress; the iris keeps closing if it already is.
+        let restart = world.event_bus.read::<EventLevelRestartRequested>().next().map(|e| e.level.clone());
+        if let Some(level_path) = restart && !self.restarting {
+            println!("[LevelTransition] Restart requested! Reloading {}", level_path);
+            let iris_closing = self.pending_level.is_some();
+            self.pending_level = Some(level_path);
+            self.restarting = true;
+            world.level_exit = None;
+            if !iris_closing {
+                world.event_bus.publish(EventStartTransition { transition_type: TransitionType::IrisOut, duration: 1.0, center: None });
+            }
+            return;
+        }
+
+        // 3. Prevent overlapping transition triggers. Under death precedence, a player dying
+        //    during the iris-out cancels the exit so the respawn sequence takes over.
+        if self.restarting { return; }
         if self.pending_level.is_some() {
+            if context.game_config.gameplay.goal_death_precedence == GoalDeathPrecedence::Death && world.event_bus.read::<EventPlayerDied>().next().is_some() {
+                println!("[LevelTransition] Player died before the level ended; cancelling the transition.");
+                self.pending_level = None;
+                world.level_exit = None;
+            }
             return;
         }
 
-        // 3. Detect physical overlap between the Player and Goal entities.
-        let player_entity = world.player_tags.keys().next().copied();
+        // 4. Gameplay may end the level without a goal (e.g. a defeated boss).
+        let requested = world.event_bus.read::<EventLevelExitRequested>().next().map(|e| e.next_level.clone());
+        if let Some(level_path) = requested {
+            println!("[LevelTransition] Exit requested! Starting transition to {}", level_path);
+            self.begin_exit(world, level_path, None);
+            return;
+        }
 
-        if let Some(player_entity) = player_entity {
-            let goal_entities: Vec<_> = world.goals.keys().copied().collect();
+        // 5. Detect physical overlap between any Player (local co-op) and the Goal entities.
+        let player_entities: Vec<_> = world.player_tags.keys().copied().collect();
+        for player_entity in player_entities {
+            // Dead players only finish the level when the goal takes precedence.
+            if context.game_config.gameplay.goal_death_precedence == GoalDeathPrecedence::Death && world.respawn_timers.contains_key(&player_entity) { continue; }
+            let Some(goal_entity) = goal_touched_by(world, player_entity) else { continue };
 
-            for &goal_entity in &goal_entities {
-                if let (Some(player_collision), Some(goal_collision)) = (world.collisions.get(&player_entity), world.collisions.get(&goal_entity))
-                        && player_collision.rect.has_intersection(goal_collision.rect) {
-                            
-                            // 4. Identify the destination level from the goal's properties.
-                            if let Some(next_level_comp) = world.next_levels.get(&goal_entity) {
-                                
-                                // 5. Orchestrate the start of the sequence: Shutter close + Music fade.
-                                println!("[LevelTransition] Goal Reached! Starting transition to {}", next_level_comp.0);
-                                self.pending_level = Some(next_level_comp.0.clone());
-                                
-                                world.event_bus.publish(EventStartTransition {
-                                    transition_type: TransitionType::IrisOut,
-                                    duration: 1.0,
-                                    center: None,
-                                });
-                                
-                                let _ = context.audio_sender.send(crate::audio::AudioEvent::FadeOutMusic(1.0));
-                            }
-                        }
+            // 6. Identify the destination level from the goal's properties.
+            if let Some(next_level_comp) = world.next_levels.get(&goal_entity) {
+                println!("[LevelTransition] Goal Reached! Starting transition to {}", next_level_comp.0);
+                let level_path = next_level_comp.0.clone();
+                let spawn_point = world.exit_spawn_points.get(&goal_entity).map(|s| s.0.clone());
+                self.begin_exit(world, level_path, spawn_point);
+                break;
             }
         }
     }
-}
\ No newline at end of file
+}
+
+/// Returns the goal whose collision box overlaps `player`, if any.
+pub fn goal_touched_by(world: &World, player: Entity) -> Option<Entity> {
+    let player_rect = world.collisions.get(&player)?.rect;
+    world.goals.keys().copied().find(|goal| world.collisions.get(goal).is_some_and(|c| player_rect.has_intersection(c.rect)))
+}
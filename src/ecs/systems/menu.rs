@@ -6,23 +6,124 @@
 
 use crate::input::{InputState, InputAction};
 use crate::menu::{MenuState, RuntimeMenuItemType, MenuAction};
-use crate::renderer::{Renderer, TextRenderParams};
+use crate::renderer::{Renderer, TextAlign, TextRenderParams};
 use crate::font_manager::FontManager;
 use sdl3::pixels::Color;
+use sdl3::rect::{Point, Rect};
+
+/// Seconds left/right must be held before a slider starts repeating.
+const SLIDER_REPEAT_DELAY: f32 = 0.35;
+/// Seconds between repeated slider steps while left/right stays held.
+const SLIDER_REPEAT_INTERVAL: f32 = 0.06;
+/// Width and height of the slider bar in screen **pixels**.
+const SLIDER_SIZE: (u32, u32) = (400, 24);
+/// Space in screen **pixels** kept free below the item list (for the "more" arrow).
+const LIST_BOTTOM_MARGIN: i32 = 80;
+/// Half-width and height in screen **pixels** of the scroll arrows.
+const ARROW_SIZE: (i32, i32) = (16, 12);
+/// Width in screen **pixels** of the band around each item that the mouse can hover or click.
+const ITEM_HIT_WIDTH: u32 = 640;
 
 /// A system responsible for updating menu state and drawing the interface.
-pub struct SystemMenu;
+#[derive(Default)]
+pub struct SystemMenu {
+    /// Time in **seconds** left/right has been held on a slider.
+    slider_hold: f32,
+    /// Hold time at which the next repeated step fires.
+    next_slider_repeat: f32,
+    /// Screen rectangle of each item drawn last frame, for mouse hit-testing.
+    item_rects: Vec<(usize, Rect)>,
+    /// Screen rectangle of each slider bar drawn last frame.
+    slider_rects: Vec<(usize, Rect)>,
+}
 
 impl SystemMenu {
-    /// Interprets input state to navigate menu indices and modify selector values.
+    pub fn new() -> Self { Self::default() }
+
+    /// How many steps (and which way) a slider moves this frame: one on press, then
+    /// a steady repeat after [SLIDER_REPEAT_DELAY] while the key stays held.
+    fn slider_steps(&mut self, input_state: &InputState, delta_time: f32) -> i32 {
+        let direction = match (input_state.is_action_pressed(InputAction::MoveLeft), input_state.is_action_pressed(InputAction::MoveRight)) {
+            (true, false) => -1,
+            (false, true) => 1,
+            _ => { self.slider_hold = 0.0; return 0; }
+        };
+        if input_state.is_action_just_pressed(InputAction::MoveLeft) || input_state.is_action_just_pressed(InputAction::MoveRight) {
+            self.slider_hold = 0.0;
+            self.next_slider_repeat = SLIDER_REPEAT_DELAY;
+            return direction;
+        }
+        self.slider_hold += delta_time;
+        let mut steps = 0;
+        while self.slider_hold >= self.next_slider_repeat {
+            self.next_slider_repeat += SLIDER_REPEAT_INTERVAL;
+            steps += direction;
+        }
+        steps
+    }
+
+    /// The item drawn under `point` last frame, if any.
+    fn item_at(&self, point: Point) -> Option<usize> {
+        self.item_rects.iter().find(|(_, rect)| rect.contains_point(point)).map(|(index, _)| *index)
+    }
+
+    /// Activates the item at `index` as a click at `point`: actions fire, selectors advance
+    /// one option and sliders jump to the clicked spot on their bar.
+    fn click(&self, menu_state: &mut MenuState, index: usize, point: Point) -> Option<MenuAction> {
+        let bar = self.slider_rects.iter().find(|(i, _)| *i == index).map(|(_, rect)| *rect);
+        match &mut menu_state.items.get_mut(index)?.item_type {
+            RuntimeMenuItemType::Action { action } => Some(action.clone()),
+            RuntimeMenuItemType::Binding { action, .. } => Some(MenuAction::CaptureBinding(*action)),
+            RuntimeMenuItemType::Selector { options, current_index, variable_name } => {
+                *current_index = (*current_index + 1) % options.len().max(1);
+                Some(MenuAction::SetOption(variable_name.clone(), options.get(*current_index)?.clone()))
+            }
+            RuntimeMenuItemType::Slider { value, min, max, step, variable_name } => {
+                let bar = bar.filter(|bar| bar.contains_point(point))?;
+                let fraction = (point.x() - bar.x()) as f32 / bar.width().max(1) as f32;
+                let clicked = crate::menu::step_slider(*min + fraction * (*max - *min), *min, *max, *step, 0);
+                if clicked == *value { return None; }
+                *value = clicked;
+                let variable_name = variable_name.clone();
+                menu_state.numbers.insert(variable_name.clone(), clicked);
+                Some(MenuAction::SetValue(variable_name, clicked))
+            }
+        }
+    }
+
+    /// Interprets input state to navigate menu indices and modify selector and slider values.
     ///
     /// ⚠️ **Hotpath**: Called every frame when in Menu state.
     pub fn update(
         &mut self,
         menu_state: &mut MenuState,
         input_state: &InputState,
+        delta_time: f32,
     ) -> Option<MenuAction> {
-        // 1. Process vertical navigation facts (Up/Down).
+        // While a binding waits for its key, the menu itself stays still.
+        if menu_state.capturing.is_some() { return None; }
+
+        // 0. Mouse: moving over an item selects it, clicking activates it. A resting cursor
+        //    never overrides keyboard navigation.
+        if menu_state.config.mouse
+            && let Some((x, y)) = input_state.mouse_position()
+            && (input_state.is_mouse_moved() || input_state.is_mouse_just_clicked())
+            && let Some(index) = self.item_at(Point::new(x as i32, y as i32)) {
+            if index != menu_state.selected_index {
+                menu_state.selected_index = index;
+                self.slider_hold = 0.0;
+                self.next_slider_repeat = SLIDER_REPEAT_DELAY;
+            }
+            if input_state.is_mouse_just_clicked() {
+                return self.click(menu_state, index, Point::new(x as i32, y as i32));
+            }
+        }
+
+        // 1. Process vertical navigation facts (Up/Down). A new item never inherits a held slider repeat.
+        if input_state.is_action_just_pressed(InputAction::Up) || input_state.is_action_just_pressed(InputAction::Down) {
+            self.slider_hold = 0.0;
+            self.next_slider_repeat = SLIDER_REPEAT_DELAY;
+        }
         if input_state.is_action_just_pressed(InputAction::Up) {
             if menu_state.selected_index > 0 { menu_state.selected_index -= 1; }
             else { menu_state.selected_index = menu_state.items.len() - 1; }
@@ -34,7 +135,7 @@ impl SystemMenu {
         // 2. Process interaction with the currently selected item.
         if let Some(selected_item) = menu_state.items.get_mut(menu_state.selected_index) {
             match &mut selected_item.item_type {
-                RuntimeMenuItemType::Selector { options, current_index, .. } => {
+                RuntimeMenuItemType::Selector { options, current_index, variable_name } => {
                     // Handle horizontal modifications for list selectors.
                     if input_state.is_action_just_pressed(InputAction::MoveLeft) {
                         if *current_index > 0 { *current_index -= 1; }
@@ -42,7 +143,21 @@ impl SystemMenu {
                     } else if input_state.is_action_just_pressed(InputAction::MoveRight) {
                         if *current_index < options.len() - 1 { *current_index += 1; }
                         else { *current_index = 0; }
+                    } else {
+                        return None;
                     }
+                    // Report the new value so the owner can apply it.
+                    return Some(MenuAction::SetOption(variable_name.clone(), options[*current_index].clone()));
+                }
+                RuntimeMenuItemType::Slider { value, min, max, step, variable_name } => {
+                    // Handle horizontal nudges, repeating while the key is held.
+                    let steps = self.slider_steps(input_state, delta_time);
+                    if steps == 0 { return None; }
+                    let moved = (0..steps.abs()).fold(*value, |v, _| crate::menu::step_slider(v, *min, *max, *step, steps.signum()));
+                    if moved == *value { return None; }
+                    *value = moved;
+                    menu_state.numbers.insert(variable_name.clone(), moved);
+                    return Some(MenuAction::SetValue(variable_name.clone(), moved));
                 }
                 RuntimeMenuItemType::Action { action } => {
                     // Handle execution of semantic actions (Jump/Accept button).
@@ -50,6 +165,12 @@ impl SystemMenu {
                         return Some(action.clone());
                     }
                 }
+                RuntimeMenuItemType::Binding { action, .. } => {
+                    // Accepting a binding starts waiting for the replacement key.
+                    if input_state.is_action_just_pressed(InputAction::Jump) {
+                        return Some(MenuAction::CaptureBinding(*action));
+                    }
+                }
             }
         }
 
@@ -57,38 +178,64 @@ impl SystemMenu {
     }
 
     /// Renders the full-screen menu overlay based on the current active screen configuration.
+    ///
+    /// # Side Effects
+    /// * Scrolls `menu_state` so the selected item fits the list area of the current output size.
     pub fn draw(
         &mut self,
         renderer: &mut Renderer,
-        menu_state: &MenuState,
+        menu_state: &mut MenuState,
         font_manager: &FontManager,
     ) -> Result<(), String> {
+        // 0. Fit the list between its start and the bottom margin, scrolled to the selection.
+        let (width, height) = renderer.output_size();
+        let (start_y, spacing) = menu_state.config.screens.get(&menu_state.current_screen)
+            .map(|screen| (screen.start_y, screen.spacing))
+            .ok_or("Current screen not found in config")?;
+        let list_height = height as i32 - start_y - LIST_BOTTOM_MARGIN;
+        menu_state.scroll_to_selection(spacing, list_height);
+        let visible = menu_state.visible_range(spacing, list_height);
+
         let config = &menu_state.config;
-        let screen_config = config.screens.get(&menu_state.current_screen).ok_or("Current screen not found in config")?;
+        let screen_config = &config.screens[&menu_state.current_screen];
 
         // 1. Render a semi-transparent black scrim to dim the background.
-        let (width, height) = renderer.output_size();
-        let screen_rect = sdl3::rect::Rect::new(0, 0, width, height);
+        let screen_rect = Rect::new(0, 0, width, height);
         renderer.fill_rect(&screen_rect, Color::RGBA(0, 0, 0, 150))?;
         
         // 2. Render the screen title with a stylistic drop shadow.
         let title_color = Color::RGB(255, 255, 255); 
         let shadow_color = Color::RGB(0, 0, 0);
-        let (title_w, _) = font_manager.measure_text("debug", &screen_config.title, config.font_size as f32).unwrap_or((0, 0));
-        let title_x = (width as i32 - title_w as i32) / 2;
+        let center_x = width as i32 / 2;
+        let title_size = config.title_font_size.unwrap_or(config.font_size) as f32;
 
         renderer.render_text(font_manager, TextRenderParams {
-            text: &screen_config.title, x: title_x + 4, y: screen_config.title_y + 4,
-            font_size: config.font_size as f32, scale: 1.0, color: shadow_color,
+            text: &screen_config.title, font: &config.title_font, x: center_x + 4, y: screen_config.title_y + 4,
+            font_size: title_size, scale: 1.0, color: shadow_color, align: TextAlign::Center,
+            ..Default::default()
         })?;
         renderer.render_text(font_manager, TextRenderParams {
-            text: &screen_config.title, x: title_x, y: screen_config.title_y,
-            font_size: config.font_size as f32, scale: 1.0, color: title_color,
+            text: &screen_config.title, font: &config.title_font, x: center_x, y: screen_config.title_y,
+            font_size: title_size, scale: 1.0, color: title_color, align: TextAlign::Center,
+            ..Default::default()
         })?;
 
-        // 3. Iterate over items and render their labels and current values.
+        // 3. Arrows above and below the list flag items scrolled out of view.
+        let muted_color = Color::RGB(config.unselected_color[0], config.unselected_color[1], config.unselected_color[2]);
+        if visible.start > 0 {
+            draw_arrow(renderer, center_x, screen_config.start_y - ARROW_SIZE.1 - 8, true, muted_color)?;
+        }
+        if visible.end < menu_state.items.len() {
+            draw_arrow(renderer, center_x, screen_config.start_y + list_height + 8, false, muted_color)?;
+        }
+
+        // 4. Iterate over the visible items and render their labels and current values,
+        //    remembering where each one landed for mouse hit-testing.
+        self.item_rects.clear();
+        self.slider_rects.clear();
         let mut y = screen_config.start_y;
-        for (index, item) in menu_state.items.iter().enumerate() {
+        for (index, item) in menu_state.items.iter().enumerate().take(visible.end).skip(visible.start) {
+            self.item_rects.push((index, Rect::new(center_x - ITEM_HIT_WIDTH as i32 / 2, y, ITEM_HIT_WIDTH, item.height(screen_config.spacing).max(1) as u32)));
             let is_selected = index == menu_state.selected_index;
             let color = if is_selected {
                 Color::RGB(config.selected_color[0], config.selected_color[1], config.selected_color[2])
@@ -96,28 +243,120 @@ impl SystemMenu {
                 Color::RGB(config.unselected_color[0], config.unselected_color[1], config.unselected_color[2])
             };
 
-            let label_text = if is_selected { format!("> {}", item.label) } else { item.label.clone() };
-            let (lw, _) = font_manager.measure_text("debug", &label_text, config.font_size as f32).unwrap_or((0, 0));
-            let lx = (width as i32 - lw as i32) / 2;
-
+            let label = match &item.item_type {
+                RuntimeMenuItemType::Binding { action, .. } if menu_state.capturing == Some(*action) => format!("{}: press a key...", item.label),
+                RuntimeMenuItemType::Binding { key, .. } => format!("{}: {}", item.label, key),
+                _ => item.label.clone(),
+            };
+            let label_text = if is_selected { format!("> {}", label) } else { label };
             renderer.render_text(font_manager, TextRenderParams {
-                text: &label_text, x: lx, y, font_size: config.font_size as f32, scale: 1.0, color,
+                text: &label_text, font: &config.font, x: center_x, y, font_size: config.font_size as f32, scale: 1.0, color, align: TextAlign::Center,
+                ..Default::default()
             })?;
 
             if let RuntimeMenuItemType::Selector { options, current_index, .. } = &item.item_type {
                 if let Some(option_text) = options.get(*current_index) {
                     let opt_text = format!("< {} >", option_text);
-                    let (ow, _) = font_manager.measure_text("debug", &opt_text, config.font_size as f32).unwrap_or((0, 0));
-                    let ox = (width as i32 - ow as i32) / 2;
                     renderer.render_text(font_manager, TextRenderParams {
-                        text: &opt_text, x: ox, y: y + 70, font_size: config.font_size as f32, scale: 1.0, color,
+                        text: &opt_text, font: &config.font, x: center_x, y: y + 70, font_size: config.font_size as f32, scale: 1.0, color, align: TextAlign::Center,
+                        ..Default::default()
                     })?;
                 }
-                y += 70 + 50;
+                y += crate::menu::VALUE_ROW_HEIGHT;
+            }
+
+            // Sliders show a bar filled to the value, outlined in the focus color.
+            if let RuntimeMenuItemType::Slider { value, min, max, .. } = &item.item_type {
+                let fraction = if max > min { ((value - min) / (max - min)).clamp(0.0, 1.0) } else { 0.0 };
+                let (bar_w, bar_h) = SLIDER_SIZE;
+                let bar_x = center_x - bar_w as i32 / 2;
+                let bar_y = y + 70;
+                self.slider_rects.push((index, Rect::new(bar_x, bar_y, bar_w, bar_h)));
+                renderer.fill_rect(&Rect::new(bar_x, bar_y, bar_w, bar_h), Color::RGBA(0, 0, 0, 180))?;
+                let filled = (bar_w as f32 * fraction).round() as u32;
+                if filled > 0 { renderer.fill_rect(&Rect::new(bar_x, bar_y, filled, bar_h), color)?; }
+                renderer.draw_rect(&Rect::new(bar_x, bar_y, bar_w, bar_h), color)?;
+                renderer.render_text(font_manager, TextRenderParams {
+                    text: &crate::menu::percent_label(fraction as f64), font: &config.font, x: bar_x + bar_w as i32 + 20, y: bar_y - 8,
+                    font_size: config.font_size as f32, scale: 1.0, color,
+                    ..Default::default()
+                })?;
+                y += crate::menu::VALUE_ROW_HEIGHT;
             }
             y += screen_config.spacing;
         }
 
+        // 5. A notice (e.g. a binding conflict) sits in the bottom margin.
+        if let Some(notice) = &menu_state.notice {
+            renderer.render_text(font_manager, TextRenderParams {
+                text: notice, font: &config.font, x: center_x, y: height as i32 - LIST_BOTTOM_MARGIN / 2,
+                font_size: config.font_size as f32 * 0.6, scale: 1.0, color: muted_color, align: TextAlign::Center,
+                ..Default::default()
+            })?;
+        }
+
         Ok(())
     }
 }
+
+/// Draws a solid triangle `ARROW_SIZE` big, pointing up or down, with its top edge at `y`.
+fn draw_arrow(renderer: &mut Renderer, center_x: i32, y: i32, up: bool, color: Color) -> Result<(), String> {
+    let (half_width, height) = ARROW_SIZE;
+    for row in 0..height {
+        // Up arrows widen from the tip downwards, down arrows narrow towards it.
+        let from_tip = if up { row } else { height - 1 - row };
+        let half = half_width * (from_tip + 1) / height;
+        renderer.fill_rect(&Rect::new(center_x - half, y + row, (half * 2) as u32, 1), color)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::GameConfig;
+
+    /// The audio screen with its items laid out 100 pixels apart, as if it had just been drawn.
+    fn drawn_audio_menu() -> (SystemMenu, MenuState) {
+        let mut menu = MenuState::new(&GameConfig::default().menu);
+        menu.load_screen("audio");
+        let mut system = SystemMenu::new();
+        for index in 0..menu.items.len() {
+            let top = index as i32 * 100;
+            system.item_rects.push((index, Rect::new(0, top, 400, 100)));
+            system.slider_rects.push((index, Rect::new(0, top + 70, 400, 24)));
+        }
+        (system, menu)
+    }
+
+    #[test]
+    fn test_hover_selects_only_when_the_mouse_moves() {
+        let (mut system, mut menu) = drawn_audio_menu();
+        let mut input = InputState::default();
+        input.set_mouse((50.0, 150.0), false);
+        assert_eq!(system.update(&mut menu, &input, 0.016), None);
+        assert_eq!(menu.selected_index, 1, "Hovering selects the item under the cursor");
+
+        // The keyboard wins over a cursor that stays put.
+        let mut down = InputState::from_actions(vec![InputAction::Down]);
+        down.calculate_deltas(&InputState::default());
+        system.update(&mut menu, &down, 0.016);
+        assert_eq!(menu.selected_index, 2);
+    }
+
+    #[test]
+    fn test_clicks_activate_actions_and_set_sliders() {
+        let (mut system, mut menu) = drawn_audio_menu();
+        let mut input = InputState::default();
+        input.set_mouse((200.0, 80.0), true);
+        assert_eq!(system.update(&mut menu, &input, 0.016), Some(MenuAction::SetValue("master_volume".to_string(), 0.5)), "A click on the bar jumps to that spot");
+
+        let back = menu.items.len() - 1;
+        input.set_mouse((50.0, back as f32 * 100.0 + 10.0), true);
+        assert_eq!(system.update(&mut menu, &input, 0.016), Some(MenuAction::Navigate("options".to_string())));
+
+        menu.config.mouse = false;
+        input.set_mouse((50.0, 10.0), true);
+        assert_eq!(system.update(&mut menu, &input, 0.016), None, "Mouse input is ignored when disabled");
+    }
+}
@@ -7,6 +7,7 @@
 pub mod movement;
 pub mod physics;
 pub mod animation_update;
+pub mod animation_events;
 pub mod input;
 pub mod tile_collision;
 pub mod gui_render;
@@ -18,6 +19,7 @@ pub mod concept_health;
 pub mod concept_vitality;
 pub mod rule_player_death;
 pub mod rule_respawn;
+pub mod rule_landing;
 pub mod game_resolution;
 pub mod audio_synchronization;
 pub mod level_transition;
@@ -30,6 +32,15 @@ pub mod animation_synchronization;
 pub mod dormancy;
 pub mod menu;
 pub mod synchronization;
+pub mod trigger;
+pub mod hazard;
+pub mod particles;
+pub mod invincibility_flash;
+pub mod projectile;
+pub mod water;
+pub mod music_layers;
+#[cfg(test)]
+pub(crate) mod test_context;
 
 use crate::ecs::world::World;
 use crate::config::{Config, GameConfig};
@@ -3,170 +3,197 @@
 //! This module standardizes how all entities (Players and Enemies) move 
 //! through the physical world. It translates abstract 'Intentions' and 
 //! 'Commands' into concrete velocity changes, handling acceleration, 
-//! friction, and air control.
+//! friction, and air control. It is the only place that turns control
+//! input into velocity; state machines express intent via [crate::ecs::component::MovementIntention].
 
 use crate::ecs::systems::{System, SystemContext};
 use crate::ecs::event::{CommandJump, EventEntityJumped};
+use crate::audio::{AudioEvent, PlaySoundParams};
 
 /// A system that applies movement forces and jump logic to entities.
 pub struct SystemMovement;
 
 impl System<SystemContext<'_>> for SystemMovement {
-
     /// Processes movement intentions and jump commands to update entity velocities.
-
     ///
-
     /// ⚠️ **Hotpath**: Called 120x per second. Contains tight arithmetic loops.
-
     ///
-
     /// # Side Effects
-
     /// * Consumes [crate::ecs::event::CommandJump] to trigger impulses.
-
     /// * Publishes [crate::ecs::event::EventEntityJumped] when a jump occurs.
-
+    /// * Honors coyote time, jump buffering and air jumps (`physics.max_jumps`) for entities with [crate::ecs::component::JumpAssist].
+    /// * Applies the variable jump height force while `MovementIntention::jump_held` is set.
+    /// * Submerged entities get damped acceleration and swim strokes instead of jumps.
+    /// * Crouching entities move at `player.crouch_speed_factor` of their top speed and ignore jumps.
     fn update(&mut self, world: &mut crate::ecs::world::World, context: &mut SystemContext<'_>) {
-
         let physics_config = &context.config.physics;
 
-
-
         // 1. Process Horizontal Movement Intentions (Locomotion).
-
         let entities: Vec<_> = world.movement_intentions.keys().copied().collect();
 
-
-
         for entity in entities {
-
             // Skip entities outside the active simulation range.
-
             if world.is_dormant(entity) { continue; }
 
-
-
-            if let Some(intention) = world.movement_intentions.get(&entity) {
-
-                let direction = intention.x;
-
-                
-
-                // Determine the Maximum Speed based on entity type (Patrol vs Player).
-
-                let max_speed = if let Some(patrol) = world.patrols.get(&entity) {
-
-                    patrol.speed
-
+            let Some(intention) = world.movement_intentions.get(&entity).copied() else { continue };
+            let direction = intention.x;
+
+            // Determine the Maximum Speed: the intention's own, else by entity type (Patrol vs Player).
+            let max_speed = if let Some(speed) = intention.max_speed {
+                speed
+            } else if let Some(patrol) = world.patrols.get(&entity) {
+                patrol.speed
+            } else {
+                physics_config.max_speed
+            };
+            // Crouching entities shuffle along at a fraction of their top speed.
+            let max_speed = if world.crouchings.contains_key(&entity) { max_speed * context.game_config.player.crouch_speed_factor } else { max_speed };
+
+            // Calculate the Target Velocity based on intent.
+            let target_speed = direction * max_speed;
+            let is_grounded = world.is_grounded(entity);
+            let is_submerged = world.is_submerged(entity);
+
+            if let (Some(vel), Some(accel)) = (world.velocities.get_mut(&entity), world.accelerations.get_mut(&entity)) {
+                let current_speed = vel.0.x;
+
+                // Choose acceleration vs deceleration based on input presence.
+                let accel_value = if direction != 0.0 {
+                    physics_config.acceleration
                 } else {
-
-                    physics_config.max_speed
-
+                    physics_config.deceleration
                 };
 
+                // Apply Air Control damping if the entity is not grounded.
+                let final_accel = if is_submerged {
+                    accel_value * physics_config.water_control_factor
+                } else if is_grounded {
+                    accel_value
+                } else {
+                    accel_value * physics_config.air_control_factor
+                };
 
+                // Integrate acceleration into velocity, clamping to the target speed to prevent overshoot.
+                let delta_v = final_accel * context.delta_time;
 
-                // Calculate the Target Velocity based on intent.
-
-                let target_speed = direction * max_speed;
-
-                let is_grounded = world.is_grounded(entity);
-
-
-
-                if let (Some(vel), Some(accel)) = (world.velocities.get_mut(&entity), world.accelerations.get_mut(&entity)) {
-
-                    let current_speed = vel.0.x;
-
-
-
-                                    // Choose acceleration vs deceleration based on input presence.
-
-                                    let accel_value = if direction != 0.0 {
-
-                                        physics_config.acceleration
-
-                                    } else {
-
-                                        physics_config.deceleration
-
-                                    };
-
-                    
-
-                                    // Apply Air Control damping if the entity is not grounded.
-
-                                    let final_accel = if is_grounded { accel_value } else { accel_value * physics_config.air_control_factor };
-
-                    
-
-                                    // Integrate acceleration into velocity, clamping to the target speed to prevent overshoot.
-
-                                    let delta_v = final_accel * context.delta_time;
-
-                    
-
-                                    if (current_speed - target_speed).abs() <= delta_v {
-
-                                        vel.0.x = target_speed;
-
-                                        accel.0.x = 0.0;
-
-                                    } else if current_speed < target_speed {
-
-                                        accel.0.x = final_accel;
-
-                                                    } else {
-
-                                                        accel.0.x = -final_accel;
-
-                                                    }
-
-                                                } else if let Some(intention) = world.movement_intentions.get(&entity) 
-
-                                                    && intention.x != 0.0 && !world.accelerations.contains_key(&entity) {
-
-                                                         println!("[SystemMovement] WARNING: Entity {:?} wants to move (Intention: {}) but has no Acceleration component!", entity, intention.x);
-
-                                                }
-
-                                            }        }
-
-
-
-        // 2. Process Jump Commands (Impulse).
-
-        let mut jump_commands = Vec::new();
-
-        for cmd in world.event_bus.read::<CommandJump>() {
-
-            jump_commands.push(*cmd);
+                if (current_speed - target_speed).abs() <= delta_v {
+                    vel.0.x = target_speed;
+                    accel.0.x = 0.0;
+                } else if current_speed < target_speed {
+                    accel.0.x = final_accel;
+                } else {
+                    accel.0.x = -final_accel;
+                }
 
+                // Variable jump height: holding jump while rising extends the arc.
+                if intention.jump_held && !is_grounded && !is_submerged && vel.0.y < 0.0 {
+                    vel.0.y -= physics_config.jump_hold_force * context.delta_time;
+                }
+            } else if direction != 0.0 && !world.accelerations.contains_key(&entity) {
+                println!("[SystemMovement] WARNING: Entity {:?} wants to move (Intention: {}) but has no Acceleration component!", entity, direction);
+            }
         }
 
+        // 2. Advance Jump Assist timers (Coyote Time & Input Buffer).
+        let dt = context.delta_time;
+        let assisted: Vec<_> = world.jump_assists.keys().copied().collect();
+        for entity in &assisted {
+            let is_grounded = world.is_grounded(*entity);
+            if let Some(assist) = world.jump_assists.get_mut(entity) {
+                // Grounded entities keep a full coyote window; airborne ones let it run out.
+                assist.coyote_timer = if is_grounded { physics_config.coyote_time } else { (assist.coyote_timer - dt).max(0.0) };
+                assist.buffer_timer = (assist.buffer_timer - dt).max(0.0);
+                // Landing refills the jumps; walking off a ledge forfeits the ground jump once coyote time is over.
+                if is_grounded {
+                    assist.jumps_remaining = physics_config.max_jumps;
+                } else if assist.coyote_timer == 0.0 && assist.jumps_remaining == physics_config.max_jumps {
+                    assist.jumps_remaining = assist.jumps_remaining.saturating_sub(1);
+                }
+            }
+        }
 
-
+        // 3. Process Jump Commands (Impulse).
+        let jump_commands: Vec<CommandJump> = world.event_bus.read::<CommandJump>().copied().collect();
         for cmd in jump_commands {
+            // Jump while crouched is reserved for dropping through one-way platforms; stand up first.
+            if world.crouchings.contains_key(&cmd.entity) { continue; }
+            if world.is_submerged(cmd.entity) {
+                // Swimming: every press is a small stroke, grounded or not.
+                if let Some(vel) = world.velocities.get_mut(&cmd.entity) { vel.0.y = vel.0.y.min(physics_config.stroke_strength); }
+            } else if let Some(assist) = world.jump_assists.get_mut(&cmd.entity) {
+                // Buffer the request; it is executed below once the entity is allowed to jump.
+                assist.buffer_timer = physics_config.jump_buffer_time.max(dt);
+            } else if world.is_grounded(cmd.entity) {
+                // Unassisted entities may only jump from a solid surface (Grounded).
+                self.execute_jump(world, context, cmd.entity, false);
+            }
+        }
 
-            // Only allow jumping from a solid surface (Grounded).
-
-            if world.is_grounded(cmd.entity)
-
-                && let Some(vel) = world.velocities.get_mut(&cmd.entity) {
-
-                    // Apply an instantaneous upward impulse.
+        // 4. Execute buffered jumps for assisted entities within their coyote window, or in the air while jumps remain.
+        for entity in assisted {
+            let Some(assist) = world.jump_assists.get(&entity).copied() else { continue };
+            if assist.buffer_timer <= 0.0 { continue; }
+            let from_ground = assist.coyote_timer > 0.0;
+            if !from_ground && (assist.jumps_remaining == 0 || world.is_submerged(entity)) { continue; }
+            if let Some(assist) = world.jump_assists.get_mut(&entity) {
+                // Consume both windows so a single press yields exactly one jump.
+                assist.buffer_timer = 0.0;
+                assist.coyote_timer = 0.0;
+                assist.jumps_remaining = assist.jumps_remaining.saturating_sub(1);
+            }
+            self.execute_jump(world, context, entity, !from_ground);
+        }
+    }
+}
 
-                    vel.0.y = physics_config.jump_strength;
+impl SystemMovement {
+    /// Applies the jump impulse and publishes the resulting facts. An `air_jump` sounds `player_air_jump`
+    /// when that event is configured.
+    fn execute_jump(&self, world: &mut crate::ecs::world::World, context: &SystemContext<'_>, entity: crate::ecs::world::Entity, air_jump: bool) {
+        let Some(vel) = world.velocities.get_mut(&entity) else { return };
 
-                    // Publish a fact that the entity has jumped (for Audio/FX).
+        // 1. Apply an instantaneous upward impulse; it replaces any fall speed, so air jumps never stack.
+        vel.0.y = context.config.physics.jump_strength;
 
-                    world.event_bus.publish(EventEntityJumped { entity: cmd.entity });
+        // 2. Publish a fact that the entity has jumped (for Audio/FX).
+        world.event_bus.publish(EventEntityJumped { entity });
 
-                }
+        // 3. Player jumps are confirmed audibly exactly once, here.
+        let sound_event = if air_jump && context.game_config.sound_events.contains_key("player_air_jump") { "player_air_jump" } else { "player_jump" };
+        if world.player_tags.contains_key(&entity)
+            && let Some(sound_name) = context.game_config.sound_events.get(sound_event) {
+                let _ = context.audio_sender.send(AudioEvent::PlaySound(sound_name.clone(), PlaySoundParams::default()));
+            }
+    }
+}
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::world::World;
+    use crate::ecs::component::{Acceleration, Grounded, MovementIntention, Position, Velocity};
+    use crate::ecs::systems::test_context::TestContext;
+    use crate::math::Vector2D;
+
+    #[test]
+    fn test_intention_drives_acceleration_with_air_control() {
+        let mut world = World::new();
+        let grounded = world.create_entity();
+        let airborne = world.create_entity();
+        for entity in [grounded, airborne] {
+            world.add_position(entity, Position(Vector2D::default()));
+            world.add_velocity(entity, Velocity(Vector2D::default()));
+            world.add_acceleration(entity, Acceleration(Vector2D::default()));
+            world.add_movement_intention(entity, MovementIntention { x: 1.0, ..Default::default() });
         }
+        world.add_grounded(grounded, Grounded);
 
-    }
+        let mut ctx = TestContext::default();
+        ctx.run(&mut SystemMovement, &mut world);
 
+        let ground_accel = world.accelerations.get(&grounded).unwrap().0.x;
+        let air_accel = world.accelerations.get(&airborne).unwrap().0.x;
+        assert_eq!(ground_accel, ctx.config.physics.acceleration);
+        assert_eq!(air_accel, ctx.config.physics.acceleration * ctx.config.physics.air_control_factor);
+    }
 }
@@ -0,0 +1,87 @@
+//! # Synchronization: Adaptive Music
+//!
+//! This module maps gameplay tension onto [crate::audio::MusicLayer] volumes.
+//! While an enemy is close to the primary player, the configured danger layer
+//! fades in on top of the soundtrack; once the area is clear, it fades out.
+
+use crate::audio::AudioEvent;
+use crate::ecs::systems::{System, SystemContext};
+use crate::ecs::world::World;
+
+/// A system that requests layer volume changes whenever the danger state flips.
+#[derive(Default)]
+pub struct SystemMusicLayers {
+    /// Danger state last sent to the audio manager; `None` until the first tick.
+    danger: Option<bool>,
+}
+
+impl SystemMusicLayers {
+    pub fn new() -> Self { Self::default() }
+
+    /// True when any enemy is within `radius` **pixels** of the primary player.
+    pub fn danger_near(world: &World, radius: f32) -> bool {
+        let Some(player) = world.player_tags.keys().copied().min_by_key(|e| world.player_slots.get(e).map_or(0, |s| s.0)) else { return false; };
+        let Some(origin) = world.positions.get(&player).map(|p| p.0) else { return false; };
+        world.enemy_tags.keys()
+            .filter_map(|e| world.positions.get(e))
+            .any(|p| {
+                let (dx, dy) = (p.0.x - origin.x, p.0.y - origin.y);
+                dx * dx + dy * dy <= radius * radius
+            })
+    }
+}
+
+impl System<SystemContext<'_>> for SystemMusicLayers {
+    /// Fades the danger layer in or out as enemies come and go.
+    ///
+    /// ⚠️ **Hotpath**: Called 120x per second.
+    ///
+    /// # Side Effects
+    /// * Sends `AudioEvent::SetLayerVolume` only when the danger state changes.
+    fn update(&mut self, world: &mut World, context: &mut SystemContext<'_>) {
+        let audio = &context.game_config.gameplay.audio;
+        let Some(layer) = &audio.danger_layer else { return; };
+
+        let danger = Self::danger_near(world, audio.danger_radius);
+        if self.danger == Some(danger) { return; }
+        self.danger = Some(danger);
+        let target = if danger { 1.0 } else { 0.0 };
+        let _ = context.audio_sender.send(AudioEvent::SetLayerVolume(layer.clone(), target, audio.layer_fade));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::component::{EnemyTag, Position};
+    use crate::level::Level;
+    use crate::math::Vector2D;
+    use crate::testing::HeadlessGame;
+
+    /// Steps one tick and returns the layer volume changes it requested.
+    fn step(game: &mut HeadlessGame) -> Vec<(String, f64)> {
+        game.step(1);
+        game.take_audio_events().into_iter().filter_map(|e| match e { AudioEvent::SetLayerVolume(name, target, _) => Some((name, target)), _ => None }).collect()
+    }
+
+    #[test]
+    fn test_danger_layer_follows_enemy_distance_and_only_sends_changes() {
+        let mut game = HeadlessGame::with_level(Level::from_tiles(32, 32, vec![vec![0; 24]; 8]));
+        game.game_config.gameplay.audio.danger_layer = Some("percussion".to_string());
+        game.game_config.gameplay.audio.danger_radius = 100.0;
+        let player = game.spawn_player(Vector2D::new(64.0, 64.0));
+        game.world.gravity_tags.remove(&player);
+        let enemy = game.world.create_entity();
+        game.world.add_enemy_tag(enemy, EnemyTag);
+        game.world.add_position(enemy, Position(Vector2D::new(564.0, 64.0)));
+
+        assert_eq!(step(&mut game), vec![("percussion".to_string(), 0.0)], "The first tick states the initial mix");
+        assert!(step(&mut game).is_empty(), "Unchanged state sends nothing");
+
+        game.world.positions.get_mut(&enemy).unwrap().0.x = 144.0;
+        assert_eq!(step(&mut game), vec![("percussion".to_string(), 1.0)]);
+
+        game.world.enemy_tags.remove(&enemy);
+        assert_eq!(step(&mut game), vec![("percussion".to_string(), 0.0)]);
+    }
+}
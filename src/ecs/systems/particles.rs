@@ -0,0 +1,186 @@
+//! # Concept: Particles
+//!
+//! This module simulates short-lived visual particles (sparks, dust, coin
+//! glitter) on the CPU. Particles are plain data in [World::particles], not
+//! entities, so thousands of them never touch the component storages.
+
+use crate::ecs::systems::{System, SystemContext};
+use crate::ecs::event::EventParticleBurst;
+use crate::ecs::resources::Particle;
+use crate::ecs::world::{Entity, World};
+use crate::config::ParticlePresetConfig;
+use crate::math::Vector2D;
+use rand::Rng;
+use std::collections::HashMap;
+
+/// Minimum downward speed in **pixels per second** before a landing kicks up dust.
+const LANDING_DUST_MIN_SPEED: f32 = 150.0;
+
+/// Distance in **pixels** outside the camera view beyond which particles are discarded.
+const CULL_MARGIN: f32 = 64.0;
+
+/// Spawns up to `count` particles from `spec` at `position`, respecting `max_particles`.
+///
+/// # Side Effects
+/// * Silently drops particles once the cap is reached.
+pub fn emit(particles: &mut Vec<Particle>, spec: &ParticlePresetConfig, position: Vector2D, count: u32, max_particles: usize) {
+    let mut rng = rand::rng();
+    let available = max_particles.saturating_sub(particles.len());
+    for _ in 0..(count as usize).min(available) {
+        let half_spread = spec.spread * 0.5;
+        let angle = (spec.angle + if half_spread > 0.0 { rng.random_range(-half_spread..=half_spread) } else { 0.0 }).to_radians();
+        let speed = random_between(&mut rng, spec.speed);
+        particles.push(Particle {
+            position,
+            velocity: Vector2D { x: angle.cos() * speed, y: angle.sin() * speed },
+            age: 0.0,
+            lifetime: random_between(&mut rng, spec.lifetime),
+            gravity: spec.gravity,
+            start_color: spec.start_color,
+            end_color: spec.end_color,
+            size: spec.size,
+        });
+    }
+}
+
+/// Spawns one burst of the named preset from `[particles.presets]`.
+pub fn emit_burst(world: &mut World, context: &SystemContext<'_>, preset: &str, position: Vector2D) {
+    let config = &context.game_config.particles;
+    if let Some(spec) = config.presets.get(preset) {
+        emit(&mut world.particles, spec, position, spec.count, config.max_particles);
+    }
+}
+
+fn random_between(rng: &mut impl Rng, range: [f32; 2]) -> f32 {
+    if range[1] > range[0] { rng.random_range(range[0]..range[1]) } else { range[0] }
+}
+
+/// A system that spawns and integrates particles.
+pub struct SystemParticles {
+    /// Vertical velocity of each player on the previous tick; used to detect landings.
+    last_fall_speed: HashMap<Entity, f32>,
+}
+
+impl SystemParticles {
+    pub fn new() -> Self { Self { last_fall_speed: HashMap::new() } }
+}
+
+impl Default for SystemParticles {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl System<SystemContext<'_>> for SystemParticles {
+    /// Emits new particles and advances existing ones by one tick.
+    ///
+    /// ⚠️ **Hotpath**: Called 120x per second.
+    ///
+    /// # Side Effects
+    /// * Mutates [World::particles]; expired particles are removed.
+    fn update(&mut self, world: &mut World, context: &mut SystemContext<'_>) {
+        let dt = context.delta_time;
+        let max_particles = context.game_config.particles.max_particles;
+
+        // 1. One-shot bursts requested by gameplay (coin pickup, stomp, ...).
+        let bursts: Vec<EventParticleBurst> = world.event_bus.read::<EventParticleBurst>().cloned().collect();
+        for burst in bursts {
+            emit_burst(world, context, &burst.preset, burst.position);
+        }
+
+        // 2. Landing dust: a player that was falling fast last tick and is now grounded.
+        let mut landings = Vec::new();
+        let players: Vec<Entity> = world.player_tags.keys().copied().collect();
+        for player in &players {
+            let fall_speed = world.velocities.get(player).map(|v| v.0.y).unwrap_or(0.0);
+            let previous = self.last_fall_speed.insert(*player, fall_speed).unwrap_or(0.0);
+            if world.grounded_tags.contains_key(player) && previous >= LANDING_DUST_MIN_SPEED
+                && let Some(collision) = world.collisions.get(player) {
+                let rect = collision.rect;
+                landings.push(Vector2D { x: rect.x() as f32 + rect.width() as f32 * 0.5, y: rect.y() as f32 + rect.height() as f32 });
+            }
+        }
+        self.last_fall_speed.retain(|e, _| players.contains(e));
+        for feet in landings {
+            emit_burst(world, context, "dust", feet);
+        }
+
+        // 3. Continuous emitters.
+        for (entity, emitter) in world.particle_emitters.iter_mut() {
+            if world.dormant_tags.contains_key(entity) { continue; }
+            let Some(pos) = world.positions.get(entity) else { continue };
+            emitter.accumulator += emitter.rate * dt;
+            let count = emitter.accumulator.floor();
+            emitter.accumulator -= count;
+            emit(&mut world.particles, &emitter.spec, pos.0, count as u32, max_particles);
+        }
+
+        // 4. Integrate, then expire particles that are too old or have left the view.
+        for p in world.particles.iter_mut() {
+            p.age += dt;
+            p.velocity.y += p.gravity * dt;
+            p.position.x += p.velocity.x * dt;
+            p.position.y += p.velocity.y * dt;
+        }
+        let camera = &context.camera;
+        let (min_x, min_y) = (camera.position.x - CULL_MARGIN, camera.position.y - CULL_MARGIN);
+        let (max_x, max_y) = (camera.position.x + camera.view_width() + CULL_MARGIN, camera.position.y + camera.view_height() + CULL_MARGIN);
+        world.particles.retain(|p| {
+            p.age < p.lifetime && p.position.x >= min_x && p.position.x <= max_x && p.position.y >= min_y && p.position.y <= max_y
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::load_game_config;
+    use crate::ecs::systems::test_context::TestContext;
+
+    fn run(system: &mut SystemParticles, world: &mut World, game_config: &crate::config::GameConfig, delta_time: f32) {
+        let mut ctx = TestContext::default();
+        (ctx.game_config, ctx.delta_time) = (game_config.clone(), delta_time);
+        ctx.run(system, world);
+    }
+
+    #[test]
+    fn test_bursts_respect_particle_cap() {
+        let mut game_config = load_game_config("assets/game_config.toml").unwrap();
+        game_config.particles.max_particles = 15;
+        let mut world = World::new();
+        for _ in 0..3 {
+            world.event_bus.publish(EventParticleBurst { preset: "coin".to_string(), position: Vector2D::default() });
+        }
+
+        run(&mut SystemParticles::new(), &mut world, &game_config, 0.0);
+        assert_eq!(world.particles.len(), 15, "Bursts beyond the cap are dropped");
+    }
+
+    #[test]
+    fn test_particles_expire_after_lifetime() {
+        let game_config = load_game_config("assets/game_config.toml").unwrap();
+        let mut world = World::new();
+        let spec = game_config.particles.presets.get("dust").unwrap().clone();
+        emit(&mut world.particles, &spec, Vector2D::default(), 4, 100);
+        assert_eq!(world.particles.len(), 4);
+
+        let mut system = SystemParticles::new();
+        run(&mut system, &mut world, &game_config, spec.lifetime[1] * 0.5);
+        assert!(!world.particles.is_empty(), "Particles live for at least their minimum lifetime");
+        run(&mut system, &mut world, &game_config, spec.lifetime[1]);
+        assert!(world.particles.is_empty(), "All particles expire after the maximum lifetime");
+    }
+
+    #[test]
+    fn test_offscreen_particles_are_culled() {
+        let game_config = load_game_config("assets/game_config.toml").unwrap();
+        let mut world = World::new();
+        let visible = Particle { position: Vector2D { x: 100.0, y: 100.0 }, lifetime: 10.0, ..Default::default() };
+        let offscreen = Particle { position: Vector2D { x: 5000.0, y: 100.0 }, lifetime: 10.0, ..Default::default() };
+        world.particles.extend([visible, offscreen]);
+
+        run(&mut SystemParticles::new(), &mut world, &game_config, 0.0);
+        assert_eq!(world.particles.len(), 1, "Particles far outside the view are dropped");
+        assert_eq!(world.particles[0].position.x, 100.0);
+    }
+}
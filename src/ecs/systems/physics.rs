@@ -14,16 +14,25 @@ impl System<SystemContext<'_>> for SystemPhysics {
     ///
     /// ⚠️ **Hotpath**: Called 120x per second. Contains tight loops over entity components.
     fn update(&mut self, world: &mut crate::ecs::world::World, context: &mut SystemContext<'_>) {
+        let physics_config = &context.config.physics;
+
         // 1. Apply Gravity (Global Constant Force) to all affected entities.
         for (entity, _gravity) in world.gravity_tags.iter() {
             // Skip entities outside the active simulation range.
             if world.is_dormant(*entity) { continue; }
 
+            // Water cancels part of gravity and lowers terminal velocity.
+            let (gravity, max_fall_speed) = if world.submerged_tags.contains_key(entity) {
+                (physics_config.gravity * (1.0 - physics_config.buoyancy), physics_config.water_max_speed)
+            } else {
+                (physics_config.gravity, physics_config.entity_max_fall_speed)
+            };
+
             if let Some(vel) = world.velocities.get_mut(entity) {
                 // Integrate gravity into vertical velocity.
-                vel.0.y += context.config.physics.gravity * context.delta_time;
+                vel.0.y += gravity * context.delta_time;
                 // Enforce terminal velocity to prevent tunneling through thin platforms.
-                vel.0.y = vel.0.y.min(context.config.physics.entity_max_fall_speed);
+                vel.0.y = vel.0.y.min(max_fall_speed);
             }
         }
 
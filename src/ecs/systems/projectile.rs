@@ -0,0 +1,151 @@
+//! # Concept: Projectiles
+//!
+//! This module owns the life of a shot outside of entity contact: it turns 
+//! fire commands into new projectiles (respecting each shooter's fire rate) 
+//! and destroys projectiles that strike level geometry. Enemies aim their own
+//! shots from `ShooterState`. Hits on players and enemies are interpreted by
+//! `SystemSynchronization`.
+
+use crate::ecs::systems::{System, SystemContext};
+use crate::ecs::event::{CommandFire, EventParticleBurst};
+use crate::ecs::component::{DeadTag, Direction};
+use crate::ecs::world::{Entity, World};
+use crate::audio::{AudioEvent, PlaySoundParams};
+use crate::math::Vector2D;
+use std::collections::HashMap;
+
+/// A system that spawns projectiles on command and removes them on tile impact.
+pub struct SystemProjectile {
+    /// Remaining cooldown in **seconds** per shooter.
+    cooldowns: HashMap<Entity, f32>,
+}
+
+impl SystemProjectile {
+    pub fn new() -> Self { Self { cooldowns: HashMap::new() } }
+}
+
+impl Default for SystemProjectile {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl System<SystemContext<'_>> for SystemProjectile {
+    /// Fires pending shots and retires projectiles that hit walls, floors or ceilings.
+    ///
+    /// ⚠️ **Hotpath**: Called 120x per second.
+    ///
+    /// # Side Effects
+    /// * Spawns projectile entities from the shooter's configured prefab.
+    /// * Adds [DeadTag] to projectiles touching solid tiles.
+    fn update(&mut self, world: &mut World, context: &mut SystemContext<'_>) {
+        // 1. Tick down fire-rate cooldowns.
+        for cooldown in self.cooldowns.values_mut() { *cooldown -= context.delta_time; }
+        self.cooldowns.retain(|e, c| *c > 0.0 && world.positions.contains_key(e));
+
+        // 2. Fire one shot per ready shooter, from the center of its hitbox along its facing.
+        let mut shooters: Vec<Entity> = world.event_bus.read::<CommandFire>().map(|c| c.entity).collect();
+        shooters.sort_unstable();
+        shooters.dedup();
+        for shooter in shooters {
+            if self.cooldowns.contains_key(&shooter) { continue; }
+            let Some(collision) = world.collisions.get(&shooter) else { continue };
+            let rect = collision.rect;
+            let direction = match world.directions.get(&shooter).map(|d| d.direction) {
+                Some(Direction::Left) => -1.0,
+                _ => 1.0,
+            };
+            let origin = Vector2D::new(rect.x() as f32 + rect.width() as f32 * 0.5, rect.y() as f32 + rect.height() as f32 * 0.5);
+            let prefab = &context.game_config.player.projectile_prefab;
+            if crate::entity_spawner::spawn_projectile(world, context.game_config, prefab, shooter, origin, direction).is_some() {
+                self.cooldowns.insert(shooter, context.game_config.player.fire_cooldown);
+                if let Some(sound_name) = context.game_config.sound_events.get("projectile_fire") {
+                    let _ = context.audio_sender.send(AudioEvent::PlaySound(sound_name.clone(), PlaySoundParams::at(origin)));
+                }
+            }
+        }
+
+        // 3. Shots that touched solid geometry this tick are spent.
+        let spent: Vec<Entity> = world.projectiles.keys()
+            .filter(|e| world.wall_hits.contains_key(e) || world.grounded_tags.contains_key(e))
+            .copied()
+            .collect();
+        for projectile in spent {
+            world.add_dead_tag(projectile, DeadTag);
+            if let Some(pos) = world.positions.get(&projectile) {
+                let position = pos.0;
+                world.event_bus.publish(EventParticleBurst { preset: "stomp".to_string(), position });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::event::EventCollision;
+    use crate::level::Level;
+    use crate::testing::HeadlessGame;
+
+    const TILE: u32 = 32;
+
+    /// A floored room walled off on the right, with a weightless player in the middle facing `direction`.
+    fn room(direction: Direction) -> (HeadlessGame, Entity) {
+        let mut tiles = vec![vec![0; 16]; 8];
+        tiles[7] = vec![1; 16];
+        for row in tiles.iter_mut() { row[15] = 1; }
+        let mut game = HeadlessGame::with_level(Level::from_tiles(TILE, TILE, tiles));
+        let player = game.spawn_player(Vector2D::new(200.0, 64.0));
+        game.world.gravity_tags.remove(&player);
+        game.world.directions.get_mut(&player).unwrap().direction = direction;
+        (game, player)
+    }
+
+    #[test]
+    fn test_fire_rate_and_ownership() {
+        let (mut game, player) = room(Direction::Left);
+        game.world.event_bus.publish(CommandFire { entity: player });
+        game.step(1);
+        assert_eq!(game.world.projectiles.len(), 1);
+        let (&shot, projectile) = game.world.projectiles.iter().next().unwrap();
+        assert_eq!(projectile.owner, Some(player));
+        assert!(projectile.from_player);
+        assert!(game.world.velocities[&shot].0.x < 0.0, "Shots travel the way the shooter faces");
+
+        // Holding fire during the cooldown does not spawn more shots.
+        game.world.event_bus.publish(CommandFire { entity: player });
+        game.step(1);
+        assert_eq!(game.world.projectiles.len(), 1);
+    }
+
+    #[test]
+    fn test_projectile_dies_on_wall_hit() {
+        let (mut game, player) = room(Direction::Right);
+        let wall_x = (15 * TILE) as f32;
+        let shot = crate::entity_spawner::spawn_projectile(&mut game.world, &game.game_config, "EnemyBullet", player, Vector2D::new(wall_x - 48.0, 64.0), 1.0).unwrap();
+        assert!(!game.world.projectiles[&shot].from_player);
+
+        game.step(1);
+        assert!(game.world.projectiles.contains_key(&shot), "Still in flight");
+        // 0.5 s: long enough to reach the wall, well short of the bullet's lifetime.
+        game.step(60);
+        assert!(!game.world.projectiles.contains_key(&shot), "Spent on the wall");
+    }
+
+    #[test]
+    fn test_synchronization_ignores_owner_and_hits_enemies() {
+        let (mut game, player) = room(Direction::Right);
+        let enemy = game.spawn("EnemySpider", Vector2D::new(100.0, 200.0));
+        let shot = crate::entity_spawner::spawn_projectile(&mut game.world, &game.game_config, "PlayerBullet", player, Vector2D::new(300.0, 40.0), 1.0).unwrap();
+        let health = game.world.healths[&player].current;
+
+        let overlap = sdl3::rect::Rect::new(0, 0, 4, 4);
+        game.world.event_bus.publish(EventCollision { entity_a: player, entity_b: shot, intersection: overlap });
+        game.world.event_bus.publish(EventCollision { entity_a: enemy, entity_b: shot, intersection: overlap });
+        game.step(1);
+
+        assert!(!game.world.positions.contains_key(&enemy), "The shot kills the enemy");
+        assert!(!game.world.projectiles.contains_key(&shot), "and is spent on it");
+        assert_eq!(game.world.healths[&player].current, health, "The shooter is never hit by its own shot");
+    }
+}
@@ -0,0 +1,40 @@
+//! # Synchronization: Player Landing
+//! 
+//! This module implements the feedback for [crate::ecs::event::EventPlayerLanded].
+//! 
+//! # Responsibilities
+//! * Plays the `player_land` sound, louder the faster the player was falling.
+//! * Shakes the camera on heavy landings via [crate::ecs::event::EventScreenShake].
+//! 
+//! Landings below `[gameplay.landing] min_speed` are ignored, which also keeps respawn
+//! teleports (whose velocity was zeroed) silent.
+
+use crate::audio::{AudioEvent, PlaySoundParams};
+use crate::ecs::event::{EventPlayerLanded, EventScreenShake};
+use crate::ecs::systems::{System, SystemContext};
+
+pub struct RuleLanding;
+
+impl System<SystemContext<'_>> for RuleLanding {
+    fn update(&mut self, world: &mut crate::ecs::world::World, context: &mut SystemContext<'_>) {
+        let landing = &context.game_config.gameplay.landing;
+        let landings: Vec<EventPlayerLanded> = world.event_bus.read::<EventPlayerLanded>().copied().collect();
+
+        for event in landings {
+            // 1. Soft landings make no noise at all.
+            let volume = landing.volume(event.impact_speed);
+            if volume <= 0.0 { continue; }
+
+            // 2. The thud fades in with the impact speed.
+            if let Some(sound_name) = context.game_config.sound_events.get("player_land") {
+                let params = PlaySoundParams { volume, ..PlaySoundParams::at(event.position) };
+                let _ = context.audio_sender.send(AudioEvent::PlaySound(sound_name.clone(), params));
+            }
+
+            // 3. Heavy landings rattle the camera.
+            if event.impact_speed >= landing.heavy_speed {
+                world.event_bus.publish(EventScreenShake { trauma: landing.heavy_trauma });
+            }
+        }
+    }
+}
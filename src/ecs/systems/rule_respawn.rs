@@ -7,10 +7,11 @@
 //! * Publishes [crate::ecs::event::EventGameOver] if lives are depleted.
 //! * Publishes [crate::ecs::event::EventRespawnStarted] to trigger transitions.
 //! * Resets player data (Health, Position, State) when [crate::ecs::component::RespawnTag] is present.
+//! * Respawns at the last activated [crate::ecs::component::Checkpoint], if any.
 
 use crate::ecs::systems::{System, SystemContext};
 use crate::ecs::component::{RespawnTag, Collision, Gravity, Invincibility};
-use crate::ecs::event::{EventGameOver, EventRespawnStarted, EventStartTransition, TransitionType};
+use crate::ecs::event::{EventGameOver, EventRespawnStarted, EventStartTransition, EventTransitionComplete, TransitionType};
 use crate::player::states::IdleState;
 use crate::state_machine::StateMachine;
 
@@ -53,24 +54,22 @@ impl System<SystemContext<'_>> for RuleRespawn {
              world.add_invincibility(entity, Invincibility { timer: 999.0 });
         }
 
-        // 2. Identify Entities Ready for Physical Respawn
+        // 2. Identify Entities Ready for Physical Respawn: their iris-out has closed and the timer ran out.
+        //    The closing is remembered per timer, since the two rarely land on the same frame.
+        let iris_closed = world.event_bus.read::<EventTransitionComplete>().any(|e| e.transition_type == TransitionType::IrisOut);
         let mut to_respawn = Vec::new();
-        if world.transition_finished {
-             for (entity, timer) in &mut world.respawn_timers {
-                 if timer.timer <= 0.0 {
-                     to_respawn.push(*entity);
-                 }
-             }
+        for (entity, timer) in &mut world.respawn_timers {
+            if iris_closed && timer.transition_started { timer.transition_complete = true; }
+            if timer.transition_complete && timer.timer <= 0.0 {
+                to_respawn.push(*entity);
+            }
         }
         
         // 3. Execute Respawn (Reset Data)
-        if !to_respawn.is_empty() {
-            world.transition_finished = false;
-            for entity in to_respawn {
-                world.respawn_timers.remove(&entity);
-                // Mark for processing in step 4
-                world.add_respawn_tag(entity, RespawnTag);
-            }
+        for entity in to_respawn {
+            world.respawn_timers.remove(&entity);
+            // Mark for processing in step 4
+            world.add_respawn_tag(entity, RespawnTag);
         }
 
         // 4. Handle RespawnTag (Physical Reset)
@@ -84,28 +83,32 @@ impl System<SystemContext<'_>> for RuleRespawn {
 
 impl RuleRespawn {
     fn reset_player(&self, entity: crate::ecs::world::Entity, world: &mut crate::ecs::world::World, context: &mut SystemContext<'_>) {
-            if let Some(pos) = world.positions.get_mut(&entity) { pos.0 = context.game_config.player.respawn_pos; }
+            // Prefer the latest checkpoint over the level's default respawn point.
+            let respawn_pos = world.stats.last_checkpoint.unwrap_or(context.game_config.player.respawn_pos);
+            if let Some(pos) = world.positions.get_mut(&entity) { pos.0 = respawn_pos; }
             if let Some(vel) = world.velocities.get_mut(&entity) { vel.0 = crate::math::Vector2D::default(); }
             if let Some(health) = world.healths.get_mut(&entity) { health.current = health.max; }
+            world.stats.lose_life_coins(context.game_config.gameplay.carry_coins_on_death);
             if let Some(renderable) = world.renderables.get_mut(&entity) { 
                 renderable.width = context.game_config.player.draw_width;
                 renderable.height = context.game_config.player.draw_height;
                 renderable.flip_vertical = false; 
                 renderable.flip_horizontal = false; 
                 renderable.rotation = 0.0; 
+                renderable.alpha = 255;
                 renderable.vertical_offset = context.game_config.player.vertical_draw_offset; 
             }
-            context.camera.snap_to(context.game_config.player.respawn_pos);
+            context.camera.snap_to(respawn_pos);
             if let Some(pos) = world.positions.get(&entity) { world.previous_positions.insert(entity, *pos); }
             
-            // Remove the infinite invincibility from the timer phase
-            world.invincibilities.remove(&entity);
+            // Drop the previous life's state, including the infinite invincibility from the timer phase
+            world.clear_life_state(entity);
             
             world.add_gravity(entity, Gravity);
             world.add_collision(entity, Collision { 
                 rect: sdl3::rect::Rect::new(
-                    context.game_config.player.respawn_pos.x as i32, 
-                    context.game_config.player.respawn_pos.y as i32, 
+                    respawn_pos.x as i32, 
+                    respawn_pos.y as i32, 
                     context.game_config.player.width, 
                     context.game_config.player.height
                 ) 
@@ -115,8 +118,8 @@ impl RuleRespawn {
             if let Some(state) = world.state_components.get_mut(&entity) { state.state_machine = StateMachine::new(IdleState); }
 
             let center = {
-                let cx = context.game_config.player.respawn_pos.x + (context.game_config.player.draw_width as f32 / 2.0) + context.game_config.player.horizontal_draw_offset as f32;
-                let cy = context.game_config.player.respawn_pos.y + (context.game_config.player.draw_height as f32 / 2.0) + context.game_config.player.vertical_draw_offset as f32;
+                let cx = respawn_pos.x + (context.game_config.player.draw_width as f32 / 2.0) + context.game_config.player.horizontal_draw_offset as f32;
+                let cy = respawn_pos.y + (context.game_config.player.draw_height as f32 / 2.0) + context.game_config.player.vertical_draw_offset as f32;
                 let screen_x = cx - context.camera.position.x;
                 let screen_y = cy - context.camera.position.y;
 
@@ -134,52 +137,77 @@ impl RuleRespawn {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
-    use crate::ecs::world::World;
-    use crate::ecs::component::RespawnTag;
-    use crate::ecs::systems::SystemContext;
-    use crate::config::{load_config, load_game_config};
+    use crate::ecs::component::{RespawnTag, RespawnTimer};
+    use crate::ecs::event::{EventTransitionComplete, TransitionType};
+    use crate::ecs::world::Entity;
     use crate::level::Level;
-    use crate::input::InputState;
+    use crate::math::Vector2D;
+    use crate::testing::HeadlessGame;
+
+    const TILE: u32 = 32;
+
+    /// A floored room with a player standing on the left and the default respawn point on the right.
+    fn room() -> (HeadlessGame, Entity) {
+        let mut tiles = vec![vec![0; 16]; 6];
+        tiles[5] = vec![1; 16];
+        let mut game = HeadlessGame::with_level(Level::from_tiles(TILE, TILE, tiles));
+        let standing_y = (5 * TILE) as f32 - game.game_config.player.height as f32;
+        game.game_config.player.respawn_pos = Vector2D::new(320.0, standing_y);
+        let player = game.spawn_player(Vector2D::new(64.0, standing_y));
+        (game, player)
+    }
 
     #[test]
     fn test_respawn_execution() {
-        let mut world = World::new();
-        let entity = world.create_entity();
-        world.add_respawn_tag(entity, RespawnTag);
-        // Needs position for reset logic
-        world.add_position(entity, crate::ecs::component::Position(crate::math::Vector2D::default()));
+        let (mut game, player) = room();
+        game.world.gravity_tags.remove(&player);
+        game.world.add_respawn_tag(player, RespawnTag);
+        game.step(1);
 
-        let mut system = RuleRespawn;
-        
-        // Mock context
-        let config = load_config().unwrap();
-        let game_config = load_game_config("assets/game_config.toml").unwrap();
-        let (audio_sender, _) = std::sync::mpsc::channel();
-        let mut camera = crate::camera::Camera::new(0.0, 0.0, 0.1, 480.0, 270.0, 1000.0, 1000.0, 0.1, 0.1, 0.1, 0.1, 0.1, 0.1, 1000.0, 100.0, 0.1);
-        let level = Level::default();
-        let input_state = InputState::default();
-        let mut next_level = None;
-        let mut benchmarker = crate::benchmarker::Benchmarker::new();
-
-        let mut mock_context = SystemContext {
-            config: &config,
-            game_config: &game_config,
-            delta_time: 0.1,
-            camera: &mut camera,
-            audio_sender: &audio_sender,
-            is_paused: false,
-            is_attract_mode: false,
-            benchmarker: &mut benchmarker,
-            level: &level,
-            input_state: &input_state,
-            next_level: &mut next_level,
-            current_soundtrack: None,
-        };
-
-        system.update(&mut world, &mut mock_context);
-
-        assert!(!world.respawn_tags.contains_key(&entity), "RespawnTag should be consumed");
-        assert!(world.gravity_tags.contains_key(&entity), "Gravity should be restored");
+        assert!(!game.world.respawn_tags.contains_key(&player), "RespawnTag should be consumed");
+        assert!(game.world.gravity_tags.contains_key(&player), "Gravity should be restored");
+        assert_eq!(game.world.positions[&player].0, game.game_config.player.respawn_pos);
+    }
+
+    #[test]
+    fn test_respawn_at_checkpoint() {
+        let (mut game, player) = room();
+        let checkpoint = Vector2D::new(160.0, game.game_config.player.respawn_pos.y);
+        game.world.stats.last_checkpoint = Some(checkpoint);
+        game.world.add_respawn_tag(player, RespawnTag);
+        game.step(1);
+
+        assert_eq!(game.world.positions[&player].0, checkpoint, "Player should respawn at the last checkpoint");
+    }
+
+    #[test]
+    fn test_coin_policy_on_respawn() {
+        for (carry, expected) in [(true, 7), (false, 0)] {
+            let (mut game, player) = room();
+            game.game_config.gameplay.carry_coins_on_death = carry;
+            game.world.stats.gold_coin_count = 7;
+            game.world.add_respawn_tag(player, RespawnTag);
+            game.step(1);
+
+            assert_eq!(game.world.stats.gold_coin_count, expected, "carry_coins_on_death = {}", carry);
+        }
+    }
+
+    #[test]
+    fn test_respawn_waits_for_both_the_iris_and_the_timer() {
+        let (mut game, player) = room();
+        game.world.add_respawn_timer(player, RespawnTimer { timer: 0.35, transition_started: true, transition_complete: false });
+
+        // The iris closes while time is still left on the clock.
+        game.world.event_bus.publish(EventTransitionComplete { transition_type: TransitionType::IrisOut });
+        game.step(1);
+        assert!(game.world.respawn_timers.contains_key(&player), "Timer still running");
+
+        // The event is gone, but the closing was remembered on the timer.
+        game.step(30);
+        assert!(game.world.respawn_timers.contains_key(&player), "Timer still running");
+        game.step(20);
+        assert!(!game.world.respawn_timers.contains_key(&player), "Respawned once the timer ran out");
+        assert_eq!(game.world.positions[&player].0, game.game_config.player.respawn_pos);
     }
 }
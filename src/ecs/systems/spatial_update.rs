@@ -3,6 +3,13 @@
 //! This module is responsible for maintaining the Spatial Grid, a uniform grid
 //! structure used to optimize collision queries and frustum culling.
 //! It ensures that entities can be efficiently located by their physical position.
+//!
+//! # Contract
+//! * It is the **single authority** that moves `Collision.rect` to `Position` each tick.
+//!   No other system re-syncs rects ad hoc.
+//! * It runs after `SystemPhysics` and before `SystemInteraction` / `SystemTileCollision`,
+//!   so every consumer of the grid or of collision rects sees this tick's positions.
+//! * Rect sizes are never changed here; only the origin is synchronized.
 
 use crate::ecs::systems::{System, SystemContext};
 
@@ -13,14 +20,73 @@ impl System<SystemContext<'_>> for SystemSpatialUpdate {
     /// Re-indexes all collidable entities into the spatial grid based on their current bounds.
     ///
     /// ⚠️ **Hotpath**: Called 120x per second. Iterates over all physical entities.
+    ///
+    /// # Side Effects
+    /// * Moves every [crate::ecs::component::Collision] rect to its entity's current [crate::ecs::component::Position].
     fn update(&mut self, world: &mut crate::ecs::world::World, _context: &mut SystemContext<'_>) {
         // 1. Clear the previous frame's spatial index.
         world.spatial_grid.clear();
 
         // 2. Iterate over all entities with a collision component.
-        for (entity, collision) in &world.collisions {
-            // 3. Insert them into the grid based on their current bounding box.
+        for (entity, collision) in &mut world.collisions {
+            // 3. Sync the bounding box with the authoritative position so moving entities
+            // (patrolling enemies, knocked-back players) never interact through a stale rect.
+            if let Some(pos) = world.positions.get(entity) {
+                collision.rect.set_x(pos.0.x as i32);
+                collision.rect.set_y(pos.0.y as i32);
+            }
+
+            // 4. Insert them into the grid based on their current bounding box.
             world.spatial_grid.insert(*entity, collision.rect);
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::world::World;
+    use crate::ecs::component::{Collision, EnemyTag, Position};
+    use crate::ecs::systems::test_context::TestContext;
+    use crate::math::Vector2D;
+
+    #[test]
+    fn test_collision_rect_follows_moved_enemy() {
+        let mut world = World::new();
+        let enemy = world.create_entity();
+        world.add_position(enemy, Position(Vector2D::new(100.0, 200.0)));
+        world.add_collision(enemy, Collision { rect: sdl3::rect::Rect::new(100, 200, 24, 12) });
+        world.add_enemy_tag(enemy, EnemyTag);
+
+        // Simulate a patrol step moving the enemy away from its spawn point.
+        world.positions.get_mut(&enemy).unwrap().0 = Vector2D::new(160.0, 190.0);
+
+        TestContext::default().run(&mut SystemSpatialUpdate, &mut world);
+
+        let rect = world.collisions.get(&enemy).unwrap().rect;
+        assert_eq!((rect.x(), rect.y()), (160, 190), "Collision rect should follow the enemy's position");
+        assert_eq!((rect.width(), rect.height()), (24, 12), "Collision size must be preserved");
+    }
+
+    #[test]
+    fn test_all_collision_rects_match_positions() {
+        let mut world = World::new();
+        let mut entities = Vec::new();
+        for i in 0..5 {
+            let entity = world.create_entity();
+            world.add_position(entity, Position(Vector2D::new(0.0, 0.0)));
+            world.add_collision(entity, Collision { rect: sdl3::rect::Rect::new(0, 0, 16, 16) });
+            // Move each entity somewhere else after spawning.
+            world.positions.get_mut(&entity).unwrap().0 = Vector2D::new(32.0 * i as f32, 10.0 + i as f32);
+            entities.push(entity);
+        }
+
+        TestContext::default().run(&mut SystemSpatialUpdate, &mut world);
+
+        for entity in entities {
+            let pos = world.positions.get(&entity).unwrap().0;
+            let rect = world.collisions.get(&entity).unwrap().rect;
+            assert_eq!((rect.x(), rect.y()), (pos.x as i32, pos.y as i32), "Entity {} rect is out of sync", entity);
+        }
+    }
+}
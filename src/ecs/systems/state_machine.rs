@@ -14,20 +14,22 @@ impl System<SystemContext<'_>> for SystemStateMachine {
     ///
     /// ⚠️ **Hotpath**: Called 120x per second.
     fn update(&mut self, world: &mut crate::ecs::world::World, context: &mut SystemContext<'_>) {
+        // 0. Tick down attack cooldowns so states can tell whether a new swing may start.
+        for cooldown in world.attack_cooldowns.values_mut() { cooldown.timer -= context.delta_time; }
+        world.attack_cooldowns.retain(|_, c| c.timer > 0.0);
+
         let entities: Vec<_> = world.state_components.keys().copied().collect();
 
         for entity in entities {
+            // Dormant entities keep their current state untouched until they wake.
+            if world.is_dormant(entity) { continue; }
+
             // 1. Temporarily extract the state machine to avoid mutable borrow conflicts.
             if let Some(mut state_comp) = world.state_components.remove(&entity) {
-                // 2. Execute the `update()` logic of the current state.
-                let next_state = state_comp.state_machine.update(entity, world, context);
-                
-                // 3. If a state transition occurred, handle exit/enter logic.
-                if next_state.is_some() {
-                    state_comp.state_machine.change_state(next_state, entity, world, context);
-                }
+                // 2. Execute the current state's logic; the machine handles any exit/enter transition itself.
+                state_comp.state_machine.update_with_context(world, context, entity);
 
-                // 4. Return the state machine component to the world.
+                // 3. Return the state machine component to the world.
                 world.state_components.insert(entity, state_comp);
             }
         }
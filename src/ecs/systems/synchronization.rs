@@ -8,9 +8,11 @@
 use crate::ecs::event::{
     EventRespawnStarted, EventStartTransition, TransitionType, 
     EventGameOver, EventCollision, EventCoinCollected, 
-    EventPlayerEnemyStomped, EventPlayerDamaged
+    EventPlayerEnemyStomped, EventPlayerDamaged, EventCheckpointReached,
+    EventProjectileHit, EventHealthCollected, EventEnemyHit
 };
 use crate::ecs::systems::{System, SystemContext};
+use crate::ecs::component::Direction;
 
 /// A system that coordinates multi-domain interactions and visual sequences.
 pub struct SystemSynchronization;
@@ -23,7 +25,11 @@ impl System<SystemContext<'_>> for SystemSynchronization {
     /// # Side Effects
     /// * Publishes [EventStartTransition] during respawn.
     /// * Publishes [EventCoinCollected] on collision with coins.
+    /// * Publishes [EventHealthCollected] on collision with health pickups.
+    /// * Publishes [EventCheckpointReached] on first contact with a checkpoint.
     /// * Publishes [EventPlayerEnemyStomped] or [EventPlayerDamaged] on collision with enemies.
+    /// * Publishes [EventProjectileHit] (and [EventPlayerDamaged] for hostile shots) on projectile contact.
+    /// * Publishes [EventEnemyHit] when a melee hitbox overlaps an enemy.
     fn update(&mut self, world: &mut crate::ecs::world::World, context: &mut SystemContext<'_>) {
         
         // --- 1. Rule: Orchestrate Respawn Sequence ---
@@ -73,14 +79,36 @@ impl System<SystemContext<'_>> for SystemSynchronization {
         }
 
         // --- 4. Rule: Keep UI in sync with Game Stats ---
-        // Ensure that the visual state of the HUD (ui_state) matches 
-        // the authoritative gameplay statistics (stats).
-        world.ui_state.display_lives = world.stats.lives;
-        world.ui_state.display_coin_count = world.stats.gold_coin_count;
+        Self::sync_ui_state(world);
+        world.ui_state.level_name_age += context.delta_time;
     }
 }
 
 impl SystemSynchronization {
+    /// Ensures that the visual state of the HUD (ui_state) matches the
+    /// authoritative gameplay statistics (stats) and the primary player's health.
+    pub fn sync_ui_state(world: &mut crate::ecs::world::World) {
+        world.ui_state.display_lives = world.stats.lives;
+        world.ui_state.display_coin_count = world.stats.gold_coin_count;
+        world.ui_state.display_score = world.stats.score;
+        let primary = world.player_tags.keys().copied().min_by_key(|e| world.player_slots.get(e).map_or(0, |s| s.0));
+        let health = primary.and_then(|p| world.healths.get(&p));
+        world.ui_state.display_health = health.map_or(0, |h| h.current);
+        world.ui_state.display_max_health = health.map_or(0, |h| h.max);
+    }
+
+    /// Pushes the player away from the enemy's center.
+    ///
+    /// Uses `Position` rather than `Collision.rect`: rects are only re-synced in
+    /// SpatialUpdate, so after a patrol turn-around they can sit on the wrong side.
+    fn knockback_direction(world: &crate::ecs::world::World, player: crate::ecs::world::Entity, enemy: crate::ecs::world::Entity) -> Direction {
+        let center_x = |entity| world.positions.get(&entity).map(|p| p.0.x + world.collisions.get(&entity).map_or(0.0, |c| c.rect.width() as f32 / 2.0));
+        match (center_x(player), center_x(enemy)) {
+            (Some(p), Some(e)) if p < e => Direction::Left,
+            _ => Direction::Right,
+        }
+    }
+
     /// Interprets a single collision fact into a semantic gameplay event.
     fn resolve_collision(&self, world: &mut crate::ecs::world::World, context: &SystemContext<'_>, event: EventCollision) {
         let (e1, e2) = (event.entity_a, event.entity_b);
@@ -93,12 +121,76 @@ impl SystemSynchronization {
         let en1 = world.enemy_tags.contains_key(&e1);
         let en2 = world.enemy_tags.contains_key(&e2);
 
+        // Rule: A projectile hits the opposing side, never its owner or another projectile.
+        let pr1 = world.projectiles.get(&e1).copied();
+        let pr2 = world.projectiles.get(&e2).copied();
+        if let Some((projectile, shot, target)) = match (pr1, pr2) {
+            (Some(shot), None) => Some((e1, shot, e2)),
+            (None, Some(shot)) => Some((e2, shot, e1)),
+            _ => None,
+        } {
+            if shot.owner == Some(target) || world.dead_tags.contains_key(&projectile) { return; }
+            let target_is_player = world.player_tags.contains_key(&target);
+            let hits = if shot.from_player { world.enemy_tags.contains_key(&target) } else { target_is_player };
+            if !hits { return; }
+
+            world.event_bus.publish(EventProjectileHit { projectile, target });
+            if target_is_player
+                && !world.invincibilities.contains_key(&target)
+                && let Some(target_pos) = world.positions.get(&target) {
+                    let knockback_force = context.game_config.gameplay.damage_knockback_force;
+                    let shot_vx = world.velocities.get(&projectile).map_or(0.0, |v| v.0.x);
+                    let direction = if shot_vx < 0.0 { Direction::Left } else { Direction::Right };
+                    world.event_bus.publish(EventPlayerDamaged {
+                        player: target,
+                        knockback_x: if direction == Direction::Left { -knockback_force } else { knockback_force },
+                        direction,
+                        position: target_pos.0,
+                        amount: shot.damage,
+                    });
+                }
+            return;
+        }
+
+        // Rule: A melee hitbox strikes enemies, never its owner.
+        let m1 = world.melee_hitboxes.get(&e1).copied();
+        let m2 = world.melee_hitboxes.get(&e2).copied();
+        if let Some((hitbox, swing, target)) = match (m1, m2) {
+            (Some(swing), None) => Some((e1, swing, e2)),
+            (None, Some(swing)) => Some((e2, swing, e1)),
+            _ => None,
+        } {
+            if swing.owner != target && world.enemy_tags.contains_key(&target) && !world.dead_tags.contains_key(&target) {
+                world.event_bus.publish(EventEnemyHit { attacker: swing.owner, hitbox, enemy: target });
+            }
+            return;
+        }
+
         // Rule: If a Player overlaps with a Gold Coin -> Publish a Collection fact.
         if (p1 && c2) || (p2 && c1) {
             let coin = if c1 { e1 } else { e2 };
             world.event_bus.publish(EventCoinCollected { coin });
         }
 
+        // Rule: If a Player overlaps with a Health Pickup -> Publish a Collection fact.
+        let h1 = world.health_pickups.get(&e1).copied();
+        let h2 = world.health_pickups.get(&e2).copied();
+        if let Some((player, pickup, health)) = match (h1, h2) {
+            (_, Some(health)) if p1 => Some((e1, e2, health)),
+            (Some(health), _) if p2 => Some((e2, e1, health)),
+            _ => None,
+        } {
+            world.event_bus.publish(EventHealthCollected { player, pickup, amount: health.amount });
+        }
+
+        // Rule: If a Player overlaps with an inactive Checkpoint -> Publish an Activation fact.
+        let cp1 = world.checkpoints.get(&e1).is_some_and(|c| !c.activated);
+        let cp2 = world.checkpoints.get(&e2).is_some_and(|c| !c.activated);
+        if (p1 && cp2) || (p2 && cp1) {
+            let (player, checkpoint) = if cp1 { (e2, e1) } else { (e1, e2) };
+            world.event_bus.publish(EventCheckpointReached { player, checkpoint });
+        }
+
         // Rule: If a Player overlaps with an Enemy -> Determine Stomp vs. Injury.
         if (p1 && en2) || (p2 && en1) {
             let player = if p1 { e1 } else { e2 };
@@ -119,18 +211,15 @@ impl SystemSynchronization {
                     } else {
                         // Otherwise, it is a damaging horizontal contact -> Injury fact.
                         let knockback_force = context.game_config.gameplay.damage_knockback_force;
-                        
-                        let enemy_rect = world.collisions.get(&enemy).map(|c| c.rect);
-                        let knockback_x = if let Some(er) = enemy_rect {
-                            if player_pos.0.x < er.x() as f32 { -knockback_force } else { knockback_force }
-                        } else {
-                            knockback_force
-                        };
+                        let direction = Self::knockback_direction(world, player, enemy);
+                        let knockback_x = if direction == Direction::Left { -knockback_force } else { knockback_force };
 
                         world.event_bus.publish(EventPlayerDamaged { 
                             player, 
                             knockback_x, 
-                            position: player_pos.0 
+                            direction,
+                            position: player_pos.0,
+                            amount: 1,
                         });
                     }
                 }
@@ -138,3 +227,91 @@ impl SystemSynchronization {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::world::World;
+    use crate::ecs::component::{Collision, EnemyTag, Health, PlayerTag, Position, Velocity};
+    use crate::ecs::systems::game_resolution::SystemGameResolution;
+    use crate::ecs::systems::test_context::TestContext;
+    use crate::math::Vector2D;
+    use sdl3::rect::Rect;
+
+    fn run<S: for<'a> System<SystemContext<'a>>>(system: &mut S, world: &mut World) {
+        let mut ctx = TestContext::default();
+        ctx.delta_time = 0.1;
+        ctx.run(system, world);
+    }
+
+    #[test]
+    fn test_enemy_on_the_left_knocks_player_right_despite_stale_rect() {
+        let mut world = World::new();
+        let player = world.create_entity();
+        world.add_player_tag(player, PlayerTag);
+        world.add_position(player, Position(Vector2D::new(100.0, 50.0)));
+        world.add_velocity(player, Velocity(Vector2D::default()));
+        world.add_collision(player, Collision { rect: Rect::new(100, 50, 16, 32) });
+        world.add_health(player, Health { current: 3, max: 3 });
+
+        // The enemy has moved to the player's left, but its rect still holds last tick's spot on the right.
+        let enemy = world.create_entity();
+        world.add_enemy_tag(enemy, EnemyTag);
+        world.add_position(enemy, Position(Vector2D::new(90.0, 50.0)));
+        world.add_collision(enemy, Collision { rect: Rect::new(110, 50, 16, 32) });
+
+        world.event_bus.publish(EventCollision { entity_a: player, entity_b: enemy, intersection: Rect::new(100, 50, 6, 32) });
+        run(&mut SystemSynchronization, &mut world);
+
+        let damage = *world.event_bus.read::<EventPlayerDamaged>().next().expect("Side contact damages the player");
+        assert_eq!(damage.direction, Direction::Right);
+        assert!(damage.knockback_x > 0.0);
+
+        run(&mut SystemGameResolution, &mut world);
+        assert!(world.velocities[&player].0.x > 0.0, "Player is pushed away from the enemy");
+        assert!(world.knockbacks.contains_key(&player), "Horizontal control is locked while the knockback plays out");
+    }
+
+    #[test]
+    fn test_single_enemy_contact_costs_one_heart_and_one_explosion_in_any_order() {
+        use crate::ecs::systems::interaction::SystemInteraction;
+        use crate::ecs::systems::spatial_update::SystemSpatialUpdate;
+
+        enum Step { Spatial, Interaction, Synchronization, Resolution }
+        // The intended schedule, detection wired in twice, and the whole pipeline run twice in one tick.
+        let orders = [
+            vec![Step::Spatial, Step::Interaction, Step::Synchronization, Step::Resolution],
+            vec![Step::Spatial, Step::Interaction, Step::Interaction, Step::Synchronization, Step::Resolution],
+            vec![Step::Spatial, Step::Interaction, Step::Synchronization, Step::Resolution, Step::Interaction, Step::Synchronization, Step::Resolution],
+            vec![Step::Spatial, Step::Interaction, Step::Resolution, Step::Synchronization, Step::Resolution, Step::Synchronization, Step::Resolution],
+        ];
+
+        for (index, order) in orders.iter().enumerate() {
+            let mut world = World::new();
+            let player = world.create_entity();
+            world.add_player_tag(player, PlayerTag);
+            world.add_position(player, Position(Vector2D::new(100.0, 50.0)));
+            world.add_velocity(player, Velocity(Vector2D::default()));
+            world.add_collision(player, Collision { rect: Rect::new(100, 50, 16, 32) });
+            world.add_health(player, Health { current: 3, max: 3 });
+
+            let enemy = world.create_entity();
+            world.add_enemy_tag(enemy, EnemyTag);
+            world.add_position(enemy, Position(Vector2D::new(110.0, 50.0)));
+            world.add_collision(enemy, Collision { rect: Rect::new(110, 50, 16, 32) });
+
+            for step in order {
+                match step {
+                    Step::Spatial => run(&mut SystemSpatialUpdate, &mut world),
+                    Step::Interaction => run(&mut SystemInteraction, &mut world),
+                    Step::Synchronization => run(&mut SystemSynchronization, &mut world),
+                    Step::Resolution => run(&mut SystemGameResolution, &mut world),
+                }
+            }
+
+            assert_eq!(world.healths[&player].current, 2, "Order {index}: one contact costs exactly one heart");
+            let effects = world.positions.keys().filter(|&&e| e != player && e != enemy).count();
+            assert_eq!(effects, 1, "Order {index}: one contact spawns exactly one explosion");
+        }
+    }
+}
@@ -0,0 +1,72 @@
+//! # Concept: System Test Context
+//!
+//! Owns everything a [SystemContext] borrows, so a system's unit tests build one
+//! with [TestContext::default] instead of wiring a camera, level, channel and
+//! benchmarker by hand. Configs come from `config.toml` and `assets/game_config.toml`.
+
+use super::{System, SystemContext};
+use crate::audio::AudioEvent;
+use crate::benchmarker::Benchmarker;
+use crate::camera::Camera;
+use crate::config::{load_config, load_game_config, Config, GameConfig};
+use crate::ecs::world::World;
+use crate::input::InputState;
+use crate::level::Level;
+use std::sync::mpsc::{channel, Receiver, Sender};
+
+pub(crate) struct TestContext {
+    pub config: Config,
+    pub game_config: GameConfig,
+    pub level: Level,
+    pub camera: Camera,
+    pub input_state: InputState,
+    pub next_level: Option<String>,
+    /// Seconds passed to the system per update; one fixed tick (1/120 s) by default.
+    pub delta_time: f32,
+    benchmarker: Benchmarker,
+    audio_sender: Sender<AudioEvent>,
+    _audio_receiver: Receiver<AudioEvent>,
+}
+
+impl Default for TestContext {
+    fn default() -> Self {
+        let (audio_sender, audio_receiver) = channel();
+        Self {
+            config: load_config().unwrap(),
+            game_config: load_game_config("assets/game_config.toml").unwrap(),
+            level: Level::default(),
+            camera: Camera::new(0.0, 0.0, 0.1, 480.0, 270.0, 1000.0, 1000.0, 0.1, 0.1, 0.1, 0.1, 0.1, 0.1, 1000.0, 100.0, 0.1),
+            input_state: InputState::default(),
+            next_level: None,
+            delta_time: 1.0 / 120.0,
+            benchmarker: Benchmarker::new(),
+            audio_sender,
+            _audio_receiver: audio_receiver,
+        }
+    }
+}
+
+impl TestContext {
+    /// Borrows the owned resources as the context systems update with.
+    pub fn context(&mut self) -> SystemContext<'_> {
+        SystemContext {
+            config: &self.config,
+            game_config: &self.game_config,
+            delta_time: self.delta_time,
+            camera: &mut self.camera,
+            audio_sender: &self.audio_sender,
+            is_paused: false,
+            is_attract_mode: false,
+            benchmarker: &mut self.benchmarker,
+            level: &self.level,
+            input_state: &self.input_state,
+            next_level: &mut self.next_level,
+            current_soundtrack: None,
+        }
+    }
+
+    /// Runs one update of `system` on `world`.
+    pub fn run<S: for<'a> System<SystemContext<'a>>>(&mut self, system: &mut S, world: &mut World) {
+        system.update(world, &mut self.context());
+    }
+}
@@ -5,7 +5,9 @@
 //! and enforces physical boundaries by adjusting positions.
 
 use crate::ecs::component::{Grounded, WallHit};
+use crate::ecs::event::EventPlayerLanded;
 use crate::ecs::systems::{System, SystemContext};
+use crate::math::Vector2D;
 use crate::physics;
 
 /// A system that resolves entity positions against solid tiles and identifies surface contact.
@@ -16,27 +18,36 @@ impl System<SystemContext<'_>> for SystemTileCollision {
     ///
     /// ⚠️ **Hotpath**: Called 120x per second. Contains tight physics loops.
     fn update(&mut self, world: &mut crate::ecs::world::World, context: &mut SystemContext) {
-        // 1. Reset state tags from the previous frame.
-        world.grounded_tags.clear();
+        // 1. Reset state tags from the previous frame, remembering who was already standing.
+        let was_grounded = std::mem::take(&mut world.grounded_tags);
         world.wall_hits.clear();
         
         let mut entities_to_ground = Vec::new();
         let mut entities_hit_wall = Vec::new();
+        let mut landings = Vec::new();
 
         for (entity, pos) in &mut world.positions {
-            // 2. Only process entities with movement (Velocity) and physical bounds (Collision).
-            if let (Some(vel), Some(collision)) = (world.velocities.get_mut(entity), world.collisions.get_mut(entity)) {
-                // 3. Sync collision rect with current position before checking tiles.
-                collision.rect.set_x(pos.0.x as i32);
-                collision.rect.set_y(pos.0.y as i32);
+            // Dormant entities are frozen where they were, including whether they stood on the ground.
+            if world.dormant_tags.contains_key(entity) {
+                if was_grounded.contains_key(entity) { entities_to_ground.push(*entity); }
+                continue;
+            }
 
-                // 4. Resolve Vertical Collisions (Gravity/Jumping vs Floors/Ceilings).
+            // 2. Only process entities with movement (Velocity) and physical bounds (Collision).
+            // Note: `collision.rect` was already synchronized to `pos` by SystemSpatialUpdate this tick.
+            if let (Some(vel), Some(collision)) = (world.velocities.get_mut(entity), world.collisions.get(entity)) {
+                // 3. Resolve Vertical Collisions (Gravity/Jumping vs Floors/Ceilings).
+                let fall_speed = vel.0.y;
                 let grounded = physics::resolve_vertical_collisions(pos, vel, collision.rect, context);
+                if grounded && !was_grounded.contains_key(entity) && world.player_tags.contains_key(entity) {
+                    let feet = Vector2D::new(pos.0.x + collision.rect.width() as f32 / 2.0, pos.0.y + collision.rect.height() as f32);
+                    landings.push(EventPlayerLanded { player: *entity, impact_speed: fall_speed, position: feet });
+                }
                 
-                // 5. Resolve Horizontal Collisions (Walking vs Walls).
+                // 4. Resolve Horizontal Collisions (Walking vs Walls).
                 let wall_hit = physics::resolve_horizontal_collisions(pos, vel, collision.rect, context);
 
-                // 6. Buffer the results to avoid simultaneous mutable borrow of the World.
+                // 5. Buffer the results to avoid simultaneous mutable borrow of the World.
                 if grounded {
                     entities_to_ground.push(*entity);
                 }
@@ -46,12 +57,15 @@ impl System<SystemContext<'_>> for SystemTileCollision {
             }
         }
 
-        // 7. Apply the derived state tags back to the entities.
+        // 6. Apply the derived state tags back to the entities.
         for entity in entities_to_ground {
             world.add_grounded(entity, Grounded);
         }
         for (entity, normal) in entities_hit_wall {
             world.add_wall_hit(entity, WallHit { normal_x: normal });
         }
+        for landing in landings {
+            world.event_bus.publish(landing);
+        }
     }
 }
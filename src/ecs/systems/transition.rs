@@ -39,7 +39,7 @@ impl SystemTransition {
     /// ⚠️ **Hotpath**: Called 120x per second.
     ///
     /// # Side Effects
-    /// * Publishes [crate::ecs::event::EventTransitionComplete] when duration is reached.
+    /// * Publishes [crate::ecs::event::EventTransitionComplete] (deferred, readable next frame) when duration is reached.
     pub fn update(&mut self, world: &mut crate::ecs::world::World, context: &mut SystemContext<'_>) {
         // 1. Monitor the event bus for new transition requests.
         for event in world.event_bus.read::<EventStartTransition>() {
@@ -48,7 +48,6 @@ impl SystemTransition {
             self.duration = event.duration;
             self.timer = 0.0;
             self.center = event.center;
-            world.transition_finished = false;
         }
 
         // 2. Advance progress if a transition is currently playing.
@@ -56,10 +55,9 @@ impl SystemTransition {
             self.timer += context.delta_time;
             
             if self.timer >= self.duration {
-                // 3. Handle transition completion: publish fact and update world flag.
+                // 3. Handle transition completion. This system runs last, so the fact is deferred to reach everyone next frame.
                 println!("[SystemTransition] Transition Finished!");
-                world.event_bus.publish(EventTransitionComplete { transition_type });
-                world.transition_finished = true;
+                world.event_bus.publish_deferred(EventTransitionComplete { transition_type });
                 
                 // 4. Enter 'Hold' state if the shutter is closed (IrisOut) to keep the screen black.
                 if transition_type == TransitionType::IrisOut {
@@ -0,0 +1,139 @@
+//! # Concept: Trigger Volumes
+//!
+//! This module watches invisible rectangular zones and publishes a named 
+//! fact when a player enters one. Cutscenes, hazards, and music changes 
+//! subscribe to [EventTriggerActivated] instead of polling for overlaps.
+//!
+//! Zones are placed in Tiled as objects of type "Trigger" and scripted with
+//! custom properties: `event` (the published name), `once`, `delay` (in
+//! **seconds**), `shake` (camera trauma), and `spawn` + `spawn_at` (a prefab
+//! created at the object named by `spawn_at`). All trigger state lives on the
+//! component, so reloading a level re-arms every trigger.
+
+use crate::ecs::systems::{System, SystemContext};
+use crate::ecs::event::EventTriggerActivated;
+use crate::ecs::world::Entity;
+
+/// A system that detects players entering [crate::ecs::component::Trigger] volumes.
+pub struct SystemTrigger;
+
+impl System<SystemContext<'_>> for SystemTrigger {
+    /// Publishes an activation fact for every trigger a player entered this tick,
+    /// or whose `delay` elapsed this tick.
+    ///
+    /// ⚠️ **Hotpath**: Called 120x per second.
+    ///
+    /// # Side Effects
+    /// * Marks `once` triggers as consumed when they are entered.
+    /// * Counts down the pending activation of delayed triggers.
+    /// * Records which players are inside each trigger.
+    fn update(&mut self, world: &mut crate::ecs::world::World, context: &mut SystemContext<'_>) {
+        // 1. Gather the bounds of every player.
+        let players: Vec<(Entity, sdl3::rect::Rect)> = world.player_tags.keys()
+            .filter_map(|e| world.collisions.get(e).map(|c| (*e, c.rect)))
+            .collect();
+
+        // 2. Test each trigger in a stable order so simultaneous activations are deterministic.
+        let mut triggers: Vec<Entity> = world.triggers.keys().copied().collect();
+        triggers.sort_unstable();
+
+        for trigger in triggers {
+            let (Some(trigger_comp), Some(collision)) = (world.triggers.get_mut(&trigger), world.collisions.get(&trigger)) else { continue };
+
+            // 3. Release delayed activations whose timer ran out.
+            if let Some((remaining, player)) = trigger_comp.pending.as_mut() {
+                *remaining -= context.delta_time;
+                if *remaining <= 0.0 {
+                    let player = *player;
+                    trigger_comp.pending = None;
+                    world.event_bus.publish(EventTriggerActivated { name: trigger_comp.event_name.clone(), trigger, player });
+                }
+            }
+            if trigger_comp.consumed { continue; }
+
+            let previous = std::mem::take(&mut trigger_comp.occupants);
+            for &(player, player_rect) in &players {
+                if !player_rect.has_intersection(collision.rect) { continue; }
+                trigger_comp.occupants.push(player);
+
+                // 4. Fire only on entry; standing inside a volume does not re-trigger it.
+                if previous.contains(&player) { continue; }
+                if trigger_comp.delay > 0.0 {
+                    // A repeatable trigger keeps its first pending activation instead of restarting it.
+                    if trigger_comp.pending.is_none() { trigger_comp.pending = Some((trigger_comp.delay, player)); }
+                } else {
+                    world.event_bus.publish(EventTriggerActivated { name: trigger_comp.event_name.clone(), trigger, player });
+                }
+
+                // 5. One-shot triggers stay in the world but ignore players until the level reloads.
+                if trigger_comp.once {
+                    trigger_comp.consumed = true;
+                    trigger_comp.occupants.clear();
+                    break;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::world::World;
+    use crate::ecs::component::{Collision, PlayerTag, Trigger};
+    use crate::ecs::systems::test_context::TestContext;
+
+    fn add_box(world: &mut World, x: i32, y: i32, w: u32, h: u32) -> Entity {
+        let entity = world.create_entity();
+        world.add_collision(entity, Collision { rect: sdl3::rect::Rect::new(x, y, w, h) });
+        entity
+    }
+
+    fn run(system: &mut SystemTrigger, world: &mut World) -> Vec<String> {
+        world.clear_events();
+        TestContext::default().run(system, world);
+        let mut names: Vec<String> = world.event_bus.read::<EventTriggerActivated>().map(|e| e.name.clone()).collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_overlapping_triggers_fire_once_on_entry() {
+        let mut world = World::new();
+        let player = add_box(&mut world, 0, 0, 16, 16);
+        world.add_player_tag(player, PlayerTag);
+        let music = add_box(&mut world, 0, 0, 64, 64);
+        world.add_trigger(music, Trigger { event_name: "music".to_string(), once: false, ..Default::default() });
+        let cutscene = add_box(&mut world, 8, 8, 64, 64);
+        world.add_trigger(cutscene, Trigger { event_name: "cutscene".to_string(), once: true, ..Default::default() });
+
+        let mut system = SystemTrigger;
+        assert_eq!(run(&mut system, &mut world), vec!["cutscene", "music"], "Each overlapped trigger fires exactly once");
+        assert_eq!(run(&mut system, &mut world), Vec::<String>::new(), "Remaining inside must not re-fire");
+        assert!(world.triggers[&cutscene].consumed, "One-shot trigger is disabled");
+
+        // Leave and re-enter: only the repeatable trigger fires again.
+        world.collisions.get_mut(&player).unwrap().rect.set_x(500);
+        assert!(run(&mut system, &mut world).is_empty());
+        world.collisions.get_mut(&player).unwrap().rect.set_x(0);
+        assert_eq!(run(&mut system, &mut world), vec!["music"]);
+    }
+
+    #[test]
+    fn test_delayed_trigger_fires_after_its_delay() {
+        let mut world = World::new();
+        let player = add_box(&mut world, 0, 0, 16, 16);
+        world.add_player_tag(player, PlayerTag);
+        let gate = add_box(&mut world, 0, 0, 64, 64);
+        world.add_trigger(gate, Trigger { event_name: "gate".to_string(), once: true, delay: 0.04, ..Default::default() });
+
+        // 0.04 s elapses on the fifth tick after entry; leaving the zone does not cancel it.
+        let mut system = SystemTrigger;
+        for _ in 0..5 {
+            assert!(run(&mut system, &mut world).is_empty());
+            world.collisions.get_mut(&player).unwrap().rect.set_x(500);
+        }
+        assert_eq!(run(&mut system, &mut world), vec!["gate"]);
+        assert!(world.triggers[&gate].pending.is_none());
+    }
+}
@@ -0,0 +1,111 @@
+//! # Concept: Water Volumes
+//!
+//! This module tracks which entities are submerged. Water comes from tiles
+//! flagged `water=true` in the TSX or from `Water` rectangle objects; an
+//! entity counts as submerged while the center of its collision box is inside.
+//! Physics and movement read the resulting [crate::ecs::component::Submerged] tag.
+
+use crate::ecs::systems::{System, SystemContext};
+use crate::ecs::component::Submerged;
+use crate::ecs::event::EventWaterSplash;
+use crate::math::Vector2D;
+
+/// A system that maintains the `Submerged` tag and publishes splashes on entry and exit.
+pub struct SystemWater;
+
+impl System<SystemContext<'_>> for SystemWater {
+    /// Updates submersion for every moving entity.
+    ///
+    /// ⚠️ **Hotpath**: Called 120x per second.
+    ///
+    /// # Side Effects
+    /// * Adds/removes [Submerged] tags.
+    /// * Publishes [EventWaterSplash] whenever an entity crosses the water surface.
+    fn update(&mut self, world: &mut crate::ecs::world::World, context: &mut SystemContext<'_>) {
+        let level = context.level;
+        if level.water_volumes.is_empty() && level.water.tiles.is_empty() && world.submerged_tags.is_empty() { return; }
+
+        let entities: Vec<_> = world.velocities.keys().copied().collect();
+        for entity in entities {
+            // Skip entities outside the active simulation range.
+            if world.is_dormant(entity) { continue; }
+            let Some(collision) = world.collisions.get(&entity) else { continue };
+
+            // 1. Sample the water at the center of the collision box.
+            let rect = collision.rect;
+            let center = Vector2D::new(rect.x() as f32 + rect.width() as f32 * 0.5, rect.y() as f32 + rect.height() as f32 * 0.5);
+            let in_water = level.is_water(center);
+            if in_water == world.is_submerged(entity) { continue; }
+
+            // 2. Toggle the tag and announce the crossing.
+            if in_water { world.add_submerged(entity, Submerged); } else { world.submerged_tags.remove(&entity); }
+            world.event_bus.publish(EventWaterSplash { entity, position: center, entered: in_water });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::world::World;
+    use crate::ecs::component::{Collision, Gravity, Position, Velocity};
+    use crate::ecs::systems::physics::SystemPhysics;
+    use crate::ecs::systems::test_context::TestContext;
+    use crate::level::{Level, Region};
+
+    fn run<S: for<'a> System<SystemContext<'a>>>(system: &mut S, world: &mut World, level: &Level) {
+        let mut ctx = TestContext::default();
+        (ctx.level, ctx.delta_time) = (level.clone(), 0.1);
+        ctx.run(system, world);
+    }
+
+    fn pool_level() -> Level {
+        Level { water_volumes: vec![Region { x: 0.0, y: 100.0, width: 200.0, height: 100.0 }], ..Default::default() }
+    }
+
+    fn spawn_body(world: &mut World, y: i32) -> crate::ecs::world::Entity {
+        let entity = world.create_entity();
+        world.add_position(entity, Position(Vector2D::new(10.0, y as f32)));
+        world.add_velocity(entity, Velocity(Vector2D::default()));
+        world.add_collision(entity, Collision { rect: sdl3::rect::Rect::new(10, y, 16, 16) });
+        world.add_gravity(entity, Gravity);
+        entity
+    }
+
+    #[test]
+    fn test_entering_and_leaving_water_splashes() {
+        let level = pool_level();
+        let mut world = World::new();
+        let entity = spawn_body(&mut world, 120);
+
+        run(&mut SystemWater, &mut world, &level);
+        assert!(world.is_submerged(entity));
+        assert!(world.event_bus.read::<EventWaterSplash>().any(|s| s.entity == entity && s.entered));
+
+        world.clear_events();
+        run(&mut SystemWater, &mut world, &level);
+        assert_eq!(world.event_bus.read::<EventWaterSplash>().count(), 0, "Staying submerged does not splash again");
+
+        world.collisions.get_mut(&entity).unwrap().rect.set_y(20);
+        run(&mut SystemWater, &mut world, &level);
+        assert!(!world.is_submerged(entity));
+        assert!(world.event_bus.read::<EventWaterSplash>().any(|s| s.entity == entity && !s.entered));
+    }
+
+    #[test]
+    fn test_submerged_bodies_sink_slowly() {
+        let level = pool_level();
+        let config = crate::config::load_config().unwrap();
+        let mut world = World::new();
+        let dry = spawn_body(&mut world, 20);
+        let wet = spawn_body(&mut world, 120);
+
+        run(&mut SystemWater, &mut world, &level);
+        run(&mut SystemPhysics, &mut world, &level);
+
+        let dry_vy = world.velocities[&dry].0.y;
+        let wet_vy = world.velocities[&wet].0.y;
+        assert!(wet_vy > 0.0 && wet_vy < dry_vy, "Buoyancy reduces gravity");
+        assert!(wet_vy <= config.physics.water_max_speed, "Sinking is capped by water_max_speed");
+    }
+}
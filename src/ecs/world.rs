@@ -4,70 +4,153 @@
 //! It owns the storage for all components (SoA), manages the entity lifecycle,
 //! and provides the Event Bus for decoupled communication between systems.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
 use crate::ecs::component::*;
 use crate::ecs::event::EventBus;
-use crate::ecs::resources::{GameState, SpatialGrid, GameStats, UIState, FrameDebugInfo};
-
-/// A unique handle for an object in the game world.
-pub type Entity = usize;
-
-/// The central repository for all game data and event queues.
-#[derive(Default)]
-pub struct World {
-    /// The current high-level state of the game application.
-    pub game_state: GameState,
-    /// The uniform grid used for optimized spatial queries.
-    pub spatial_grid: SpatialGrid,
-    /// Persistent gameplay statistics like lives and coins.
-    pub stats: GameStats,
-    /// The visual state of the HUD, decoupled for juice effects.
-    pub ui_state: UIState,
-    /// Debug info updated each frame.
-    pub frame_debug_info: FrameDebugInfo,
-    /// The source of unique IDs for new entities.
-    next_entity_id: usize,
-    /// The type-based event bus for cross-system facts.
-    pub event_bus: EventBus,
-    
-    // --- Component Storage (Structure of Arrays) ---
-    pub positions: HashMap<Entity, Position>,
-    pub previous_positions: HashMap<Entity, Position>,
-    pub velocities: HashMap<Entity, Velocity>,
-    pub accelerations: HashMap<Entity, Acceleration>,
-    pub renderables: HashMap<Entity, Renderable>,
-    pub animations: HashMap<Entity, Animation>,
-    pub player_tags: HashMap<Entity, PlayerTag>,
-    pub gold_coins: HashMap<Entity, GoldCoin>,
-    pub enemy_tags: HashMap<Entity, EnemyTag>,
-    pub dead_tags: HashMap<Entity, DeadTag>,
-    pub patrols: HashMap<Entity, Patrol>,
-    pub gravity_tags: HashMap<Entity, Gravity>,
-    pub collisions: HashMap<Entity, Collision>,
-    pub grounded_tags: HashMap<Entity, Grounded>,
-    pub wall_hits: HashMap<Entity, WallHit>,
-    pub state_components: HashMap<Entity, StateComponent>,
-    pub respawn_tags: HashMap<Entity, RespawnTag>,
-    pub respawn_timers: HashMap<Entity, RespawnTimer>,
-    pub healths: HashMap<Entity, Health>,
-    pub invincibilities: HashMap<Entity, Invincibility>,
-    pub lifetimes: HashMap<Entity, Lifetime>,
-    pub directions: HashMap<Entity, Directional>,
-    pub goals: HashMap<Entity, Goal>,
-    pub next_levels: HashMap<Entity, NextLevel>,
-    pub music_state: MusicState,
-    pub transition_finished: bool,
-    pub movement_intentions: HashMap<Entity, MovementIntention>,
-    pub dormant_tags: HashMap<Entity, DormantTag>,
+use crate::ecs::resources::{AnimationClock, GameRng, GameState, SpatialGrid, GameStats, UIState, FrameDebugInfo, LevelExit, Particle};
+use crate::score::LevelTally;
+
+/// A generational handle for an object in the game world.
+///
+/// The `index` slot is recycled after [World::despawn], but every reuse bumps its
+/// `generation`. Component storages are keyed by the whole handle, so a stale handle
+/// (e.g. one carried by an event raised before the despawn) matches nothing instead
+/// of the entity that took its slot over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Entity {
+    index: u32,
+    generation: u32,
+}
+
+impl Entity {
+    /// The recycled slot this handle points at.
+    pub fn index(self) -> u32 { self.index }
+
+    /// How many entities used this slot before this one.
+    pub fn generation(self) -> u32 { self.generation }
+}
+
+impl fmt::Display for Entity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}v{}", self.index, self.generation)
+    }
+}
+
+/// Declares [World] with one `HashMap<Entity, T>` storage per listed component and
+/// generates the storage sweeps behind [World::despawn] over exactly those storages, so
+/// registering a component here is all it takes for it to be removed together with its entity.
+/// Storages marked `#[per_life]` are also swept by [World::clear_life_state] when a player respawns.
+macro_rules! world_storages {
+    ($($(#[$scope:ident])? $storage:ident: $component:ty),* $(,)?) => {
+        /// The central repository for all game data and event queues.
+        #[derive(Default)]
+        pub struct World {
+            /// The current high-level state of the game application.
+            pub game_state: GameState,
+            /// The uniform grid used for optimized spatial queries.
+            pub spatial_grid: SpatialGrid,
+            /// Persistent gameplay statistics like lives and coins.
+            pub stats: GameStats,
+            /// The visual state of the HUD, decoupled for juice effects.
+            pub ui_state: UIState,
+            /// Debug info updated each frame.
+            pub frame_debug_info: FrameDebugInfo,
+            /// Set when a Goal exit is taken; tells the next level swap where to spawn and what to carry over.
+            pub level_exit: Option<LevelExit>,
+            /// Live visual particles (not entities); capped by `[particles] max_particles`.
+            pub particles: Vec<Particle>,
+            /// Time base for animations whose prefab asks for `phase = "Synced"`.
+            pub animation_clock: AnimationClock,
+            /// Seeded source of all gameplay randomness.
+            pub rng: GameRng,
+            /// The end-of-level summary while [GameState::LevelComplete] is shown.
+            pub level_tally: Option<LevelTally>,
+            /// Current generation of every slot ever handed out, indexed by [Entity::index].
+            generations: Vec<u32>,
+            /// Slots released by [World::despawn], reused before new ones are allocated.
+            free_slots: Vec<u32>,
+            /// The type-based event bus for cross-system facts.
+            pub event_bus: EventBus,
+            pub music_state: MusicState,
+
+            // --- Component Storage (Structure of Arrays) ---
+            $(pub $storage: HashMap<Entity, $component>,)*
+        }
+
+        impl World {
+            fn remove_components(&mut self, entity: Entity) {
+                $(self.$storage.remove(&entity);)*
+            }
+
+            fn clear_components(&mut self) {
+                $(self.$storage.clear();)*
+            }
+
+            /// True if any component storage still holds `entity`.
+            pub fn has_components(&self, entity: Entity) -> bool {
+                false $(|| self.$storage.contains_key(&entity))*
+            }
+
+            /// Removes every `#[per_life]` component of `entity`: the state a respawn starts over without.
+            pub fn clear_life_state(&mut self, entity: Entity) {
+                $($(world_storages!(@$scope self.$storage, entity);)?)*
+            }
+        }
+    };
+    (@per_life $world:ident.$storage:ident, $entity:ident) => { $world.$storage.remove(&$entity); };
+}
+
+world_storages! {
+    positions: Position,
+    previous_positions: Position,
+    velocities: Velocity,
+    accelerations: Acceleration,
+    renderables: Renderable,
+    animations: Animation,
+    player_tags: PlayerTag,
+    gold_coins: GoldCoin,
+    health_pickups: HealthPickup,
+    melee_hitboxes: MeleeHitbox,
+    attack_cooldowns: AttackCooldown,
+    enemy_tags: EnemyTag,
+    dead_tags: DeadTag,
+    patrols: Patrol,
+    gravity_tags: Gravity,
+    collisions: Collision,
+    grounded_tags: Grounded,
+    submerged_tags: Submerged,
+    wall_hits: WallHit,
+    state_components: StateComponent,
+    respawn_tags: RespawnTag,
+    respawn_timers: RespawnTimer,
+    healths: Health,
+    #[per_life] invincibilities: Invincibility,
+    #[per_life] knockbacks: Knockback,
+    lifetimes: Lifetime,
+    directions: Directional,
+    goals: Goal,
+    next_levels: NextLevel,
+    exit_spawn_points: ExitSpawnPoint,
+    checkpoints: Checkpoint,
+    triggers: Trigger,
+    particle_emitters: ParticleEmitter,
+    projectiles: Projectile,
+    jump_assists: JumpAssist,
+    #[per_life] crouchings: Crouching,
+    stomp_combos: StompCombo,
+    player_slots: PlayerSlot,
+    bosses: Boss,
+    movement_intentions: MovementIntention,
+    dormant_tags: DormantTag,
 }
 
 impl World {
     /// Initializes a new, empty simulation world.
     pub fn new() -> Self {
         Self {
-            transition_finished: false,
             game_state: GameState::default(),
-            spatial_grid: SpatialGrid::new(64.0),
+            spatial_grid: SpatialGrid::default(),
             stats: GameStats::default(),
             ui_state: UIState::default(),
             frame_debug_info: FrameDebugInfo::default(),
@@ -80,11 +163,60 @@ impl World {
         self.event_bus.clear_events();
     }
 
-    /// Generates a new unique Entity ID.
+    /// Empties the world for a level swap: every entity is despawned and the level-local
+    /// resources are reset, including the RNG so a level always replays identically.
+    /// Game state, stats and the slot generations carry over, so handles from the old level
+    /// never match new entities.
+    pub fn clear_level(&mut self) {
+        self.despawn_all();
+        self.spatial_grid.clear();
+        self.ui_state = UIState::default();
+        self.frame_debug_info = FrameDebugInfo::default();
+        self.level_exit = None;
+        self.level_tally = None;
+        self.particles.clear();
+        self.animation_clock = AnimationClock::default();
+        self.rng = GameRng::default();
+        self.event_bus = EventBus::default();
+        self.music_state = MusicState::default();
+    }
+
+    /// Hands out a handle, reusing the most recently freed slot when there is one.
     pub fn create_entity(&mut self) -> Entity {
-        let entity_id = self.next_entity_id;
-        self.next_entity_id += 1;
-        entity_id
+        if let Some(index) = self.free_slots.pop() {
+            return Entity { index, generation: self.generations[index as usize] };
+        }
+        self.generations.push(0);
+        Entity { index: (self.generations.len() - 1) as u32, generation: 0 }
+    }
+
+    /// True while `entity` has not been despawned, i.e. its generation is still current.
+    pub fn is_alive(&self, entity: Entity) -> bool {
+        self.generations.get(entity.index as usize) == Some(&entity.generation)
+    }
+
+    /// Removes `entity` from every component storage and frees its slot for reuse.
+    ///
+    /// # Side Effects
+    /// * Bumps the slot's generation, so every outstanding copy of the handle goes stale.
+    /// * Despawning a stale handle is a no-op and never touches the slot's new occupant.
+    pub fn despawn(&mut self, entity: Entity) {
+        if !self.is_alive(entity) { return; }
+        self.remove_components(entity);
+        self.generations[entity.index as usize] += 1;
+        self.free_slots.push(entity.index);
+    }
+
+    /// Despawns every live entity, keeping the world's resources.
+    pub fn despawn_all(&mut self) {
+        self.clear_components();
+        // 1. Every slot that is not already free held a live entity.
+        let free: HashSet<u32> = self.free_slots.iter().copied().collect();
+        for (index, generation) in self.generations.iter_mut().enumerate() {
+            if free.contains(&(index as u32)) { continue; }
+            *generation += 1;
+            self.free_slots.push(index as u32);
+        }
     }
 
     // --- Component Storage Helpers ---
@@ -135,6 +267,14 @@ impl World {
         self.grounded_tags.contains_key(&entity)
     }
 
+    pub fn add_submerged(&mut self, entity: Entity, component: Submerged) {
+        self.submerged_tags.insert(entity, component);
+    }
+
+    pub fn is_submerged(&self, entity: Entity) -> bool {
+        self.submerged_tags.contains_key(&entity)
+    }
+
     pub fn add_wall_hit(&mut self, entity: Entity, component: WallHit) {
         self.wall_hits.insert(entity, component);
     }
@@ -167,6 +307,18 @@ impl World {
         self.gold_coins.insert(entity, component);
     }
 
+    pub fn add_health_pickup(&mut self, entity: Entity, component: HealthPickup) {
+        self.health_pickups.insert(entity, component);
+    }
+
+    pub fn add_melee_hitbox(&mut self, entity: Entity, component: MeleeHitbox) {
+        self.melee_hitboxes.insert(entity, component);
+    }
+
+    pub fn add_attack_cooldown(&mut self, entity: Entity, component: AttackCooldown) {
+        self.attack_cooldowns.insert(entity, component);
+    }
+
     pub fn add_health(&mut self, entity: Entity, component: Health) {
         self.healths.insert(entity, component);
     }
@@ -175,6 +327,10 @@ impl World {
         self.invincibilities.insert(entity, component);
     }
 
+    pub fn add_knockback(&mut self, entity: Entity, component: Knockback) {
+        self.knockbacks.insert(entity, component);
+    }
+
     pub fn add_lifetime(&mut self, entity: Entity, component: Lifetime) {
         self.lifetimes.insert(entity, component);
     }
@@ -191,6 +347,46 @@ impl World {
         self.next_levels.insert(entity, component);
     }
 
+    pub fn add_exit_spawn_point(&mut self, entity: Entity, component: ExitSpawnPoint) {
+        self.exit_spawn_points.insert(entity, component);
+    }
+
+    pub fn add_checkpoint(&mut self, entity: Entity, component: Checkpoint) {
+        self.checkpoints.insert(entity, component);
+    }
+
+    pub fn add_trigger(&mut self, entity: Entity, component: Trigger) {
+        self.triggers.insert(entity, component);
+    }
+
+    pub fn add_particle_emitter(&mut self, entity: Entity, component: ParticleEmitter) {
+        self.particle_emitters.insert(entity, component);
+    }
+
+    pub fn add_projectile(&mut self, entity: Entity, component: Projectile) {
+        self.projectiles.insert(entity, component);
+    }
+
+    pub fn add_jump_assist(&mut self, entity: Entity, component: JumpAssist) {
+        self.jump_assists.insert(entity, component);
+    }
+
+    pub fn add_crouching(&mut self, entity: Entity, component: Crouching) {
+        self.crouchings.insert(entity, component);
+    }
+
+    pub fn add_stomp_combo(&mut self, entity: Entity, component: StompCombo) {
+        self.stomp_combos.insert(entity, component);
+    }
+
+    pub fn add_player_slot(&mut self, entity: Entity, component: PlayerSlot) {
+        self.player_slots.insert(entity, component);
+    }
+
+    pub fn add_boss(&mut self, entity: Entity, component: Boss) {
+        self.bosses.insert(entity, component);
+    }
+
     pub fn add_dormant_tag(&mut self, entity: Entity, tag: DormantTag) {
         self.dormant_tags.insert(entity, tag);
     }
@@ -0,0 +1,172 @@
+//! # Concept: Boss Behavior (Charge & Stun)
+//!
+//! This module defines the state loop of a [Boss]: it winds up (Idle), charges
+//! at the nearest player (Charge) and, when the charge ends in a wall, reels
+//! (Stunned). Only a stunned boss can be hurt; the tuning of every state comes
+//! from `[bosses.<key>]` and tightens as the boss moves through its phases.
+//!
+//! [Boss]: crate::ecs::component::Boss
+
+use crate::state_machine::State;
+use crate::config::{BossTuning, GameConfig};
+use crate::ecs::world::{World, Entity};
+use crate::ecs::systems::SystemContext;
+use crate::ecs::event::EventScreenShake;
+
+/// Tint applied while the boss is stunned, signalling that it can be hit.
+const STUNNED_TINT: sdl3::pixels::Color = sdl3::pixels::Color::RGB(255, 220, 120);
+
+/// Trauma added when a charge ends in a wall.
+const CRASH_TRAUMA: f32 = 0.5;
+
+/// The tuning for the boss's current phase, or `None` if its config key is unknown.
+fn tuning(world: &World, game_config: &GameConfig, entity: Entity) -> Option<BossTuning> {
+    let boss = world.bosses.get(&entity)?;
+    game_config.bosses.get(&boss.config_key).map(|config| config.tuning(boss.phase))
+}
+
+/// Sets the horizontal velocity (in **pixels per second**) and tint of the boss.
+fn drive(world: &mut World, entity: Entity, velocity_x: f32, tint: sdl3::pixels::Color) {
+    if let Some(vel) = world.velocities.get_mut(&entity) { vel.0.x = velocity_x; }
+    if let Some(renderable) = world.renderables.get_mut(&entity) { renderable.tint = tint; }
+}
+
+/// True while the boss is reeling from a crash, the only time it can be damaged.
+pub fn is_vulnerable(world: &World, entity: Entity) -> bool {
+    world.state_components.get(&entity)
+        .and_then(|s| s.state_machine.current_state.as_ref())
+        .is_some_and(|state| state.get_name() == "BossStunnedState")
+}
+
+/// Takes `amount` health from the boss and advances its phase; returns true once it is defeated.
+///
+/// # Side Effects
+/// * Marks the hit so the boss recovers from its stun on the next state update.
+pub fn damage_boss(world: &mut World, game_config: &GameConfig, entity: Entity, amount: u32) -> bool {
+    let Some(health) = world.healths.get_mut(&entity) else { return false };
+    health.current = health.current.saturating_sub(amount);
+    let current = health.current;
+    if let Some(boss) = world.bosses.get_mut(&entity) {
+        boss.hit_while_stunned = true;
+        if let Some(config) = game_config.bosses.get(&boss.config_key) {
+            boss.phase = boss.phase.max(config.phase_for(current));
+        }
+    }
+    current == 0
+}
+
+/// Winding up: the boss stands still, then charges toward the nearest player.
+pub struct BossIdleState { timer: f32 }
+
+impl BossIdleState {
+    pub fn new() -> Self { Self { timer: 0.0 } }
+
+    /// Direction (-1 left, 1 right) from the boss to the horizontally closest player.
+    fn direction_to_player(world: &World, entity: Entity) -> f32 {
+        let Some(origin) = world.positions.get(&entity).map(|p| p.0.x) else { return 1.0 };
+        let target = world.player_tags.keys()
+            .filter_map(|player| world.positions.get(player).map(|p| p.0.x))
+            .min_by(|a, b| (a - origin).abs().total_cmp(&(b - origin).abs()));
+        match target {
+            Some(x) if x < origin => -1.0,
+            _ => 1.0,
+        }
+    }
+}
+
+impl Default for BossIdleState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl State for BossIdleState {
+    fn enter(&mut self) {}
+    fn exit(&mut self) {}
+
+    fn update_with_context(&mut self, world: &mut World, context: &mut SystemContext, entity: Entity) {
+        drive(world, entity, 0.0, sdl3::pixels::Color::WHITE);
+        self.timer += context.delta_time;
+    }
+
+    fn transition_with_context(&mut self, world: &mut World, context: &mut SystemContext, entity: Entity) -> Option<Box<dyn State>> {
+        let tuning = tuning(world, context.game_config, entity)?;
+        (self.timer >= tuning.idle_time).then(|| Box::new(BossChargeState::new(Self::direction_to_player(world, entity))) as Box<dyn State>)
+    }
+
+    fn get_name(&self) -> &str { "BossIdleState" }
+}
+
+/// Charging: the boss runs in a straight line until it hits a wall or runs out of steam.
+pub struct BossChargeState { timer: f32, direction: f32 }
+
+impl BossChargeState {
+    pub fn new(direction: f32) -> Self { Self { timer: 0.0, direction } }
+}
+
+impl State for BossChargeState {
+    fn enter(&mut self) {}
+    fn exit(&mut self) {}
+
+    fn update_with_context(&mut self, world: &mut World, context: &mut SystemContext, entity: Entity) {
+        let speed = tuning(world, context.game_config, entity).map_or(0.0, |t| t.charge_speed);
+        drive(world, entity, self.direction * speed, sdl3::pixels::Color::WHITE);
+        let anim_prefix = world.bosses.get(&entity)
+            .and_then(|boss| context.game_config.bosses.get(&boss.config_key))
+            .and_then(|config| config.anim_prefix.as_deref());
+        if let (Some(prefix), Some(animation)) = (anim_prefix, world.animations.get_mut(&entity)) {
+            let suffix = if self.direction < 0.0 { "walk_left" } else { "walk_right" };
+            animation.controller.set_animation(&format!("{}_{}", prefix, suffix));
+        }
+        self.timer += context.delta_time;
+    }
+
+    fn transition_with_context(&mut self, world: &mut World, context: &mut SystemContext, entity: Entity) -> Option<Box<dyn State>> {
+        // 1. A wall hit (reported by TileCollision this tick) ends the charge in a stun.
+        if world.wall_hits.contains_key(&entity) {
+            world.event_bus.publish(EventScreenShake { trauma: CRASH_TRAUMA });
+            return Some(Box::new(BossStunnedState::new()));
+        }
+
+        // 2. A charge that found no wall simply peters out.
+        let tuning = tuning(world, context.game_config, entity)?;
+        (self.timer >= tuning.charge_time).then(|| Box::new(BossIdleState::new()) as Box<dyn State>)
+    }
+
+    fn get_name(&self) -> &str { "BossChargeState" }
+}
+
+/// Reeling after a crash: the boss is vulnerable until the stun wears off or it takes a hit.
+pub struct BossStunnedState { timer: f32 }
+
+impl BossStunnedState {
+    pub fn new() -> Self { Self { timer: 0.0 } }
+}
+
+impl Default for BossStunnedState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl State for BossStunnedState {
+    fn enter(&mut self) {}
+    fn exit(&mut self) {}
+
+    fn update_with_context(&mut self, world: &mut World, context: &mut SystemContext, entity: Entity) {
+        drive(world, entity, 0.0, STUNNED_TINT);
+        self.timer += context.delta_time;
+    }
+
+    fn transition_with_context(&mut self, world: &mut World, context: &mut SystemContext, entity: Entity) -> Option<Box<dyn State>> {
+        let stun_time = tuning(world, context.game_config, entity).map_or(0.0, |t| t.stun_time);
+        let boss = world.bosses.get_mut(&entity)?;
+        if boss.hit_while_stunned || self.timer >= stun_time {
+            boss.hit_while_stunned = false;
+            return Some(Box::new(BossIdleState::new()));
+        }
+        None
+    }
+
+    fn get_name(&self) -> &str { "BossStunnedState" }
+}
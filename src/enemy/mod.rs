@@ -2,6 +2,8 @@
 //! 
 //! This module acts as the central hub for the Enemy domain. 
 //! It organizes the codebase into atomic behavioral units:
-//! - **states:** HSM logic for automated enemy behavior (e.g., Patrol).
+//! - **states:** HSM logic for automated enemy behavior (Patrol, Chase).
+//! - **boss:** The charge/stun loop and damage rules of multi-phase bosses.
 
-pub mod states;
\ No newline at end of file
+pub mod states;
+pub mod boss;
@@ -1,20 +1,68 @@
-//! # Concept: Enemy Behavior (Patrol)
+//! # Concept: Enemy Behavior (Patrol & Chase)
 //! 
 //! This module defines the logical states for automated enemies. 
 //! It provides the 'Patrol' behavior, where an entity moves horizontally 
 //! and automatically reverses direction when it detects physical obstacles 
-//! or upcoming platform ledges.
+//! or upcoming platform ledges. An enemy whose `[enemy_behavior]` has a detection
+//! radius leaves its patrol for a player it can see: it either runs at them
+//! ('Chase') or, with a fire interval, holds still and shoots at them ('Shooter').
 
 use crate::state_machine::State;
 use crate::ecs::world::{World, Entity};
 use crate::ecs::systems::SystemContext;
 use crate::ecs::component::MovementIntention;
+use crate::audio::{AudioEvent, PlaySoundParams};
+use crate::config::EnemyBehaviorConfig;
+use crate::level::Level;
+use crate::math::Vector2D;
+
+/// Horizontal distance in **pixels** at which a chaser counts as under its target and stops.
+const ARRIVE_DISTANCE: f32 = 4.0;
+
+/// The behavior named by the entity's patrol, if it can detect players at all.
+fn behavior_config<'a>(world: &World, context: &SystemContext<'a>, entity: Entity) -> Option<&'a EnemyBehaviorConfig> {
+    let key = world.patrols.get(&entity)?.behavior.as_ref()?;
+    context.game_config.enemy_behavior.get(key).filter(|config| config.detection_radius > 0.0)
+}
+
+/// Center (in **pixels**) of the entity's collision box, falling back to its position.
+fn body_center(world: &World, entity: Entity) -> Option<Vector2D> {
+    match world.collisions.get(&entity) {
+        Some(c) => Some(Vector2D::new(c.rect.x() as f32 + c.rect.width() as f32 / 2.0, c.rect.y() as f32 + c.rect.height() as f32 / 2.0)),
+        None => world.positions.get(&entity).map(|p| p.0),
+    }
+}
+
+/// Center of the closest living player within `radius` **pixels** of `entity` that no solid tile hides.
+pub fn visible_player(world: &World, level: &Level, entity: Entity, radius: f32) -> Option<Vector2D> {
+    let origin = body_center(world, entity)?;
+    let distance = |p: Vector2D| ((p.x - origin.x).powi(2) + (p.y - origin.y).powi(2)).sqrt();
+    world.player_tags.keys()
+        .filter(|player| !world.respawn_timers.contains_key(player))
+        .filter_map(|player| body_center(world, *player))
+        .filter(|&p| distance(p) <= radius && level.line_of_sight(origin, p))
+        .min_by(|a, b| distance(*a).total_cmp(&distance(*b)))
+}
 
 /// The primary state for ground-based automated enemies.
 pub struct PatrolState;
 
 impl PatrolState {
     pub fn new() -> Self { Self }
+
+    /// True when the ground ends just past the leading edge of a body at (`left`, `top`) of
+    /// `width` x `height` **pixels** walking in `direction`.
+    ///
+    /// Probes the tile diagonally below the leading edge, so wide bodies turn at their own edge
+    /// rather than one tile-width in. Any tile that can be stood on (including slopes) counts as ground.
+    pub fn ledge_ahead(level: &crate::level::Level, left: f32, top: f32, width: f32, height: f32, direction: f32) -> bool {
+        let probe_x = if direction > 0.0 { left + width } else { left - 1.0 };
+        let probe_y = top + height;
+        if probe_x < 0.0 || probe_y < 0.0 { return true; }
+        let tile_x = (probe_x / level.tileset.tile_width.max(1) as f32).floor() as usize;
+        let tile_y = (probe_y / level.tileset.tile_height.max(1) as f32).floor() as usize;
+        !level.is_ground(tile_x, tile_y)
+    }
 }
 
 impl Default for PatrolState {
@@ -37,37 +85,48 @@ impl State for PatrolState {
         };
         
         // 2. Publish a movement intention to the motor system.
-        world.add_movement_intention(entity, MovementIntention { x: dir });
+        world.add_movement_intention(entity, MovementIntention { x: dir, ..Default::default() });
     }
 
     /// Evaluates environmental constraints to determine when to reverse movement direction.
     fn transition_with_context(&mut self, world: &mut World, context: &mut SystemContext, entity: Entity) -> Option<Box<dyn State>> {
+        // 0. A behavior with a detection radius engages as soon as a player is in sight.
+        if let Some(config) = behavior_config(world, context, entity)
+            && visible_player(world, context.level, entity, config.detection_radius).is_some() {
+            if config.fire_interval > 0.0 { return Some(Box::new(ShooterState::new())); }
+            return Some(Box::new(ChaseState::new()));
+        }
+
         let is_grounded = world.is_grounded(entity);
         
         let mut should_reverse = false;
         let mut current_dir = 1.0;
+        let mut turn_at_ledge = true;
         let mut enemy_width = 32.0;
         let mut enemy_height = 32.0;
         let mut pos_x = 0.0;
         let mut pos_y = 0.0;
         
         // 1. Gather the physical state required for environmental probing.
+        // The collision rect is authoritative; it may be offset from the position.
         if let Some(pos) = world.positions.get(&entity) {
             pos_x = pos.0.x;
             pos_y = pos.0.y;
         }
         
         if let Some(collision) = world.collisions.get(&entity) {
+            pos_x = collision.rect.x() as f32;
+            pos_y = collision.rect.y() as f32;
             enemy_width = collision.rect.width() as f32;
             enemy_height = collision.rect.height() as f32;
         }
         
         if let Some(patrol) = world.patrols.get(&entity) {
             current_dir = patrol.direction;
+            turn_at_ledge = patrol.turn_at_ledge;
         }
 
         let tile_width = context.level.tileset.tile_width as f32;
-        let tile_height = context.level.tileset.tile_height as f32;
 
         // 2. Check for authoritative wall hits reported by the physics engine (Priority 1).
         if let Some(wall_hit) = world.wall_hits.get(&entity) {
@@ -86,27 +145,14 @@ impl State for PatrolState {
                 pos_x - 4.0
             };
 
-            let map_width = context.level.map.tiles[0].len() as f32 * tile_width;
+            let map_width = context.level.map.width as f32 * tile_width;
             
             if wall_check_x < 0.0 || wall_check_x > map_width {
                 should_reverse = true;
             } 
-            else if is_grounded {
+            else if is_grounded && turn_at_ledge && Self::ledge_ahead(context.level, pos_x, pos_y, enemy_width, enemy_height, current_dir) {
                 // B. Ledges (Only if grounded and not at map edge)
-                let check_x = if current_dir > 0.0 {
-                    pos_x + enemy_width + 1.0
-                } else {
-                    pos_x - 1.0
-                };
-
-                // Check one pixel below feet to detect upcoming ledges.
-                let ground_check_y = pos_y + enemy_height + 1.0;
-                let ground_tile_x = (check_x / tile_width).floor() as usize;
-                let ground_tile_y = (ground_check_y / tile_height).floor() as usize;
-
-                if !context.level.is_solid(ground_tile_x, ground_tile_y) {
-                    should_reverse = true;
-                }
+                should_reverse = true;
             }
         }
 
@@ -126,4 +172,103 @@ impl State for PatrolState {
     }
 
     fn get_name(&self) -> &str { "PatrolState" }
-}
\ No newline at end of file
+}
+
+/// Pursuit: the enemy runs at the nearest visible player's X until they escape its detection radius.
+pub struct ChaseState;
+
+impl ChaseState {
+    pub fn new() -> Self { Self }
+}
+
+impl Default for ChaseState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl State for ChaseState {
+    fn enter(&mut self) {}
+    fn exit(&mut self) {}
+
+    /// Steers toward the target, holding at platform edges unless the behavior is reckless.
+    fn update_with_context(&mut self, world: &mut World, context: &mut SystemContext, entity: Entity) {
+        let Some(config) = behavior_config(world, context, entity) else { return };
+        let (Some(target), Some(center)) = (visible_player(world, context.level, entity, config.detection_radius), body_center(world, entity)) else { return };
+
+        // 1. Head for the player's X, stopping once right beneath or above them.
+        let offset = target.x - center.x;
+        let mut direction = if offset.abs() <= ARRIVE_DISTANCE { 0.0 } else { offset.signum() };
+        if direction != 0.0 && let Some(patrol) = world.patrols.get_mut(&entity) {
+            patrol.direction = direction;
+        }
+
+        // 2. A careful chaser waits at the edge rather than following the player down.
+        if direction != 0.0 && !config.reckless && world.is_grounded(entity)
+            && let Some(collision) = world.collisions.get(&entity) {
+            let rect = collision.rect;
+            if PatrolState::ledge_ahead(context.level, rect.x() as f32, rect.y() as f32, rect.width() as f32, rect.height() as f32, direction) {
+                direction = 0.0;
+                if let Some(vel) = world.velocities.get_mut(&entity) { vel.0.x = 0.0; }
+            }
+        }
+
+        // 3. Publish the intention at chase speed.
+        world.add_movement_intention(entity, MovementIntention { x: direction, max_speed: Some(config.chase_speed), ..Default::default() });
+    }
+
+    /// Gives up and resumes patrolling (in the current heading) once no player is in sight.
+    fn transition_with_context(&mut self, world: &mut World, context: &mut SystemContext, entity: Entity) -> Option<Box<dyn State>> {
+        let radius = behavior_config(world, context, entity).map_or(0.0, |config| config.detection_radius);
+        visible_player(world, context.level, entity, radius).is_none().then(|| Box::new(PatrolState::new()) as Box<dyn State>)
+    }
+
+    fn get_name(&self) -> &str { "ChaseState" }
+}
+
+/// Ranged attack: the enemy halts, faces the nearest visible player and fires at them every
+/// `fire_interval` seconds until they are out of sight.
+pub struct ShooterState { timer: f32 }
+
+impl ShooterState {
+    pub fn new() -> Self { Self { timer: 0.0 } }
+}
+
+impl Default for ShooterState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl State for ShooterState {
+    fn enter(&mut self) {}
+    fn exit(&mut self) {}
+
+    /// Holds position and fires an aimed shot whenever the interval has elapsed.
+    fn update_with_context(&mut self, world: &mut World, context: &mut SystemContext, entity: Entity) {
+        // 1. Stand still, turned toward the target.
+        world.add_movement_intention(entity, MovementIntention::default());
+        let Some(config) = behavior_config(world, context, entity) else { return };
+        let (Some(target), Some(origin)) = (visible_player(world, context.level, entity, config.detection_radius), body_center(world, entity)) else { return };
+        if let Some(patrol) = world.patrols.get_mut(&entity) {
+            patrol.direction = if target.x < origin.x { -1.0 } else { 1.0 };
+        }
+
+        // 2. Fire once the interval has elapsed.
+        self.timer += context.delta_time;
+        if self.timer < config.fire_interval { return; }
+        self.timer = 0.0;
+        if crate::entity_spawner::spawn_aimed_projectile(world, context.game_config, &config.projectile_prefab, entity, origin, target, config.projectile_speed).is_some()
+            && let Some(sound_name) = context.game_config.sound_events.get("enemy_fire") {
+            let _ = context.audio_sender.send(AudioEvent::PlaySound(sound_name.clone(), PlaySoundParams::at(origin)));
+        }
+    }
+
+    /// Resumes patrolling once no player is in sight.
+    fn transition_with_context(&mut self, world: &mut World, context: &mut SystemContext, entity: Entity) -> Option<Box<dyn State>> {
+        let radius = behavior_config(world, context, entity).map_or(0.0, |config| config.detection_radius);
+        visible_player(world, context.level, entity, radius).is_none().then(|| Box::new(PatrolState::new()) as Box<dyn State>)
+    }
+
+    fn get_name(&self) -> &str { "ShooterState" }
+}
@@ -6,115 +6,304 @@
 
 use crate::ecs::world::World;
 use crate::level::Level;
-use crate::config::{GameConfig, ComponentConfig};
+use crate::config::{AnimationPhase, GameConfig, ComponentConfig};
 use crate::ecs::component::*;
 use crate::state_machine::StateMachine;
 use crate::enemy::states::PatrolState;
+use crate::enemy::boss::BossIdleState;
 use crate::animation::AnimationController;
 
 /// Populates the ECS world based on the entity templates defined in a level.
 pub fn spawn_entities(world: &mut World, level: &Level, game_config: &GameConfig) {
     // 1. Iterate over every object definition parsed from the TMX file.
     for entity_data in &level.entities {
-        let entity = world.create_entity();
-        
-        // 2. Locate the corresponding 'Prefab' definition in the game configuration.
-        if let Some(prefab) = game_config.prefabs.get(&entity_data.r#type) {
-            
-            // 3. Iterate over the component list defined for this prefab.
-            for component_config in &prefab.components {
-                match component_config {
-                    ComponentConfig::Position => {
-                        // Use the world-space position specified in the level layout.
-                        world.add_position(entity, Position(entity_data.position));
-                    }
-                    ComponentConfig::Velocity { x, y } => {
-                        world.add_velocity(entity, Velocity(crate::math::Vector2D::new(*x, *y)));
-                    }
-                    ComponentConfig::Acceleration { x, y } => {
-                        world.add_acceleration(entity, Acceleration(crate::math::Vector2D::new(*x, *y)));
-                    }
-                    ComponentConfig::Renderable { draw_width, draw_height, z_index, horizontal_offset, vertical_offset } => {
-                        world.add_renderable(entity, Renderable {
-                            width: *draw_width,
-                            height: *draw_height,
-                            horizontal_offset: *horizontal_offset,
-                            vertical_offset: *vertical_offset,
-                            z_index: *z_index,
-                            rotation: 0.0,
-                            flip_horizontal: false,
-                            flip_vertical: false,
-                        });
-                    }
-                    ComponentConfig::Animation { animations, initial_animation } => {
-                        // Build the animation controller and pre-load all clips.
-                        let mut anim_controller = AnimationController::new();
-                        for anim_name in animations {
-                            if let Some(anim_config) = game_config.animation.get(anim_name) {
-                                let mut frames = Vec::new();
-                                for i in 0..anim_config.frame_count {
-                                    let padding = anim_config.frame_padding.unwrap_or(0);
-                                    frames.push(sdl3::rect::Rect::new(
-                                        anim_config.start_x + (i * (anim_config.frame_width + padding)) as i32,
-                                        anim_config.start_y,
-                                        anim_config.frame_width,
-                                        anim_config.frame_height,
-                                    ));
-                                }
-                                if anim_config.reverse.unwrap_or(false) {
-                                    frames.reverse();
-                                }
-                                let animation = crate::animation::Animation {
-                                    texture_name: anim_config.texture.clone(),
-                                    frames,
-                                    frame_duration: anim_config.frame_duration,
-                                    loops: anim_config.loops,
-                                };
-                                anim_controller.add_animation(anim_name.clone(), animation);
-                            }
+        spawn_entity(world, entity_data, game_config);
+    }
+}
+
+/// Creates one live entity from a template, assembling the components of its prefab.
+pub fn spawn_entity(world: &mut World, entity_data: &crate::level::Entity, game_config: &GameConfig) -> crate::ecs::world::Entity {
+    let entity = world.create_entity();
+    
+    // 2. Locate the corresponding 'Prefab' definition in the game configuration.
+    if let Some(prefab) = game_config.prefabs.get(&entity_data.r#type) {
+        // Region-style prefabs and tile objects collide with the (rotated) bounds drawn in the editor.
+        // The entity is anchored at the corner of those bounds, and its sprite shifted back to where
+        // Tiled showed it; unrotated objects keep their own corner.
+        let sized_by_object = entity_data.is_tile || prefab.components.iter().any(|c| matches!(c, ComponentConfig::Collision { use_object_size: true, .. }));
+        let object_bounds = entity_data.bounds().filter(|_| sized_by_object);
+        let anchor = object_bounds.map_or(entity_data.position, |(corner, _)| corner);
+        let sprite_shift = ((entity_data.position.x - anchor.x).round() as i32, (entity_data.position.y - anchor.y).round() as i32);
+
+        // 3. Iterate over the component list defined for this prefab.
+        for component_config in &prefab.components {
+            match component_config {
+                ComponentConfig::Position => {
+                    // Use the world-space position specified in the level layout.
+                    world.add_position(entity, Position(anchor));
+                }
+                ComponentConfig::Velocity { x, y } => {
+                    world.add_velocity(entity, Velocity(crate::math::Vector2D::new(*x, *y)));
+                }
+                ComponentConfig::Acceleration { x, y } => {
+                    world.add_acceleration(entity, Acceleration(crate::math::Vector2D::new(*x, *y)));
+                }
+                ComponentConfig::Renderable { draw_width, draw_height, z_index, horizontal_offset, vertical_offset } => {
+                    world.add_renderable(entity, Renderable {
+                        width: *draw_width,
+                        height: *draw_height,
+                        horizontal_offset: *horizontal_offset + sprite_shift.0,
+                        vertical_offset: *vertical_offset + sprite_shift.1,
+                        z_index: *z_index,
+                        rotation: entity_data.rotation as f64,
+                        flip_horizontal: false,
+                        flip_vertical: false,
+                        tint: sdl3::pixels::Color::WHITE,
+                        alpha: 255,
+                    });
+                }
+                ComponentConfig::Animation { animations, initial_animation, phase } => {
+                    // Build the animation controller and pre-load all clips.
+                    let mut anim_controller = AnimationController::new();
+                    for anim_name in animations {
+                        if let Some(anim_config) = game_config.animation.get(anim_name) {
+                            anim_controller.add_animation(anim_name.clone(), crate::animation::Animation::from_config(anim_config));
                         }
-                        anim_controller.set_animation(initial_animation);
-                        world.add_animation(entity, Animation { controller: anim_controller });
-                    }
-                    ComponentConfig::Collision { width, height } => {
-                        world.add_collision(entity, Collision {
-                            rect: sdl3::rect::Rect::new(
-                                entity_data.position.x as i32,
-                                entity_data.position.y as i32,
-                                *width,
-                                *height,
-                            ),
-                        });
-                    }
-                    ComponentConfig::Gravity => {
-                        world.add_gravity(entity, Gravity);
                     }
-                    ComponentConfig::Patrol { speed, anim_prefix } => {
-                        world.add_patrol(entity, Patrol { speed: *speed, anim_prefix: anim_prefix.clone(), direction: 1.0 });
+                    anim_controller.set_animation(initial_animation);
+                    match phase {
+                        AnimationPhase::Independent => {}
+                        AnimationPhase::Synced => anim_controller.set_synced(true),
+                        AnimationPhase::Random => anim_controller.randomize_phase(&mut world.rng.0),
                     }
-                    ComponentConfig::EnemyTag => {
-                        world.add_enemy_tag(entity, EnemyTag);
-                    }
-                    ComponentConfig::GoldCoin => {
-                        world.add_gold_coin(entity, GoldCoin);
-                    }
-                    ComponentConfig::Goal => {
-                        world.add_goal(entity, Goal);
+                    world.add_animation(entity, Animation { controller: anim_controller });
+                }
+                ComponentConfig::Collision { width, height, .. } => {
+                    let (width, height) = object_bounds.map_or((*width, *height), |(_, (w, h))| (w as u32, h as u32));
+                    world.add_collision(entity, Collision {
+                        rect: sdl3::rect::Rect::new(
+                            anchor.x as i32,
+                            anchor.y as i32,
+                            width,
+                            height,
+                        ),
+                    });
+                }
+                ComponentConfig::Gravity => {
+                    world.add_gravity(entity, Gravity);
+                }
+                ComponentConfig::Patrol { speed, anim_prefix, direction, turn_at_ledge, behavior } => {
+                    // A TMX `direction` property overrides the prefab, which overrides the sign of `speed`.
+                    let direction = entity_data.properties.get("direction").map(String::as_str)
+                        .or(direction.as_deref())
+                        .and_then(parse_direction)
+                        .unwrap_or(if *speed < 0.0 { -1.0 } else { 1.0 });
+                    let behavior = entity_data.properties.get("behavior").or(behavior.as_ref()).cloned();
+                    world.add_patrol(entity, Patrol { speed: speed.abs(), anim_prefix: anim_prefix.clone(), direction, turn_at_ledge: *turn_at_ledge, behavior });
+                }
+                ComponentConfig::EnemyTag => {
+                    world.add_enemy_tag(entity, EnemyTag);
+                }
+                ComponentConfig::GoldCoin => {
+                    world.add_gold_coin(entity, GoldCoin);
+                }
+                ComponentConfig::HealthPickup { amount } => {
+                    let amount = parse_property(&entity_data.properties, "amount").unwrap_or(*amount);
+                    world.add_health_pickup(entity, HealthPickup { amount });
+                }
+                ComponentConfig::Goal => {
+                    world.add_goal(entity, Goal);
+                }
+                ComponentConfig::Checkpoint => {
+                    world.add_checkpoint(entity, Checkpoint::default());
+                }
+                ComponentConfig::Projectile { damage, speed, lifetime, gravity } => {
+                    // Level-placed shots fly along their `direction` property; fired shots are re-aimed by `spawn_projectile`.
+                    let direction = entity_data.properties.get("direction").and_then(|d| parse_direction(d)).unwrap_or(1.0);
+                    world.add_projectile(entity, Projectile { owner: None, from_player: false, damage: *damage, speed: *speed, lifetime: *lifetime, gravity: *gravity });
+                    world.add_velocity(entity, Velocity(crate::math::Vector2D::new(speed * direction, 0.0)));
+                    world.add_lifetime(entity, Lifetime { timer: *lifetime });
+                    if *gravity { world.add_gravity(entity, Gravity); }
+                }
+                ComponentConfig::ParticleEmitter { preset, rate } => {
+                    if let Some(spec) = game_config.particles.presets.get(preset) {
+                        world.add_particle_emitter(entity, ParticleEmitter { spec: spec.clone(), rate: *rate, accumulator: 0.0 });
+                    } else {
+                        eprintln!("[Spawner] Unknown particle preset '{}'", preset);
                     }
-                    ComponentConfig::StateComponent { initial_state } => {
-                        // Initialize logic state machines for AI-driven entities.
-                        if initial_state == "PatrolState" {
-                            world.add_state_component(entity, StateComponent { state_machine: StateMachine::new(PatrolState::new()) });
+                }
+                ComponentConfig::Trigger { event_name, once, delay, shake } => {
+                    let properties = &entity_data.properties;
+                    let event_name = properties.get("event").or(properties.get("event_name")).unwrap_or(event_name).clone();
+                    let spawn = match (properties.get("spawn"), properties.get("spawn_at")) {
+                        (Some(prefab), Some(marker)) => Some(TriggerSpawn { prefab: prefab.clone(), marker: marker.clone() }),
+                        (None, None) => None,
+                        _ => {
+                            eprintln!("[Spawner] Trigger '{}' needs both `spawn` and `spawn_at`", event_name);
+                            None
                         }
+                    };
+                    world.add_trigger(entity, Trigger {
+                        event_name,
+                        once: parse_property(properties, "once").unwrap_or(*once),
+                        delay: parse_property(properties, "delay").unwrap_or(*delay),
+                        shake: parse_property(properties, "shake").unwrap_or(*shake),
+                        spawn,
+                        ..Default::default()
+                    });
+                }
+                ComponentConfig::StateComponent { initial_state } => {
+                    // Initialize logic state machines for AI-driven entities.
+                    if initial_state == "PatrolState" {
+                        world.add_state_component(entity, StateComponent { state_machine: StateMachine::new(PatrolState::new()) });
                     }
                 }
+                ComponentConfig::Boss { config_key } => {
+                    // The boss's Health and state machine come from its tuning, not the prefab.
+                    let config_key = entity_data.properties.get("boss").cloned().unwrap_or_else(|| config_key.clone());
+                    let Some(boss_config) = game_config.bosses.get(&config_key) else {
+                        eprintln!("[Spawner] Unknown boss '{}'", config_key);
+                        continue;
+                    };
+                    world.add_health(entity, Health { current: boss_config.max_health, max: boss_config.max_health });
+                    world.add_state_component(entity, StateComponent { state_machine: StateMachine::new(BossIdleState::new()) });
+                    world.add_boss(entity, Boss { config_key, ..Default::default() });
+                }
             }
         }
+    }
 
-        // 4. Resolve custom TMX properties (e.g., 'next_level' paths for goals).
-        if let Some(next_level_path) = entity_data.properties.get("next_level") {
-            use crate::ecs::component::NextLevel;
-            world.add_next_level(entity, NextLevel(next_level_path.clone()));
+    // 4. Face patrolling entities their initial way so they don't walk backwards on the first frames.
+    if let Some(patrol) = world.patrols.get(&entity)
+        && let Some(animation) = world.animations.get_mut(&entity) {
+            let suffix = if patrol.direction < 0.0 { "walk_left" } else { "walk_right" };
+            animation.controller.set_animation(&format!("{}_{}", patrol.anim_prefix, suffix));
         }
+
+    // 5. Resolve custom TMX properties (e.g., 'next_level' paths for goals).
+    if let Some(next_level_path) = entity_data.properties.get("next_level") {
+        use crate::ecs::component::NextLevel;
+        world.add_next_level(entity, NextLevel(next_level_path.clone()));
+    }
+    if let Some(spawn_point) = entity_data.properties.get("spawn_point") {
+        world.add_exit_spawn_point(entity, ExitSpawnPoint(spawn_point.clone()));
+    }
+
+    entity
+}
+
+/// Fires a projectile prefab from `owner`, centered on `origin` and travelling along `direction` (-1 left, 1 right).
+///
+/// # Side Effects
+/// * Spawns a new entity; returns `None` if the prefab is unknown or has no `Projectile` component.
+pub fn spawn_projectile(world: &mut World, game_config: &GameConfig, prefab: &str, owner: crate::ecs::world::Entity, origin: crate::math::Vector2D, direction: f32) -> Option<crate::ecs::world::Entity> {
+    // 1. Build the shot from its prefab.
+    if !game_config.prefabs.contains_key(prefab) {
+        eprintln!("[Spawner] Unknown projectile prefab '{}'", prefab);
+        return None;
+    }
+    let template = crate::level::Entity { r#type: prefab.to_string(), position: origin, ..Default::default() };
+    let entity = spawn_entity(world, &template, game_config);
+    let Some(projectile) = world.projectiles.get_mut(&entity) else {
+        world.add_dead_tag(entity, DeadTag);
+        return None;
+    };
+
+    // 2. Claim ownership so the shooter is never hit by its own shot.
+    projectile.owner = Some(owner);
+    projectile.from_player = world.player_tags.contains_key(&owner);
+    let speed = projectile.speed;
+
+    // 3. Aim along the shooter's facing, centering the hitbox on the origin.
+    world.add_velocity(entity, Velocity(crate::math::Vector2D::new(speed * direction, 0.0)));
+    if let Some(collision) = world.collisions.get_mut(&entity) {
+        collision.rect.set_x(origin.x as i32 - collision.rect.width() as i32 / 2);
+        collision.rect.set_y(origin.y as i32 - collision.rect.height() as i32 / 2);
+        let top_left = crate::math::Vector2D::new(collision.rect.x() as f32, collision.rect.y() as f32);
+        world.add_position(entity, Position(top_left));
+    }
+    if let Some(renderable) = world.renderables.get_mut(&entity) { renderable.flip_horizontal = direction < 0.0; }
+    Some(entity)
+}
+
+/// Fires a projectile prefab from `owner` at `target` (in **pixels**) with `speed` in **pixels per second**,
+/// overriding the prefab's horizontal aim and speed.
+///
+/// # Side Effects
+/// * Spawns a new entity via [spawn_projectile].
+pub fn spawn_aimed_projectile(world: &mut World, game_config: &GameConfig, prefab: &str, owner: crate::ecs::world::Entity, origin: crate::math::Vector2D, target: crate::math::Vector2D, speed: f32) -> Option<crate::ecs::world::Entity> {
+    let (dx, dy) = (target.x - origin.x, target.y - origin.y);
+    let length = (dx * dx + dy * dy).sqrt();
+    let entity = spawn_projectile(world, game_config, prefab, owner, origin, if dx < 0.0 { -1.0 } else { 1.0 })?;
+    if length > 0.0 && let Some(vel) = world.velocities.get_mut(&entity) {
+        vel.0 = crate::math::Vector2D::new(dx / length * speed, dy / length * speed);
+    }
+    if let Some(projectile) = world.projectiles.get_mut(&entity) { projectile.speed = speed; }
+    Some(entity)
+}
+
+/// Parses a designer-facing direction name into a patrol heading.
+fn parse_direction(value: &str) -> Option<f32> {
+    match value.to_ascii_lowercase().as_str() {
+        "left" => Some(-1.0),
+        "right" => Some(1.0),
+        _ => None,
+    }
+}
+
+/// Parses a typed Tiled property, warning about values that do not parse.
+fn parse_property<T: std::str::FromStr>(properties: &std::collections::HashMap<String, String>, key: &str) -> Option<T> {
+    let value = properties.get(key)?;
+    let parsed = value.trim().parse().ok();
+    if parsed.is_none() { eprintln!("[Spawner] Ignoring malformed `{}` property '{}'", key, value); }
+    parsed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{load_game_config, PrefabConfig};
+    use crate::level::Entity;
+
+    fn spawn_single(patrol: ComponentConfig, properties: &[(&str, &str)]) -> (World, crate::ecs::world::Entity) {
+        let mut game_config = load_game_config("assets/game_config.toml").unwrap();
+        game_config.prefabs.insert("TestWalker".to_string(), PrefabConfig { components: vec![
+            ComponentConfig::Position,
+            ComponentConfig::Animation {
+                animations: vec!["enemy_spider_walk_right".to_string(), "enemy_spider_walk_left".to_string()],
+                initial_animation: "enemy_spider_walk_right".to_string(),
+                phase: AnimationPhase::Independent,
+            },
+            patrol,
+        ]});
+        let level = Level { entities: vec![Entity {
+            r#type: "TestWalker".to_string(),
+            properties: properties.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect(),
+            ..Default::default()
+        }], ..Default::default() };
+
+        let mut world = World::new();
+        spawn_entities(&mut world, &level, &game_config);
+        let entity = *world.patrols.keys().next().unwrap();
+        (world, entity)
+    }
+
+    #[test]
+    fn test_prefab_left_direction_sets_heading_and_animation() {
+        let (world, entity) = spawn_single(ComponentConfig::Patrol { speed: 60.0, anim_prefix: "enemy_spider".to_string(), direction: Some("left".to_string()), turn_at_ledge: true, behavior: None }, &[]);
+
+        assert_eq!(world.patrols[&entity].direction, -1.0);
+        assert_eq!(world.animations[&entity].controller.current_animation_name(), Some("enemy_spider_walk_left"));
+    }
+
+    #[test]
+    fn test_negative_speed_and_tmx_override() {
+        let (world, entity) = spawn_single(ComponentConfig::Patrol { speed: -60.0, anim_prefix: "enemy_spider".to_string(), direction: None, turn_at_ledge: true, behavior: None }, &[]);
+        assert_eq!(world.patrols[&entity].direction, -1.0);
+        assert_eq!(world.patrols[&entity].speed, 60.0, "Speed is stored as a magnitude");
+
+        let (world, entity) = spawn_single(ComponentConfig::Patrol { speed: -60.0, anim_prefix: "enemy_spider".to_string(), direction: None, turn_at_ledge: true, behavior: None }, &[("direction", "right")]);
+        assert_eq!(world.patrols[&entity].direction, 1.0);
+        assert_eq!(world.animations[&entity].controller.current_animation_name(), Some("enemy_spider_walk_right"));
     }
-}
\ No newline at end of file
+}
@@ -3,34 +3,96 @@
 //! This module is the authority for typography. It provides an abstraction 
 //! over the 'rusttype' crate to load TrueType fonts and render them into 
 //! SDL surfaces, enabling crisp pixel-art text for UI and debugging.
+//! A font is compiled into the binary so text still renders when asset 
+//! files are missing. Several named fonts can be loaded side by side, and
+//! rasterized glyphs are cached per font, size and subpixel offset.
 
 use rusttype::{Font, Scale, point};
 use sdl3::surface::Surface;
 use sdl3::pixels::{Color, PixelFormatEnum};
+use std::cell::RefCell;
 use std::collections::HashMap;
 use std::fs;
+use crate::renderer::TextCache;
+
+/// Font embedded in the binary, used when a font file cannot be read or parsed.
+const FALLBACK_FONT: &[u8] = include_bytes!("../assets/fonts/PressStart2P-Regular.ttf");
+
+/// Glyphs kept rasterized at once across all fonts and sizes.
+const GLYPH_CACHE_CAPACITY: usize = 2048;
+
+/// Glyph positions are snapped to quarter pixels, so each glyph has at most 16 cached variants per size.
+const SUBPIXEL_STEPS: f32 = 4.0;
+
+/// Identifies one rasterized glyph: font name, `f32::to_bits` of the size, glyph id and quarter-pixel offset.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GlyphKey {
+    pub font: String,
+    pub size_bits: u32,
+    pub glyph: u16,
+    pub subpixel: (u8, u8),
+}
+
+/// Coverage of one glyph, relative to the whole pixel its pen position falls in.
+struct RasterGlyph {
+    offset: (i32, i32),
+    width: usize,
+    coverage: Vec<u8>,
+}
 
 /// A manager for loading and rendering TrueType fonts.
 pub struct FontManager {
     fonts: HashMap<String, Font<'static>>,
+    /// Filled while rendering, which only borrows the manager immutably.
+    glyphs: RefCell<TextCache<RasterGlyph, GlyphKey>>,
 }
 
 impl FontManager {
     /// Initializes an empty font cache.
     pub fn new() -> Self {
-        Self { fonts: HashMap::new() }
+        Self { fonts: HashMap::new(), glyphs: RefCell::new(TextCache::new(GLYPH_CACHE_CAPACITY)) }
+    }
+
+    /// Loads every `name = path` pair (the `[fonts]` table); each name can then be passed to `render_text`.
+    pub fn load_all(&mut self, fonts: &HashMap<String, String>) -> Result<(), String> {
+        for (name, path) in fonts { self.load(name, path)?; }
+        Ok(())
     }
 
+    /// True once a font has been registered under `name`.
+    pub fn has_font(&self, name: &str) -> bool { self.fonts.contains_key(name) }
+
+    /// Number of glyph bitmaps currently cached.
+    pub fn cached_glyphs(&self) -> usize { self.glyphs.borrow().len() }
+
     /// Loads a .ttf file from disk into the cache.
+    ///
+    /// # Side Effects
+    /// * Falls back to the embedded font (and logs it) when the file is missing or invalid.
     pub fn load(&mut self, name: &str, path: &str) -> Result<(), String> {
         // 1. Read the font file bytes from disk.
-        let data = fs::read(path).map_err(|e| e.to_string())?;
-        
-        // 2. Leak the memory to create a 'static buffer required by rusttype for performance.
-        let data: &'static [u8] = Box::leak(data.into_boxed_slice());
-        
-        // 3. Parse the bytes into a usable Font structure.
-        let font = Font::try_from_bytes(data).ok_or("Failed to parse font data")?;
+        let font = match fs::read(path) {
+            Ok(data) => {
+                // 2. Leak the memory to create a 'static buffer required by rusttype for performance.
+                let data: &'static [u8] = Box::leak(data.into_boxed_slice());
+
+                // 3. Parse the bytes into a usable Font structure.
+                Font::try_from_bytes(data).or_else(|| {
+                    eprintln!("[FontManager] Failed to parse font '{}', using built-in fallback.", path);
+                    None
+                })
+            }
+            Err(e) => {
+                eprintln!("[FontManager] Could not read font '{}' ({}), using built-in fallback.", path, e);
+                None
+            }
+        };
+
+        // 4. Substitute the embedded font so text keeps rendering.
+        let font = match font {
+            Some(font) => font,
+            None => Font::try_from_bytes(FALLBACK_FONT).ok_or("Failed to parse built-in fallback font")?,
+        };
         self.fonts.insert(name.to_string(), font);
         Ok(())
     }
@@ -59,6 +121,50 @@ impl FontManager {
         Ok((width, height))
     }
 
+    /// Breaks `text` into lines no wider than `max_width` **pixels** at `size`.
+    /// Lines break between words and at `\n`; only a word wider than a whole line is split.
+    pub fn wrap_text(&self, name: &str, text: &str, size: f32, max_width: u32) -> Result<Vec<String>, String> {
+        let fits = |line: &str| self.measure_text(name, line, size).map(|(width, _)| width <= max_width);
+        let mut lines = Vec::new();
+        for paragraph in text.split('\n') {
+            let mut line = String::new();
+            for word in paragraph.split_whitespace() {
+                // 1. Append the word to the current line while it still fits.
+                let candidate = if line.is_empty() { word.to_string() } else { format!("{} {}", line, word) };
+                if fits(&candidate)? { line = candidate; continue; }
+                if !line.is_empty() { lines.push(std::mem::take(&mut line)); }
+                if fits(word)? { line = word.to_string(); continue; }
+
+                // 2. A word wider than a whole line is split between characters.
+                for ch in word.chars() {
+                    let mut next = line.clone();
+                    next.push(ch);
+                    if !line.is_empty() && !fits(&next)? { lines.push(std::mem::replace(&mut line, ch.to_string())); } else { line = next; }
+                }
+            }
+            lines.push(line);
+        }
+        Ok(lines)
+    }
+
+    /// Size in **pixels** of `text` wrapped to `max_width` (see [Self::wrap_text]), with lines
+    /// `line_height` apart (the font height when `None`). Useful for sizing dialog boxes.
+    pub fn measure_block(&self, name: &str, text: &str, size: f32, max_width: Option<u32>, line_height: Option<u32>) -> Result<(u32, u32), String> {
+        let lines = match max_width {
+            Some(max_width) => self.wrap_text(name, text, size, max_width)?,
+            None => text.split('\n').map(str::to_string).collect(),
+        };
+        let mut width = 0;
+        let mut font_height = 0;
+        for line in &lines {
+            let (w, h) = self.measure_text(name, line, size)?;
+            width = width.max(w);
+            font_height = h;
+        }
+        let step = line_height.unwrap_or(font_height);
+        Ok((width, step * (lines.len() as u32 - 1) + font_height))
+    }
+
     /// Renders a string into a stand-alone pixel buffer (SDL Surface).
     pub fn render_surface(&self, name: &str, text: &str, size: f32, color: Color) -> Result<Surface<'static>, String> {
         // 1. Determine the necessary canvas size for the text.
@@ -78,27 +184,34 @@ impl FontManager {
         let offset = point(0.0, v_metrics.ascent);
         let glyphs: Vec<_> = font.layout(text, scale, offset).collect();
 
-        // 3. Manually rasterize each glyph into the target pixel buffer.
+        // 3. Copy each glyph's cached coverage into the target pixel buffer. The layout above
+        //    still positions every glyph, so advances and kerning are those of this size.
+        let mut cache = self.glyphs.borrow_mut();
         surface.with_lock_mut(|buffer: &mut [u8]| {
             for g in glyphs {
-                if let Some(bb) = g.pixel_bounding_box() {
-                    // Iterate over every pixel affected by the glyph's coverage.
-                    g.draw(|x, y, v| {
-                        let x = x as i32 + bb.min.x;
-                        let y = y as i32 + bb.min.y;
-                        if x >= 0 && x < width as i32 && y >= 0 && y < height as i32 {
-                            let alpha = (v * 255.0) as u8;
-                            if alpha > 0 {
-                                let index = (y as usize * width as usize + x as usize) * 4;
-                                
-                                // Map the font coverage to the surface's RGBA byte array.
-                                buffer[index] = color.r;
-                                buffer[index + 1] = color.g;
-                                buffer[index + 2] = color.b;
-                                buffer[index + 3] = alpha; 
-                            }
-                        }
-                    });
+                let position = g.position();
+                let (base_x, base_y) = (position.x.floor(), position.y.floor());
+                let subpixel = (((position.x - base_x) * SUBPIXEL_STEPS) as u8, ((position.y - base_y) * SUBPIXEL_STEPS) as u8);
+                let key = GlyphKey { font: name.to_string(), size_bits: size.to_bits(), glyph: g.id().0, subpixel };
+                if cache.get(&key).is_none() {
+                    let glyph = g.unpositioned().clone().positioned(point(subpixel.0 as f32 / SUBPIXEL_STEPS, subpixel.1 as f32 / SUBPIXEL_STEPS));
+                    cache.insert(key.clone(), rasterize(&glyph));
+                }
+                let Some(raster) = cache.get(&key) else { continue };
+
+                // Iterate over every pixel affected by the glyph's coverage.
+                for (i, &alpha) in raster.coverage.iter().enumerate() {
+                    let x = base_x as i32 + raster.offset.0 + (i % raster.width) as i32;
+                    let y = base_y as i32 + raster.offset.1 + (i / raster.width) as i32;
+                    if alpha > 0 && x >= 0 && x < width as i32 && y >= 0 && y < height as i32 {
+                        let index = (y as usize * width as usize + x as usize) * 4;
+
+                        // Map the font coverage to the surface's RGBA byte array.
+                        buffer[index] = color.r;
+                        buffer[index + 1] = color.g;
+                        buffer[index + 2] = color.b;
+                        buffer[index + 3] = alpha;
+                    }
                 }
             }
         });
@@ -107,8 +220,73 @@ impl FontManager {
     }
 }
 
+/// Rasterizes a glyph placed near the origin into an 8-bit coverage bitmap.
+fn rasterize(glyph: &rusttype::PositionedGlyph<'_>) -> RasterGlyph {
+    let Some(bb) = glyph.pixel_bounding_box() else { return RasterGlyph { offset: (0, 0), width: 1, coverage: Vec::new() } };
+    let width = bb.width() as usize;
+    let mut coverage = vec![0; width * bb.height() as usize];
+    glyph.draw(|x, y, v| coverage[y as usize * width + x as usize] = (v * 255.0) as u8);
+    RasterGlyph { offset: (bb.min.x, bb.min.y), width, coverage }
+}
+
 impl Default for FontManager {
     fn default() -> Self {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_font_falls_back_to_embedded() {
+        let mut font_manager = FontManager::new();
+        assert!(font_manager.load("debug", "assets/fonts/DoesNotExist.ttf").is_ok());
+
+        let (width, height) = font_manager.measure_text("debug", "HUD", 8.0).unwrap();
+        assert!(width > 0 && height > 0, "Fallback font must be able to lay out text");
+    }
+
+    #[test]
+    fn test_wrap_breaks_between_words_and_splits_only_oversized_ones() {
+        let mut font_manager = FontManager::new();
+        font_manager.load("debug", "assets/fonts/PressStart2P-Regular.ttf").unwrap();
+        let width_of = |text: &str| font_manager.measure_text("debug", text, 8.0).unwrap().0;
+
+        let max = width_of("HELLO") + 4;
+        assert_eq!(font_manager.wrap_text("debug", "HELLO WORLD\nHI", 8.0, max).unwrap(), vec!["HELLO", "WORLD", "HI"]);
+
+        let lines = font_manager.wrap_text("debug", "ABCDEFGHIJKL", 8.0, max).unwrap();
+        assert!(lines.len() > 1);
+        assert_eq!(lines.concat(), "ABCDEFGHIJKL");
+        assert!(lines.iter().all(|line| width_of(line) <= max));
+    }
+
+    #[test]
+    fn test_glyphs_are_cached_per_font_and_size() {
+        let mut font_manager = FontManager::new();
+        font_manager.load_all(&[("title".to_string(), "assets/fonts/PressStart2P-Regular.ttf".to_string()), ("body".to_string(), "assets/fonts/LiberationMono-Regular.ttf".to_string())].into()).unwrap();
+        assert!(font_manager.has_font("title") && font_manager.has_font("body"));
+
+        font_manager.render_surface("title", "AAB", 16.0, Color::WHITE).unwrap();
+        assert_eq!(font_manager.cached_glyphs(), 2, "Repeated glyphs reuse one bitmap");
+        font_manager.render_surface("title", "BA", 16.0, Color::WHITE).unwrap();
+        assert_eq!(font_manager.cached_glyphs(), 2);
+        font_manager.render_surface("title", "A", 32.0, Color::WHITE).unwrap();
+        font_manager.render_surface("body", "A", 16.0, Color::WHITE).unwrap();
+        assert_eq!(font_manager.cached_glyphs(), 4, "Each size and font gets its own bitmap");
+    }
+
+    #[test]
+    fn test_block_size_covers_every_wrapped_line() {
+        let mut font_manager = FontManager::new();
+        font_manager.load("debug", "assets/fonts/PressStart2P-Regular.ttf").unwrap();
+        let (word_width, font_height) = font_manager.measure_text("debug", "WORLD", 8.0).unwrap();
+
+        let (width, height) = font_manager.measure_block("debug", "HI WORLD", 8.0, Some(word_width + 2), Some(12)).unwrap();
+        assert_eq!(width, word_width, "The widest line sets the block width");
+        assert_eq!(height, 12 + font_height);
+        assert_eq!(font_manager.measure_block("debug", "", 8.0, None, None).unwrap().1, font_height);
+    }
+}
@@ -1,5 +1,311 @@
-//! # Concept: Frame Capture (FFmpeg Bridge)
-//! 
-//! This module is responsible for the 'Offline Rendering' pipeline. 
-//! it captures raw canvas pixel buffers and pipes them to an FFmpeg 
-//! subprocess to generate high-fidelity, lag-free gameplay video.
\ No newline at end of file
+//! # Concept: Frame Capture
+//!
+//! This module turns rendered frames into files for bug reports and clips.
+//! A screenshot saves one canvas read-back as a timestamped PNG; GIF capture
+//! keeps a rolling buffer of the last few seconds of downscaled frames and
+//! encodes it once capture is toggled off. Encoding and disk I/O happen on a
+//! worker thread fed through a channel, so the render loop only pays for the
+//! read-back itself. Results (and failures) come back as status lines for the
+//! debug overlay instead of panics.
+
+use crate::config::DebugConfig;
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Default output directory, relative to the working directory.
+pub const CAPTURE_DIR: &str = "captures";
+/// How long a capture result stays on screen.
+const STATUS_DURATION: Duration = Duration::from_secs(4);
+
+/// A canvas read-back as tightly packed RGBA8 rows.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CapturedFrame {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>,
+}
+
+impl CapturedFrame {
+    /// Nearest-neighbour copy keeping every `factor`-th pixel on both axes.
+    pub fn downscaled(&self, factor: u32) -> CapturedFrame {
+        let factor = factor.max(1);
+        if factor == 1 || self.width == 0 || self.height == 0 { return self.clone(); }
+        let (width, height) = ((self.width / factor).max(1), (self.height / factor).max(1));
+        let mut pixels = Vec::with_capacity((width * height * 4) as usize);
+        for y in 0..height {
+            let row = (y * factor).min(self.height - 1) * self.width;
+            for x in 0..width {
+                let i = ((row + (x * factor).min(self.width - 1)) * 4) as usize;
+                pixels.extend_from_slice(&self.pixels[i..i + 4]);
+            }
+        }
+        CapturedFrame { width, height, pixels }
+    }
+}
+
+/// Fixed-size buffer of the most recent frames; pushing into a full ring drops the oldest.
+pub struct FrameRing {
+    frames: VecDeque<CapturedFrame>,
+    capacity: usize,
+}
+
+impl FrameRing {
+    pub fn new(capacity: usize) -> Self {
+        Self { frames: VecDeque::with_capacity(capacity.max(1)), capacity: capacity.max(1) }
+    }
+
+    pub fn push(&mut self, frame: CapturedFrame) {
+        if self.frames.len() == self.capacity { self.frames.pop_front(); }
+        self.frames.push_back(frame);
+    }
+
+    pub fn len(&self) -> usize { self.frames.len() }
+
+    pub fn is_empty(&self) -> bool { self.frames.is_empty() }
+
+    /// Empties the ring, returning its frames oldest first.
+    pub fn drain(&mut self) -> Vec<CapturedFrame> { self.frames.drain(..).collect() }
+}
+
+/// `dir/prefix_<unix seconds>_<milliseconds>.ext`, so captures sort by time and never collide within a session.
+pub fn capture_path(dir: &Path, prefix: &str, time: SystemTime, extension: &str) -> PathBuf {
+    let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    dir.join(format!("{}_{}_{:03}.{}", prefix, since_epoch.as_secs(), since_epoch.subsec_millis(), extension))
+}
+
+/// Work handed to the encoder thread.
+enum CaptureJob {
+    Screenshot { frame: CapturedFrame, path: PathBuf },
+    Gif { frames: Vec<CapturedFrame>, frame_delay_ms: u32, path: PathBuf },
+}
+
+/// Encodes and writes one job; the `Ok` value is the message shown to the user.
+fn run_job(job: CaptureJob) -> Result<String, String> {
+    match job {
+        CaptureJob::Screenshot { frame, path } => {
+            if let Some(dir) = path.parent() { std::fs::create_dir_all(dir).map_err(|e| e.to_string())?; }
+            let image = image::RgbaImage::from_raw(frame.width, frame.height, frame.pixels).ok_or("Screenshot buffer has the wrong size")?;
+            image.save(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+            Ok(format!("Saved {}", path.display()))
+        }
+        CaptureJob::Gif { frames, frame_delay_ms, path } => {
+            use image::codecs::gif::{GifEncoder, Repeat};
+            if let Some(dir) = path.parent() { std::fs::create_dir_all(dir).map_err(|e| e.to_string())?; }
+            let file = std::fs::File::create(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+            let count = frames.len();
+            let mut encoder = GifEncoder::new_with_speed(std::io::BufWriter::new(file), 10);
+            encoder.set_repeat(Repeat::Infinite).map_err(|e| e.to_string())?;
+            for frame in frames {
+                let image = image::RgbaImage::from_raw(frame.width, frame.height, frame.pixels).ok_or("GIF frame buffer has the wrong size")?;
+                let delay = image::Delay::from_numer_denom_ms(frame_delay_ms, 1);
+                encoder.encode_frame(image::Frame::from_parts(image, 0, 0, delay)).map_err(|e| e.to_string())?;
+            }
+            Ok(format!("Saved {} ({} frames)", path.display(), count))
+        }
+    }
+}
+
+/// A line for the debug overlay; errors are drawn in a warning color.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CaptureStatus {
+    pub text: String,
+    pub is_error: bool,
+}
+
+/// Owns the capture state for the running session and the encoder thread.
+pub struct FrameCapture {
+    dir: PathBuf,
+    jobs: Option<Sender<CaptureJob>>,
+    results: Option<Receiver<Result<String, String>>>,
+    /// The rolling GIF buffer; `Some` while capture is on.
+    recording: Option<FrameRing>,
+    screenshot_pending: bool,
+    /// Time in **seconds** since the last GIF frame was kept.
+    since_last_frame: f32,
+    gif_frame_due: bool,
+    buffer_seconds: f32,
+    frame_rate: u32,
+    downscale: u32,
+    status: Option<(CaptureStatus, Instant)>,
+}
+
+impl FrameCapture {
+    /// Starts the encoder thread; output goes to `dir`.
+    pub fn new(dir: impl Into<PathBuf>, config: &DebugConfig) -> Self {
+        let (job_sender, job_receiver) = channel::<CaptureJob>();
+        let (result_sender, result_receiver) = channel();
+        let mut capture = Self {
+            dir: dir.into(), jobs: None, results: None, recording: None, screenshot_pending: false,
+            since_last_frame: 0.0, gif_frame_due: false, buffer_seconds: config.gif_buffer_seconds,
+            frame_rate: config.gif_frame_rate.max(1), downscale: config.gif_downscale.max(1), status: None,
+        };
+        let worker = std::thread::Builder::new().name("frame-capture".to_string()).spawn(move || {
+            for job in job_receiver { if result_sender.send(run_job(job)).is_err() { break; } }
+        });
+        match worker {
+            Ok(_) => { capture.jobs = Some(job_sender); capture.results = Some(result_receiver); }
+            Err(e) => capture.set_status(format!("[Capture] Encoder thread failed to start: {}", e), true),
+        }
+        capture
+    }
+
+    /// Picks up changed GIF settings after a config reload; a capture in progress keeps its buffer.
+    pub fn apply_config(&mut self, config: &DebugConfig) {
+        self.buffer_seconds = config.gif_buffer_seconds;
+        self.frame_rate = config.gif_frame_rate.max(1);
+        self.downscale = config.gif_downscale.max(1);
+    }
+
+    pub fn is_recording(&self) -> bool { self.recording.is_some() }
+
+    /// Asks for the next submitted frame to be saved as a PNG.
+    pub fn request_screenshot(&mut self) { self.screenshot_pending = true; }
+
+    /// Starts buffering frames, or stops and sends the buffer off to be encoded.
+    pub fn toggle_gif(&mut self) {
+        match self.recording.take() {
+            None => {
+                let capacity = (self.buffer_seconds.max(0.0) * self.frame_rate as f32).ceil() as usize;
+                self.recording = Some(FrameRing::new(capacity));
+                self.since_last_frame = 0.0;
+                self.gif_frame_due = true;
+            }
+            Some(ring) if ring.is_empty() => self.set_status("[Capture] GIF capture stopped with no frames".to_string(), true),
+            Some(mut ring) => {
+                let path = capture_path(&self.dir, "clip", SystemTime::now(), "gif");
+                let frame_delay_ms = 1000 / self.frame_rate;
+                self.send(CaptureJob::Gif { frames: ring.drain(), frame_delay_ms, path });
+                self.set_status("[Capture] Encoding GIF...".to_string(), false);
+            }
+        }
+    }
+
+    /// Advances the GIF clock and reports whether the frame being rendered should be read back.
+    ///
+    /// ⚠️ **Hotpath**: Called once per rendered frame.
+    pub fn wants_frame(&mut self, frame_time: f32) -> bool {
+        if self.recording.is_some() {
+            self.since_last_frame += frame_time;
+            let interval = 1.0 / self.frame_rate as f32;
+            if self.since_last_frame >= interval {
+                self.since_last_frame = (self.since_last_frame - interval).min(interval);
+                self.gif_frame_due = true;
+            }
+        }
+        self.screenshot_pending || (self.recording.is_some() && self.gif_frame_due)
+    }
+
+    /// Hands over the frame read back after [Self::wants_frame] returned true.
+    pub fn submit(&mut self, frame: CapturedFrame) {
+        if self.gif_frame_due && let Some(ring) = &mut self.recording {
+            ring.push(frame.downscaled(self.downscale));
+            self.gif_frame_due = false;
+        }
+        if self.screenshot_pending {
+            self.screenshot_pending = false;
+            let path = capture_path(&self.dir, "screenshot", SystemTime::now(), "png");
+            self.send(CaptureJob::Screenshot { frame, path });
+        }
+    }
+
+    /// Records a read-back failure; the pending screenshot is dropped rather than retried every frame.
+    pub fn report_error(&mut self, error: &str) {
+        self.screenshot_pending = false;
+        self.gif_frame_due = false;
+        self.set_status(format!("[Capture] Read-back failed: {}", error), true);
+    }
+
+    /// Collects finished jobs from the encoder thread.
+    pub fn poll(&mut self) {
+        let finished: Vec<_> = self.results.as_ref().map(|r| r.try_iter().collect()).unwrap_or_default();
+        for result in finished {
+            match result {
+                Ok(message) => self.set_status(format!("[Capture] {}", message), false),
+                Err(e) => self.set_status(format!("[Capture] Failed: {}", e), true),
+            }
+        }
+    }
+
+    /// What the overlay should show right now: the REC indicator while capturing, else the latest recent result.
+    pub fn status(&self) -> Option<CaptureStatus> {
+        if let Some(ring) = &self.recording {
+            return Some(CaptureStatus { text: format!("[Capture] REC GIF {:.1}s", ring.len() as f32 / self.frame_rate as f32), is_error: false });
+        }
+        self.status.as_ref().filter(|(_, at)| at.elapsed() < STATUS_DURATION).map(|(status, _)| status.clone())
+    }
+
+    fn send(&mut self, job: CaptureJob) {
+        let sent = self.jobs.as_ref().is_some_and(|jobs| jobs.send(job).is_ok());
+        if !sent { self.set_status("[Capture] Encoder thread is not running".to_string(), true); }
+    }
+
+    fn set_status(&mut self, text: String, is_error: bool) {
+        if is_error { eprintln!("{}", text); }
+        self.status = Some((CaptureStatus { text, is_error }, Instant::now()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solid(width: u32, height: u32) -> CapturedFrame {
+        let pixels = (0..width * height).flat_map(|i| [i as u8, 0, 0, 255]).collect();
+        CapturedFrame { width, height, pixels }
+    }
+
+    fn debug_config() -> DebugConfig {
+        toml::from_str("show_debug_info = false\ndebug_draw_collision_boxes = false\ntext_start_x = 0\ntext_start_y = 0\ntext_line_spacing = 0\ngif_frame_rate = 10\ngif_buffer_seconds = 0.5").unwrap()
+    }
+
+    #[test]
+    fn test_ring_keeps_only_the_latest_frames() {
+        let mut ring = FrameRing::new(3);
+        for width in 1..=5 { ring.push(solid(width, 1)); }
+        let widths: Vec<u32> = ring.drain().iter().map(|f| f.width).collect();
+        assert_eq!(widths, vec![3, 4, 5]);
+        assert!(ring.is_empty());
+    }
+
+    #[test]
+    fn test_downscale_samples_every_nth_pixel() {
+        let frame = solid(4, 4);
+        let half = frame.downscaled(2);
+        assert_eq!((half.width, half.height), (2, 2));
+        let reds: Vec<u8> = half.pixels.chunks_exact(4).map(|p| p[0]).collect();
+        assert_eq!(reds, vec![0, 2, 8, 10]);
+    }
+
+    #[test]
+    fn test_capture_paths_are_timestamped() {
+        let time = UNIX_EPOCH + Duration::from_millis(1_700_000_000_042);
+        assert_eq!(capture_path(Path::new("captures"), "screenshot", time, "png"), PathBuf::from("captures/screenshot_1700000000_042.png"));
+    }
+
+    #[test]
+    fn test_gif_buffer_fills_at_the_capture_rate_and_encodes_on_stop() {
+        let dir = std::env::temp_dir().join(format!("gfx_capture_test_{}", std::process::id()));
+        let mut capture = FrameCapture::new(dir.clone(), &debug_config());
+        capture.toggle_gif();
+
+        // 2 seconds at 100 FPS, kept at 10 FPS in a half-second buffer.
+        let mut kept = 0;
+        for _ in 0..200 {
+            if capture.wants_frame(0.01) { capture.submit(solid(8, 8)); kept += 1; }
+        }
+        assert!((19..=21).contains(&kept), "Kept {} frames", kept);
+        assert_eq!(capture.status().unwrap().text, "[Capture] REC GIF 0.5s");
+
+        capture.toggle_gif();
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while capture.status().is_some_and(|s| s.text.contains("Encoding")) && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(10));
+            capture.poll();
+        }
+        let status = capture.status().unwrap();
+        assert!(!status.is_error && status.text.contains("(5 frames)"), "{}", status.text);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
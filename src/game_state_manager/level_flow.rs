@@ -0,0 +1,178 @@
+//! # Concept: Level Flow
+//!
+//! The parts of [GameStateManager] that move the player between levels and screens:
+//! starting a game, loading the next level (restarts included), the level tally, the
+//! game-over countdown, and the iris-out back to the main menu.
+
+use std::collections::HashMap;
+use crate::level::load_level;
+use crate::config::{Config, GameConfig, LevelOverride};
+use crate::audio::GameAudioManager;
+use crate::input::InputState;
+use crate::texture_manager::TextureManager;
+use crate::ecs::resources::{GameState, Screen};
+use crate::score::{LevelTally, HIGH_SCORES_PATH};
+use sdl3::render::TextureCreator;
+use sdl3::video::WindowContext;
+use super::{GameStateManager, ReplayMode};
+
+impl GameStateManager {
+    /// Starts a new game from the menu with full lives, no coins and no score.
+    pub(super) fn start_game(&mut self, config: &Config, game_config: &GameConfig, audio_manager: &mut GameAudioManager) {
+        self.world.game_state = GameState::Playing;
+        self.replay_mode = ReplayMode::None;
+        self.next_level = Some(config.game.start_level.clone());
+        self.world.stats.lives = game_config.player.lives;
+        // A fresh game never inherits coins from the previous run.
+        self.world.stats.gold_coin_count = 0;
+        self.level_start_coin_count = 0;
+        self.world.stats.score = 0;
+        self.world.stats.level_start_score = 0;
+        let soundtrack = self.get_soundtrack_name();
+        self.crossfade_soundtrack(audio_manager, &soundtrack, game_config);
+    }
+
+    /// Replaces the world's level with `next_level`, carrying stats and player health over as configured.
+    ///
+    /// # Side Effects
+    /// * Clears the level's entities, spawns the new level's entities and players, and starts an iris-in.
+    pub(super) fn load_next_level(&mut self, next_level: String, game_config: &GameConfig, audio_manager: &mut GameAudioManager, texture_manager: &mut TextureManager, texture_creator: &TextureCreator<WindowContext>) -> Result<(), String> {
+        self.level = load_level(&next_level)?;
+        self.level_override = LevelOverride::load_for(&next_level)?;
+        self.level_configs = None;
+        self.replay_config = None;
+        self.current_level_path = next_level;
+        Self::swap_level_textures(&self.level, texture_manager, texture_creator);
+        // Leaving through a Goal carries each player's health over, keyed by player slot.
+        let level_exit = self.world.level_exit.take();
+        let carried_health: HashMap<usize, u32> = if level_exit.is_some() {
+            self.world.player_tags.keys()
+                .filter_map(|e| Some((self.world.player_slots.get(e).map_or(0, |s| s.0), self.world.healths.get(e)?.current)))
+                .collect()
+        } else { HashMap::new() };
+        self.world.clear_level();
+        if self.world.game_state == GameState::Playing { self.world.ui_state.level_name = crate::level::level_title(&self.current_level_path); }
+        // Checkpoints are level-local; the new level starts from its default respawn point.
+        self.world.stats.last_checkpoint = None;
+        // Coins collected in the current level respawn on restart, so the count is rolled back;
+        // otherwise `carry_coins_on_level` decides whether the total follows the player.
+        let restart_from = self.is_restarting_level.then_some(self.level_start_coin_count);
+        self.level_start_coin_count = self.world.stats.enter_level(game_config.gameplay.carry_coins_on_level, restart_from);
+        self.is_restarting_level = false;
+        self.next_level = None;
+        self.spawn_entities_from_level(game_config);
+        // Exits may name a SpawnPoint in the destination level; otherwise use the configured start.
+        let start = level_exit.and_then(|exit| exit.spawn_point)
+            .and_then(|name| self.level.spawn_point(&name).or_else(|| { eprintln!("[GameFlow] Spawn point '{}' not found, using start position.", name); None }))
+            .unwrap_or(game_config.player.start_pos);
+        self.player_entity = Self::spawn_players(&mut self.world, game_config, start);
+        for (entity, slot) in self.world.player_slots.iter().map(|(e, s)| (*e, s.0)) {
+            if let (Some(&current), Some(health)) = (carried_health.get(&slot), self.world.healths.get_mut(&entity)) {
+                health.current = current.min(health.max).max(1);
+            }
+        }
+        if let Some(pe) = self.player_entity && let Some(p) = self.world.positions.get(&pe) {
+            self.camera.set_zone(self.level.camera_zone_at(p.0));
+            self.camera.snap_to(p.0);
+        }
+        use crate::ecs::event::{EventStartTransition, TransitionType};
+        self.world.event_bus.publish(EventStartTransition { transition_type: TransitionType::IrisIn, duration: 1.0, center: None });
+        self.crossfade_soundtrack(audio_manager, &self.get_soundtrack_name(), game_config);
+        Ok(())
+    }
+
+    /// Ends the current level with a [LevelTally] and shows it until it is skipped or times out.
+    ///
+    /// # Side Effects
+    /// * Adds the time bonus to the score and saves [HIGH_SCORES_PATH] when the level's best is beaten.
+    pub(super) fn begin_tally(&mut self, next_level: String, game_config: &GameConfig) {
+        let mut tally = LevelTally::finish_level(&mut self.world.stats, &self.current_level_path, next_level, &game_config.gameplay.score);
+        if self.high_scores.record(&mut tally)
+            && let Err(e) = self.high_scores.save(HIGH_SCORES_PATH) { eprintln!("[Score] Failed to save '{}': {}", HIGH_SCORES_PATH, e); }
+        self.world.level_tally = Some(tally);
+        self.world.game_state = GameState::LevelComplete;
+    }
+
+    /// Counts down the game-over screen, then returns to the main menu.
+    pub(super) fn update_game_over(&mut self, delta_time: f32, config: &Config, game_config: &GameConfig, audio_manager: &mut GameAudioManager) {
+        self.game_over_timer -= delta_time;
+        if self.game_over_timer <= 0.0 {
+            self.finish_recording();
+            self.world.game_state = GameState::Menu(Screen::Main);
+            self.next_level = Some(config.game.start_level.clone());
+            self.crossfade_soundtrack(audio_manager, "soundtrack_01", game_config);
+        }
+    }
+
+    /// Shows the [LevelTally] until it times out or is skipped with Jump, Fire or Attack, then loads the next level.
+    pub(super) fn update_level_tally(&mut self, delta_time: f32, game_config: &GameConfig, input_state: &InputState, audio_manager: &mut GameAudioManager, texture_manager: &mut TextureManager, texture_creator: &TextureCreator<WindowContext>) -> Result<(), String> {
+        let skipped = [crate::input::InputAction::Jump, crate::input::InputAction::Fire, crate::input::InputAction::Attack].into_iter().any(|a| input_state.is_action_just_pressed(a));
+        let Some(tally) = self.world.level_tally.as_mut() else { self.world.game_state = GameState::Playing; return Ok(()); };
+        tally.remaining -= delta_time;
+        if skipped || tally.remaining <= 0.0 {
+            let next_level = tally.next_level.clone();
+            self.world.level_tally = None;
+            self.world.game_state = GameState::Playing;
+            self.load_next_level(next_level, game_config, audio_manager, texture_manager, texture_creator)?;
+        }
+        Ok(())
+    }
+
+    /// Reloads the current level behind an iris-out; lives are kept, level state is reset.
+    ///
+    /// # Side Effects
+    /// * Publishes `EventLevelRestartRequested`; the level transition system closes the iris and sets `next_level`.
+    pub(super) fn request_restart(&mut self) {
+        println!("[GameFlow] Restarting level: {}", self.current_level_path);
+        self.is_restarting_level = true;
+        self.world.event_bus.publish(crate::ecs::event::EventLevelRestartRequested { level: self.current_level_path.clone() });
+    }
+
+    /// Starts the return to the main menu: closes the iris and fades the music out.
+    pub(super) fn begin_exit_to_menu(&mut self, audio_manager: &mut GameAudioManager) {
+        println!("[GameFlow] Starting return to menu sequence...");
+        self.is_exiting_to_menu = true;
+
+        // Trigger visual fade out
+        use crate::ecs::event::{EventStartTransition, TransitionType};
+        self.world.event_bus.publish(EventStartTransition {
+            transition_type: TransitionType::IrisOut,
+            duration: 1.0,
+            center: None,
+        });
+
+        // Trigger audio fade out
+        let _ = audio_manager.event_sender().send(crate::audio::AudioEvent::FadeOutMusic(1.0));
+    }
+
+    /// Completes the return to the main menu once the iris has closed, restarting attract mode behind it.
+    pub(super) fn finish_exit_to_menu(&mut self, config: &Config, game_config: &GameConfig, audio_manager: &mut GameAudioManager, texture_manager: &mut TextureManager, texture_creator: &TextureCreator<WindowContext>) -> Result<(), String> {
+        println!("[GameFlow] Transition to menu complete.");
+        self.is_exiting_to_menu = false;
+        self.world.game_state = GameState::Menu(Screen::Main);
+        self.menu_state.load_screen("main");
+
+        // A recording ends with the session it captured.
+        self.finish_recording();
+
+        // Restart Attract Mode (Replay) on a clean copy of the level it was recorded on.
+        if let Some((replay, path, level)) = Self::load_attract_mode(&config.game.start_level) {
+            println!("[GameFlow] Restarting Attract Mode.");
+            self.replay_mode = ReplayMode::Playback;
+            self.current_replay = replay;
+            self.replay_tick = 0;
+            self.enter_fresh_level(path, level, config, game_config, texture_manager, texture_creator)?;
+        }
+
+        // Trigger IrisIn to show the menu
+        use crate::ecs::event::{EventStartTransition, TransitionType};
+        self.world.event_bus.publish(EventStartTransition {
+            transition_type: TransitionType::IrisIn,
+            duration: 1.0,
+            center: None,
+        });
+
+        self.crossfade_soundtrack(audio_manager, "soundtrack_01", game_config);
+        Ok(())
+    }
+}
@@ -0,0 +1,615 @@
+//! # Manager: Application State Orchestration
+//! 
+//! This module is the "Grand Conductor" of the engine's high-level flow. 
+//! It is responsible for level loading, world-swapping during transitions, 
+//! persistent state management (stats/lives), and the coordination of 
+//! the menu and replay systems.
+
+use crate::ecs::world::{World, Entity};
+use std::rc::Rc;
+use crate::ecs::system_manager::SystemManager;
+use crate::level::{Level, load_level};
+use crate::camera::Camera;
+use crate::config::{AudioSettings, Config, GameConfig, InputConfig, LevelOverride, UserSettings, USER_SETTINGS_PATH};
+use crate::player::factory::PlayerFactory;
+use crate::ecs::systems::RenderContext;
+use crate::audio::GameAudioManager;
+use crate::input::InputState;
+use crate::texture_manager::{TextureManager, TextureScope};
+use crate::font_manager::FontManager;
+use crate::math::Vector2D;
+use crate::menu::{MenuState, MenuAction};
+use crate::ecs::systems::menu::SystemMenu;
+use crate::ecs::systems::debug_render::DebugOverlays;
+use crate::ecs::resources::Screen;
+use crate::replay::{Replay, ATTRACT_REPLAY};
+use crate::benchmarker::Benchmarker;
+use crate::ecs::resources::{GameState, GameStats, GAME_RNG_SEED};
+use crate::score::{HighScores, HIGH_SCORES_PATH};
+use crate::simulation::{Simulation, TickOptions};
+use sdl3::render::TextureCreator;
+use sdl3::video::WindowContext;
+
+mod level_flow;
+
+#[derive(PartialEq, Clone, Copy)]
+#[allow(dead_code)]
+pub enum ReplayMode { None, Recording, Playback }
+
+/// The primary manager for orchestrating the application's high-level state and data.
+pub struct GameStateManager {
+    pub world: World,
+    pub system_manager: SystemManager,
+    pub font_manager: FontManager,
+    pub benchmarker: Benchmarker,
+    pub level: Level,
+    pub camera: Camera,
+    pub player_entity: Option<Entity>,
+    pub game_over_timer: f32,
+    pub next_level: Option<String>,
+    pub current_level_path: String,
+    pub menu_state: MenuState,
+    pub menu_system: SystemMenu,
+    pub selected_character_index: usize,
+    pub replay_mode: ReplayMode,
+    pub current_replay: Replay,
+    pub replay_tick: u64,
+    /// Finished benchmark sessions (menu, attract mode, each level), oldest first.
+    pub session_benchmarks: Vec<crate::benchmarker::BenchmarkSection>,
+    /// Whether the game is currently in the process of returning to the main menu.
+    pub is_exiting_to_menu: bool,
+    /// Whether the pending level load is a player-requested restart of the current level.
+    pub is_restarting_level: bool,
+    /// The coin count when the current level was entered, restored on restart.
+    pub level_start_coin_count: u32,
+    /// Best score of each level, saved to [HIGH_SCORES_PATH] whenever a tally beats it.
+    pub high_scores: HighScores,
+    /// Volumes currently applied to the audio manager.
+    pub audio_settings: AudioSettings,
+    /// Key bindings currently applied to the input handler.
+    pub input_bindings: InputConfig,
+    /// Bindings changed in the Controls menu that the input handler has not picked up yet.
+    rebound_input: Option<InputConfig>,
+    /// Developer overlays; toggled in [Self::handle_input] and kept across levels and states.
+    pub debug_overlays: DebugOverlays,
+    /// Values from the current level's sibling `.toml`; empty when the level has none.
+    pub level_override: LevelOverride,
+    /// The global configs with [Self::level_override] merged on top, rebuilt on the next tick after it is cleared.
+    level_configs: Option<Rc<(Config, GameConfig)>>,
+    /// The effective config with the attract replay's recorded physics applied; only set during playback.
+    replay_config: Option<Rc<Config>>,
+}
+
+impl GameStateManager {
+    pub fn new(config: &Config, game_config: &GameConfig, texture_manager: &mut TextureManager, texture_creator: &TextureCreator<WindowContext>) -> Result<Self, String> {
+        let mut font_manager = FontManager::new();
+        font_manager.load("debug", "assets/fonts/PressStart2P-Regular.ttf")?;
+        font_manager.load_all(&game_config.fonts)?;
+        // Attract mode runs on the level its replay was recorded on; without a playable replay the menu shows the start level.
+        let (replay_mode, current_replay, level_path, level) = match Self::load_attract_mode(&config.game.start_level) {
+            Some((replay, path, level)) => (ReplayMode::Playback, replay, path, level),
+            None => (ReplayMode::None, Replay::default(), config.game.start_level.clone(), load_level(&config.game.start_level)?),
+        };
+        let level_override = LevelOverride::load_for(&level_path)?;
+        Self::swap_level_textures(&level, texture_manager, texture_creator);
+        let mut world = World::new();
+        let player_entity = Self::spawn_players(&mut world, game_config, game_config.player.start_pos);
+        let camera = Self::create_camera(config, game_config, &level);
+        let mut menu_state = MenuState::new(&game_config.menu);
+        Self::sync_audio_menu(&mut menu_state, config.audio);
+        Self::sync_controls_menu(&mut menu_state, &config.input);
+        world.game_state = GameState::Menu(Screen::Main);
+        world.stats.lives = game_config.player.lives;
+        let mut instance = Self {
+            world, level, camera, system_manager: SystemManager::new(), font_manager,
+            player_entity, game_over_timer: 0.0, next_level: None,
+            current_level_path: level_path, menu_state,
+            menu_system: SystemMenu::new(), selected_character_index: 0, replay_mode,
+            current_replay, replay_tick: 0,
+            benchmarker: Benchmarker::new(), session_benchmarks: Vec::new(),
+            is_exiting_to_menu: false, is_restarting_level: false, level_start_coin_count: 0,
+            high_scores: HighScores::load(HIGH_SCORES_PATH),
+            audio_settings: config.audio, input_bindings: config.input.clone(), rebound_input: None, debug_overlays: DebugOverlays::from_config(&config.debug),
+            level_override, level_configs: None, replay_config: None,
+        };
+        instance.spawn_entities_from_level(game_config);
+        Ok(instance)
+    }
+
+    /// Loads the attract-mode replay together with the level it was recorded on.
+    ///
+    /// Returns `None`, disabling attract mode, when there is no replay or its level cannot be loaded.
+    fn load_attract_mode(default_level: &str) -> Option<(Replay, String, Level)> {
+        let replay = Replay::load(ATTRACT_REPLAY).ok()?;
+        match replay.load_level(default_level) {
+            Ok((path, level)) => Some((replay, path, level)),
+            Err(e) => { eprintln!("[Replay] Attract mode disabled: {}", e); None }
+        }
+    }
+
+    /// Loads the tileset of `level` and releases the previous level's textures it does not share.
+    ///
+    /// The tileset is registered before the old level scope is released, so a restart, attract
+    /// loop or seek keeps it decoded. A new tileset is decoded here rather than on the first frame
+    /// drawn; a missing file falls back to the placeholder.
+    fn swap_level_textures(level: &Level, texture_manager: &mut TextureManager, texture_creator: &TextureCreator<WindowContext>) {
+        let tileset = level.tileset.texture.as_str();
+        texture_manager.register(tileset, tileset, TextureScope::Level);
+        texture_manager.unload_level_scope(&[tileset]);
+        texture_manager.get_or_load(tileset, texture_creator);
+    }
+
+    /// Replaces the world with a fresh copy of `level`, for attract-mode playback or a benchmark run.
+    #[allow(clippy::too_many_arguments)]
+    fn enter_fresh_level(&mut self, path: String, level: Level, config: &Config, game_config: &GameConfig, texture_manager: &mut TextureManager, texture_creator: &TextureCreator<WindowContext>) -> Result<(), String> {
+        Self::swap_level_textures(&level, texture_manager, texture_creator);
+        self.level_override = LevelOverride::load_for(&path)?;
+        self.level_configs = None;
+        self.replay_config = None;
+        self.level = level;
+        self.current_level_path = path;
+
+        self.world.clear_level();
+        self.world.stats = GameStats::default();
+        self.world.stats.lives = game_config.player.lives;
+        self.spawn_entities_from_level(game_config);
+        self.player_entity = Self::spawn_players(&mut self.world, game_config, game_config.player.start_pos);
+        self.camera = Self::create_camera(config, game_config, &self.level);
+        Ok(())
+    }
+
+    /// Starts a `--bench` run: loads the stress level at `path` and plays it directly, without menu or transition.
+    pub fn start_benchmark(&mut self, path: &str, config: &Config, game_config: &GameConfig, texture_manager: &mut TextureManager, texture_creator: &TextureCreator<WindowContext>) -> Result<(), String> {
+        let level = load_level(path)?;
+        self.replay_mode = ReplayMode::None;
+        self.enter_fresh_level(path.to_string(), level, config, game_config, texture_manager, texture_creator)?;
+        self.world.game_state = GameState::Playing;
+        Ok(())
+    }
+
+    pub(crate) fn create_camera(config: &Config, game_config: &GameConfig, level: &Level) -> Camera {
+        let player_start_pos = game_config.player.start_pos;
+        let map_width_in_tiles = level.map.width as f32;
+        let map_height_in_tiles = level.map.height as f32;
+        let total_map_width = map_width_in_tiles * level.tileset.tile_width as f32;
+        let total_map_height = map_height_in_tiles * level.tileset.tile_height as f32;
+        let (virtual_width_in_world, virtual_height_in_world) = crate::units::Units::viewport_in_world(&config.window, 1.0);
+        let player_center_x = player_start_pos.x + (game_config.player.width as f32 / 2.0);
+        let player_center_y = player_start_pos.y + (game_config.player.height as f32 / 2.0);
+        let initial_camera_x = player_center_x - (virtual_width_in_world / 2.0);
+        let initial_camera_y = player_center_y - (virtual_height_in_world / 2.0);
+        let mut camera = Camera::new(initial_camera_x, initial_camera_y, config.window.camera_tightness, virtual_width_in_world, virtual_height_in_world, total_map_width, total_map_height, config.window.camera_slow_zone, config.window.camera_fast_zone, config.window.camera_vertical_snap_threshold, config.window.camera_vertical_tightness, config.window.camera_falling_tightness, config.window.camera_falling_velocity_threshold, config.physics.entity_max_fall_speed, config.window.camera_lookahead_distance, config.window.camera_smoothing_speed);
+        camera.bounds_blend_time = config.window.camera_zone_blend_time;
+        camera.mode = config.window.camera_mode;
+        camera.deadzone = Vector2D::new(config.window.camera_deadzone_width, config.window.camera_deadzone_height);
+        camera.set_zone(level.camera_zone_at(player_start_pos));
+        camera
+    }
+
+    /// Spawns one player per configured local slot and returns the primary (slot 0) player.
+    fn spawn_players(world: &mut World, game_config: &GameConfig, start: Vector2D) -> Option<Entity> {
+        let count = game_config.player.local_players.clamp(1, 2);
+        let players: Vec<Entity> = (0..count).map(|slot| PlayerFactory::create_for_slot(world, game_config, slot, start)).collect();
+        players.first().copied()
+    }
+
+    /// Applies a hot-reloaded game config: live animation clips are rebuilt in place, and
+    /// prefab edits restart the current level so every entity is respawned from the new prefabs.
+    pub fn apply_reloaded_config(&mut self, game_config: &GameConfig, respawn: bool) {
+        // The level's override is re-read and merged onto the new globals on the next tick.
+        match LevelOverride::load_for(&self.current_level_path) {
+            Ok(level_override) => self.level_override = level_override,
+            Err(e) => eprintln!("[Config] Keeping the previous level override: {}", e),
+        }
+        self.level_configs = None;
+        self.replay_config = None;
+        for animation in self.world.animations.values_mut() {
+            animation.controller.reload_clips(&game_config.animation);
+        }
+        if respawn {
+            self.is_restarting_level = true;
+            self.next_level = Some(self.current_level_path.clone());
+        }
+    }
+
+    /// Sends `settings` to the audio manager and reflects them in the Options -> Audio selectors.
+    pub fn apply_audio_settings(&mut self, settings: AudioSettings, audio_manager: &GameAudioManager) {
+        use crate::audio::AudioEvent;
+        let sender = audio_manager.event_sender();
+        let _ = sender.send(AudioEvent::SetMasterVolume(settings.master_volume));
+        let _ = sender.send(AudioEvent::SetMusicVolume(settings.music_volume));
+        let _ = sender.send(AudioEvent::SetSfxVolume(settings.sfx_volume));
+        self.audio_settings = settings;
+        Self::sync_audio_menu(&mut self.menu_state, settings);
+    }
+
+    fn sync_audio_menu(menu_state: &mut MenuState, settings: AudioSettings) {
+        menu_state.set_value("master_volume", &crate::menu::percent_label(settings.master_volume));
+        menu_state.set_value("music_volume", &crate::menu::percent_label(settings.music_volume));
+        menu_state.set_value("sfx_volume", &crate::menu::percent_label(settings.sfx_volume));
+        menu_state.set_number("master_volume", settings.master_volume as f32);
+        menu_state.set_number("music_volume", settings.music_volume as f32);
+        menu_state.set_number("sfx_volume", settings.sfx_volume as f32);
+    }
+
+    /// Shows the current key of every action on the Controls screen.
+    fn sync_controls_menu(menu_state: &mut MenuState, input: &InputConfig) {
+        for action in crate::input::InputAction::ALL {
+            menu_state.set_key(action, input.key_for(action));
+        }
+    }
+
+    /// Whether the Controls menu is waiting for a key; the input handler then reports keys raw.
+    pub fn is_capturing_key(&self) -> bool {
+        self.menu_state.capturing.is_some()
+    }
+
+    /// Bindings changed since the last call, for the owner of the input handler to apply.
+    pub fn take_rebound_input(&mut self) -> Option<InputConfig> {
+        self.rebound_input.take()
+    }
+
+    /// Replaces the live bindings (e.g. after a config reload) without saving them.
+    pub fn apply_input_bindings(&mut self, input: &InputConfig) {
+        self.input_bindings = input.clone();
+        Self::sync_controls_menu(&mut self.menu_state, input);
+    }
+
+    /// Binds a captured `key` to `action`, swapping with the action that held it, and applies it at once.
+    /// The binding is only written to [USER_SETTINGS_PATH] when the Controls screen is saved.
+    fn rebind(&mut self, action: crate::input::InputAction, key: &str) {
+        let swapped = self.input_bindings.rebind(action, key);
+        self.menu_state.capturing = None;
+        Self::sync_controls_menu(&mut self.menu_state, &self.input_bindings);
+        self.menu_state.notice = match (swapped, self.input_bindings.shared_with(key)) {
+            (Some(other), _) => Some(format!("{} was on {:?}; the two keys were swapped", key, other)),
+            (None, Some(shared)) => Some(format!("{} is also bound to {}", key, shared)),
+            (None, None) => None,
+        };
+        self.rebound_input = Some(self.input_bindings.clone());
+    }
+
+    /// Writes the live key bindings to [USER_SETTINGS_PATH].
+    fn save_controls(&mut self) {
+        let mut settings = UserSettings::load(USER_SETTINGS_PATH);
+        settings.input = Some(self.input_bindings.clone());
+        self.menu_state.notice = Some(match settings.save(USER_SETTINGS_PATH) {
+            Ok(()) => "Controls saved".to_string(),
+            Err(e) => { eprintln!("[Settings] Failed to save '{}': {}", USER_SETTINGS_PATH, e); "Could not save the controls".to_string() }
+        });
+    }
+
+    /// Applies a selector changed in the menu and saves it to [USER_SETTINGS_PATH].
+    fn set_option(&mut self, variable: &str, value: &str, audio_manager: &GameAudioManager) {
+        let Some(volume) = crate::menu::parse_percent(value) else { return };
+        self.set_volume(variable, volume, audio_manager);
+    }
+
+    /// Applies a volume variable (0..1) from a selector or slider and saves it to [USER_SETTINGS_PATH].
+    fn set_volume(&mut self, variable: &str, volume: f64, audio_manager: &GameAudioManager) {
+        let volume = volume.clamp(0.0, 1.0);
+        let mut audio = self.audio_settings;
+        match variable {
+            "master_volume" => audio.master_volume = volume,
+            "music_volume" => audio.music_volume = volume,
+            "sfx_volume" => audio.sfx_volume = volume,
+            _ => return,
+        }
+        self.apply_audio_settings(audio, audio_manager);
+
+        let mut settings = UserSettings::load(USER_SETTINGS_PATH);
+        settings.audio = Some(audio);
+        if let Err(e) = settings.save(USER_SETTINGS_PATH) { eprintln!("[Settings] Failed to save '{}': {}", USER_SETTINGS_PATH, e); }
+    }
+
+    fn spawn_entities_from_level(&mut self, game_config: &GameConfig) { crate::entity_spawner::spawn_entities(&mut self.world, &self.level, game_config); }
+
+    fn get_soundtrack_name(&self) -> String {
+        if let Some(soundtrack) = &self.level_override.soundtrack { return soundtrack.clone(); }
+        match self.selected_character_index {
+            0 => "soundtrack_01".to_string(),
+            1 => "soundtrack_02".to_string(),
+            2 => "soundtrack_03".to_string(),
+            _ => "soundtrack_01".to_string(),
+        }
+    }
+
+    /// Blends into `name` over `[gameplay.audio] music_crossfade`; a track that is already playing continues.
+    fn crossfade_soundtrack(&self, audio_manager: &mut GameAudioManager, name: &str, game_config: &GameConfig) {
+        let duration = game_config.gameplay.audio.music_crossfade;
+        let _ = audio_manager.event_sender().send(crate::audio::AudioEvent::CrossfadeMusic(name.to_string(), duration));
+    }
+
+    /// Pans the debug free camera with the hardware movement keys.
+    ///
+    /// Called once per rendered frame with the unscaled frame time, so the camera stays
+    /// responsive while the simulation is paused or slowed down.
+    pub fn pan_free_camera(&mut self, input_state: &InputState, config: &Config, frame_time: f32) {
+        if !self.debug_overlays.free_camera { return; }
+        use crate::input::InputAction;
+        let axis = |neg, pos| input_state.is_action_pressed(pos) as i32 as f32 - input_state.is_action_pressed(neg) as i32 as f32;
+        let fast = if input_state.is_debug_modifier_held() { config.debug.free_camera_fast_multiplier } else { 1.0 };
+        let speed = config.debug.free_camera_speed * fast * frame_time;
+        self.camera.pan(Vector2D::new(axis(InputAction::MoveLeft, InputAction::MoveRight) * speed, axis(InputAction::Up, InputAction::Down) * speed));
+    }
+
+    /// Starts recording a replay, or stops the running recording and saves it.
+    ///
+    /// Recording restarts the current level from its file first (entities, RNG, stats and camera),
+    /// the same fresh state playback starts from, so the saved replay reproduces the session.
+    pub fn toggle_recording(&mut self, config: &Config, game_config: &GameConfig, texture_manager: &mut TextureManager, texture_creator: &TextureCreator<WindowContext>) -> Result<(), String> {
+        if self.replay_mode == ReplayMode::Recording { return self.stop_recording(); }
+        if self.world.game_state != GameState::Playing || self.is_exiting_to_menu || self.next_level.is_some() {
+            return Err("Recording can only start during gameplay".to_string());
+        }
+
+        // 1. Rebuild the level so the recording starts from a reproducible world.
+        let path = self.current_level_path.clone();
+        let level = load_level(&path)?;
+        self.enter_fresh_level(path.clone(), level, config, game_config, texture_manager, texture_creator)?;
+        self.level_start_coin_count = 0;
+        self.world.ui_state.level_name = crate::level::level_title(&path);
+
+        // 2. Capture what playback needs besides the input: level, seed and the physics in effect.
+        self.refresh_effective_configs(config, game_config);
+        let physics = &self.level_configs.as_deref().map_or(config, |(c, _)| c).physics;
+        self.current_replay = Replay::new(GAME_RNG_SEED, &path, physics);
+        self.replay_tick = 0;
+        self.replay_mode = ReplayMode::Recording;
+        self.debug_overlays.time_scale = 1.0;
+        println!("[Replay] Recording started on {}", path);
+        Ok(())
+    }
+
+    /// Ends the running recording and writes it to `assets/replays/recording_<unix seconds>.replay`.
+    fn stop_recording(&mut self) -> Result<(), String> {
+        self.replay_mode = ReplayMode::None;
+        let timestamp = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+        let name = format!("recording_{}", timestamp);
+        self.current_replay.save(&name)?;
+        println!("[Replay] Saved {} ticks to assets/replays/{}.replay", self.current_replay.total_ticks, name);
+        Ok(())
+    }
+
+    /// Saves a recording that is still running when its session ends (game over, exit to menu).
+    fn finish_recording(&mut self) {
+        if self.replay_mode != ReplayMode::Recording { return; }
+        if let Err(e) = self.stop_recording() { eprintln!("[Replay] Failed to save recording: {}", e); }
+    }
+
+    /// "REC" and the recorded length while a recording runs.
+    pub fn recording_label(&self) -> Option<String> {
+        (self.replay_mode == ReplayMode::Recording).then(|| format!("REC {:.1}s", self.replay_tick as f32 * crate::timestep::FIXED_TIMESTEP))
+    }
+
+    /// Rebuilds the cached configs the simulation runs against after they were cleared.
+    ///
+    /// Levels with an override file simulate against merged copies of the global configs. Replay
+    /// playback then applies the physics the replay was recorded with; live values return once it ends.
+    fn refresh_effective_configs(&mut self, config: &Config, game_config: &GameConfig) {
+        if self.level_configs.is_none() && !self.level_override.is_empty() {
+            self.level_configs = Some(Rc::new(self.level_override.apply(config, game_config)));
+        }
+        if self.replay_mode != ReplayMode::Playback {
+            self.replay_config = None;
+        } else if self.replay_config.is_none() && let Some(physics) = &self.current_replay.physics {
+            let mut replay_config = self.level_configs.as_deref().map_or(config, |(c, _)| c).clone();
+            physics.apply(&mut replay_config.physics);
+            self.replay_config = Some(Rc::new(replay_config));
+        }
+    }
+
+    /// Jumps the playing replay to `target_tick` by restarting it on a fresh copy of its level and
+    /// re-simulating every tick up to the target, without rendering or sound.
+    ///
+    /// The ticks run through the same [Simulation::tick] and reconstruct the same input as [Self::update],
+    /// so the world and camera end up as real-time playback would leave them at that tick. Seeking
+    /// backwards works the same way, from the start. Music beats are the exception: they come from the audio device and are not replayed.
+    ///
+    /// # Side Effects
+    /// * Replaces the world, stopping early if the replay triggers a level change (the next update loads it)
+    ///   or ends in a game over.
+    pub fn seek_replay(&mut self, target_tick: u64, config: &Config, game_config: &GameConfig, texture_manager: &mut TextureManager, texture_creator: &TextureCreator<WindowContext>) -> Result<(), String> {
+        if self.replay_mode != ReplayMode::Playback { return Err("No replay is playing".to_string()); }
+
+        // 1. Start over from the replay's first tick.
+        let (path, level) = self.current_replay.load_level(&config.game.start_level)?;
+        self.enter_fresh_level(path, level, config, game_config, texture_manager, texture_creator)?;
+        self.replay_tick = 0;
+
+        // 2. Re-run the ticks through the same path as playback, without rendering or sound.
+        self.refresh_effective_configs(config, game_config);
+        let (level_configs, replay_config) = (self.level_configs.clone(), self.replay_config.clone());
+        let (config, game_config) = level_configs.as_deref().map_or((config, game_config), |(c, g)| (c, g));
+        let config = replay_config.as_deref().unwrap_or(config);
+        let replay = std::mem::take(&mut self.current_replay);
+        let current_soundtrack = Some(self.get_soundtrack_name());
+        self.replay_tick = self.simulation().fast_forward(&replay, 0, target_tick, config, game_config, current_soundtrack);
+        self.current_replay = replay;
+        if self.world.game_state == GameState::GameOver { self.game_over_timer = game_config.gameplay.game_over_duration; }
+        println!("[Replay] Seeked to tick {} of {}", self.replay_tick, self.current_replay.total_ticks);
+        Ok(())
+    }
+
+    /// Advances the game logic by one fixed timestep.
+    ///
+    /// ⚠️ **Hotpath**: Called 120x per second during the physics/logic phase.
+    /// Manages replay recording/playback, system updates, and level transitions.
+    #[allow(clippy::too_many_arguments)]
+    pub fn update(&mut self, delta_time: f32, config: &Config, game_config: &GameConfig, input_state: &InputState, audio_manager: &mut GameAudioManager, texture_manager: &mut TextureManager, texture_creator: &TextureCreator<WindowContext>) -> Result<(), String> {
+        // 1. Keep menu, attract and per-level profiles in separate benchmark sessions.
+        let session = match self.world.game_state {
+            GameState::Menu(_) if self.replay_mode == ReplayMode::Playback => "attract".to_string(),
+            GameState::Menu(_) => "menu".to_string(),
+            _ => self.current_level_path.clone(),
+        };
+        self.begin_benchmark_session(&session);
+
+        self.refresh_effective_configs(config, game_config);
+        let (level_configs, replay_config) = (self.level_configs.clone(), self.replay_config.clone());
+        let (config, game_config) = level_configs.as_deref().map_or((config, game_config), |(c, g)| (c, g));
+        let config = replay_config.as_deref().unwrap_or(config);
+
+        if self.world.game_state == GameState::GameOver {
+            self.update_game_over(delta_time, config, game_config, audio_manager);
+            return Ok(());
+        }
+        if self.world.game_state == GameState::LevelComplete {
+            return self.update_level_tally(delta_time, game_config, input_state, audio_manager, texture_manager, texture_creator);
+        }
+        // 2. Resolve the effective input fact (Hardware vs Replay Buffer).
+        let mut simulated_input = InputState::default();
+        let effective_input = match self.replay_mode {
+            ReplayMode::Recording => { self.current_replay.record(self.replay_tick, input_state.get_pressed_actions(), input_state.get_player_two_actions()); self.replay_tick += 1; input_state },
+            ReplayMode::Playback => {
+                // Playback loops: past the last recorded tick it starts over from the first.
+                if self.replay_tick >= self.current_replay.total_ticks { self.replay_tick = 0; }
+                simulated_input = self.current_replay.input_at(self.replay_tick);
+                self.replay_tick += 1;
+                &simulated_input
+            },
+            ReplayMode::None => input_state,
+        };
+        
+        // Handle menu exit completion
+        let iris_closed = self.world.event_bus.read::<crate::ecs::event::EventTransitionComplete>().any(|e| e.transition_type == crate::ecs::event::TransitionType::IrisOut);
+        if self.is_exiting_to_menu && iris_closed {
+            return self.finish_exit_to_menu(config, game_config, audio_manager, texture_manager, texture_creator);
+        }
+
+        let audio_sender = audio_manager.event_sender();
+        let options = TickOptions {
+            delta_time,
+            is_paused: self.is_exiting_to_menu,
+            is_attract_mode: self.replay_mode == ReplayMode::Playback,
+            // The free camera is panned per frame in [Self::pan_free_camera].
+            follow_camera: !self.debug_overlays.free_camera && !self.is_exiting_to_menu,
+            current_soundtrack: Some(self.get_soundtrack_name()),
+        };
+        let outcome = self.simulation().tick(config, game_config, effective_input, &audio_sender, Some(audio_manager), options);
+        if outcome.game_over { self.game_over_timer = game_config.gameplay.game_over_duration; let _ = audio_sender.send(crate::audio::AudioEvent::StopMusic); }
+        // 3. A level finished in a live game is summed up before the next one loads.
+        if outcome.level_completed && self.world.game_state == GameState::Playing && self.replay_mode != ReplayMode::Playback
+            && let Some(next_level) = self.next_level.take() {
+                self.begin_tally(next_level, game_config);
+            }
+        if let Some(next_level) = self.next_level.clone() {
+            self.load_next_level(next_level, game_config, audio_manager, texture_manager, texture_creator)?;
+        }
+        
+        // --- Populate Frame Debug Info ---
+        self.world.frame_debug_info.camera_pos = Some(self.camera.position);
+        self.world.frame_debug_info.renderable_count = self.world.renderables.len();
+        if let Some(pe) = self.player_entity {
+            self.world.frame_debug_info.player_pos = self.world.positions.get(&pe).map(|p| p.0);
+            self.world.frame_debug_info.player_prev_pos = self.world.previous_positions.get(&pe).map(|p| p.0);
+            if let Some(r) = self.world.renderables.get(&pe) {
+                self.world.frame_debug_info.player_render_w = r.width;
+                self.world.frame_debug_info.player_render_h = r.height;
+            }
+        }
+        // --- End Populate ---
+        
+        Ok(())
+    }
+
+    /// Borrows the parts of the session that [Simulation::tick] advances.
+    fn simulation(&mut self) -> Simulation<'_> {
+        Simulation {
+            world: &mut self.world,
+            system_manager: &mut self.system_manager,
+            camera: &mut self.camera,
+            benchmarker: &mut self.benchmarker,
+            next_level: &mut self.next_level,
+            level: &self.level,
+            player_entity: self.player_entity,
+        }
+    }
+
+    /// Archives the running benchmark session and starts measuring `name`; no-op if already active.
+    fn begin_benchmark_session(&mut self, name: &str) {
+        if self.benchmarker.session_name == name { return; }
+        if let Some(section) = self.benchmarker.begin_session(name) { self.session_benchmarks.push(section); }
+    }
+
+    /// Writes every finished session plus the current one to a timestamped JSON file.
+    pub fn export_benchmarks(&self) -> Result<String, String> {
+        let mut sections = self.session_benchmarks.clone();
+        sections.push(self.benchmarker.section(&self.benchmarker.session_name));
+        let timestamp = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+        let path = format!("benchmarks/benchmark_{}.json", timestamp);
+        crate::benchmarker::export_report_json(&path, sections)?;
+        Ok(path)
+    }
+
+    pub fn handle_input(&mut self, input_state: &InputState, audio_manager: &mut GameAudioManager, config: &Config, game_config: &GameConfig, frame_time: f32) -> Result<(), String> {
+        // A key captured for the Controls menu is a binding, never an action.
+        if let Some(action) = self.menu_state.capturing {
+            if let Some(key) = input_state.captured_key() { self.rebind(action, key); }
+            return Ok(());
+        }
+        let was_free_camera = self.debug_overlays.free_camera;
+        self.debug_overlays.apply_input(input_state);
+        // Replays store no time scale and always play back at 1x, so a recording is simulated at 1x too.
+        if self.replay_mode == ReplayMode::Recording && self.debug_overlays.time_scale != 1.0 {
+            println!("[Replay] The time scale stays at 1x while recording.");
+            self.debug_overlays.time_scale = 1.0;
+        }
+        if was_free_camera && !self.debug_overlays.free_camera {
+            // Return straight to the player instead of easing back across the map.
+            if let Some(pe) = self.player_entity && let Some(pos) = self.world.positions.get(&pe) {
+                self.camera.set_zone(self.level.camera_zone_at(pos.0));
+                self.camera.snap_to(pos.0);
+            }
+        }
+        if self.debug_overlays.free_camera && input_state.is_debug_action_just_pressed(crate::input::DebugAction::InspectEntity) {
+            self.debug_overlays.inspected = crate::ecs::systems::debug_render::nearest_entity(&self.world, self.camera.view_center());
+        }
+        if input_state.is_action_just_pressed(crate::input::InputAction::Quit) {
+            match &self.world.game_state {
+                GameState::Menu(_) => return Err("QUIT".to_string()),
+                GameState::Playing => { self.begin_exit_to_menu(audio_manager); return Ok(()); },
+                _ => {}
+            }
+        }
+        if input_state.is_action_just_pressed(crate::input::InputAction::RestartLevel)
+            && self.world.game_state == GameState::Playing && !self.is_exiting_to_menu && self.next_level.is_none() {
+                self.request_restart();
+                return Ok(());
+        }
+        if let GameState::Menu(_) = self.world.game_state 
+            && let Some(action) = self.menu_system.update(&mut self.menu_state, input_state, frame_time) {
+                match action {
+                    MenuAction::StartGame => self.start_game(config, game_config, audio_manager),
+                    MenuAction::Quit => return Err("QUIT".to_string()),
+                    MenuAction::Navigate(target) => self.menu_state.load_screen(&target),
+                    MenuAction::SelectCharacter(idx) => {
+                        self.selected_character_index = idx;
+                        self.start_game(config, game_config, audio_manager);
+                    },
+                    MenuAction::SetOption(variable, value) => self.set_option(&variable, &value, audio_manager),
+                    MenuAction::SetValue(variable, value) => self.set_volume(&variable, value as f64, audio_manager),
+                    MenuAction::CaptureBinding(action) => { self.menu_state.capturing = Some(action); self.menu_state.notice = None; }
+                    MenuAction::SaveControls => self.save_controls(),
+                    _ => {},
+                }
+        }
+        Ok(())
+    }
+
+    /// Renders the visual state of the game world and UI.
+    ///
+    /// ⚠️ **Hotpath**: Called every frame at monitor refresh rate.
+    /// Delegates to specific render systems based on the current `GameState`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw(&mut self, renderer: &mut crate::renderer::Renderer, texture_manager: &mut TextureManager, config: &Config, game_config: &GameConfig, _input_state: &InputState, frame_count: u64, fps: u32, _alpha: f32) -> Result<(), String> {
+        if let GameState::Menu(_) = self.world.game_state { self.menu_system.draw(renderer, &mut self.menu_state, &self.font_manager)?; return Ok(()); }
+        let render_context = RenderContext { config, game_config, player_entity: self.player_entity, benchmarker: &self.benchmarker };
+        if self.world.game_state == GameState::GameOver { self.system_manager.gui_render_system.render_game_over(renderer, texture_manager, &render_context)?; return Ok(()); }
+        if let Some(tally) = self.world.level_tally.as_ref() { self.system_manager.gui_render_system.render_level_tally(renderer, &self.font_manager, tally)?; return Ok(()); }
+        self.system_manager.gui_render_system.update(renderer, texture_manager, &self.font_manager, &self.world, &self.camera, &render_context)?;
+        self.system_manager.debug_render_system.update(renderer, &self.world, &render_context, &self.camera.render_view(), &self.font_manager, frame_count, fps, self.debug_overlays, &self.level)?;
+        self.system_manager.transition_system.draw(renderer, &render_context)?;
+        Ok(())
+    }
+}
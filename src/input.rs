@@ -1,7 +1,8 @@
 use crate::config::InputConfig;
 
-use sdl3::event::Event;
+use sdl3::event::{Event, WindowEvent};
 use sdl3::keyboard::Keycode;
+use sdl3::mouse::MouseButton;
 use sdl3::EventPump;
 use std::collections::{HashMap, HashSet};
 use serde::{Serialize, Deserialize};
@@ -11,16 +12,50 @@ pub enum InputAction {
     MoveLeft,
     MoveRight,
     Jump,
+    Fire,
+    Attack,
     Up,
     Down,
     Quit,
+    RestartLevel,
+}
+
+impl InputAction {
+    /// Every gameplay action, in the order the Controls menu lists them.
+    pub const ALL: [InputAction; 9] = [
+        InputAction::MoveLeft, InputAction::MoveRight, InputAction::Jump, InputAction::Fire, InputAction::Attack,
+        InputAction::Up, InputAction::Down, InputAction::Quit, InputAction::RestartLevel,
+    ];
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 pub enum DebugAction {
     ToggleDebugInfo,
+    ToggleCollisionBoxes,
+    ToggleHotspots,
+    ToggleTileGrid,
     ToggleRecording,
     SaveReplay,
+    ExportBenchmarks,
+    ReloadConfig,
+    /// Detaches the camera from the player so it can be panned freely.
+    ToggleFreeCamera,
+    /// Selects the entity nearest the free camera's crosshair (also bound to left click).
+    InspectEntity,
+    /// Saves the next rendered frame as a PNG in `captures/`.
+    Screenshot,
+    /// Starts the rolling GIF buffer, or stops it and encodes the last few seconds.
+    ToggleGifCapture,
+    /// Freezes every gameplay system; rendering and the free camera keep running.
+    PauseSimulation,
+    /// While paused, runs exactly one fixed update tick.
+    StepFrame,
+    /// Cycles the simulation speed through 1x, 0.5x and 0.25x.
+    CycleTimeScale,
+    /// Rewinds the playing replay by `replay_seek_ticks`, re-simulating it from the start.
+    ReplaySeekBack,
+    /// Fast-forwards the playing replay by `replay_seek_ticks`.
+    ReplaySeekForward,
 }
 
 #[derive(Default, Clone)]
@@ -29,6 +64,21 @@ pub struct InputState {
     actions_just_pressed: HashSet<InputAction>,
     actions_just_released: HashSet<InputAction>,
     debug_actions_just_pressed: HashSet<DebugAction>,
+    /// Whether the debug speed modifier (e.g. Shift for the free camera) is held.
+    debug_modifier_held: bool,
+    /// Gameplay actions held on the second local player's bindings.
+    player_two_pressed: HashSet<InputAction>,
+    player_two_just_pressed: HashSet<InputAction>,
+    /// Cursor position in canvas **pixels**, once the mouse has been over the window.
+    mouse_position: Option<(f32, f32)>,
+    /// Whether the cursor moved this frame.
+    mouse_moved: bool,
+    /// Whether the left button went down this frame.
+    mouse_clicked: bool,
+    /// Name of the key pressed this frame while the handler was capturing (see [InputHandler::set_capturing]).
+    captured_key: Option<String>,
+    /// Whether the window's pixel size changed this frame (resize, fullscreen toggle, display change).
+    window_resized: bool,
 }
 
 impl InputState {
@@ -40,16 +90,75 @@ impl InputState {
         self.actions_just_pressed.contains(&action)
     }
 
+    /// Checks a held action on the device assigned to the given player slot.
+    pub fn is_player_action_pressed(&self, slot: usize, action: InputAction) -> bool {
+        if slot == 0 { self.is_action_pressed(action) } else { self.player_two_pressed.contains(&action) }
+    }
+
+    /// Checks a newly pressed action on the device assigned to the given player slot.
+    pub fn is_player_action_just_pressed(&self, slot: usize, action: InputAction) -> bool {
+        if slot == 0 { self.is_action_just_pressed(action) } else { self.player_two_just_pressed.contains(&action) }
+    }
+
 
 
     pub fn is_debug_action_just_pressed(&self, action: DebugAction) -> bool {
         self.debug_actions_just_pressed.contains(&action)
     }
 
+    pub fn is_debug_modifier_held(&self) -> bool {
+        self.debug_modifier_held
+    }
+
+    /// Cursor position in canvas **pixels** (the menu's drawing space), if known.
+    pub fn mouse_position(&self) -> Option<(f32, f32)> {
+        self.mouse_position
+    }
+
+    /// Whether the window changed size this frame, so the view transform must be refitted.
+    pub fn window_resized(&self) -> bool {
+        self.window_resized
+    }
+
+    pub fn is_mouse_moved(&self) -> bool {
+        self.mouse_moved
+    }
+
+    pub fn is_mouse_just_clicked(&self) -> bool {
+        self.mouse_clicked
+    }
+
+    /// The raw key captured this frame for rebinding, by SDL key name.
+    pub fn captured_key(&self) -> Option<&str> {
+        self.captured_key.as_deref()
+    }
+
+    /// Reports `key` as captured this frame, as the handler does in capture mode.
+    pub fn set_captured_key(&mut self, key: &str) {
+        self.captured_key = Some(key.to_string());
+    }
+
+    /// Places the cursor at `position` (output **pixels**) as if it moved there this frame, optionally clicking.
+    pub fn set_mouse(&mut self, position: (f32, f32), clicked: bool) {
+        self.mouse_position = Some(position);
+        self.mouse_moved = true;
+        self.mouse_clicked = clicked;
+    }
+
     pub fn get_pressed_actions(&self) -> Vec<InputAction> {
         self.actions_pressed.iter().copied().collect()
     }
 
+    /// The actions held on the second local player's bindings.
+    pub fn get_player_two_actions(&self) -> Vec<InputAction> {
+        self.player_two_pressed.iter().copied().collect()
+    }
+
+    /// Holds `actions` on the second local player's bindings, as replay playback does.
+    pub fn set_player_two_actions(&mut self, actions: Vec<InputAction>) {
+        self.player_two_pressed = actions.into_iter().collect();
+    }
+
     #[allow(dead_code)]
     pub fn get_pressed_actions_debug(&self) -> String {
         self.actions_pressed.iter().map(|a| format!("{:?}", a)).collect::<Vec<_>>().join(", ")
@@ -78,12 +187,21 @@ impl InputState {
                 self.actions_just_released.insert(*action);
             }
         }
+        for action in &self.player_two_pressed {
+            if !previous.player_two_pressed.contains(action) {
+                self.player_two_just_pressed.insert(*action);
+            }
+        }
     }
 }
 
 pub struct InputHandler {
     key_bindings: HashMap<Keycode, InputAction>,
     debug_bindings: HashMap<Keycode, DebugAction>,
+    debug_modifier: Option<Keycode>,
+    player_two_bindings: HashMap<Keycode, InputAction>,
+    /// When set, the next key press is reported raw instead of being mapped to an action.
+    capturing: bool,
 }
 
 impl InputHandler {
@@ -98,6 +216,8 @@ impl InputHandler {
         if let Some(k) = parse_key(&config.left) { key_bindings.insert(k, InputAction::MoveLeft); }
         if let Some(k) = parse_key(&config.right) { key_bindings.insert(k, InputAction::MoveRight); }
         if let Some(k) = parse_key(&config.jump) { key_bindings.insert(k, InputAction::Jump); }
+        if let Some(k) = parse_key(&config.fire) { key_bindings.insert(k, InputAction::Fire); }
+        if let Some(k) = parse_key(&config.attack) { key_bindings.insert(k, InputAction::Attack); }
         
         // Menu Navigation Bindings
         // Note: Using Same keys for Game Move and Menu Move is common, 
@@ -107,27 +227,75 @@ impl InputHandler {
         if let Some(k) = parse_key(&config.up) { key_bindings.insert(k, InputAction::Up); }
         if let Some(k) = parse_key(&config.down) { key_bindings.insert(k, InputAction::Down); }
         if let Some(k) = parse_key(&config.quit) { key_bindings.insert(k, InputAction::Quit); }
+        if let Some(k) = parse_key(&config.restart_level) { key_bindings.insert(k, InputAction::RestartLevel); }
         
         let mut debug_bindings = HashMap::new();
         if let Some(k) = parse_key(&config.debug_toggle) { debug_bindings.insert(k, DebugAction::ToggleDebugInfo); }
+        if let Some(k) = parse_key(&config.toggle_collision_boxes) { debug_bindings.insert(k, DebugAction::ToggleCollisionBoxes); }
+        if let Some(k) = parse_key(&config.toggle_hotspots) { debug_bindings.insert(k, DebugAction::ToggleHotspots); }
+        if let Some(k) = parse_key(&config.toggle_tile_grid) { debug_bindings.insert(k, DebugAction::ToggleTileGrid); }
         if let Some(k) = parse_key(&config.record_toggle) { debug_bindings.insert(k, DebugAction::ToggleRecording); }
         if let Some(k) = parse_key(&config.save_replay) { debug_bindings.insert(k, DebugAction::SaveReplay); }
+        if let Some(k) = parse_key(&config.export_benchmarks) { debug_bindings.insert(k, DebugAction::ExportBenchmarks); }
+        if let Some(k) = parse_key(&config.reload_config) { debug_bindings.insert(k, DebugAction::ReloadConfig); }
+        if let Some(k) = parse_key(&config.toggle_free_camera) { debug_bindings.insert(k, DebugAction::ToggleFreeCamera); }
+        if let Some(k) = parse_key(&config.inspect_entity) { debug_bindings.insert(k, DebugAction::InspectEntity); }
+        if let Some(k) = parse_key(&config.toggle_gif_capture) { debug_bindings.insert(k, DebugAction::ToggleGifCapture); }
+        if let Some(k) = parse_key(&config.screenshot) { debug_bindings.insert(k, DebugAction::Screenshot); }
+        if let Some(k) = parse_key(&config.pause_simulation) { debug_bindings.insert(k, DebugAction::PauseSimulation); }
+        if let Some(k) = parse_key(&config.step_frame) { debug_bindings.insert(k, DebugAction::StepFrame); }
+        if let Some(k) = parse_key(&config.cycle_time_scale) { debug_bindings.insert(k, DebugAction::CycleTimeScale); }
+        if let Some(k) = parse_key(&config.replay_seek_back) { debug_bindings.insert(k, DebugAction::ReplaySeekBack); }
+        if let Some(k) = parse_key(&config.replay_seek_forward) { debug_bindings.insert(k, DebugAction::ReplaySeekForward); }
+        let debug_modifier = parse_key(&config.free_camera_fast);
+
+        // Second local player (co-op) bindings share the keyboard with player one.
+        let mut player_two_bindings = HashMap::new();
+        if let Some(k) = parse_key(&config.p2_left) { player_two_bindings.insert(k, InputAction::MoveLeft); }
+        if let Some(k) = parse_key(&config.p2_right) { player_two_bindings.insert(k, InputAction::MoveRight); }
+        if let Some(k) = parse_key(&config.p2_jump) { player_two_bindings.insert(k, InputAction::Jump); }
+        if let Some(k) = parse_key(&config.p2_fire) { player_two_bindings.insert(k, InputAction::Fire); }
+        if let Some(k) = parse_key(&config.p2_attack) { player_two_bindings.insert(k, InputAction::Attack); }
 
         Self {
             key_bindings,
             debug_bindings,
+            debug_modifier,
+            player_two_bindings,
+            capturing: false,
         }
     }
 
-    pub fn process_events(&self, event_pump: &mut EventPump, input_state: &mut InputState) -> bool {
+    /// Enters or leaves key-capture mode, used while the Controls menu waits for a new binding.
+    pub fn set_capturing(&mut self, capturing: bool) {
+        self.capturing = capturing;
+    }
+
+    /// Drains the OS event queue into `input_state`; returns false when the window is closed.
+    ///
+    /// `pointer_scale` converts window coordinates to output pixels (see [crate::renderer::Renderer::pointer_scale])
+    /// and `view` moves them onto the letterboxed canvas, so mouse positions line up with what was drawn
+    /// on high-DPI or fullscreen outputs.
+    pub fn process_events(&self, event_pump: &mut EventPump, input_state: &mut InputState, pointer_scale: (f32, f32), view: &crate::units::ViewTransform) -> bool {
         // Clear "just" states
         input_state.actions_just_pressed.clear();
         input_state.actions_just_released.clear();
         input_state.debug_actions_just_pressed.clear();
+        input_state.player_two_just_pressed.clear();
+        input_state.mouse_moved = false;
+        input_state.mouse_clicked = false;
+        input_state.captured_key = None;
+        input_state.window_resized = false;
+        let to_output = |x: f32, y: f32| view.output_to_canvas(x * pointer_scale.0, y * pointer_scale.1);
 
         for event in event_pump.poll_iter() {
             match event {
                 Event::Quit { .. } => return false,
+                Event::Window { win_event: WindowEvent::PixelSizeChanged(..) | WindowEvent::Resized(..), .. } => input_state.window_resized = true,
+                // While capturing, the first press is reported by name and triggers nothing.
+                Event::KeyDown { keycode: Some(keycode), repeat: false, .. } if self.capturing => {
+                    if input_state.captured_key.is_none() { input_state.captured_key = Some(keycode.name()); }
+                }
                 Event::KeyDown { keycode: Some(keycode), repeat: false, .. } => {
                     // Check normal bindings
                     if let Some(&action) = self.key_bindings.get(&keycode) {
@@ -138,12 +306,31 @@ impl InputHandler {
                     if let Some(&action) = self.debug_bindings.get(&keycode) {
                         input_state.debug_actions_just_pressed.insert(action);
                     }
+                    // Check second player bindings
+                    if let Some(&action) = self.player_two_bindings.get(&keycode) {
+                        input_state.player_two_pressed.insert(action);
+                        input_state.player_two_just_pressed.insert(action);
+                    }
+                    if self.debug_modifier == Some(keycode) { input_state.debug_modifier_held = true; }
                 }
                 Event::KeyUp { keycode: Some(keycode), .. } => {
                     if let Some(&action) = self.key_bindings.get(&keycode) {
                         input_state.actions_pressed.remove(&action);
                         input_state.actions_just_released.insert(action);
                     }
+                    if let Some(&action) = self.player_two_bindings.get(&keycode) {
+                        input_state.player_two_pressed.remove(&action);
+                    }
+                    if self.debug_modifier == Some(keycode) { input_state.debug_modifier_held = false; }
+                }
+                Event::MouseMotion { x, y, .. } => {
+                    input_state.mouse_position = Some(to_output(x, y));
+                    input_state.mouse_moved = true;
+                }
+                Event::MouseButtonDown { mouse_btn: MouseButton::Left, x, y, .. } => {
+                    input_state.debug_actions_just_pressed.insert(DebugAction::InspectEntity);
+                    input_state.mouse_position = Some(to_output(x, y));
+                    input_state.mouse_clicked = true;
                 }
                 _ => {}
             }
@@ -0,0 +1,133 @@
+//! # Concept: Tile Layer Data
+//!
+//! Decodes the `<data>` of TMX tile layers into grids of tile GIDs: CSV or Base64
+//! (uncompressed, zlib or gzip), and the `<chunk>`s of infinite maps, which are stitched
+//! onto one dense grid.
+
+use super::{TmxData, TmxLayer};
+use base64::Engine;
+use std::io::Read;
+
+/// Decodes a layer's `<data>` element into rows of tile GIDs.
+/// Supports CSV and Base64 (uncompressed, zlib or gzip) encodings.
+pub(super) fn decode_layer_data(data: &TmxData, width: u32) -> Result<Vec<Vec<u32>>, String> {
+    // 1. Resolve the flat GID array from the layer's encoding.
+    let tile_data = decode_gids(data, &data.content)?;
+
+    // 2. Split the flat array into rows of `width` tiles.
+    Ok(tile_data.chunks(width as usize)
+        .map(|chunk| chunk.to_vec())
+        .collect())
+}
+
+/// The tile rectangle covered by the chunks of an infinite map, widened to include the origin.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) struct ChunkBounds { pub(super) min_x: i32, pub(super) min_y: i32, pub(super) width: usize, pub(super) height: usize }
+
+impl ChunkBounds {
+    /// Returns `None` for fixed-size maps (no layer has `<chunk>` data).
+    pub(super) fn of(layers: &[TmxLayer]) -> Option<Self> {
+        let mut chunks = layers.iter().flat_map(|l| &l.data.chunks).peekable();
+        chunks.peek()?;
+        let (mut min_x, mut min_y, mut max_x, mut max_y) = (0, 0, 0, 0);
+        for chunk in chunks {
+            min_x = min_x.min(chunk.x);
+            min_y = min_y.min(chunk.y);
+            max_x = max_x.max(chunk.x + chunk.width as i32);
+            max_y = max_y.max(chunk.y + chunk.height as i32);
+        }
+        Some(Self { min_x, min_y, width: (max_x - min_x) as usize, height: (max_y - min_y) as usize })
+    }
+}
+
+/// Decodes every `<chunk>` of a layer into a dense grid of `bounds`; tiles no chunk covers are 0.
+pub(super) fn stitch_chunks(data: &TmxData, bounds: ChunkBounds) -> Result<Vec<Vec<u32>>, String> {
+    let mut tiles = vec![vec![0; bounds.width]; bounds.height];
+    for chunk in &data.chunks {
+        let gids = decode_gids(data, &chunk.content)?;
+        if gids.len() != (chunk.width * chunk.height) as usize {
+            return Err(format!("Chunk at ({}, {}) has {} tiles, expected {}x{}.", chunk.x, chunk.y, gids.len(), chunk.width, chunk.height));
+        }
+        let (left, top) = ((chunk.x - bounds.min_x) as usize, (chunk.y - bounds.min_y) as usize);
+        for (row, line) in gids.chunks(chunk.width as usize).enumerate() {
+            tiles[top + row][left..left + line.len()].copy_from_slice(line);
+        }
+    }
+    Ok(tiles)
+}
+
+/// Decodes one run of GIDs (a whole layer or a single chunk) using the layer's encoding.
+fn decode_gids(data: &TmxData, content: &str) -> Result<Vec<u32>, String> {
+    Ok(match data.encoding.as_str() {
+        "csv" => content
+            .split(',')
+            .map(|s| s.trim())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.parse::<u32>().unwrap_or(0))
+            .collect(),
+        "base64" => {
+            let encoded: String = content.chars().filter(|c| !c.is_whitespace()).collect();
+            let bytes = base64::engine::general_purpose::STANDARD.decode(encoded)
+                .map_err(|e| format!("Invalid base64 layer data: {}", e))?;
+            let raw = decompress_layer_bytes(bytes, data.compression.as_deref())?;
+            if raw.len() % 4 != 0 {
+                return Err(format!("Layer data length {} is not a multiple of 4 bytes.", raw.len()));
+            }
+            // GIDs are stored as little-endian u32 values.
+            raw.chunks_exact(4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]])).collect()
+        }
+        other => return Err(format!("Unsupported layer encoding '{}' (expected csv or base64).", other)),
+    })
+}
+
+/// Inflates Base64-decoded layer bytes according to the TMX `compression` attribute.
+fn decompress_layer_bytes(bytes: Vec<u8>, compression: Option<&str>) -> Result<Vec<u8>, String> {
+    let mut raw = Vec::new();
+    match compression {
+        None | Some("") => return Ok(bytes),
+        Some("zlib") => { flate2::read::ZlibDecoder::new(bytes.as_slice()).read_to_end(&mut raw).map_err(|e| format!("Failed to inflate zlib layer data: {}", e))?; }
+        Some("gzip") => { flate2::read::GzDecoder::new(bytes.as_slice()).read_to_end(&mut raw).map_err(|e| format!("Failed to inflate gzip layer data: {}", e))?; }
+        Some(other) => return Err(format!("Unsupported layer compression '{}'.", other)),
+    }
+    Ok(raw)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode_base64(gids: &[u32], compression: Option<&str>) -> TmxData {
+        use std::io::Write;
+        let raw: Vec<u8> = gids.iter().flat_map(|g| g.to_le_bytes()).collect();
+        let bytes = match compression {
+            Some("zlib") => { let mut e = flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default()); e.write_all(&raw).unwrap(); e.finish().unwrap() }
+            Some("gzip") => { let mut e = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default()); e.write_all(&raw).unwrap(); e.finish().unwrap() }
+            _ => raw,
+        };
+        TmxData {
+            encoding: "base64".to_string(),
+            compression: compression.map(str::to_string),
+            content: format!("\n   {}\n", base64::engine::general_purpose::STANDARD.encode(bytes)),
+            chunks: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_base64_layer_data_matches_csv() {
+        let gids = [0, 1, 2, 3, 17, 0, 255, 1024];
+        let csv = TmxData { encoding: "csv".to_string(), compression: None, content: "0,1,2,3,\n17,0,255,1024".to_string(), chunks: Vec::new() };
+        let expected = decode_layer_data(&csv, 4).unwrap();
+        assert_eq!(expected, vec![vec![0, 1, 2, 3], vec![17, 0, 255, 1024]]);
+
+        for compression in [None, Some("zlib"), Some("gzip")] {
+            let data = encode_base64(&gids, compression);
+            assert_eq!(decode_layer_data(&data, 4).unwrap(), expected, "compression {:?}", compression);
+        }
+    }
+
+    #[test]
+    fn test_unknown_layer_encoding_is_rejected() {
+        let data = TmxData { encoding: "base64".to_string(), compression: Some("zstd".to_string()), content: "AAAAAA==".to_string(), chunks: Vec::new() };
+        assert!(decode_layer_data(&data, 1).is_err());
+    }
+}
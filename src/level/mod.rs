@@ -0,0 +1,648 @@
+//! # Manager: Level Infrastructure
+//! 
+//! This module is the authority for game world representation. It parses 
+//! Tiled (TMX) and Tileset (TSX) data to build the physical collision 
+//! grid, visual tile map, and entity spawning templates.
+
+use crate::math::Vector2D;
+use serde::Deserialize;
+use quick_xml::de::from_str;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::fs;
+
+mod chunks;
+mod templates;
+
+use chunks::{decode_layer_data, stitch_chunks, ChunkBounds};
+use templates::apply_template;
+
+/// # Concept: Level Data
+/// The engine-ready representation of a game world.
+#[derive(Clone, Default)]
+pub struct Level {
+    pub tileset: Tileset,
+    pub map: Map,
+    /// Grid of [TileKind]s from the collision layer, driving all tile physics.
+    pub collision: Collision<TileKind>,
+    /// Grid of damaging tiles (1 = hazard), parsed from the TSX `hazard` property.
+    pub hazards: Collision,
+    /// Camera lock regions from `CameraZone` objects and the `CameraBounds` layer.
+    pub camera_zones: Vec<Region>,
+    /// Grid of water tiles (1 = water), parsed from the TSX `water` property.
+    pub water: Collision,
+    /// Swimmable regions from `Water` rectangle objects.
+    pub water_volumes: Vec<Region>,
+    pub entities: Vec<Entity>,
+}
+
+/// # Concept: Tile Kind
+/// How a collision-layer tile blocks movement.
+///
+/// Slopes come from the TSX `slope` property: `"right"` rises towards the right (`/`),
+/// `"left"` rises towards the left (`\`). `base` and `height` are the floor heights at the low
+/// and high edge as fractions of the tile height, so a pair of half slopes is
+/// `slope_height = 0.5` followed by `slope_base = 0.5`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum TileKind {
+    #[default]
+    Empty,
+    Solid,
+    Slope { rises_right: bool, base: f32, height: f32 },
+}
+
+impl TileKind {
+    /// Full blocks stop movement from every side; slopes only carry entities standing on them.
+    pub fn is_solid(self) -> bool { self == TileKind::Solid }
+
+    /// Floor height above the tile's bottom edge, as a fraction of the tile height, at
+    /// `fraction_x` (`0.0` = left edge, `1.0` = right edge). `None` for non-slopes.
+    pub fn slope_height_at(self, fraction_x: f32) -> Option<f32> {
+        let TileKind::Slope { rises_right, base, height } = self else { return None; };
+        let t = fraction_x.clamp(0.0, 1.0);
+        let t = if rises_right { t } else { 1.0 - t };
+        Some(base + (height - base) * t)
+    }
+}
+
+/// # Concept: Region
+/// An axis-aligned world-space rectangle (in **pixels**), such as a camera zone the view is confined
+/// to while the player is inside it, or a water volume.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Region {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Region {
+    pub fn contains(&self, point: Vector2D) -> bool {
+        point.x >= self.x && point.x < self.x + self.width && point.y >= self.y && point.y < self.y + self.height
+    }
+}
+
+impl Level {
+    /// Returns the first camera zone containing `point`, if any.
+    pub fn camera_zone_at(&self, point: Vector2D) -> Option<Region> {
+        self.camera_zones.iter().copied().find(|z| z.contains(point))
+    }
+
+    /// Finds the position of the `SpawnPoint` object with the given Tiled name.
+    pub fn spawn_point(&self, name: &str) -> Option<Vector2D> {
+        self.entities.iter()
+            .find(|e| e.r#type == "SpawnPoint" && e.name.as_deref() == Some(name))
+            .map(|e| e.position)
+    }
+
+    /// Finds a `Marker` object by name (used as a spawn location by scripted triggers).
+    pub fn marker(&self, name: &str) -> Option<Vector2D> {
+        self.entities.iter()
+            .find(|e| e.r#type == "Marker" && e.name.as_deref() == Some(name))
+            .map(|e| e.position)
+    }
+
+    /// Returns the surface name (TSX `surface` property) of the collision tile at grid coordinates.
+    pub fn surface_at(&self, x: usize, y: usize) -> Option<&str> {
+        let gid = *self.map.layers.get(self.map.collision_layer)?.tiles.get(y)?.get(x)?;
+        self.tileset.surfaces.get(&gid).map(String::as_str)
+    }
+
+    /// Builds a level from an in-memory grid (no TMX/TSX), e.g. for headless tests.
+    /// Non-zero entries are solid and are also drawn as the single tile layer.
+    pub fn from_tiles(tile_width: u32, tile_height: u32, tiles: Vec<Vec<u32>>) -> Self {
+        let collision = tiles.iter().map(|row| row.iter().map(|&t| if t != 0 { TileKind::Solid } else { TileKind::Empty }).collect()).collect();
+        let map = Map {
+            width: tiles.first().map_or(0, Vec::len),
+            height: tiles.len(),
+            layers: vec![TileLayer { name: "collision".to_string(), tiles, z_index: BACKGROUND_LAYER_Z, opacity: 1.0 }],
+            collision_layer: 0,
+        };
+        Self {
+            tileset: Tileset { tile_width, tile_height, ..Default::default() },
+            map,
+            collision: Collision { tiles: collision },
+            ..Default::default()
+        }
+    }
+
+    /// Checks whether a world-space point (in **pixels**) lies in a water tile or `Water` volume.
+    pub fn is_water(&self, point: Vector2D) -> bool {
+        if self.water_volumes.iter().any(|v| v.contains(point)) { return true; }
+        let (tile_w, tile_h) = (self.tileset.tile_width as f32, self.tileset.tile_height as f32);
+        if tile_w <= 0.0 || tile_h <= 0.0 || point.x < 0.0 || point.y < 0.0 { return false; }
+        let (x, y) = ((point.x / tile_w) as usize, (point.y / tile_h) as usize);
+        self.water.tiles.get(y).and_then(|row| row.get(x)).is_some_and(|&t| t != 0)
+    }
+
+    /// Checks whether the tile at grid coordinates damages the player on contact.
+    pub fn is_hazard(&self, x: usize, y: usize) -> bool {
+        self.hazards.tiles.get(y).and_then(|row| row.get(x)).is_some_and(|&t| t != 0)
+    }
+
+    /// Authoritative check for full-block solidity at grid coordinates. Slopes are not solid.
+    pub fn is_solid(&self, x: usize, y: usize) -> bool {
+        self.tile_kind(x, y).is_solid()
+    }
+
+    /// The collision kind of the tile at grid coordinates; `Empty` outside the map.
+    pub fn tile_kind(&self, x: usize, y: usize) -> TileKind {
+        self.collision.tiles.get(y).and_then(|row| row.get(x)).copied().unwrap_or_default()
+    }
+
+    /// Whether an entity can stand on the tile at grid coordinates (full block or slope).
+    pub fn is_ground(&self, x: usize, y: usize) -> bool {
+        self.tile_kind(x, y) != TileKind::Empty
+    }
+
+    /// The world-space y (in **pixels**) of the slope surface at world x `x` in tile row `row`,
+    /// or `None` when that tile is not a slope.
+    pub fn slope_floor_y(&self, x: f32, row: usize) -> Option<f32> {
+        let (tile_w, tile_h) = (self.tileset.tile_width as f32, self.tileset.tile_height as f32);
+        if tile_w <= 0.0 || x < 0.0 { return None; }
+        let column = (x / tile_w).floor();
+        let height = self.tile_kind(column as usize, row).slope_height_at(x / tile_w - column)?;
+        Some((row as f32 + 1.0 - height) * tile_h)
+    }
+
+    /// True when no solid tile lies on the segment between two world-space points (in **pixels**).
+    ///
+    /// Samples the segment every half tile, so a wall one tile thick is never stepped over.
+    pub fn line_of_sight(&self, from: Vector2D, to: Vector2D) -> bool {
+        let (tile_w, tile_h) = (self.tileset.tile_width as f32, self.tileset.tile_height as f32);
+        if tile_w <= 0.0 || tile_h <= 0.0 { return true; }
+        let (dx, dy) = (to.x - from.x, to.y - from.y);
+        let steps = ((dx * dx + dy * dy).sqrt() / (tile_w.min(tile_h) / 2.0)).ceil().max(1.0) as usize;
+        (0..=steps).all(|step| {
+            let t = step as f32 / steps as f32;
+            let (x, y) = (from.x + dx * t, from.y + dy * t);
+            x < 0.0 || y < 0.0 || !self.is_solid((x / tile_w) as usize, (y / tile_h) as usize)
+        })
+    }
+}
+
+/// Decodes TMX and TSX XML files into an engine-ready Level structure.
+pub fn load_level(path: &str) -> Result<Level, String> {
+    // 1. Read the TMX map file from disk.
+    let tmx_str = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let tmx_map: TmxMap = from_str(&tmx_str).map_err(|e| e.to_string())?;
+
+    // 2. Split the map's children into tilesets, tile layers and object groups, preserving document order.
+    let mut tileset_refs = Vec::new();
+    let mut tmx_layers = Vec::new();
+    let mut object_groups = Vec::new();
+    for item in tmx_map.content {
+        match item {
+            TmxMapContent::Tileset(tileset) => tileset_refs.push(tileset),
+            TmxMapContent::Layer(layer) => tmx_layers.push(layer),
+            TmxMapContent::ObjectGroup(group) => object_groups.push(group),
+            TmxMapContent::Other => {}
+        }
+    }
+    if tmx_layers.is_empty() { return Err("No tile layer found in TMX file".to_string()); }
+
+    // 3. Decode every tile layer. The collision layer is the one flagged `collision = true`,
+    // or the first layer when none is flagged (single-layer levels).
+    let collision_index = tmx_layers.iter()
+        .position(|l| l.property("collision").is_some_and(|v| v == "true"))
+        .unwrap_or(0);
+
+    // Infinite maps store chunks at signed tile coordinates; every layer is stitched onto one
+    // grid whose origin includes (0, 0), and objects are shifted by the same offset.
+    let chunk_bounds = ChunkBounds::of(&tmx_layers);
+    let (map_width, map_height) = chunk_bounds.map_or((tmx_map.width as usize, tmx_map.height as usize), |b| (b.width, b.height));
+    let object_offset = chunk_bounds.map_or(Vector2D::default(), |b| {
+        Vector2D::new((-b.min_x * tmx_map.tile_width as i32) as f32, (-b.min_y * tmx_map.tile_height as i32) as f32)
+    });
+
+    let mut layers = Vec::with_capacity(tmx_layers.len());
+    for (index, tmx_layer) in tmx_layers.iter().enumerate() {
+        let tiles = match chunk_bounds {
+            Some(bounds) => stitch_chunks(&tmx_layer.data, bounds)?,
+            None => decode_layer_data(&tmx_layer.data, tmx_map.width)?,
+        };
+        // Layers up to the collision layer sit behind entities; later layers are foreground.
+        let default_z = if index <= collision_index { BACKGROUND_LAYER_Z } else { FOREGROUND_LAYER_Z };
+        let z_index = tmx_layer.property("z_index").and_then(|v| v.parse().ok()).unwrap_or(default_z);
+        layers.push(TileLayer {
+            name: tmx_layer.name.clone().unwrap_or_default(),
+            tiles,
+            z_index,
+            opacity: tmx_layer.opacity.unwrap_or(1.0).clamp(0.0, 1.0),
+        });
+    }
+    let map_tiles = &layers[collision_index].tiles;
+
+    // 4. Resolve the external TSX tileset reference.
+    let tileset_ref = tileset_refs.first().ok_or("No <tileset> reference found in TMX file")?;
+    let tmx_path = Path::new(path);
+    let tsx_path = tmx_path.parent().unwrap_or_else(|| Path::new("")).join(&tileset_ref.source);
+    
+    let tsx_str = fs::read_to_string(&tsx_path).map_err(|e| format!("Failed to read TSX file: {}", e))?;
+    let tmx_tileset: TmxTileset = from_str(&tsx_str).map_err(|e| format!("Failed to parse TSX file: {}", e))?;
+
+    let mut solid_tiles = HashSet::new();
+    let mut slope_tiles = HashMap::new();
+    let mut hazard_tiles = HashSet::new();
+    let mut water_tiles = HashSet::new();
+    let mut surfaces = HashMap::new();
+    let mut animations = HashMap::new();
+    let mut image_source = "".to_string();
+
+    // 5. Identify solid tiles (custom TSX properties) and animated tiles (<animation> frames).
+    for item in tmx_tileset.content {
+        match item {
+            TmxTilesetContent::Image(image) => {
+                let image_path = tsx_path.parent().unwrap_or_else(|| Path::new("")).join(image.source);
+                let canonical_path = fs::canonicalize(&image_path).map_err(|e| e.to_string())?;
+                image_source = canonical_path.to_string_lossy().to_string();
+            }
+            TmxTilesetContent::Tile(tile) => {
+                if let Some(animation) = tile.animation {
+                    let frames: Vec<(u32, u32)> = animation.frames.iter()
+                        .map(|f| (f.tile_id + tileset_ref.firstgid, f.duration.max(1)))
+                        .collect();
+                    if !frames.is_empty() {
+                        animations.insert(tile.id + tileset_ref.firstgid, TileAnimation::new(frames));
+                    }
+                }
+                if let Some(properties) = tile.properties {
+                    for prop in properties.properties {
+                        if prop.name == "solid" && prop.property_type.as_deref() == Some("bool") && prop.value == "true" {
+                            solid_tiles.insert(tile.id + tileset_ref.firstgid);
+                        }
+                        if prop.name == "hazard" && prop.property_type.as_deref() == Some("bool") && prop.value == "true" {
+                            hazard_tiles.insert(tile.id + tileset_ref.firstgid);
+                        }
+                        if prop.name == "water" && prop.property_type.as_deref() == Some("bool") && prop.value == "true" {
+                            water_tiles.insert(tile.id + tileset_ref.firstgid);
+                        }
+                        if prop.name == "surface" {
+                            surfaces.insert(tile.id + tileset_ref.firstgid, prop.value.clone());
+                        }
+                    }
+                    if let Some(slope) = slope_kind(&properties.properties) {
+                        slope_tiles.insert(tile.id + tileset_ref.firstgid, slope);
+                    }
+                }
+            }
+        }
+    }
+
+    // 6. Construct the physical collision, hazard and water grids from the designated collision layer.
+    let collision_tiles: Vec<Vec<TileKind>> = map_tiles.iter()
+        .map(|row| {
+            row.iter().map(|&tile_id| {
+                if solid_tiles.contains(&tile_id) { TileKind::Solid } else { slope_tiles.get(&tile_id).copied().unwrap_or_default() }
+            }).collect()
+        })
+        .collect();
+    let hazard_grid: Vec<Vec<u32>> = map_tiles.iter()
+        .map(|row| {
+            row.iter().map(|&tile_id| if hazard_tiles.contains(&tile_id) { 1 } else { 0 }).collect()
+        })
+        .collect();
+    let water_grid: Vec<Vec<u32>> = map_tiles.iter()
+        .map(|row| {
+            row.iter().map(|&tile_id| if water_tiles.contains(&tile_id) { 1 } else { 0 }).collect()
+        })
+        .collect();
+
+    let tileset = Tileset {
+        texture: image_source,
+        tile_width: tmx_tileset.tile_width,
+        tile_height: tmx_tileset.tile_height,
+        animations,
+        surfaces,
+    };
+
+    // 7. Resolve Tiled object templates, then map TMX objects to internal Entity templates and apply scale.
+    let mut templates = HashMap::new();
+    for object in object_groups.iter_mut().flat_map(|g| g.objects.iter_mut()) {
+        if object.template.is_some() { *object = apply_template(object, tmx_path.parent().unwrap_or_else(|| Path::new("")), &mut templates)?; }
+    }
+    let (entities, camera_zones, water_volumes) = split_objects(&object_groups, object_offset);
+
+    let map = Map { width: map_width, height: map_height, layers, collision_layer: collision_index };
+    Ok(Level { tileset, map, collision: Collision { tiles: collision_tiles }, hazards: Collision { tiles: hazard_grid }, camera_zones, water: Collision { tiles: water_grid }, water_volumes, entities })
+}
+
+/// A display title for the level at `path`: the folder name for `<folder>/level.tmx`, else
+/// the file name, with underscores as spaces and each word capitalized ("World 1 Level 1").
+pub fn level_title(path: &str) -> String {
+    let path = Path::new(path);
+    let name = match path.file_stem().and_then(|s| s.to_str()) {
+        Some("level") | None => path.parent().and_then(|p| p.file_name()).and_then(|s| s.to_str()).unwrap_or_default(),
+        Some(stem) => stem,
+    };
+    name.split('_').filter(|w| !w.is_empty()).map(|word| {
+        let mut chars = word.chars();
+        chars.next().map_or(String::new(), |first| first.to_uppercase().chain(chars).collect())
+    }).collect::<Vec<_>>().join(" ")
+}
+
+/// Name of the object layer whose rectangles all become camera bounds, whatever their type.
+pub const CAMERA_BOUNDS_LAYER: &str = "CameraBounds";
+
+/// Separates level geometry from spawnable objects: `CameraZone` rectangles (and every rectangle
+/// on the [CAMERA_BOUNDS_LAYER]) become camera bounds, and `Water` rectangles become water volumes.
+fn split_objects(object_groups: &[TmxObjectGroup], object_offset: Vector2D) -> (Vec<Entity>, Vec<Region>, Vec<Region>) {
+    let mut entities = Vec::new();
+    let mut camera_zones = Vec::new();
+    let mut water_volumes = Vec::new();
+    for object_group in object_groups {
+        let is_bounds_layer = object_group.name.as_deref() == Some(CAMERA_BOUNDS_LAYER);
+        for object in &object_group.objects {
+            let mut entity = object_to_entity(object);
+            entity.position = Vector2D::new(entity.position.x + object_offset.x, entity.position.y + object_offset.y);
+            if is_bounds_layer || entity.r#type == "CameraZone" {
+                match entity.size {
+                    Some((width, height)) => camera_zones.push(Region { x: entity.position.x, y: entity.position.y, width, height }),
+                    None => eprintln!("[Level] CameraZone '{}' has no size and was ignored.", entity.name.as_deref().unwrap_or("")),
+                }
+                continue;
+            }
+            if entity.r#type == "Water" {
+                match entity.size {
+                    Some((width, height)) => water_volumes.push(Region { x: entity.position.x, y: entity.position.y, width, height }),
+                    None => eprintln!("[Level] Water '{}' has no size and was ignored.", entity.name.as_deref().unwrap_or("")),
+                }
+                continue;
+            }
+            entities.push(entity);
+        }
+    }
+    (entities, camera_zones, water_volumes)
+}
+
+/// Reads the `slope`, `slope_base` and `slope_height` tile properties; `None` unless `slope` is "left" or "right".
+fn slope_kind(properties: &[TmxProperty]) -> Option<TileKind> {
+    let get = |name: &str| properties.iter().find(|p| p.name == name).map(|p| p.value.as_str());
+    let rises_right = match get("slope")? {
+        "right" => true,
+        "left" => false,
+        other => { eprintln!("[Level] Unknown slope direction '{}' (expected left or right).", other); return None; }
+    };
+    let fraction = |name: &str, default: f32| get(name).and_then(|v| v.parse::<f32>().ok()).unwrap_or(default).clamp(0.0, 1.0);
+    Some(TileKind::Slope { rises_right, base: fraction("slope_base", 0.0), height: fraction("slope_height", 1.0) })
+}
+
+/// Converts a TMX object into an Entity template with a top-left origin.
+/// Tiled anchors tile objects (those with a `gid`) at their bottom-left corner,
+/// while rectangles and points are anchored at their top-left corner. Both rotate clockwise
+/// about that anchor, so a rotated object's position is that of the unrotated box sharing
+/// its center, which is the pivot the renderer rotates sprites around.
+fn object_to_entity(object: &TmxObject) -> Entity {
+    let mut properties = HashMap::new();
+    if let Some(props) = &object.properties {
+        for prop in &props.properties {
+            properties.insert(prop.name.clone(), prop.value.clone());
+        }
+    }
+
+    let size = match (object.width, object.height) {
+        (Some(w), Some(h)) if w > 0.0 && h > 0.0 => Some((w, h)),
+        _ => None,
+    };
+    let rotation = object.rotation.unwrap_or(0.0);
+    let position = match size {
+        Some((w, h)) => {
+            // 1. Offset from the anchor to the center, before rotation.
+            let (dx, dy) = if object.gid.is_some() { (w / 2.0, -h / 2.0) } else { (w / 2.0, h / 2.0) };
+            // 2. Rotate it clockwise (y points down) and step back to the top-left corner.
+            let (sin, cos) = rotation.to_radians().sin_cos();
+            Vector2D::new(object.x + dx * cos - dy * sin - w / 2.0, object.y + dx * sin + dy * cos - h / 2.0)
+        }
+        None => Vector2D::new(object.x, object.y),
+    };
+
+    Entity {
+        r#type: object.r#type.clone().unwrap_or("Default".to_string()),
+        name: object.name.clone(),
+        position,
+        size,
+        rotation,
+        is_tile: object.gid.is_some(),
+        properties,
+    }
+}
+
+// --- Internal Data Models ---
+/// `surfaces` maps tile GIDs to the TSX `surface` property (e.g. "grass"), used for footstep sounds.
+#[derive(Clone, Default)] pub struct Tileset { pub texture: String, pub tile_width: u32, pub tile_height: u32, pub animations: HashMap<u32, TileAnimation>, pub surfaces: HashMap<u32, String> }
+/// # Concept: Animated Tile
+/// A looping sequence of tile GIDs parsed from a TSX `<animation>` element.
+#[derive(Clone, Debug, Default)]
+pub struct TileAnimation {
+    /// Frames as (tile GID, duration in **milliseconds**).
+    pub frames: Vec<(u32, u32)>,
+    /// Length of one full loop in **milliseconds**.
+    pub total_duration: u32,
+}
+
+impl TileAnimation {
+    pub fn new(frames: Vec<(u32, u32)>) -> Self {
+        let total_duration = frames.iter().map(|(_, d)| d).sum();
+        Self { frames, total_duration }
+    }
+
+    /// Resolves the GID to draw at the given global animation time (in **seconds**).
+    pub fn frame_at(&self, time: f32) -> u32 {
+        if self.total_duration == 0 { return self.frames.first().map_or(0, |f| f.0); }
+        let mut t = ((time * 1000.0) as u64 % self.total_duration as u64) as u32;
+        for &(gid, duration) in &self.frames {
+            if t < duration { return gid; }
+            t -= duration;
+        }
+        self.frames[0].0
+    }
+}
+
+/// Default depth for tile layers drawn behind entities.
+pub const BACKGROUND_LAYER_Z: u8 = 200;
+/// Default depth for tile layers drawn in front of entities.
+pub const FOREGROUND_LAYER_Z: u8 = 50;
+
+/// # Concept: Tile Layer
+/// One visual layer of the tile map, drawn at its own depth.
+#[derive(Clone, Default)]
+pub struct TileLayer {
+    pub name: String,
+    pub tiles: Vec<Vec<u32>>,
+    /// Draw order (higher values are drawn first/behind), comparable to `Renderable.z_index`.
+    pub z_index: u8,
+    /// Layer opacity in the range `[0.0, 1.0]`.
+    pub opacity: f32,
+}
+
+/// # Concept: Tile Map
+/// All tile layers of a level in TMX document order, with the map size in **tiles**.
+/// `collision_layer` indexes the entry of `layers` that drives collision and surface lookups.
+#[derive(Clone, Default)] pub struct Map { pub width: usize, pub height: usize, pub layers: Vec<TileLayer>, pub collision_layer: usize }
+/// A per-tile grid: [TileKind]s for the collision layer, `1`/`0` flags for hazards and water.
+#[derive(Clone, Default)] pub struct Collision<T = u32> { pub tiles: Vec<Vec<T>> }
+/// # Concept: Entity Template
+/// A spawnable object from the level. `size` is the rectangle drawn in Tiled (width, height in **pixels**), if any,
+/// `rotation` its clockwise rotation in **degrees**, and `is_tile` whether it was placed as a tile object (`gid`).
+#[derive(Clone, Default)] pub struct Entity { pub r#type: String, pub name: Option<String>, pub position: Vector2D, pub size: Option<(f32, f32)>, pub rotation: f32, pub is_tile: bool, pub properties: HashMap<String, String> }
+
+impl Entity {
+    /// The axis-aligned box around the object as drawn in Tiled, rotation included:
+    /// top-left corner and (width, height) in **pixels**. `None` for point objects.
+    pub fn bounds(&self) -> Option<(Vector2D, (f32, f32))> {
+        let (w, h) = self.size?;
+        if self.rotation == 0.0 { return Some((self.position, (w, h))); }
+        // Snapped to whole pixels, so right angles do not lose a pixel to float error.
+        let (sin, cos) = self.rotation.to_radians().sin_cos();
+        let (bw, bh) = ((w * cos.abs() + h * sin.abs()).round(), (w * sin.abs() + h * cos.abs()).round());
+        let center = Vector2D::new(self.position.x + w / 2.0, self.position.y + h / 2.0);
+        Some((Vector2D::new((center.x - bw / 2.0).round(), (center.y - bh / 2.0).round()), (bw, bh)))
+    }
+}
+
+#[derive(Debug, Deserialize)] struct TmxMap { #[serde(rename = "@width")] width: u32, #[serde(rename = "@height")] height: u32, #[serde(rename = "@tilewidth", default)] tile_width: u32, #[serde(rename = "@tileheight", default)] tile_height: u32, #[serde(rename = "$value", default)] content: Vec<TmxMapContent> }
+#[derive(Debug, Deserialize)] enum TmxMapContent { #[serde(rename = "tileset")] Tileset(TmxTilesetRef), #[serde(rename = "layer")] Layer(TmxLayer), #[serde(rename = "objectgroup")] ObjectGroup(TmxObjectGroup), #[serde(other)] Other }
+#[derive(Debug, Deserialize)] struct TmxTilesetRef { #[serde(rename = "@firstgid")] firstgid: u32, #[serde(rename = "@source")] source: String }
+#[derive(Debug, Deserialize)] struct TmxLayer { #[serde(rename = "@name")] name: Option<String>, #[serde(rename = "@opacity")] opacity: Option<f32>, properties: Option<TmxProperties>, data: TmxData }
+
+impl TmxLayer {
+    /// Looks up a custom Tiled property value by name.
+    fn property(&self, name: &str) -> Option<&str> {
+        self.properties.as_ref()?.properties.iter().find(|p| p.name == name).map(|p| p.value.as_str())
+    }
+}
+/// Fixed-size maps carry their GIDs as text; infinite maps carry them in `<chunk>` children instead.
+#[derive(Debug, Deserialize)] struct TmxData { #[serde(rename = "@encoding")] encoding: String, #[serde(rename = "@compression")] compression: Option<String>, #[serde(rename = "$text", default)] content: String, #[serde(rename = "chunk", default)] chunks: Vec<TmxChunk> }
+/// A block of an infinite map's layer at tile position (`x`, `y`), which may be negative.
+#[derive(Debug, Deserialize)] struct TmxChunk { #[serde(rename = "@x")] x: i32, #[serde(rename = "@y")] y: i32, #[serde(rename = "@width")] width: u32, #[serde(rename = "@height")] height: u32, #[serde(rename = "$text", default)] content: String }
+#[derive(Debug, Deserialize)] struct TmxObjectGroup { #[serde(rename = "@name")] name: Option<String>, #[serde(rename = "object", default)] objects: Vec<TmxObject> }
+/// `x`/`y` default to 0 because template (`.tx`) objects carry no position of their own.
+#[derive(Debug, Clone, Deserialize)] struct TmxObject { #[serde(rename = "@name")] name: Option<String>, #[serde(rename = "@type")] r#type: Option<String>, #[serde(rename = "@gid")] gid: Option<u32>, #[serde(rename = "@x", default)] x: f32, #[serde(rename = "@y", default)] y: f32, #[serde(rename = "@width")] width: Option<f32>, #[serde(rename = "@height")] height: Option<f32>, #[serde(rename = "@rotation")] rotation: Option<f32>, #[serde(rename = "@template")] template: Option<String>, properties: Option<TmxProperties> }
+#[derive(Debug, Clone, Deserialize)] struct TmxProperties { #[serde(rename = "property", default)] properties: Vec<TmxProperty> }
+#[derive(Debug, Clone, Deserialize)] struct TmxProperty { #[serde(rename = "@name")] name: String, #[serde(rename = "@type")] property_type: Option<String>, #[serde(rename = "@value")] value: String }
+#[derive(Debug, Deserialize)] struct TmxTileset { #[serde(rename = "@tilewidth")] tile_width: u32, #[serde(rename = "@tileheight")] tile_height: u32, #[serde(rename = "$value")] content: Vec<TmxTilesetContent> }
+#[derive(Debug, Deserialize)] enum TmxTilesetContent { #[serde(rename = "image")] Image(TmxImage), #[serde(rename = "tile")] Tile(TmxTile) }
+#[derive(Debug, Deserialize)] struct TmxImage { #[serde(rename = "@source")] source: String }
+#[derive(Debug, Deserialize)] struct TmxTile { #[serde(rename = "@id")] id: u32, properties: Option<TmxProperties>, animation: Option<TmxAnimation> }
+#[derive(Debug, Deserialize)] struct TmxAnimation { #[serde(rename = "frame", default)] frames: Vec<TmxFrame> }
+#[derive(Debug, Deserialize)] struct TmxFrame { #[serde(rename = "@tileid")] tile_id: u32, #[serde(rename = "@duration")] duration: u32 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tile_animation_loops() {
+        let anim = TileAnimation::new(vec![(10, 100), (11, 200)]);
+        assert_eq!(anim.frame_at(0.0), 10);
+        assert_eq!(anim.frame_at(0.15), 11);
+        // 0.35s wraps around the 300ms loop to 50ms -> first frame again.
+        assert_eq!(anim.frame_at(0.35), 10);
+    }
+
+    #[test]
+    fn test_object_size_and_origin() {
+        let rect: TmxObject = from_str(r#"<object type="Trigger" x="100" y="200" width="64" height="32"/>"#).unwrap();
+        let entity = object_to_entity(&rect);
+        assert_eq!(entity.size, Some((64.0, 32.0)));
+        assert_eq!((entity.position.x, entity.position.y), (100.0, 200.0), "Rectangles are anchored top-left");
+
+        let tile: TmxObject = from_str(r#"<object type="GoldCoin" gid="5" x="100" y="200" width="32" height="32"/>"#).unwrap();
+        let entity = object_to_entity(&tile);
+        assert_eq!((entity.position.x, entity.position.y), (100.0, 168.0), "Tile objects are anchored bottom-left");
+
+        let point: TmxObject = from_str(r#"<object type="Spawn" x="10" y="20"/>"#).unwrap();
+        assert_eq!(object_to_entity(&point).size, None);
+    }
+
+    #[test]
+    fn test_level_title_from_path() {
+        assert_eq!(level_title("assets/levels/world_1_level_1/level.tmx"), "World 1 Level 1");
+        assert_eq!(level_title("tests/fixtures/infinite_map.tmx"), "Infinite Map");
+    }
+
+    #[test]
+    fn test_spawn_point_lookup_by_name() {
+        let door: TmxObject = from_str(r#"<object name="from_cave" type="SpawnPoint" x="300" y="400"/>"#).unwrap();
+        let coin: TmxObject = from_str(r#"<object name="from_cave" type="GoldCoin" x="1" y="2"/>"#).unwrap();
+        let level = Level { entities: vec![object_to_entity(&coin), object_to_entity(&door)], ..Default::default() };
+
+        assert_eq!(level.spawn_point("from_cave"), Some(Vector2D::new(300.0, 400.0)));
+        assert_eq!(level.spawn_point("missing"), None);
+    }
+
+    #[test]
+    fn test_camera_zone_lookup() {
+        let level = Level { camera_zones: vec![Region { x: 100.0, y: 0.0, width: 200.0, height: 100.0 }], ..Default::default() };
+
+        assert!(level.camera_zone_at(Vector2D::new(150.0, 50.0)).is_some());
+        assert!(level.camera_zone_at(Vector2D::new(300.0, 50.0)).is_none(), "Right edge is exclusive");
+        assert!(level.camera_zone_at(Vector2D::new(50.0, 50.0)).is_none());
+    }
+
+    #[test]
+    fn test_camera_bounds_layer_rectangles_become_zones() {
+        let bounds: TmxObjectGroup = from_str(r#"<objectgroup name="CameraBounds"><object name="cave" x="64" y="32" width="320" height="160"/></objectgroup>"#).unwrap();
+        let objects: TmxObjectGroup = from_str(r#"<objectgroup name="Objects"><object type="GoldCoin" x="1" y="2"/><object type="CameraZone" x="0" y="0" width="10" height="10"/></objectgroup>"#).unwrap();
+
+        let (entities, zones, water) = split_objects(&[bounds, objects], Vector2D::new(0.0, 0.0));
+        assert_eq!(zones, vec![
+            Region { x: 64.0, y: 32.0, width: 320.0, height: 160.0 },
+            Region { x: 0.0, y: 0.0, width: 10.0, height: 10.0 },
+        ]);
+        assert_eq!(entities.len(), 1, "Only the coin is spawnable");
+        assert!(water.is_empty());
+    }
+
+    #[test]
+    fn test_water_lookup_checks_tiles_and_volumes() {
+        let mut level = Level { water_volumes: vec![Region { x: 100.0, y: 0.0, width: 50.0, height: 50.0 }], ..Default::default() };
+        level.tileset.tile_width = 16;
+        level.tileset.tile_height = 16;
+        level.water = Collision { tiles: vec![vec![0, 1]] };
+
+        assert!(level.is_water(Vector2D::new(20.0, 5.0)), "Inside the water tile at column 1");
+        assert!(!level.is_water(Vector2D::new(5.0, 5.0)));
+        assert!(level.is_water(Vector2D::new(120.0, 20.0)), "Inside the Water volume");
+        assert!(!level.is_water(Vector2D::new(-5.0, 5.0)));
+    }
+
+    #[test]
+    fn test_surface_lookup_uses_collision_layer() {
+        let mut level = Level::default();
+        level.map.layers = vec![
+            TileLayer { tiles: vec![vec![7, 7]], ..Default::default() },
+            TileLayer { tiles: vec![vec![0, 3]], ..Default::default() },
+        ];
+        level.map.collision_layer = 1;
+        level.tileset.surfaces.insert(3, "stone".to_string());
+        level.tileset.surfaces.insert(7, "grass".to_string());
+
+        assert_eq!(level.surface_at(1, 0), Some("stone"));
+        assert_eq!(level.surface_at(0, 0), None, "Decoration layers do not define the surface");
+        assert_eq!(level.surface_at(5, 5), None);
+    }
+
+    #[test]
+    fn test_slope_floor_is_interpolated_across_the_tile() {
+        let mut level = Level::from_tiles(32, 32, vec![vec![0, 0]; 2]);
+        level.collision.tiles[1][0] = TileKind::Slope { rises_right: true, base: 0.0, height: 1.0 };
+        level.collision.tiles[1][1] = TileKind::Slope { rises_right: false, base: 0.0, height: 0.5 };
+
+        assert_eq!(level.slope_floor_y(0.0, 1), Some(64.0), "Low edge sits on the tile bottom");
+        assert_eq!(level.slope_floor_y(16.0, 1), Some(48.0));
+        assert_eq!(level.slope_floor_y(32.0, 1), Some(48.0), "Half slope peaks at half height on its left edge");
+        assert_eq!(level.slope_floor_y(48.0, 1), Some(56.0));
+        assert_eq!(level.slope_floor_y(16.0, 0), None);
+        assert!(!level.is_solid(0, 1) && level.is_ground(0, 1), "Slopes carry entities but are not walls");
+    }
+
+    #[test]
+    fn test_slope_properties_are_parsed() {
+        let prop = |name: &str, value: &str| TmxProperty { name: name.to_string(), property_type: None, value: value.to_string() };
+        assert_eq!(slope_kind(&[prop("slope", "left"), prop("slope_height", "0.5")]), Some(TileKind::Slope { rises_right: false, base: 0.0, height: 0.5 }));
+        assert_eq!(slope_kind(&[prop("slope", "right"), prop("slope_base", "0.5")]), Some(TileKind::Slope { rises_right: true, base: 0.5, height: 1.0 }));
+        assert_eq!(slope_kind(&[prop("slope", "up")]), None);
+        assert_eq!(slope_kind(&[prop("solid", "true")]), None);
+    }
+}
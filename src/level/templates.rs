@@ -0,0 +1,46 @@
+//! # Concept: Object Templates
+//!
+//! Resolves objects placed from Tiled object templates (`.tx` files): the instance inherits
+//! every attribute and property it does not set itself.
+
+use super::{TmxObject, TmxProperties};
+use quick_xml::de::from_str;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Fills in what an object placed from a Tiled template (`template="coin.tx"`) leaves unset.
+/// The template path is relative to `base_dir`; the instance's own attributes and properties
+/// win over the template's. Parsed templates are cached by path in `cache`.
+pub(super) fn apply_template(object: &TmxObject, base_dir: &Path, cache: &mut HashMap<String, TmxObject>) -> Result<TmxObject, String> {
+    let Some(source) = &object.template else { return Ok(object.clone()) };
+    if !cache.contains_key(source) {
+        let path = base_dir.join(source);
+        let xml = fs::read_to_string(&path).map_err(|e| format!("Failed to read template '{}': {}", path.display(), e))?;
+        let template: TmxTemplate = from_str(&xml).map_err(|e| format!("Failed to parse template '{}': {}", path.display(), e))?;
+        cache.insert(source.clone(), template.object);
+    }
+    let template = &cache[source];
+
+    let mut properties = template.properties.as_ref().map_or_else(Vec::new, |p| p.properties.clone());
+    for prop in object.properties.iter().flat_map(|p| &p.properties) {
+        properties.retain(|p| p.name != prop.name);
+        properties.push(prop.clone());
+    }
+    Ok(TmxObject {
+        name: object.name.clone().or_else(|| template.name.clone()),
+        r#type: object.r#type.clone().or_else(|| template.r#type.clone()),
+        gid: object.gid.or(template.gid),
+        x: object.x,
+        y: object.y,
+        width: object.width.or(template.width),
+        height: object.height.or(template.height),
+        rotation: object.rotation.or(template.rotation),
+        template: None,
+        properties: Some(TmxProperties { properties }),
+    })
+}
+
+/// A Tiled object template file (`.tx`): one `<object>` that placed instances inherit from.
+#[derive(Debug, Deserialize)] struct TmxTemplate { object: TmxObject }
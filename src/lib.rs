@@ -6,6 +6,7 @@ pub mod animation;
 pub mod app;
 pub mod audio;
 pub mod audio_analysis;
+pub mod bench;
 pub mod benchmarker;
 pub mod camera;
 pub mod config;
@@ -13,6 +14,7 @@ pub mod ecs;
 pub mod enemy;
 pub mod entity_spawner;
 pub mod font_manager;
+pub mod frame_capture;
 pub mod game_state_manager;
 pub mod input;
 pub mod level;
@@ -22,5 +24,10 @@ pub mod physics;
 pub mod player;
 pub mod renderer;
 pub mod replay;
+pub mod score;
+pub mod simulation;
 pub mod state_machine;
+pub mod testing;
 pub mod texture_manager;
+pub mod timestep;
+pub mod units;
@@ -5,6 +5,8 @@
 //! selectors, bridging the static configuration with the Menu System logic.
 
 use crate::config::{MenuItemType, MenuConfig};
+use crate::input::InputAction;
+use std::collections::HashMap;
 
 /// # Concept: Menu Action
 /// Represents a semantic command triggered by a user's menu selection.
@@ -14,6 +16,14 @@ pub enum MenuAction {
     Quit,
     Navigate(String),
     SelectCharacter(usize),
+    /// A selector changed: (variable, chosen option text).
+    SetOption(String, String),
+    /// A slider moved: (variable, new value).
+    SetValue(String, f32),
+    /// Wait for the next key press and bind it to the action.
+    CaptureBinding(InputAction),
+    /// Write the current key bindings to the user settings.
+    SaveControls,
     None,
 }
 
@@ -37,11 +47,15 @@ impl From<&str> for MenuAction {
         match s {
             "StartGame" => MenuAction::StartGame,
             "Quit" => MenuAction::Quit,
+            "SaveControls" => MenuAction::SaveControls,
             _ => MenuAction::None,
         }
     }
 }
 
+/// Space in screen **pixels** a selector or slider adds below its label for the value row.
+pub const VALUE_ROW_HEIGHT: i32 = 70 + 50;
+
 /// A single interactive element within a menu screen.
 #[derive(Debug, Clone)]
 pub struct MenuItem {
@@ -49,6 +63,16 @@ pub struct MenuItem {
     pub item_type: RuntimeMenuItemType,
 }
 
+impl MenuItem {
+    /// Height in screen **pixels** the item takes in a list laid out `spacing` apart.
+    pub fn height(&self, spacing: i32) -> i32 {
+        match self.item_type {
+            RuntimeMenuItemType::Action { .. } | RuntimeMenuItemType::Binding { .. } => spacing,
+            RuntimeMenuItemType::Selector { .. } | RuntimeMenuItemType::Slider { .. } => spacing + VALUE_ROW_HEIGHT,
+        }
+    }
+}
+
 /// Specialized state for different types of interactive items.
 #[derive(Debug, Clone)]
 pub enum RuntimeMenuItemType {
@@ -56,9 +80,19 @@ pub enum RuntimeMenuItemType {
     Selector { 
         options: Vec<String>, 
         current_index: usize, 
-        #[allow(dead_code)]
         variable_name: String 
     },
+    Slider {
+        value: f32,
+        min: f32,
+        max: f32,
+        step: f32,
+        variable_name: String,
+    },
+    Binding {
+        action: InputAction,
+        key: String,
+    },
 }
 
 /// The persistent state of the active menu interface.
@@ -68,6 +102,18 @@ pub struct MenuState {
     pub selected_index: usize,
     pub config: MenuConfig,
     pub current_screen: String,
+    /// Current option of each selector variable, kept across screen changes.
+    pub values: HashMap<String, String>,
+    /// Current value of each slider variable, kept across screen changes.
+    pub numbers: HashMap<String, f32>,
+    /// Index of the first item drawn, so long lists keep the selection on screen.
+    pub scroll_offset: usize,
+    /// Key bound to each action, shown by binding items.
+    pub keys: HashMap<InputAction, String>,
+    /// The action waiting for a key press, if a binding is being captured.
+    pub capturing: Option<InputAction>,
+    /// One-line message shown under the items (e.g. a binding conflict); cleared on screen changes.
+    pub notice: Option<String>,
 }
 
 impl MenuState {
@@ -78,6 +124,12 @@ impl MenuState {
             selected_index: 0,
             config: config.clone(),
             current_screen: String::new(),
+            values: HashMap::new(),
+            numbers: HashMap::new(),
+            scroll_offset: 0,
+            keys: HashMap::new(),
+            capturing: None,
+            notice: None,
         };
         state.load_screen("main");
         state
@@ -89,6 +141,9 @@ impl MenuState {
         if let Some(screen_config) = self.config.screens.get(screen_name) {
             self.current_screen = screen_name.to_string();
             self.selected_index = 0;
+            self.scroll_offset = 0;
+            self.capturing = None;
+            self.notice = None;
             
             // 2. Transform static item configs into live runtime models.
             self.items = screen_config.items.iter().map(|item_config| {
@@ -98,9 +153,20 @@ impl MenuState {
                     },
                     MenuItemType::Selector { options, variable } => RuntimeMenuItemType::Selector {
                         options: options.clone(),
-                        current_index: 0,
+                        current_index: self.values.get(variable).and_then(|v| options.iter().position(|o| o == v)).unwrap_or(0),
+                        variable_name: variable.clone(),
+                    },
+                    MenuItemType::Slider { variable, min, max, step } => RuntimeMenuItemType::Slider {
+                        value: self.numbers.get(variable).copied().unwrap_or(*min).clamp(*min, *max),
+                        min: *min,
+                        max: *max,
+                        step: *step,
                         variable_name: variable.clone(),
                     },
+                    MenuItemType::Binding { action } => RuntimeMenuItemType::Binding {
+                        action: *action,
+                        key: self.keys.get(action).cloned().unwrap_or_default(),
+                    },
                 };
                 MenuItem {
                     label: item_config.label.clone(),
@@ -111,4 +177,250 @@ impl MenuState {
             eprintln!("Error: Menu screen '{}' not found in config.", screen_name);
         }
     }
-}
\ No newline at end of file
+
+    /// The items that fit in `available_height` screen **pixels** from the scroll offset on.
+    /// At least one item is always visible, even if it is taller than the space.
+    pub fn visible_range(&self, spacing: i32, available_height: i32) -> std::ops::Range<usize> {
+        let start = self.scroll_offset.min(self.items.len());
+        let mut end = start;
+        let mut used = 0;
+        while let Some(item) = self.items.get(end) {
+            used += item.height(spacing);
+            if used > available_height && end > start { break; }
+            end += 1;
+        }
+        start..end
+    }
+
+    /// Scrolls just far enough to bring the selected item into view: up when it is above
+    /// the window (including a wrap back to the top), down one row at a time when below it.
+    pub fn scroll_to_selection(&mut self, spacing: i32, available_height: i32) {
+        if self.selected_index < self.scroll_offset {
+            self.scroll_offset = self.selected_index;
+        }
+        while self.scroll_offset < self.selected_index && !self.visible_range(spacing, available_height).contains(&self.selected_index) {
+            self.scroll_offset += 1;
+        }
+    }
+
+    /// Records the option of a selector variable and updates any visible selector bound to it.
+    pub fn set_value(&mut self, variable: &str, value: &str) {
+        self.values.insert(variable.to_string(), value.to_string());
+        for item in &mut self.items {
+            if let RuntimeMenuItemType::Selector { options, current_index, variable_name } = &mut item.item_type
+                && variable_name == variable
+                && let Some(index) = options.iter().position(|o| o == value) {
+                *current_index = index;
+            }
+        }
+    }
+
+    /// Records the key bound to `action` and updates any visible binding item for it.
+    pub fn set_key(&mut self, action: InputAction, key: &str) {
+        self.keys.insert(action, key.to_string());
+        for item in &mut self.items {
+            if let RuntimeMenuItemType::Binding { action: item_action, key: item_key } = &mut item.item_type
+                && *item_action == action {
+                *item_key = key.to_string();
+            }
+        }
+    }
+
+    /// Records the value of a slider variable and updates any visible slider bound to it.
+    pub fn set_number(&mut self, variable: &str, number: f32) {
+        self.numbers.insert(variable.to_string(), number);
+        for item in &mut self.items {
+            if let RuntimeMenuItemType::Slider { value, min, max, variable_name, .. } = &mut item.item_type
+                && variable_name == variable {
+                *value = number.clamp(*min, *max);
+            }
+        }
+    }
+}
+
+/// Moves a slider `direction` (-1 or +1) steps, snapped to the `step` grid from `min` and clamped to the range,
+/// so repeated nudges never drift off round values.
+pub fn step_slider(value: f32, min: f32, max: f32, step: f32, direction: i32) -> f32 {
+    if step <= 0.0 { return value.clamp(min, max); }
+    let index = ((value - min) / step).round() + direction as f32;
+    (min + index * step).clamp(min, max)
+}
+
+/// Parses a selector option such as "75%" into a fraction (0.75).
+pub fn parse_percent(option: &str) -> Option<f64> {
+    option.trim().strip_suffix('%')?.trim().parse::<f64>().ok().map(|p| (p / 100.0).clamp(0.0, 1.0))
+}
+
+/// Formats a fraction as selector option text, e.g. 0.75 -> "75%".
+pub fn percent_label(fraction: f64) -> String {
+    format!("{}%", (fraction * 100.0).round() as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::GameConfig;
+
+    #[test]
+    fn test_percent_round_trip() {
+        assert_eq!(parse_percent("75%"), Some(0.75));
+        assert_eq!(parse_percent("0%"), Some(0.0));
+        assert_eq!(parse_percent("loud"), None);
+        assert_eq!(percent_label(0.25), "25%");
+    }
+
+    /// The bundled menu with an extra "presets" screen holding a volume selector.
+    fn menu_with_selector() -> MenuConfig {
+        let mut config = GameConfig::default().menu;
+        let item: crate::config::MenuItemConfig = toml::from_str(r#"
+            label = "Music Volume"
+            type = "Selector"
+            options = ["0%", "25%", "50%", "75%", "100%"]
+            variable = "music_volume"
+        "#).unwrap();
+        config.screens.insert("presets".to_string(), crate::config::MenuScreenConfig {
+            title: "Presets".to_string(), title_x: 0, title_y: 0, start_x: 0, start_y: 0, spacing: 60, items: vec![item],
+        });
+        config
+    }
+
+    #[test]
+    fn test_selector_values_survive_screen_changes() {
+        let mut menu = MenuState::new(&menu_with_selector());
+        menu.set_value("music_volume", "50%");
+        menu.load_screen("presets");
+        let music = menu.items.iter().find_map(|item| match &item.item_type {
+            RuntimeMenuItemType::Selector { options, current_index, variable_name } if variable_name == "music_volume" => Some(options[*current_index].clone()),
+            _ => None,
+        });
+        assert_eq!(music.as_deref(), Some("50%"));
+    }
+
+    fn slider_value(menu: &MenuState, variable: &str) -> Option<f32> {
+        menu.items.iter().find_map(|item| match &item.item_type {
+            RuntimeMenuItemType::Slider { value, variable_name, .. } if variable_name == variable => Some(*value),
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn test_slider_steps_snap_and_clamp() {
+        assert!((step_slider(0.5, 0.0, 1.0, 0.05, 1) - 0.55).abs() < 1e-6);
+        assert_eq!(step_slider(0.98, 0.0, 1.0, 0.05, 1), 1.0, "Clamped at max");
+        assert_eq!(step_slider(0.0, 0.0, 1.0, 0.05, -1), 0.0, "Clamped at min");
+        assert_eq!(step_slider(0.33, 0.0, 1.0, 0.25, 1), 0.5, "Off-grid values snap back onto the step grid");
+
+        let mut value = 0.0;
+        for _ in 0..20 { value = step_slider(value, 0.0, 1.0, 0.05, 1); }
+        assert_eq!(value, 1.0, "Twenty nudges land exactly on the end, no drift");
+    }
+
+    #[test]
+    fn test_slider_values_survive_screen_changes() {
+        let mut menu = MenuState::new(&GameConfig::default().menu);
+        menu.set_number("sfx_volume", 0.4);
+        menu.load_screen("audio");
+        assert_eq!(slider_value(&menu, "sfx_volume"), Some(0.4));
+        assert_eq!(slider_value(&menu, "music_volume"), Some(0.0), "Unset sliders start at min");
+
+        menu.set_number("sfx_volume", 3.0);
+        assert_eq!(slider_value(&menu, "sfx_volume"), Some(1.0), "Visible sliders clamp to their range");
+    }
+
+    /// The bundled menu with a "levels" screen of `count` plain actions.
+    fn menu_with_long_list(count: usize) -> MenuConfig {
+        let mut config = GameConfig::default().menu;
+        let items = (0..count).map(|i| toml::from_str(&format!("label = \"Level {}\"\ntype = \"Action\"\naction = \"StartGame\"", i + 1)).unwrap()).collect();
+        config.screens.insert("levels".to_string(), crate::config::MenuScreenConfig {
+            title: "Levels".to_string(), title_x: 0, title_y: 0, start_x: 0, start_y: 0, spacing: 60, items,
+        });
+        config
+    }
+
+    #[test]
+    fn test_scrolling_keeps_the_selection_visible() {
+        let mut menu = MenuState::new(&menu_with_long_list(10));
+        menu.load_screen("levels");
+        let (spacing, height) = (60, 300);
+        assert_eq!(menu.visible_range(spacing, height), 0..5, "Five rows fit");
+
+        // Moving down inside the window does not scroll; past its last row scrolls by one.
+        menu.selected_index = 4;
+        menu.scroll_to_selection(spacing, height);
+        assert_eq!(menu.scroll_offset, 0);
+        menu.selected_index = 5;
+        menu.scroll_to_selection(spacing, height);
+        assert_eq!(menu.visible_range(spacing, height), 1..6);
+
+        // Wrapping bottom -> top resets the scroll, and top -> bottom shows the last page.
+        menu.selected_index = 9;
+        menu.scroll_to_selection(spacing, height);
+        assert_eq!(menu.visible_range(spacing, height), 5..10);
+        menu.selected_index = 0;
+        menu.scroll_to_selection(spacing, height);
+        assert_eq!(menu.scroll_offset, 0);
+
+        menu.load_screen("main");
+        assert_eq!(menu.scroll_offset, 0, "A new screen starts at the top");
+    }
+
+    #[test]
+    fn test_value_rows_take_extra_space() {
+        let mut menu = MenuState::new(&GameConfig::default().menu);
+        menu.load_screen("audio");
+        let sliders = menu.items.iter().filter(|i| matches!(i.item_type, RuntimeMenuItemType::Slider { .. })).count();
+        assert!(sliders > 0);
+        let visible = menu.visible_range(60, 60 + VALUE_ROW_HEIGHT);
+        assert_eq!(visible.len(), 1, "A slider row fills the space its label and bar need");
+    }
+
+    #[test]
+    fn test_binding_items_show_and_capture_keys() {
+        use crate::ecs::systems::menu::SystemMenu;
+        use crate::input::InputState;
+
+        let mut menu = MenuState::new(&GameConfig::default().menu);
+        menu.set_key(InputAction::Jump, "Space");
+        menu.load_screen("controls");
+        let jump = menu.items.iter().position(|item| matches!(&item.item_type, RuntimeMenuItemType::Binding { action: InputAction::Jump, key } if key == "Space"));
+        let jump = jump.expect("The controls screen lists Jump with its key");
+
+        menu.selected_index = jump;
+        let mut accept = InputState::from_actions(vec![InputAction::Jump]);
+        accept.calculate_deltas(&InputState::default());
+        let mut system = SystemMenu::new();
+        assert_eq!(system.update(&mut menu, &accept, 0.016), Some(MenuAction::CaptureBinding(InputAction::Jump)));
+
+        menu.capturing = Some(InputAction::Jump);
+        let mut down = InputState::from_actions(vec![InputAction::Down]);
+        down.calculate_deltas(&InputState::default());
+        assert_eq!(system.update(&mut menu, &down, 0.016), None, "Navigation waits while a key is captured");
+        assert_eq!(menu.selected_index, jump);
+
+        menu.set_key(InputAction::Jump, "Return");
+        assert!(matches!(&menu.items[jump].item_type, RuntimeMenuItemType::Binding { key, .. } if key == "Return"));
+    }
+
+    #[test]
+    fn test_held_input_repeats_after_a_delay() {
+        use crate::ecs::systems::menu::SystemMenu;
+        use crate::input::{InputAction, InputState};
+
+        let mut menu = MenuState::new(&GameConfig::default().menu);
+        menu.load_screen("audio");
+        let mut system = SystemMenu::new();
+        let frame = 1.0 / 60.0;
+        let held = InputState::from_actions(vec![InputAction::MoveRight]);
+        let mut pressed = held.clone();
+        pressed.calculate_deltas(&InputState::default());
+
+        assert_eq!(system.update(&mut menu, &pressed, frame), Some(MenuAction::SetValue("master_volume".to_string(), 0.05)));
+        // Within the repeat delay nothing more happens.
+        for _ in 0..18 { assert_eq!(system.update(&mut menu, &held, frame), None); }
+        // Past it, a step every repeat interval until the bar is full.
+        for _ in 0..120 { system.update(&mut menu, &held, frame); }
+        assert_eq!(slider_value(&menu, "master_volume"), Some(1.0));
+        assert_eq!(menu.numbers.get("master_volume"), Some(&1.0));
+        assert_eq!(system.update(&mut menu, &held, frame), None, "A full slider reports no change");
+    }
+}
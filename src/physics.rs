@@ -6,6 +6,46 @@
 
 use crate::ecs::component::{Position, Velocity};
 use crate::ecs::systems::SystemContext;
+use crate::ecs::world::{Entity, World};
+use crate::level::Level;
+
+/// Finds the highest slope surface (world y in **pixels**) under world x `center_x` between
+/// `from_y` and `to_y`. Slopes are sampled at the entity's bottom-center only.
+fn slope_surface(level: &Level, center_x: f32, from_y: f32, to_y: f32) -> Option<f32> {
+    let tile_height = level.tileset.tile_height as f32;
+    if tile_height <= 0.0 || to_y < 0.0 { return None; }
+    let first_row = (from_y.max(0.0) / tile_height).floor() as usize;
+    let last_row = (to_y / tile_height).floor() as usize;
+    (first_row..=last_row)
+        .filter_map(|row| level.slope_floor_y(center_x, row))
+        .filter(|surface| (from_y..=to_y).contains(surface))
+        .reduce(f32::min)
+}
+
+/// The tile indices touched by the span `from..=to` (in **pixels**) along an axis with `tile_size`
+/// cells, ordered from `from` towards `to`, so the first solid hit is the nearest one.
+/// Cells left of or above the map (negative coordinates) are clamped to index 0.
+fn cells_crossed(from: f32, to: f32, tile_size: f32) -> impl Iterator<Item = usize> {
+    let first = (from / tile_size).floor().max(0.0) as usize;
+    let last = (to / tile_size).floor().max(0.0) as usize;
+    let reversed = last < first;
+    let (low, high) = if reversed { (last, first) } else { (first, last) };
+    (low..=high).map(move |i| if reversed { high - (i - low) } else { i })
+}
+
+/// Whether any tile in `row` between columns `columns` (inclusive) is solid.
+fn row_is_blocked(level: &Level, row: usize, columns: (usize, usize)) -> bool {
+    (columns.0..=columns.1).any(|column| level.is_solid(column, row))
+}
+
+/// Whether any tile in `column` between rows `rows` (inclusive) is solid.
+fn column_is_blocked(level: &Level, column: usize, rows: (usize, usize)) -> bool {
+    (rows.0..=rows.1).any(|row| level.is_solid(column, row))
+}
+
+/// How far (in **pixels**) an entity may be lifted onto a slope it walked into: half its width,
+/// which covers the deepest embed on a 45° slope, since slopes are sampled at the center.
+fn slope_climb(bounds_width: f32) -> f32 { bounds_width / 2.0 + 1.0 }
 
 /// Resolves vertical constraints and identifies surface contact.
 ///
@@ -32,37 +72,48 @@ pub fn resolve_vertical_collisions(
     let next_y = pos.0.y + vel.0.y * context.delta_time;
     let mut grounded = false;
 
-    if vel.0.y > 0.0 { 
-        // 2. Resolve downward movement (Falling/Landing).
-        let left_x = pos.0.x + collision_nudge;
-        let right_x = pos.0.x + scaled_bounds_width - collision_nudge;
-        let bottom_y = next_y + scaled_bounds_height;
+    // 2. Slopes: stand on the surface under the bottom-center. While descending, the entity is
+    // pulled down by up to one step of horizontal travel so it stays grounded instead of hopping.
+    if vel.0.y > 0.0 {
+        let center_x = pos.0.x + scaled_bounds_width / 2.0;
+        let bottom = pos.0.y + scaled_bounds_height;
+        let snap = vel.0.x.abs() * context.delta_time + 1.0;
+        if let Some(surface) = slope_surface(context.level, center_x, bottom - slope_climb(scaled_bounds_width), next_y + scaled_bounds_height + snap) {
+            pos.0.y = surface - scaled_bounds_height;
+            vel.0.y = 0.0;
+            return true;
+        }
+    }
+
+    // Every column under the entity, inset so it doesn't catch on walls it is merely touching.
+    let columns = (
+        ((pos.0.x + collision_nudge) / tile_width).floor().max(0.0) as usize,
+        ((pos.0.x + scaled_bounds_width - collision_nudge) / tile_width).floor().max(0.0) as usize,
+    );
 
-        let left_tile = (left_x / tile_width).floor() as usize;
-        let right_tile = (right_x / tile_width).floor() as usize;
-        let bottom_tile = (bottom_y / tile_height).floor() as usize;
+    if vel.0.y > 0.0 { 
+        // 3. Resolve downward movement (Falling/Landing). The bottom edge is swept through every
+        // row between this tick and the next, so a fast fall can't skip over a thin floor.
+        let bottom_y = pos.0.y + scaled_bounds_height;
+        let floor = cells_crossed(bottom_y, next_y + scaled_bounds_height, tile_height)
+            .find(|&row| row_is_blocked(context.level, row, columns));
 
-        if context.level.is_solid(left_tile, bottom_tile) || context.level.is_solid(right_tile, bottom_tile) {
+        if let Some(floor_tile) = floor {
             // Snap to the surface of the floor tile and zero vertical velocity.
-            pos.0.y = (bottom_tile as f32 * tile_height) - scaled_bounds_height;
+            pos.0.y = (floor_tile as f32 * tile_height) - scaled_bounds_height;
             vel.0.y = 0.0;
             grounded = true;
         } else {
             pos.0.y = next_y;
         }
     } else if vel.0.y < 0.0 { 
-        // 3. Resolve upward movement (Jumping/Bonking).
-        let left_x = pos.0.x + collision_nudge;
-        let right_x = pos.0.x + scaled_bounds_width - collision_nudge;
-        let top_y = next_y;
+        // 4. Resolve upward movement (Jumping/Bonking), swept like the fall. Slopes are open from below.
+        let ceiling = cells_crossed(pos.0.y, next_y, tile_height)
+            .find(|&row| row_is_blocked(context.level, row, columns));
 
-        let left_tile = (left_x / tile_width).floor() as usize;
-        let right_tile = (right_x / tile_width).floor() as usize;
-        let top_tile = (top_y / tile_height).floor() as usize;
-
-        if context.level.is_solid(left_tile, top_tile) || context.level.is_solid(right_tile, top_tile) {
+        if let Some(ceiling_tile) = ceiling {
             // Snap to the bottom edge of the ceiling tile and zero vertical velocity.
-            pos.0.y = (top_tile as f32 * tile_height) + tile_height;
+            pos.0.y = (ceiling_tile as f32 * tile_height) + tile_height;
             vel.0.y = 0.0;
         } else {
             pos.0.y = next_y;
@@ -98,17 +149,24 @@ pub fn resolve_horizontal_collisions(
     let next_x = pos.0.x + vel.0.x * context.delta_time;
     let mut wall_normal = None;
 
-    if vel.0.x > 0.0 { 
-        // 2. Resolve movement to the right.
-        let top_y = pos.0.y + collision_nudge;
-        let bottom_y = pos.0.y + scaled_bounds_height - collision_nudge;
-        let right_x = next_x + scaled_bounds_width;
+    // Standing on a slope, the feet may sit below the top of the block the slope leads onto;
+    // probing that low would report the block as a wall, so the lower probe is raised.
+    let feet = pos.0.y + scaled_bounds_height;
+    let center_x = pos.0.x + scaled_bounds_width / 2.0;
+    let step = if slope_surface(context.level, center_x, feet - 1.0, feet + 1.0).is_some() { slope_climb(scaled_bounds_width) } else { 0.0 };
 
-        let top_tile = (top_y / tile_height).floor() as usize;
-        let bottom_tile = (bottom_y / tile_height).floor() as usize;
-        let right_tile = (right_x / tile_width).floor() as usize;
+    // Every row beside the entity, inset at the top and raised at the feet as described above.
+    let top_y = pos.0.y + collision_nudge;
+    let bottom_y = (pos.0.y + scaled_bounds_height - collision_nudge - step).max(top_y);
+    let rows = ((top_y / tile_height).floor().max(0.0) as usize, (bottom_y / tile_height).floor().max(0.0) as usize);
+
+    if vel.0.x > 0.0 { 
+        // 2. Resolve movement to the right, sweeping the leading edge through every column it crosses.
+        let right_x = pos.0.x + scaled_bounds_width;
+        let wall = cells_crossed(right_x, next_x + scaled_bounds_width, tile_width)
+            .find(|&column| column_is_blocked(context.level, column, rows));
 
-        if context.level.is_solid(right_tile, top_tile) || context.level.is_solid(right_tile, bottom_tile) {
+        if let Some(right_tile) = wall {
             // Stop at the wall surface and report a leftward normal.
             pos.0.x = (right_tile as f32 * tile_width) - scaled_bounds_width - collision_nudge;
             vel.0.x = 0.0;
@@ -117,16 +175,11 @@ pub fn resolve_horizontal_collisions(
             pos.0.x = next_x;
         }
     } else if vel.0.x < 0.0 { 
-        // 3. Resolve movement to the left.
-        let top_y = pos.0.y + collision_nudge;
-        let bottom_y = pos.0.y + scaled_bounds_height - collision_nudge;
-        let left_x = next_x;
+        // 3. Resolve movement to the left, swept the same way.
+        let wall = cells_crossed(pos.0.x, next_x, tile_width)
+            .find(|&column| column_is_blocked(context.level, column, rows));
 
-        let top_tile = (top_y / tile_height).floor() as usize;
-        let bottom_tile = (bottom_y / tile_height).floor() as usize;
-        let left_tile = (left_x / tile_width).floor() as usize;
-
-        if context.level.is_solid(left_tile, top_tile) || context.level.is_solid(left_tile, bottom_tile) {
+        if let Some(left_tile) = wall {
             // Stop at the wall surface and report a rightward normal.
             pos.0.x = (left_tile as f32 * tile_width) + tile_width + collision_nudge;
             vel.0.x = 0.0;
@@ -139,7 +192,7 @@ pub fn resolve_horizontal_collisions(
     }
 
     // 4. Enforce hard world boundaries defined by the level map dimensions.
-    let map_width_in_tiles = context.level.map.tiles[0].len() as f32;
+    let map_width_in_tiles = context.level.map.width as f32;
     let map_width = map_width_in_tiles * tile_width;
 
     if pos.0.x < 0.0 {
@@ -155,4 +208,39 @@ pub fn resolve_horizontal_collisions(
     }
 
     wall_normal
-}
\ No newline at end of file
+}
+
+/// Whether the collision box `bounds` at `pos` can grow to `height` **pixels** with its feet in place,
+/// i.e. no solid tile sits in the strip between its current top and the taller top.
+pub fn has_headroom(level: &Level, pos: &Position, bounds: sdl3::rect::Rect, height: u32) -> bool {
+    let collision_nudge = 0.2;
+    let grow = height as f32 - bounds.height() as f32;
+    if grow <= 0.0 { return true; }
+
+    let (tile_width, tile_height) = (level.tileset.tile_width as f32, level.tileset.tile_height as f32);
+    let columns = (
+        ((pos.0.x + collision_nudge) / tile_width).floor().max(0.0) as usize,
+        ((pos.0.x + bounds.width() as f32 - collision_nudge) / tile_width).floor().max(0.0) as usize,
+    );
+    cells_crossed(pos.0.y - collision_nudge, pos.0.y - grow, tile_height)
+        .all(|row| !row_is_blocked(level, row, columns))
+}
+
+/// Changes the height of an entity's collision box to `height` **pixels**, keeping its bottom edge fixed.
+///
+/// Positions are the top-left of the box, so the tile resolvers see the feet exactly where they were.
+///
+/// # Side Effects
+/// * Shifts `Position` and `previous_positions` by the height difference (no interpolation jump).
+/// * Counters the shift in `Renderable::vertical_offset` so the sprite stays in place.
+pub fn resize_from_top(world: &mut World, entity: Entity, height: u32) {
+    let Some(collision) = world.collisions.get_mut(&entity) else { return };
+    let delta = collision.rect.height() as i32 - height as i32;
+    if delta == 0 { return; }
+    collision.rect.set_height(height);
+    collision.rect.set_y(collision.rect.y() + delta);
+
+    if let Some(pos) = world.positions.get_mut(&entity) { pos.0.y += delta as f32; }
+    if let Some(prev) = world.previous_positions.get_mut(&entity) { prev.0.y += delta as f32; }
+    if let Some(renderable) = world.renderables.get_mut(&entity) { renderable.vertical_offset -= delta; }
+}
@@ -18,9 +18,16 @@ pub struct PlayerFactory;
 impl PlayerFactory {
     /// Constructs a fully-equipped Player entity within the provided World.
     pub fn create(world: &mut World, game_config: &GameConfig) -> crate::ecs::world::Entity {
+        Self::create_for_slot(world, game_config, 0, game_config.player.start_pos)
+    }
+
+    /// Constructs a Player entity driven by the input device of the given local slot.
+    /// Additional players spawn at `start` shifted by `coop_spawn_offset` per slot.
+    pub fn create_for_slot(world: &mut World, game_config: &GameConfig, slot: usize, start: Vector2D) -> crate::ecs::world::Entity {
         // 1. Generate a new unique Entity ID.
         let player_entity = world.create_entity();
-        let player_position = Position(game_config.player.start_pos);
+        let offset = game_config.player.coop_spawn_offset;
+        let player_position = Position(Vector2D::new(start.x + offset.x * slot as f32, start.y + offset.y * slot as f32));
 
         // 2. Assemble physical components (Position, Velocity, Gravity, Collision).
         world.add_position(player_entity, player_position);
@@ -32,10 +39,12 @@ impl PlayerFactory {
             height: game_config.player.draw_height,
             horizontal_offset: game_config.player.horizontal_draw_offset,
             vertical_offset: game_config.player.vertical_draw_offset,
-            z_index: 100,
+            z_index: game_config.player.z_index,
             rotation: 0.0,
             flip_horizontal: false,
             flip_vertical: false,
+            tint: sdl3::pixels::Color::WHITE,
+            alpha: 255,
         });
 
         // 3. Build and filter the animation controller from global game configuration.
@@ -43,24 +52,7 @@ impl PlayerFactory {
         for (name, anim_config) in &game_config.animation {
             // Identify player-specific clips based on naming conventions.
             if !name.starts_with("enemy") && !name.starts_with("gold_coin") && !name.starts_with("explosion") {
-                let mut frames = Vec::new();
-                for i in 0..anim_config.frame_count {
-                    let padding = anim_config.frame_padding.unwrap_or(0);
-                    frames.push(sdl3::rect::Rect::new(
-                        anim_config.start_x + (i * (anim_config.frame_width + padding)) as i32,
-                        anim_config.start_y,
-                        anim_config.frame_width,
-                        anim_config.frame_height,
-                    ));
-                }
-                if anim_config.reverse.unwrap_or(false) { frames.reverse(); }
-                let animation = crate::animation::Animation {
-                    texture_name: anim_config.texture.clone(),
-                    frames,
-                    frame_duration: anim_config.frame_duration,
-                    loops: anim_config.loops,
-                };
-                player_animation_controller.add_animation(name.clone(), animation);
+                player_animation_controller.add_animation(name.clone(), crate::animation::Animation::from_config(anim_config));
             }
         }
         player_animation_controller.set_animation("idle_right");
@@ -68,6 +60,7 @@ impl PlayerFactory {
         
         // 4. Register semantic tags and logic controllers (State Machine, Health, Direction).
         world.add_player_tag(player_entity, PlayerTag);
+        world.add_player_slot(player_entity, PlayerSlot(slot));
         world.add_gravity(player_entity, Gravity);
         world.add_collision(player_entity, Collision {
             rect: sdl3::rect::Rect::new(
@@ -81,6 +74,7 @@ impl PlayerFactory {
         world.add_state_component(player_entity, StateComponent { state_machine: StateMachine::new(IdleState) });
         world.add_health(player_entity, Health { current: game_config.player.max_health, max: game_config.player.max_health });
         world.add_direction(player_entity, Directional { direction: Direction::Right });
+        world.add_jump_assist(player_entity, JumpAssist::default());
 
         player_entity
     }
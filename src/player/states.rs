@@ -8,7 +8,53 @@ use crate::state_machine::State;
 use crate::ecs::world::{World, Entity};
 use crate::ecs::systems::SystemContext;
 use crate::input::InputAction;
-use crate::audio::{AudioEvent, PlaySoundParams};
+use crate::ecs::component::{AttackCooldown, Collision, Crouching, DeadTag, Direction, Directional, Lifetime, MeleeHitbox, Position};
+use crate::ecs::event::{CommandAttack, CommandJump};
+use crate::math::Vector2D;
+use crate::physics;
+
+/// # Concept: Grounded State
+/// Parent of [IdleState] and [WalkingState]: starts attacks and crouches, and leaves the
+/// ground for the water or the air. The children only decide between standing and walking.
+pub struct GroundedState;
+
+impl State for GroundedState {
+    fn enter(&mut self) {}
+    fn exit(&mut self) {}
+    fn update_with_context(&mut self, _world: &mut World, _context: &mut SystemContext, _entity: Entity) {}
+
+    fn transition_with_context(&mut self, world: &mut World, context: &mut SystemContext, entity: Entity) -> Option<Box<dyn State>> {
+        if let Some(attack) = AttackingState::try_start(world, context, entity) { return Some(attack); }
+        if let Some(crouch) = CrouchingState::try_start(world, context, entity) { return Some(crouch); }
+        if world.is_submerged(entity) { return Some(Box::new(SwimmingState)); }
+        // The jump impulse itself (incl. coyote time/buffering) is applied by SystemMovement;
+        // the state only reacts to the resulting upward velocity.
+        if let Some(vel) = world.velocities.get(&entity) && !world.is_grounded(entity) {
+            if vel.0.y < 0.0 { return Some(Box::new(JumpingState)); }
+            else if vel.0.y > 100.0 { return Some(Box::new(FallingState)); }
+        }
+        None
+    }
+    fn get_name(&self) -> &str { "GroundedState" }
+}
+
+/// # Concept: Airborne State
+/// Parent of [JumpingState] and [FallingState]: starts air attacks and hands over to
+/// swimming on entering water. The children only track the vertical direction and landing.
+pub struct AirborneState;
+
+impl State for AirborneState {
+    fn enter(&mut self) {}
+    fn exit(&mut self) {}
+    fn update_with_context(&mut self, _world: &mut World, _context: &mut SystemContext, _entity: Entity) {}
+
+    fn transition_with_context(&mut self, world: &mut World, context: &mut SystemContext, entity: Entity) -> Option<Box<dyn State>> {
+        if let Some(attack) = AttackingState::try_start(world, context, entity) { return Some(attack); }
+        if world.is_submerged(entity) { return Some(Box::new(SwimmingState)); }
+        None
+    }
+    fn get_name(&self) -> &str { "AirborneState" }
+}
 
 /// # Concept: Idle State
 /// Handles the stationary behavior of the player on solid ground.
@@ -30,32 +76,17 @@ impl State for IdleState {
 
     fn transition_with_context(&mut self, world: &mut World, context: &mut SystemContext, entity: Entity) -> Option<Box<dyn State>> {
         let input_state = context.input_state;
-        let physics_config = &context.config.physics;
-
-        if let Some(vel) = world.velocities.get(&entity) {
-            if input_state.is_action_just_pressed(InputAction::Jump) && world.is_grounded(entity) {
-                if let Some(vel_mut) = world.velocities.get_mut(&entity) { vel_mut.0.y = physics_config.jump_strength; }
-                if let Some(sound_name) = context.game_config.sound_events.get("player_jump") {
-                    let _ = context.audio_sender.send(AudioEvent::PlaySound(sound_name.clone(), PlaySoundParams::default()));
-                }
-                return Some(Box::new(JumpingState));
+        // Transition to Walking if moving OR if intending to move (pushing wall).
+        if let Some(vel) = world.velocities.get(&entity)
+            && (vel.0.x.abs() > context.config.physics.velocity_threshold
+                || input_state.is_action_pressed(InputAction::MoveLeft)
+                || input_state.is_action_pressed(InputAction::MoveRight)) {
+                return Some(Box::new(WalkingState));
             }
-
-            if !world.is_grounded(entity) {
-                if vel.0.y < 0.0 { return Some(Box::new(JumpingState)); } 
-                else if vel.0.y > 100.0 { return Some(Box::new(FallingState)); }
-            }
-
-            // Transition to Walking if moving OR if intending to move (pushing wall).
-            if vel.0.x.abs() > physics_config.velocity_threshold 
-                || input_state.is_action_pressed(InputAction::MoveLeft) 
-                || input_state.is_action_pressed(InputAction::MoveRight) { 
-                    return Some(Box::new(WalkingState)); 
-            }
-        }
         None
     }
     fn get_name(&self) -> &str { "IdleState" }
+    fn parent(&self) -> Option<Box<dyn State>> { Some(Box::new(GroundedState)) }
 }
 
 /// # Concept: Walking State
@@ -98,32 +129,93 @@ impl State for WalkingState {
 
     fn transition_with_context(&mut self, world: &mut World, context: &mut SystemContext, entity: Entity) -> Option<Box<dyn State>> {
         let input_state = context.input_state;
-        let physics_config = &context.config.physics;
-
-        if let Some(vel) = world.velocities.get(&entity) {
-            if input_state.is_action_just_pressed(InputAction::Jump) && world.is_grounded(entity) {
-                if let Some(vel_mut) = world.velocities.get_mut(&entity) { vel_mut.0.y = physics_config.jump_strength; }
-                if let Some(sound_name) = context.game_config.sound_events.get("player_jump") {
-                    let _ = context.audio_sender.send(AudioEvent::PlaySound(sound_name.clone(), PlaySoundParams::default()));
-                }
-                return Some(Box::new(JumpingState));
-            }
-            
-            if !world.is_grounded(entity) {
-                if vel.0.y < 0.0 { return Some(Box::new(JumpingState)); } 
-                else if vel.0.y > 100.0 { return Some(Box::new(FallingState)); }
+        // Only transition to Idle if stopped AND not trying to move.
+        if let Some(vel) = world.velocities.get(&entity)
+            && vel.0.x.abs() < context.config.physics.velocity_threshold
+            && !input_state.is_action_pressed(InputAction::MoveLeft)
+            && !input_state.is_action_pressed(InputAction::MoveRight) {
+                return Some(Box::new(IdleState));
             }
+        None
+    }
+    fn get_name(&self) -> &str { "WalkingState" }
+    fn parent(&self) -> Option<Box<dyn State>> { Some(Box::new(GroundedState)) }
+}
+
+/// # Concept: Crouching State
+/// Entered by holding Down on the ground. The collision box shrinks from the top to
+/// `player.crouch_height` and SystemMovement slows the entity while its [Crouching] is present.
+/// The player only stands up again once the full-height box fits under the ceiling.
+pub struct CrouchingState;
+
+impl CrouchingState {
+    /// Starts a crouch if a grounded, dry entity holds Down this tick.
+    ///
+    /// Down+Jump is reserved for dropping through one-way platforms, so a Jump pressed in the
+    /// same tick never starts a crouch.
+    ///
+    /// # Side Effects
+    /// * Adds [Crouching] to `entity` and shrinks its collision box (see [physics::resize_from_top]).
+    pub fn try_start(world: &mut World, context: &SystemContext, entity: Entity) -> Option<Box<dyn State>> {
+        if !world.is_grounded(entity) || world.is_submerged(entity) { return None; }
+        if !world.movement_intentions.get(&entity).is_some_and(|i| i.crouch_held) { return None; }
+        if world.event_bus.read::<CommandJump>().any(|c| c.entity == entity) { return None; }
 
-            // Only transition to Idle if stopped AND not trying to move.
-            if vel.0.x.abs() < physics_config.velocity_threshold 
-                && !input_state.is_action_pressed(InputAction::MoveLeft) 
-                && !input_state.is_action_pressed(InputAction::MoveRight) { 
-                    return Some(Box::new(IdleState)); 
+        let standing_height = world.collisions.get(&entity)?.rect.height();
+        world.add_crouching(entity, Crouching { standing_height });
+        physics::resize_from_top(world, entity, context.game_config.player.crouch_height.min(standing_height));
+        Some(Box::new(CrouchingState))
+    }
+
+    /// Restores the standing collision box if there is headroom for it.
+    ///
+    /// # Returns
+    /// * `true` if the entity is standing again.
+    fn try_stand_up(world: &mut World, context: &SystemContext, entity: Entity) -> bool {
+        let Some(crouching) = world.crouchings.get(&entity).copied() else { return true };
+        let (Some(pos), Some(collision)) = (world.positions.get(&entity), world.collisions.get(&entity)) else { return true };
+        if !physics::has_headroom(context.level, pos, collision.rect, crouching.standing_height) { return false; }
+
+        physics::resize_from_top(world, entity, crouching.standing_height);
+        world.crouchings.remove(&entity);
+        true
+    }
+}
+
+impl State for CrouchingState {
+    fn enter(&mut self) {}
+    fn exit(&mut self) {}
+
+    fn update_with_context(&mut self, world: &mut World, _context: &mut SystemContext, entity: Entity) {
+        let intent_x = world.movement_intentions.get(&entity).map(|i| i.x).unwrap_or(0.0);
+        if intent_x.abs() > 0.1 {
+            let direction = if intent_x < 0.0 { Direction::Left } else { Direction::Right };
+            world.add_direction(entity, Directional { direction });
+        }
+        if let Some(anim) = world.animations.get_mut(&entity) {
+            match world.directions.get(&entity).map(|d| d.direction).unwrap_or(Direction::Right) {
+                Direction::Left => anim.controller.set_animation("crouch_left"),
+                Direction::Right => anim.controller.set_animation("crouch_right"),
             }
         }
-        None
     }
-    fn get_name(&self) -> &str { "WalkingState" }
+
+    fn transition_with_context(&mut self, world: &mut World, context: &mut SystemContext, entity: Entity) -> Option<Box<dyn State>> {
+        let crouch_held = world.movement_intentions.get(&entity).is_some_and(|i| i.crouch_held);
+        let (grounded, submerged) = (world.is_grounded(entity), world.is_submerged(entity));
+        if crouch_held && grounded && !submerged { return None; }
+
+        // Under a low ceiling the player stays crouched, even after releasing Down.
+        if !Self::try_stand_up(world, context, entity) { return None; }
+        if submerged { return Some(Box::new(SwimmingState)); }
+        if !grounded {
+            let rising = world.velocities.get(&entity).is_some_and(|v| v.0.y < 0.0);
+            return if rising { Some(Box::new(JumpingState)) } else { Some(Box::new(FallingState)) };
+        }
+        let moving = world.velocities.get(&entity).is_some_and(|v| v.0.x.abs() > context.config.physics.velocity_threshold);
+        if moving { Some(Box::new(WalkingState)) } else { Some(Box::new(IdleState)) }
+    }
+    fn get_name(&self) -> &str { "CrouchingState" }
 }
 
 /// # Concept: Jumping State
@@ -134,21 +226,15 @@ impl State for JumpingState {
     fn enter(&mut self) {}
     fn exit(&mut self) {}
 
-    fn update_with_context(&mut self, world: &mut World, context: &mut SystemContext, entity: Entity) {
-        let input_state = context.input_state;
-        let physics_config = &context.config.physics;
-
-        if let Some(vel) = world.velocities.get_mut(&entity)
-            && input_state.is_action_pressed(InputAction::Jump) && vel.0.y < 0.0 {
-                vel.0.y -= physics_config.jump_hold_force * context.delta_time;
-            }
-    }
+    /// The hold-to-jump-higher force is applied by SystemMovement from the entity's MovementIntention.
+    fn update_with_context(&mut self, _world: &mut World, _context: &mut SystemContext, _entity: Entity) {}
 
     fn transition_with_context(&mut self, world: &mut World, _context: &mut SystemContext, entity: Entity) -> Option<Box<dyn State>> {
         if let Some(vel) = world.velocities.get(&entity) && vel.0.y >= 0.0 { return Some(Box::new(FallingState)); }
         None
     }
     fn get_name(&self) -> &str { "JumpingState" }
+    fn parent(&self) -> Option<Box<dyn State>> { Some(Box::new(AirborneState)) }
 }
 
 /// # Concept: Falling State
@@ -162,9 +248,119 @@ impl State for FallingState {
 
     fn transition_with_context(&mut self, world: &mut World, _context: &mut SystemContext, entity: Entity) -> Option<Box<dyn State>> {
         if world.is_grounded(entity) { return Some(Box::new(IdleState)); }
+        // A coyote-time or air jump can start while already falling.
+        if let Some(vel) = world.velocities.get(&entity) && vel.0.y < 0.0 { return Some(Box::new(JumpingState)); }
         None
     }
     fn get_name(&self) -> &str { "FallingState" }
+    fn parent(&self) -> Option<Box<dyn State>> { Some(Box::new(AirborneState)) }
+}
+
+/// # Concept: Attacking State
+/// A melee swing. A [MeleeHitbox] follows the side the player faces until `timer` runs out;
+/// movement keeps working, so the swing is available on the ground and in the air.
+pub struct AttackingState {
+    /// Remaining swing time in **seconds**.
+    pub timer: f32,
+    pub hitbox: Entity,
+}
+
+impl AttackingState {
+    /// Starts a swing if the entity requested one this tick and is able to attack.
+    ///
+    /// Knocked-back (freshly damaged) players and players still on cooldown cannot attack.
+    ///
+    /// # Side Effects
+    /// * Spawns the hitbox entity and adds an [AttackCooldown] to `entity`.
+    pub fn try_start(world: &mut World, context: &SystemContext, entity: Entity) -> Option<Box<dyn State>> {
+        if !world.event_bus.read::<CommandAttack>().any(|c| c.entity == entity) { return None; }
+        if world.knockbacks.contains_key(&entity) || world.attack_cooldowns.contains_key(&entity) { return None; }
+
+        let player = &context.game_config.player;
+        let hitbox = world.create_entity();
+        world.add_melee_hitbox(hitbox, MeleeHitbox { owner: entity });
+        world.add_lifetime(hitbox, Lifetime { timer: player.attack_duration });
+        world.add_collision(hitbox, Collision { rect: sdl3::rect::Rect::new(0, 0, player.attack_width, player.attack_height) });
+        Self::place_hitbox(world, entity, hitbox);
+        world.add_attack_cooldown(entity, AttackCooldown { timer: player.attack_cooldown });
+        Some(Box::new(AttackingState { timer: player.attack_duration, hitbox }))
+    }
+
+    /// Puts the hitbox against the side of the owner's collision box it is facing, vertically centered.
+    fn place_hitbox(world: &mut World, owner: Entity, hitbox: Entity) {
+        let (Some(body), Some(hit)) = (world.collisions.get(&owner).map(|c| c.rect), world.collisions.get(&hitbox).map(|c| c.rect)) else { return };
+        let x = match world.directions.get(&owner).map(|d| d.direction).unwrap_or(Direction::Right) {
+            Direction::Left => body.x() - hit.width() as i32,
+            Direction::Right => body.x() + body.width() as i32,
+        };
+        let y = body.y() + (body.height() as i32 - hit.height() as i32) / 2;
+        world.add_position(hitbox, Position(Vector2D::new(x as f32, y as f32)));
+        if let Some(collision) = world.collisions.get_mut(&hitbox) {
+            collision.rect.set_x(x);
+            collision.rect.set_y(y);
+        }
+    }
+}
+
+impl State for AttackingState {
+    fn enter(&mut self) {}
+    fn exit(&mut self) {}
+
+    fn update_with_context(&mut self, world: &mut World, context: &mut SystemContext, entity: Entity) {
+        self.timer -= context.delta_time;
+        if world.melee_hitboxes.contains_key(&self.hitbox) { Self::place_hitbox(world, entity, self.hitbox); }
+        if let Some(anim) = world.animations.get_mut(&entity) {
+            match world.directions.get(&entity).map(|d| d.direction).unwrap_or(Direction::Right) {
+                Direction::Left => anim.controller.set_animation("attack_left"),
+                Direction::Right => anim.controller.set_animation("attack_right"),
+            }
+        }
+    }
+
+    fn transition_with_context(&mut self, world: &mut World, _context: &mut SystemContext, entity: Entity) -> Option<Box<dyn State>> {
+        // Taking a hit cancels the swing.
+        let interrupted = world.knockbacks.contains_key(&entity);
+        if interrupted && world.melee_hitboxes.contains_key(&self.hitbox) { world.add_dead_tag(self.hitbox, DeadTag); }
+        if !interrupted && self.timer > 0.0 { return None; }
+
+        if world.is_submerged(entity) { return Some(Box::new(SwimmingState)); }
+        if world.is_grounded(entity) { return Some(Box::new(IdleState)); }
+        let rising = world.velocities.get(&entity).is_some_and(|v| v.0.y < 0.0);
+        if rising { Some(Box::new(JumpingState)) } else { Some(Box::new(FallingState)) }
+    }
+    fn get_name(&self) -> &str { "AttackingState" }
+}
+
+/// # Concept: Swimming State
+/// Active while the player is submerged; Jump presses become swim strokes (applied by SystemMovement).
+pub struct SwimmingState;
+
+impl State for SwimmingState {
+    fn enter(&mut self) {}
+    fn exit(&mut self) {}
+
+    /// Reuses the walk cycle, facing the direction of travel.
+    fn update_with_context(&mut self, world: &mut World, _context: &mut SystemContext, entity: Entity) {
+        let intent_x = world.movement_intentions.get(&entity).map(|i| i.x).unwrap_or(0.0);
+        if intent_x.abs() > 0.1 {
+            let dir = if intent_x < 0.0 { crate::ecs::component::Direction::Left } else { crate::ecs::component::Direction::Right };
+            world.add_direction(entity, crate::ecs::component::Directional { direction: dir });
+        }
+        if let Some(anim) = world.animations.get_mut(&entity) {
+            match world.directions.get(&entity).map(|d| d.direction).unwrap_or(crate::ecs::component::Direction::Right) {
+                crate::ecs::component::Direction::Left => anim.controller.set_animation("walk_left"),
+                crate::ecs::component::Direction::Right => anim.controller.set_animation("walk_right"),
+            }
+        }
+    }
+
+    fn transition_with_context(&mut self, world: &mut World, _context: &mut SystemContext, entity: Entity) -> Option<Box<dyn State>> {
+        if world.is_submerged(entity) { return None; }
+        // Leaving the water at the surface keeps the stroke's momentum as a jump.
+        let rising = world.velocities.get(&entity).is_some_and(|v| v.0.y < 0.0);
+        if rising { Some(Box::new(JumpingState)) } else { Some(Box::new(FallingState)) }
+    }
+    fn get_name(&self) -> &str { "SwimmingState" }
 }
 
 /// # Concept: Dying State
@@ -0,0 +1,171 @@
+//! # Concept: Sprite & Tile Batching
+//!
+//! Groups textured quads by texture and submits each group with one `SDL_RenderGeometry`
+//! call instead of one `copy` per sprite or tile. Tile layers are culled to the camera view
+//! with [visible_tile_range] before they are queued.
+
+use sdl3::render::{WindowCanvas, FRect};
+use sdl3::pixels::Color;
+use sdl3_sys::render::SDL_Vertex;
+use std::ops::Range;
+use crate::camera::Camera;
+use crate::texture_manager::TextureManager;
+use super::{Renderer, SpriteDrawParams};
+
+/// Orders (z_index, texture, entity) back to front like [sort_back_to_front], but groups
+/// equal-depth sprites by texture so [Renderer::draw_sprites] can submit them as one batch.
+/// Depth always wins: sprites only ever swap places with others at the same `z_index`.
+pub fn sort_for_batching(items: &mut [(u8, &str, crate::ecs::world::Entity)]) {
+    items.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(b.1)).then(a.2.cmp(&b.2)));
+}
+
+/// The source rectangle (in texture **pixels**) for `frame`, mirrored by swapping its edges
+/// so a [QuadBatch] flips the quad without a separate flag.
+pub fn flipped_source(frame: &sdl3::rect::Rect, flip_horizontal: bool, flip_vertical: bool) -> FRect {
+    let (mut x, mut y, mut w, mut h) = (frame.x as f32, frame.y as f32, frame.width() as f32, frame.height() as f32);
+    if flip_horizontal { x += w; w = -w; }
+    if flip_vertical { y += h; h = -h; }
+    FRect::new(x, y, w, h)
+}
+
+/// Returns the (rows, cols) of a `rows` x `cols` tile grid that overlap the camera view.
+/// Tiles partially inside the view are included; the ranges are clamped to the grid.
+pub fn visible_tile_range(camera: &Camera, tile_width: u32, tile_height: u32, rows: usize, cols: usize) -> (Range<usize>, Range<usize>) {
+    // Culling happens in Retro Space (the zoomed view of the 480x270 virtual screen).
+    let min_col = (camera.position.x / tile_width as f32).floor() as i32;
+    let max_col = ((camera.position.x + camera.view_width()) / tile_width as f32).ceil() as i32;
+    let min_row = (camera.position.y / tile_height as f32).floor() as i32;
+    let max_row = ((camera.position.y + camera.view_height()) / tile_height as f32).ceil() as i32;
+
+    let end_row = (max_row.max(0) as usize).min(rows);
+    let end_col = (max_col.max(0) as usize).min(cols);
+    ((min_row.max(0) as usize).min(end_row)..end_row, (min_col.max(0) as usize).min(end_col)..end_col)
+}
+
+/// Textured quads sharing one texture, submitted with a single `SDL_RenderGeometry` call
+/// instead of one `copy` per quad.
+#[derive(Default)]
+pub struct QuadBatch {
+    vertices: Vec<SDL_Vertex>,
+    indices: Vec<i32>,
+}
+
+impl QuadBatch {
+    pub fn clear(&mut self) { self.vertices.clear(); self.indices.clear(); }
+
+    /// The number of quads queued since the last [QuadBatch::clear].
+    pub fn len(&self) -> usize { self.vertices.len() / 4 }
+
+    pub fn is_empty(&self) -> bool { self.vertices.is_empty() }
+
+    pub fn vertices(&self) -> &[SDL_Vertex] { &self.vertices }
+
+    pub fn indices(&self) -> &[i32] { &self.indices }
+
+    /// Queues `src` (in texture **pixels**) to be drawn at `dst` (in screen **pixels**).
+    /// `alpha` (0.0-1.0) replaces the texture alpha mod, which geometry rendering ignores.
+    pub fn push(&mut self, src: FRect, dst: FRect, texture_size: (f32, f32), alpha: f32) {
+        self.push_tinted(src, dst, texture_size, sdl3_sys::pixels::SDL_FColor { r: 1.0, g: 1.0, b: 1.0, a: alpha });
+    }
+
+    /// Like [QuadBatch::push], with `color` standing in for both the color and alpha mod.
+    /// A negative `src` width or height mirrors the quad (see [flipped_source]).
+    pub fn push_tinted(&mut self, src: FRect, dst: FRect, texture_size: (f32, f32), color: sdl3_sys::pixels::SDL_FColor) {
+        let base = self.vertices.len() as i32;
+        let (u0, v0) = (src.x / texture_size.0, src.y / texture_size.1);
+        let (u1, v1) = ((src.x + src.w) / texture_size.0, (src.y + src.h) / texture_size.1);
+        let corners = [(dst.x, dst.y, u0, v0), (dst.x + dst.w, dst.y, u1, v0), (dst.x + dst.w, dst.y + dst.h, u1, v1), (dst.x, dst.y + dst.h, u0, v1)];
+        for (x, y, u, v) in corners {
+            self.vertices.push(SDL_Vertex { position: sdl3_sys::rect::SDL_FPoint { x, y }, color, tex_coord: sdl3_sys::rect::SDL_FPoint { x: u, y: v } });
+        }
+        self.indices.extend_from_slice(&[base, base + 1, base + 2, base, base + 2, base + 3]);
+    }
+}
+
+/// Where `params` lands in screen **pixels**, snapped to whole pixels so batched and
+/// individually drawn sprites line up exactly.
+pub(super) fn sprite_destination(params: &SpriteDrawParams, camera: &Camera, scale: f32) -> FRect {
+    FRect::new(
+        ((((params.pos.x - camera.position.x) + params.offsets.0 as f32) * scale) as i32) as f32,
+        ((((params.pos.y - camera.position.y) + params.offsets.1 as f32) * scale) as i32) as f32,
+        ((params.size.0 as f32 * scale) as u32) as f32,
+        ((params.size.1 as f32 * scale) as u32) as f32,
+    )
+}
+
+/// Submits every quad of `batch` textured with `texture` in one `SDL_RenderGeometry` call.
+pub(super) fn render_geometry(canvas: &WindowCanvas, texture: &sdl3::render::Texture, batch: &QuadBatch) -> Result<(), String> {
+    let (vertices, indices) = (batch.vertices(), batch.indices());
+    let submitted = unsafe {
+        sdl3_sys::render::SDL_RenderGeometry(canvas.raw(), texture.raw(), vertices.as_ptr(), vertices.len() as i32, indices.as_ptr(), indices.len() as i32)
+    };
+    if !submitted { return Err(sdl3::get_error().to_string()); }
+    Ok(())
+}
+
+impl Renderer {
+    /// Draws `quads` of `texture` with a single geometry submission. Each entry is
+    /// (`src` in texture **pixels**, `dst` in screen **pixels**, tint); the tint's alpha is the opacity.
+    ///
+    /// # Side Effects
+    /// * Ignores the texture's color and alpha mods; the per-quad tint replaces them.
+    pub fn draw_batch(&mut self, texture: &sdl3::render::Texture, quads: &[(FRect, FRect, Color)]) -> Result<(), String> {
+        if quads.is_empty() { return Ok(()); }
+        let query = texture.query();
+        let texture_size = (query.width as f32, query.height as f32);
+        self.sprite_batch.clear();
+        for (src, dst, tint) in quads {
+            let color = sdl3_sys::pixels::SDL_FColor { r: tint.r as f32 / 255.0, g: tint.g as f32 / 255.0, b: tint.b as f32 / 255.0, a: tint.a as f32 / 255.0 };
+            self.sprite_batch.push_tinted(*src, *dst, texture_size, color);
+        }
+        render_geometry(&self.canvas, texture, &self.sprite_batch)
+    }
+
+    /// Draws `sprites` in order, merging consecutive sprites that share a texture into one
+    /// [Renderer::draw_batch]. Rotated sprites can't be expressed as axis-aligned quads and fall
+    /// back to [Renderer::draw_sprite]. Sort with [sort_for_batching] first to get long runs.
+    ///
+    /// ⚠️ **Hotpath**: Called once per frame with every visible entity.
+    pub fn draw_sprites(&mut self, sprites: &[SpriteDrawParams], texture_manager: &mut TextureManager, camera: &Camera) -> Result<(), String> {
+        // The run buffer is borrowed out of `self` so it can be filled while `self` draws.
+        let mut run = std::mem::take(&mut self.sprite_run);
+        let result = self.draw_sprite_runs(sprites, &mut run, texture_manager, camera);
+        self.sprite_run = run;
+        result
+    }
+
+    fn draw_sprite_runs(&mut self, sprites: &[SpriteDrawParams], run: &mut Vec<(FRect, FRect, Color)>, texture_manager: &mut TextureManager, camera: &Camera) -> Result<(), String> {
+        let scale = self.view.screen_scale(camera.zoom);
+        let mut start = 0;
+        while start < sprites.len() {
+            let first = sprites[start];
+            // 1. Rotated sprites are drawn one by one.
+            if first.rotation != 0.0 {
+                self.draw_sprite(first, texture_manager, camera)?;
+                self.draw_stats.sprites += 1;
+                self.draw_stats.sprite_batches += 1;
+                start += 1;
+                continue;
+            }
+
+            // 2. Extend the run while the texture stays the same.
+            let end = sprites[start..].iter()
+                .position(|s| s.texture_name != first.texture_name || s.rotation != 0.0)
+                .map_or(sprites.len(), |len| start + len);
+            run.clear();
+            for sprite in &sprites[start..end] {
+                let tint = sprite.color_mod.unwrap_or(Color::WHITE);
+                run.push((flipped_source(sprite.frame_rect, sprite.flip_horizontal, sprite.flip_vertical), sprite_destination(sprite, camera, scale), Color::RGBA(tint.r, tint.g, tint.b, sprite.alpha)));
+            }
+
+            // 3. Submit it in one call.
+            if let Some(texture) = texture_manager.get_or_load(first.texture_name, &self.texture_creator) {
+                self.draw_batch(texture, run)?;
+                self.draw_stats.sprites += run.len() as u32;
+                self.draw_stats.sprite_batches += 1;
+            }
+            start = end;
+        }
+        Ok(())
+    }
+}
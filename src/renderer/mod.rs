@@ -0,0 +1,532 @@
+//! # Manager: Visual Engine (SDL3 Bridge)
+//! 
+//! This module handles all physical drawing operations. It encapsulates the 
+//! SDL3 WindowCanvas and provides a high-level API for rendering the game 
+//! world, UI elements, and cinematic transitions.
+//!
+//! # Depth Convention
+//! Every `z_index` in the engine (sprites, tile layers, parallax) follows one rule:
+//! **higher values are further back** and are drawn first. Entities sit at
+//! [ENTITY_LAYER_Z] by default, so an effect at 101 appears behind the player
+//! and an effect at 99 appears in front of it.
+
+use sdl3::render::{WindowCanvas, FRect, TextureCreator};
+use sdl3::video::WindowContext;
+use sdl3::pixels::Color;
+use crate::units::ViewTransform;
+use crate::level::{Level, TileLayer};
+use crate::camera::Camera;
+use crate::math::Vector2D;
+use crate::texture_manager::TextureManager;
+use crate::font_manager::FontManager;
+
+mod batch;
+mod parallax;
+
+pub use batch::{flipped_source, sort_for_batching, visible_tile_range, QuadBatch};
+pub use parallax::{parallax_origin, parallax_pass};
+use batch::{render_geometry, sprite_destination};
+
+/// Tile layers with a `z_index` below this value are drawn in front of entities.
+pub const ENTITY_LAYER_Z: u8 = 100;
+
+/// Orders (z_index, entity) pairs back to front: higher z first, ties broken by entity ID
+/// so equal-depth sprites never swap order between frames.
+pub fn sort_back_to_front(items: &mut [(u8, crate::ecs::world::Entity)]) {
+    items.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+}
+
+/// Per-frame draw counters shown in the debug overlay next to the hotspots.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DrawStats {
+    /// Tiles submitted across all tile layers.
+    pub tiles: u32,
+    /// Geometry submissions issued for those tiles (one per non-empty layer).
+    pub tile_batches: u32,
+    /// Text lines drawn from the [TextCache].
+    pub text_cache_hits: u32,
+    /// Text lines that had to be rasterized this frame.
+    pub text_rasterized: u32,
+    /// Sprites drawn by [Renderer::draw_sprites].
+    pub sprites: u32,
+    /// Draw calls issued for those sprites: one per texture run, plus one per rotated sprite.
+    pub sprite_batches: u32,
+}
+
+/// Cache key for one rasterized line of text. Lines are rasterized in white and tinted
+/// when drawn, so the color is not part of the key.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TextKey {
+    pub font: String,
+    /// `f32::to_bits` of the font size.
+    pub size_bits: u32,
+    pub text: String,
+}
+
+/// # Concept: Text Cache
+/// Rasterized lines of text (or, with another key, glyphs), so text that stays the same is not
+/// re-rasterized every frame. Holds at most `capacity` entries; the least recently used one is
+/// evicted to make room.
+pub struct TextCache<T, K = TextKey> {
+    entries: std::collections::HashMap<K, (T, u64)>,
+    capacity: usize,
+    clock: u64,
+}
+
+impl<T, K: std::hash::Hash + Eq + Clone> TextCache<T, K> {
+    pub fn new(capacity: usize) -> Self {
+        Self { entries: std::collections::HashMap::new(), capacity: capacity.max(1), clock: 0 }
+    }
+
+    /// Looks up a line and marks it as recently used.
+    pub fn get(&mut self, key: &K) -> Option<&mut T> {
+        self.clock += 1;
+        let clock = self.clock;
+        self.entries.get_mut(key).map(|(value, used)| { *used = clock; value })
+    }
+
+    /// Stores a line, returning the evicted entry (if any) so its resources can be released.
+    pub fn insert(&mut self, key: K, value: T) -> Option<T> {
+        self.clock += 1;
+        let mut evicted = None;
+        if !self.entries.contains_key(&key) && self.entries.len() >= self.capacity {
+            let oldest = self.entries.iter().min_by_key(|(_, (_, used))| *used).map(|(k, _)| k.clone());
+            evicted = oldest.and_then(|k| self.entries.remove(&k)).map(|(value, _)| value);
+        }
+        if let Some((old, _)) = self.entries.insert(key, (value, self.clock)) { evicted = Some(old); }
+        evicted
+    }
+
+    pub fn len(&self) -> usize { self.entries.len() }
+
+    pub fn is_empty(&self) -> bool { self.entries.is_empty() }
+}
+
+/// A cached line texture with its size in **pixels**.
+struct CachedText {
+    texture: sdl3::render::Texture,
+    width: u32,
+    height: u32,
+}
+
+/// Lines of text kept rasterized at once.
+const TEXT_CACHE_CAPACITY: usize = 256;
+
+/// # Concept: CRT Post-Process
+/// The frame is drawn into a texture at the virtual resolution and stretched to the window
+/// in a single copy, so tiles can no longer leave subpixel seams between them. A 1-pixel-wide
+/// scanline strip is then multiplied over the result.
+struct PostProcess {
+    target: sdl3::render::Texture,
+    scanlines: Option<sdl3::render::Texture>,
+}
+
+/// Brightness (0-255) of each row of the scanline strip: two rows per virtual row, the lower one dimmed.
+pub fn scanline_rows(virtual_height: u32, strength: f32) -> Vec<u8> {
+    let dimmed = (255.0 * (1.0 - strength.clamp(0.0, 1.0))).round() as u8;
+    (0..virtual_height * 2).map(|row| if row % 2 == 0 { 255 } else { dimmed }).collect()
+}
+
+/// The primary context for GPU-accelerated 2D rendering.
+pub struct Renderer {
+    pub canvas: WindowCanvas,
+    /// Kept so textures can be loaded on first draw (see [TextureManager::get_or_load]).
+    texture_creator: TextureCreator<WindowContext>,
+    /// Global clock driving animated tiles in **seconds**.
+    tile_animation_time: f32,
+    /// Reusable per-color rect lists so particles are drawn with one `fill_rects` call per color.
+    /// Ordered, so overlapping colors always stack the same way.
+    particle_batches: std::collections::BTreeMap<[u8; 4], Vec<FRect>>,
+    /// Reusable quad list for the tile layer currently being drawn.
+    tile_batch: QuadBatch,
+    /// Reusable quad list for the sprite run currently being drawn.
+    sprite_batch: QuadBatch,
+    /// Reusable (src, dst, tint) list collected by [Renderer::draw_sprites] for one texture run.
+    sprite_run: Vec<(FRect, FRect, Color)>,
+    /// Counters for the frame in progress; reset by [Renderer::clear].
+    pub draw_stats: DrawStats,
+    /// How the virtual screen is scaled and centered in the window; see [Renderer::update_view].
+    pub view: ViewTransform,
+    /// Virtual resolution and scaling mode the [ViewTransform] is fitted from.
+    window_config: crate::config::WindowConfig,
+    /// Offscreen target used instead of the window when the CRT pass is enabled.
+    post_process: Option<PostProcess>,
+    text_cache: TextCache<CachedText>,
+}
+
+#[derive(Clone, Copy)]
+pub struct SpriteDrawParams<'a> {
+    pub pos: Vector2D,
+    pub size: (u32, u32),
+    pub offsets: (i32, i32),
+    pub texture_name: &'a str,
+    pub frame_rect: &'a sdl3::rect::Rect,
+    pub color_mod: Option<Color>,
+    /// Sprite opacity (255 = opaque).
+    pub alpha: u8,
+    pub rotation: f64,
+    pub flip_horizontal: bool,
+    pub flip_vertical: bool,
+}
+
+/// Horizontal placement of each line relative to [TextRenderParams::x].
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum TextAlign {
+    #[default] Left,
+    Center,
+    Right,
+}
+
+pub struct TextRenderParams<'a> {
+    pub text: &'a str,
+    /// Name the font was registered under with [FontManager::load].
+    pub font: &'a str,
+    pub x: i32,
+    pub y: i32,
+    pub font_size: f32,
+    pub scale: f32,
+    pub color: Color,
+    /// Whether `x` is the left edge, the center or the right edge of every line.
+    pub align: TextAlign,
+    /// Word-wraps lines wider than this many screen **pixels**; `None` only breaks at `\n`.
+    pub max_width: Option<u32>,
+    /// Distance in screen **pixels** between line tops; `None` uses the font height.
+    pub line_height: Option<i32>,
+}
+
+/// Left edge of a line `width` **pixels** wide drawn at `x` with `align`.
+/// Every line of a block is aligned on its own, so centered paragraphs stay centered line by line.
+pub fn aligned_x(align: TextAlign, x: i32, width: u32) -> i32 {
+    match align {
+        TextAlign::Left => x,
+        TextAlign::Center => x - width as i32 / 2,
+        TextAlign::Right => x - width as i32,
+    }
+}
+
+impl Default for TextRenderParams<'_> {
+    fn default() -> Self {
+        Self { text: "", font: "debug", x: 0, y: 0, font_size: 24.0, scale: 1.0, color: Color::WHITE, align: TextAlign::Left, max_width: None, line_height: None }
+    }
+}
+
+impl Renderer {
+    pub fn new(canvas: WindowCanvas, window_config: &crate::config::WindowConfig) -> Result<Self, String> {
+        let texture_creator = canvas.texture_creator();
+        let mut renderer = Self { canvas, texture_creator, tile_animation_time: 0.0, particle_batches: std::collections::BTreeMap::new(), tile_batch: QuadBatch::default(), sprite_batch: QuadBatch::default(), sprite_run: Vec::new(), draw_stats: DrawStats::default(), view: ViewTransform::default(), window_config: window_config.clone(), post_process: None, text_cache: TextCache::new(TEXT_CACHE_CAPACITY) };
+        renderer.update_view();
+        Ok(renderer)
+    }
+
+    /// The creator for textures drawn on this renderer's canvas.
+    pub fn texture_creator(&self) -> &TextureCreator<WindowContext> { &self.texture_creator }
+
+    /// Refits the [ViewTransform] to the current output size; call after the window is resized
+    /// or switches between windowed and fullscreen.
+    pub fn update_view(&mut self) {
+        let output = self.canvas.output_size().unwrap_or(self.view.canvas_size);
+        self.view = ViewTransform::for_window(&self.window_config, output);
+    }
+
+    /// Redirects all drawing into a `virtual_width` x `virtual_height` texture that [Self::present]
+    /// stretches over the letterboxed canvas, with scanlines of `scanline_strength` (0 disables them).
+    pub fn enable_post_process(&mut self, virtual_width: u32, virtual_height: u32, scanline_strength: f32) -> Result<(), String> {
+        let texture_creator = self.canvas.texture_creator();
+
+        // 1. The offscreen frame, upscaled with nearest-neighbour sampling.
+        let target = texture_creator.create_texture_target(None, virtual_width, virtual_height).map_err(|e| e.to_string())?;
+        unsafe { sdl3_sys::render::SDL_SetTextureScaleMode(target.raw(), sdl3_sys::surface::SDL_SCALEMODE_NEAREST); }
+
+        // 2. A one-pixel-wide strip of scanlines, multiplied over the stretched frame.
+        let scanlines = if scanline_strength > 0.0 {
+            let rows = scanline_rows(virtual_height, scanline_strength);
+            let mut surface = sdl3::surface::Surface::new(1, rows.len() as u32, sdl3::pixels::PixelFormatEnum::ABGR8888.into()).map_err(|e| e.to_string())?;
+            surface.with_lock_mut(|pixels| {
+                for (pixel, value) in pixels.chunks_exact_mut(4).zip(&rows) { pixel.copy_from_slice(&[*value, *value, *value, 255]); }
+            });
+            let mut texture = texture_creator.create_texture_from_surface(&surface).map_err(|e| e.to_string())?;
+            texture.set_blend_mode(sdl3::render::BlendMode::Mod);
+            unsafe { sdl3_sys::render::SDL_SetTextureScaleMode(texture.raw(), sdl3_sys::surface::SDL_SCALEMODE_NEAREST); }
+            Some(texture)
+        } else { None };
+
+        self.post_process = Some(PostProcess { target, scanlines });
+        Ok(())
+    }
+
+    /// Advances the global tile animation clock by the real frame time.
+    pub fn update_tile_animations(&mut self, delta_time: f32) { self.tile_animation_time += delta_time; }
+
+    /// Size of the letterboxed canvas in **pixels**; everything is drawn relative to its top-left corner.
+    pub fn output_size(&self) -> (u32, u32) { self.view.canvas_size }
+    /// Output **pixels** per window coordinate on each axis (above 1.0 on high-DPI displays), for mapping the mouse.
+    pub fn pointer_scale(&self) -> (f32, f32) {
+        let (output_width, output_height) = self.canvas.output_size().unwrap_or(self.view.canvas_size);
+        let (window_width, window_height) = self.canvas.window().size();
+        (output_width as f32 / window_width.max(1) as f32, output_height as f32 / window_height.max(1) as f32)
+    }
+    #[allow(dead_code)]
+    pub fn set_draw_color(&mut self, color: Color) { self.canvas.set_draw_color(color); }
+    /// Starts a frame. The window is cleared to black and drawing is confined to the canvas, so
+    /// whatever is left around it shows as letterbox bars. With the CRT pass, drawing goes to the
+    /// offscreen target instead, scaled down so callers keep using canvas coordinates.
+    pub fn clear(&mut self, color: Color) {
+        self.draw_stats = DrawStats::default();
+        if let Some(post) = &self.post_process {
+            let scale = 1.0 / self.view.scale;
+            unsafe {
+                sdl3_sys::render::SDL_SetRenderTarget(self.canvas.raw(), post.target.raw());
+                sdl3_sys::render::SDL_SetRenderScale(self.canvas.raw(), scale, scale);
+            }
+            self.canvas.set_draw_color(color); self.canvas.clear();
+            return;
+        }
+        // 1. Bars: the whole window, viewport or not.
+        self.canvas.set_viewport(None);
+        self.canvas.set_draw_color(Color::BLACK); self.canvas.clear();
+        // 2. The canvas: every later draw is offset into (and clipped to) it.
+        self.canvas.set_viewport(Some(self.view.canvas_rect()));
+        let (width, height) = self.view.canvas_size;
+        self.canvas.set_draw_color(color);
+        let _ = self.canvas.fill_rect(sdl3::rect::Rect::new(0, 0, width, height));
+    }
+
+    /// Shows the frame. With the CRT pass, the offscreen target is first stretched to the window
+    /// and the scanlines are multiplied on top.
+    pub fn present(&mut self) {
+        if let Some(post) = &self.post_process {
+            unsafe {
+                sdl3_sys::render::SDL_SetRenderTarget(self.canvas.raw(), std::ptr::null_mut());
+                sdl3_sys::render::SDL_SetRenderScale(self.canvas.raw(), 1.0, 1.0);
+            }
+            self.canvas.set_viewport(None);
+            self.canvas.set_draw_color(Color::BLACK);
+            self.canvas.clear();
+            let canvas_rect = self.view.canvas_rect();
+            let _ = self.canvas.copy(&post.target, None, canvas_rect);
+            if let Some(scanlines) = &post.scanlines { let _ = self.canvas.copy(scanlines, None, canvas_rect); }
+        }
+        self.canvas.present();
+    }
+
+    /// Reads back the frame drawn so far as RGBA8; call before [Self::present].
+    /// With the CRT pass this is the virtual-resolution target, without the scanlines.
+    pub fn read_pixels(&mut self) -> Result<crate::frame_capture::CapturedFrame, String> {
+        unsafe {
+            // 1. Copy the current target into a surface and normalize its layout.
+            let raw = sdl3_sys::render::SDL_RenderReadPixels(self.canvas.raw(), std::ptr::null());
+            if raw.is_null() { return Err(sdl3::get_error().to_string()); }
+            let rgba = sdl3_sys::surface::SDL_ConvertSurface(raw, sdl3_sys::pixels::SDL_PIXELFORMAT_RGBA32);
+            sdl3_sys::surface::SDL_DestroySurface(raw);
+            if rgba.is_null() { return Err(sdl3::get_error().to_string()); }
+
+            // 2. Strip the row padding.
+            let (width, height, pitch) = ((*rgba).w.max(0) as u32, (*rgba).h.max(0) as u32, (*rgba).pitch.max(0) as usize);
+            let source = std::slice::from_raw_parts((*rgba).pixels as *const u8, pitch * height as usize);
+            let mut pixels = Vec::with_capacity((width * height * 4) as usize);
+            for row in source.chunks_exact(pitch.max(1)) { pixels.extend_from_slice(&row[..width as usize * 4]); }
+            sdl3_sys::surface::SDL_DestroySurface(rgba);
+            Ok(crate::frame_capture::CapturedFrame { width, height, pixels })
+        }
+    }
+
+    pub fn copy(&mut self, texture: &sdl3::render::Texture, src: Option<sdl3::rect::Rect>, dst: Option<sdl3::rect::Rect>) -> Result<(), String> {
+        self.canvas.copy(
+            texture,
+            src.map(|r| FRect::new(r.x as f32, r.y as f32, r.width() as f32, r.height() as f32)),
+            dst.map(|r| FRect::new(r.x as f32, r.y as f32, r.width() as f32, r.height() as f32))
+        ).map_err(|e| e.to_string())
+    }
+
+    /// Fills a rectangle with the specified color.
+    pub fn fill_rect(&mut self, rect: &sdl3::rect::Rect, color: Color) -> Result<(), String> {
+        self.canvas.set_blend_mode(sdl3::render::BlendMode::Blend);
+        self.canvas.set_draw_color(color);
+        let frect = FRect::new(rect.x as f32, rect.y as f32, rect.width() as f32, rect.height() as f32);
+        self.canvas.fill_rect(frect).map_err(|e| e.to_string())?;
+        self.canvas.set_blend_mode(sdl3::render::BlendMode::None);
+        Ok(())
+    }
+
+    /// Draws the outline of a rectangle with the specified color.
+    pub fn draw_rect(&mut self, rect: &sdl3::rect::Rect, color: Color) -> Result<(), String> {
+        self.canvas.set_draw_color(color);
+        self.canvas.draw_rect((*rect).into()).map_err(|e| e.to_string())?;
+        Ok(())
+    }
+
+    /// Draws (possibly multi-line) text; each line is rasterized once and then reused from the [TextCache].
+    pub fn render_text(&mut self, font_manager: &FontManager, params: TextRenderParams) -> Result<(), String> {
+        // 0. Unknown font names (e.g. a typo in `[menu]`) fall back to the built-in debug font.
+        let font = if font_manager.has_font(params.font) { params.font } else { "debug" };
+
+        // 1. Break the text into lines: explicit newlines, then word-wrapping in unscaled font pixels.
+        let lines = match params.max_width {
+            Some(max_width) => font_manager.wrap_text(font, params.text, params.font_size, (max_width as f32 / params.scale) as u32)?,
+            None => params.text.split('\n').map(str::to_string).collect(),
+        };
+
+        let mut y = params.y;
+        for line in lines {
+            // 2. Fetch the rasterized line, rasterizing it in white on a miss.
+            let key = TextKey { font: font.to_string(), size_bits: params.font_size.to_bits(), text: line };
+            if self.text_cache.get(&key).is_some() {
+                self.draw_stats.text_cache_hits += 1;
+            } else {
+                let surface = font_manager.render_surface(font, &key.text, params.font_size, Color::WHITE)?;
+                let mut texture = self.canvas.texture_creator().create_texture_from_surface(&surface).map_err(|e| e.to_string())?;
+                texture.set_blend_mode(sdl3::render::BlendMode::Blend);
+                unsafe { sdl3_sys::render::SDL_SetTextureScaleMode(texture.raw(), sdl3_sys::surface::SDL_SCALEMODE_NEAREST); }
+                let cached = CachedText { texture, width: surface.width(), height: surface.height() };
+                if let Some(evicted) = self.text_cache.insert(key.clone(), cached) { unsafe { evicted.texture.destroy(); } }
+                self.draw_stats.text_rasterized += 1;
+            }
+            let Some(cached) = self.text_cache.get(&key) else { continue };
+
+            // 3. Tint and place the line according to the alignment.
+            cached.texture.set_color_mod(params.color.r, params.color.g, params.color.b);
+            cached.texture.set_alpha_mod(params.color.a);
+            let width = (cached.width as f32 * params.scale) as u32;
+            let height = (cached.height as f32 * params.scale) as u32;
+            let x = aligned_x(params.align, params.x, width);
+            if !key.text.is_empty() {
+                let dest_rect = sdl3::rect::Rect::new(x, y, width, height);
+                self.canvas.copy(&cached.texture, None, dest_rect).map_err(|e| e.to_string())?;
+            }
+            y += params.line_height.unwrap_or(height as i32);
+        }
+        Ok(())
+    }
+
+    /// Renders the parallax background layers followed by the tile layers that sit behind entities.
+    ///
+    /// ⚠️ **Hotpath**: Called every frame. Loops over all map tiles within the view.
+    pub fn draw_level(&mut self, level: &Level, texture_manager: &mut TextureManager, camera: &Camera, parallax_config: &crate::config::ParallaxConfig) -> Result<(), String> {
+        // 1. Background parallax layers sit behind the tile map.
+        self.draw_parallax_layers(parallax_config, texture_manager, camera, true)?;
+
+        // 2. Background tile layers (z_index >= ENTITY_LAYER_Z), back to front.
+        let mut layers: Vec<_> = level.map.layers.iter().filter(|l| l.z_index >= ENTITY_LAYER_Z).collect();
+        layers.sort_by(|a, b| b.z_index.cmp(&a.z_index));
+        for layer in layers {
+            self.draw_tile_layer(level, layer, texture_manager, camera)?;
+        }
+        Ok(())
+    }
+
+    /// Renders everything that sits in front of entities: foreground tile layers, then foreground parallax.
+    ///
+    /// ⚠️ **Hotpath**: Called every frame after the entity pass.
+    pub fn draw_foreground(&mut self, level: &Level, texture_manager: &mut TextureManager, camera: &Camera, parallax_config: &crate::config::ParallaxConfig) -> Result<(), String> {
+        // 1. Foreground tile layers (z_index < ENTITY_LAYER_Z), back to front.
+        let mut layers: Vec<_> = level.map.layers.iter().filter(|l| l.z_index < ENTITY_LAYER_Z).collect();
+        layers.sort_by(|a, b| b.z_index.cmp(&a.z_index));
+        for layer in layers {
+            self.draw_tile_layer(level, layer, texture_manager, camera)?;
+        }
+
+        // 2. Foreground parallax layers.
+        self.draw_parallax_layers(parallax_config, texture_manager, camera, false)
+    }
+
+    /// Draws a single tile layer, culled to the camera view and modulated by the layer opacity.
+    ///
+    /// Only the tile range overlapping the view is visited, and every visible tile of the
+    /// layer is submitted as one [QuadBatch].
+    fn draw_tile_layer(&mut self, level: &Level, layer: &TileLayer, texture_manager: &mut TextureManager, camera: &Camera) -> Result<(), String> {
+        let scale = self.view.screen_scale(camera.zoom);
+        let anim_time = self.tile_animation_time;
+        if layer.opacity <= 0.0 || layer.tiles.is_empty() { return Ok(()); }
+
+        if let Some(tileset_texture) = texture_manager.get_or_load(&level.tileset.texture, &self.texture_creator) {
+            let tile_width = level.tileset.tile_width;
+            let tile_height = level.tileset.tile_height;
+            let query = tileset_texture.query();
+            let texture_size = (query.width as f32, query.height as f32);
+            // A missing tileset draws the placeholder, which may be narrower than one tile.
+            let tileset_cols = (query.width / tile_width).max(1);
+            let dest_w = (tile_width as f32 * scale) as u32 as f32;
+            let dest_h = (tile_height as f32 * scale) as u32 as f32;
+
+            // 1. Collect the visible tiles of this layer.
+            let (rows, cols) = visible_tile_range(camera, tile_width, tile_height, layer.tiles.len(), layer.tiles[0].len());
+            self.tile_batch.clear();
+            for row_idx in rows {
+                let row = &layer.tiles[row_idx];
+                for (col_idx, &tile_id) in row.iter().enumerate().take(cols.end).skip(cols.start) {
+                    if tile_id == 0 { continue; }
+                    // Static tiles stay on the fast path; animated ones resolve their current frame.
+                    let tile_id = if level.tileset.animations.is_empty() { tile_id } else {
+                        level.tileset.animations.get(&tile_id).map_or(tile_id, |a| a.frame_at(anim_time))
+                    };
+                    let src_x = ((tile_id - 1) % tileset_cols) * tile_width;
+                    let src_y = ((tile_id - 1) / tileset_cols) * tile_height;
+                    let src = FRect::new(src_x as f32, src_y as f32, tile_width as f32, tile_height as f32);
+
+                    // Scale positions to Screen Space, snapped to whole pixels like `copy` with a Rect.
+                    let dest_x = (((col_idx as f32 * tile_width as f32) - camera.position.x) * scale) as i32;
+                    let dest_y = (((row_idx as f32 * tile_height as f32) - camera.position.y) * scale) as i32;
+                    self.tile_batch.push(src, FRect::new(dest_x as f32, dest_y as f32, dest_w, dest_h), texture_size, layer.opacity.min(1.0));
+                }
+            }
+
+            // 2. Submit the whole layer at once.
+            if self.tile_batch.is_empty() { return Ok(()); }
+            render_geometry(&self.canvas, tileset_texture, &self.tile_batch)?;
+            self.draw_stats.tiles += self.tile_batch.len() as u32;
+            self.draw_stats.tile_batches += 1;
+        }
+        Ok(())
+    }
+
+    /// Draws every particle as a small alpha-blended square, culled to the camera view.
+    ///
+    /// ⚠️ **Hotpath**: Called every frame with up to `max_particles` items.
+    pub fn draw_particles(&mut self, particles: &[crate::ecs::resources::Particle], camera: &Camera) -> Result<(), String> {
+        let scale = self.view.screen_scale(camera.zoom);
+        let (view_w, view_h) = (camera.view_width(), camera.view_height());
+
+        // 1. Bucket visible particles by color; the low bits are dropped so fading particles share batches.
+        //    Colors unused last frame are dropped, so the map never outgrows the colors on screen.
+        self.particle_batches.retain(|_, batch| {
+            let was_used = !batch.is_empty();
+            batch.clear();
+            was_used
+        });
+        for p in particles {
+            let (x, y) = (p.position.x - camera.position.x, p.position.y - camera.position.y);
+            if x + p.size < 0.0 || y + p.size < 0.0 || x > view_w || y > view_h { continue; }
+            let color = p.color().map(|c| c & 0xF0 | c >> 4);
+            self.particle_batches.entry(color).or_default().push(FRect::new(x * scale, y * scale, p.size * scale, p.size * scale));
+        }
+
+        // 2. One draw call per color.
+        self.canvas.set_blend_mode(sdl3::render::BlendMode::Blend);
+        for ([r, g, b, a], rects) in &self.particle_batches {
+            if rects.is_empty() { continue; }
+            self.canvas.set_draw_color(Color::RGBA(*r, *g, *b, *a));
+            self.canvas.fill_rects(rects).map_err(|e| e.to_string())?;
+        }
+        self.canvas.set_blend_mode(sdl3::render::BlendMode::None);
+        Ok(())
+    }
+
+    /// Renders a single sprite with support for rotation, scaling, and color modulation.
+    ///
+    /// ⚠️ **Hotpath**: Called many times per frame.
+    ///
+    /// # Side Effects
+    /// * Temporarily modulates the shared texture; color and alpha are reset after the copy
+    ///   so other entities using the same texture are unaffected.
+    pub fn draw_sprite(&mut self, params: SpriteDrawParams, texture_manager: &mut TextureManager, camera: &Camera) -> Result<(), String> {
+        if let Some(texture) = texture_manager.get_or_load(params.texture_name, &self.texture_creator) {
+            if let Some(color) = params.color_mod { texture.set_color_mod(color.r, color.g, color.b); }
+            if params.alpha < 255 { texture.set_alpha_mod(params.alpha); }
+            let fsrc = FRect::new(params.frame_rect.x as f32, params.frame_rect.y as f32, params.frame_rect.width() as f32, params.frame_rect.height() as f32);
+            self.canvas.copy_ex(texture, fsrc, sprite_destination(&params, camera, self.view.screen_scale(camera.zoom)), params.rotation, None, params.flip_horizontal, params.flip_vertical).map_err(|e| e.to_string())?;
+            if params.color_mod.is_some() { texture.set_color_mod(255, 255, 255); }
+            if params.alpha < 255 { texture.set_alpha_mod(255); }
+        }
+        Ok(())
+    }
+}
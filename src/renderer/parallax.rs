@@ -0,0 +1,68 @@
+//! # Concept: Parallax Layers
+//!
+//! Repeating background and foreground images that scroll at a fraction of the
+//! camera speed. Layers with a `z_index` above `tile_z_index` are drawn behind the
+//! tile map, the rest in front of it; each pass is drawn back to front.
+
+use crate::camera::Camera;
+use crate::config::{ParallaxConfig, ParallaxLayerConfig};
+use crate::math::Vector2D;
+use crate::texture_manager::TextureManager;
+use super::Renderer;
+
+/// The layers of one parallax pass, back to front: behind the tile map when `behind_tiles`,
+/// in front of it otherwise.
+pub fn parallax_pass(config: &ParallaxConfig, behind_tiles: bool) -> Vec<&ParallaxLayerConfig> {
+    let mut layers: Vec<_> = config.layers.iter().filter(|l| (l.z_index > config.tile_z_index) == behind_tiles).collect();
+    layers.sort_by(|a, b| b.z_index.cmp(&a.z_index));
+    layers
+}
+
+/// Screen position (in **pixels**) of the top-left copy of a `texture_size` texture (in
+/// texture **pixels**) drawn at `scale`. The camera position is scaled by the layer's scroll
+/// speeds; wrapped axes start in `(-size, 0]` so the copies cover the screen seamlessly.
+pub fn parallax_origin(layer: &ParallaxLayerConfig, camera_position: Vector2D, texture_size: (u32, u32), scale: f32) -> (f32, f32) {
+    // 1. Scroll offset in Retro Space.
+    let scroll_x = camera_position.x * layer.scroll_speed_x;
+    let scroll_y = camera_position.y * layer.scroll_speed_y;
+
+    // 2. Wrap into the first copy, in Screen Space.
+    let wrap = |scroll: f32, size: u32| {
+        let offset = (-scroll % size as f32) * scale;
+        if offset > 0.0 { offset - size as f32 * scale } else { offset }
+    };
+    let x = wrap(scroll_x, texture_size.0);
+    let y = if layer.repeat_y { wrap(scroll_y, texture_size.1) } else { -scroll_y * scale };
+    (x, y)
+}
+
+impl Renderer {
+    /// Draws one parallax pass (see [parallax_pass]), wrapping each texture seamlessly.
+    /// Layers scale with the camera zoom like the tiles, so a co-op zoom-out shrinks them too.
+    pub(super) fn draw_parallax_layers(&mut self, parallax_config: &ParallaxConfig, texture_manager: &mut TextureManager, camera: &Camera, behind_tiles: bool) -> Result<(), String> {
+        let scale = self.view.screen_scale(camera.zoom);
+        let (screen_width, screen_height) = self.output_size();
+
+        for layer in parallax_pass(parallax_config, behind_tiles) {
+            if let Some(texture) = texture_manager.get_or_load(&layer.texture, &self.texture_creator) {
+                let q = texture.query();
+                let scaled_width = q.width as f32 * scale;
+                let scaled_height = q.height as f32 * scale;
+                let (start_x, start_y) = parallax_origin(layer, camera.position, (q.width, q.height), scale);
+
+                let mut draw_y = start_y;
+                loop {
+                    let mut draw_x = start_x;
+                    while draw_x < screen_width as f32 {
+                        let dest_rect = sdl3::rect::Rect::new(draw_x as i32, draw_y as i32, scaled_width as u32, scaled_height as u32);
+                        self.canvas.copy(texture, None, dest_rect).map_err(|e| e.to_string())?;
+                        draw_x += scaled_width;
+                    }
+                    draw_y += scaled_height;
+                    if !layer.repeat_y || draw_y >= screen_height as f32 { break; }
+                }
+            }
+        }
+        Ok(())
+    }
+}
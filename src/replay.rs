@@ -5,33 +5,170 @@
 //! to disk, enabling deterministic "Attract Mode" and bug reproduction.
 
 use serde::{Serialize, Deserialize};
-use crate::input::InputAction;
+use crate::input::{InputAction, InputState};
+use crate::level::{load_level, Level};
+use crate::config::PhysicsConfig;
+
+/// Format version written by [Replay::save]. Files without a version predate the level metadata;
+/// version 1 files carry no physics snapshot. Up to version 2 every tick is stored as its own frame;
+/// from version 3 only the ticks where the input changes are (see [Replay::record]). Version 4 adds
+/// the second local player's actions.
+pub const REPLAY_VERSION: u32 = 4;
+
+/// The replay played behind the main menu.
+pub const ATTRACT_REPLAY: &str = "attract_mode";
 
 /// A single snapshot of all pressed actions at a specific simulation tick.
-#[derive(Serialize, Deserialize, Clone)]
+/// In a [Replay] it holds from `tick` until the next frame's tick.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct InputFrame {
     pub tick: u64,
     pub pressed_actions: Vec<InputAction>,
+    /// Held on the second local player's bindings; empty in single-player sessions and files older than version 4.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub player_two_actions: Vec<InputAction>,
+}
+
+/// The physics values a session was recorded with. Playback simulates against these, so
+/// tuning the live config cannot desync the recorded inputs.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PhysicsSnapshot {
+    pub gravity: f32,
+    pub max_speed: f32,
+    pub entity_max_fall_speed: f32,
+    pub acceleration: f32,
+    pub deceleration: f32,
+    pub jump_strength: f32,
+    pub jump_hold_force: f32,
+    pub air_control_factor: f32,
+    pub coyote_time: f32,
+    pub jump_buffer_time: f32,
+    pub max_jumps: u32,
+}
+
+impl PhysicsSnapshot {
+    /// Copies the movement-relevant values out of `physics`.
+    pub fn capture(physics: &PhysicsConfig) -> Self {
+        Self {
+            gravity: physics.gravity,
+            max_speed: physics.max_speed,
+            entity_max_fall_speed: physics.entity_max_fall_speed,
+            acceleration: physics.acceleration,
+            deceleration: physics.deceleration,
+            jump_strength: physics.jump_strength,
+            jump_hold_force: physics.jump_hold_force,
+            air_control_factor: physics.air_control_factor,
+            coyote_time: physics.coyote_time,
+            jump_buffer_time: physics.jump_buffer_time,
+            max_jumps: physics.max_jumps,
+        }
+    }
+
+    /// Overwrites the snapshotted values in `physics`, leaving the rest (water, timestep) untouched.
+    pub fn apply(&self, physics: &mut PhysicsConfig) {
+        physics.gravity = self.gravity;
+        physics.max_speed = self.max_speed;
+        physics.entity_max_fall_speed = self.entity_max_fall_speed;
+        physics.acceleration = self.acceleration;
+        physics.deceleration = self.deceleration;
+        physics.jump_strength = self.jump_strength;
+        physics.jump_hold_force = self.jump_hold_force;
+        physics.air_control_factor = self.air_control_factor;
+        physics.coyote_time = self.coyote_time;
+        physics.jump_buffer_time = self.jump_buffer_time;
+        physics.max_jumps = self.max_jumps;
+    }
 }
 
 /// A collection of input frames representing a complete gameplay session.
+///
+/// In memory `frames` are always run-length encoded: a frame is only stored at the ticks where the
+/// pressed actions change, and holds until the next one. [Self::load] converts older per-tick files.
 #[derive(Serialize, Deserialize, Clone, Default)]
 pub struct Replay {
+    /// Format version of the file this replay was read from; `0` for files recorded before versioning.
+    #[serde(default)]
+    pub version: u32,
+    /// Crate version of the engine that recorded the session; `None` in files older than version 3.
+    #[serde(default)]
+    pub engine_version: Option<String>,
     pub seed: u64,
+    /// Path of the TMX level the session was recorded on; `None` in legacy files (the start level).
+    #[serde(default)]
+    pub level: Option<String>,
+    /// Physics at recording time; `None` in files older than version 2, which play against the live config.
+    #[serde(default)]
+    pub physics: Option<PhysicsSnapshot>,
+    /// Length of the session in ticks, including any unchanged input after the last frame.
+    #[serde(default)]
+    pub total_ticks: u64,
     pub frames: Vec<InputFrame>,
 }
 
 impl Replay {
-    #[allow(dead_code)]
-    pub fn new(seed: u64) -> Self {
-        Self { seed, frames: Vec::new() }
+    pub fn new(seed: u64, level: &str, physics: &PhysicsConfig) -> Self {
+        Self {
+            version: REPLAY_VERSION,
+            engine_version: Some(env!("CARGO_PKG_VERSION").to_string()),
+            seed,
+            level: Some(level.to_string()),
+            physics: Some(PhysicsSnapshot::capture(physics)),
+            total_ticks: 0,
+            frames: Vec::new(),
+        }
+    }
+
+    /// Appends the input of `tick` for both local players, storing a frame only if it differs from
+    /// the input held so far. Ticks must be recorded in order.
+    pub fn record(&mut self, tick: u64, pressed_actions: Vec<InputAction>, player_two_actions: Vec<InputAction>) {
+        let unchanged = match self.frames.last() {
+            Some(last) => same_actions(&last.pressed_actions, &pressed_actions) && same_actions(&last.player_two_actions, &player_two_actions),
+            None => pressed_actions.is_empty() && player_two_actions.is_empty(),
+        };
+        if !unchanged { self.frames.push(InputFrame { tick, pressed_actions, player_two_actions }); }
+        self.total_ticks = self.total_ticks.max(tick + 1);
+    }
+
+    /// The frame holding at `tick`: the last one at or before it (none before the first frame).
+    fn frame_at(&self, tick: u64) -> Option<&InputFrame> {
+        let index = self.frames.partition_point(|frame| frame.tick <= tick);
+        index.checked_sub(1).map(|i| &self.frames[i])
+    }
+
+    /// The actions held at `tick` by the first player.
+    pub fn actions_at(&self, tick: u64) -> &[InputAction] {
+        self.frame_at(tick).map_or(&[], |frame| &frame.pressed_actions)
+    }
+
+    /// The actions held at `tick` on the second player's bindings.
+    pub fn player_two_actions_at(&self, tick: u64) -> &[InputAction] {
+        self.frame_at(tick).map_or(&[], |frame| &frame.player_two_actions)
+    }
+
+    /// Both players' held actions at `tick`, without presses.
+    fn held_at(&self, tick: u64) -> InputState {
+        let mut input = InputState::from_actions(self.actions_at(tick).to_vec());
+        input.set_player_two_actions(self.player_two_actions_at(tick).to_vec());
+        input
+    }
+
+    /// The input of `tick` as the simulation saw it, with presses relative to the tick before so
+    /// "just pressed" fires exactly once. Needs no playback history, so any tick can be reconstructed directly.
+    pub fn input_at(&self, tick: u64) -> InputState {
+        let previous = match tick.checked_sub(1) {
+            Some(previous_tick) => self.held_at(previous_tick),
+            None => InputState::default(),
+        };
+        let mut input = self.held_at(tick);
+        input.calculate_deltas(&previous);
+        input
     }
 
-    /// Serializes the current replay buffer to a JSON file on disk.
-    #[allow(dead_code)]
+    /// Serializes the current replay buffer to a JSON file on disk, always in the current format.
     pub fn save(&self, name: &str) -> Result<(), String> {
-        // 1. Transform the struct into a human-readable JSON string.
-        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        // 1. Transform the struct into a human-readable JSON string, stamped with the format it is written in.
+        let current = Self { version: REPLAY_VERSION, ..self.clone() };
+        let json = serde_json::to_string_pretty(&current).map_err(|e| e.to_string())?;
         // 2. Write the resulting bytes to the assets/replays directory.
         std::fs::write(format!("assets/replays/{}.replay", name), json).map_err(|e| e.to_string())
     }
@@ -40,7 +177,164 @@ impl Replay {
     pub fn load(name: &str) -> Result<Self, String> {
         // 1. Read the raw bytes from the specified file.
         let content = std::fs::read_to_string(format!("assets/replays/{}.replay", name)).map_err(|e| e.to_string())?;
-        // 2. Parse the JSON back into a Replay struct.
-        serde_json::from_str(&content).map_err(|e| e.to_string())
+        // 2. Parse the JSON and bring the frames into the encoded form.
+        Self::parse(&content).map_err(|e| format!("Replay '{}': {}", name, e))
+    }
+
+    /// Parses replay JSON of any supported version. Per-tick frames from files older than version 3
+    /// are run-length encoded on the way in, so playback never needs to know the file format.
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let mut replay: Self = serde_json::from_str(content).map_err(|e| e.to_string())?;
+        // Refuse files written by a newer engine rather than misreading them.
+        if replay.version > REPLAY_VERSION {
+            return Err(format!("version {} is newer than the supported {}", replay.version, REPLAY_VERSION));
+        }
+        if replay.version < 3 {
+            let frames = std::mem::take(&mut replay.frames);
+            replay.total_ticks = 0;
+            for frame in frames { replay.record(frame.tick, frame.pressed_actions, frame.player_two_actions); }
+        }
+        Ok(replay)
+    }
+
+    /// The level this replay must be played on; legacy replays fall back to `default_level`.
+    pub fn level_path<'a>(&'a self, default_level: &'a str) -> &'a str {
+        self.level.as_deref().unwrap_or(default_level)
+    }
+
+    /// Loads the level the replay was recorded on, returning its path alongside it.
+    pub fn load_level(&self, default_level: &str) -> Result<(String, Level), String> {
+        let path = self.level_path(default_level);
+        let level = load_level(path).map_err(|e| format!("Replay level '{}' failed to load: {}", path, e))?;
+        Ok((path.to_string(), level))
+    }
+}
+
+/// Whether two action lists hold the same actions; the order is whatever the input set produced.
+fn same_actions(a: &[InputAction], b: &[InputAction]) -> bool {
+    a.len() == b.len() && a.iter().all(|action| b.contains(action))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    const START_LEVEL: &str = "assets/levels/world_1_level_1/level.tmx";
+
+    #[test]
+    fn test_replay_loads_the_level_it_was_recorded_on() {
+        let replay = Replay::new(0, "assets/levels/world_1_level_2/level.tmx", &Config::default().physics);
+        let (path, level) = replay.load_level(START_LEVEL).expect("Recorded level loads");
+        assert_eq!(path, "assets/levels/world_1_level_2/level.tmx");
+        let start = load_level(START_LEVEL).unwrap();
+        assert!(level.collision.tiles != start.collision.tiles, "The recorded level is used, not the start level");
+    }
+
+    #[test]
+    fn test_legacy_replay_uses_the_start_level() {
+        let replay: Replay = serde_json::from_str(r#"{ "seed": 0, "frames": [] }"#).unwrap();
+        assert_eq!(replay.version, 0);
+        assert_eq!(replay.level_path(START_LEVEL), START_LEVEL);
+        assert!(replay.physics.is_none(), "Headerless replays play against the live physics");
+    }
+
+    #[test]
+    fn test_shipped_headerless_replay_still_loads() {
+        let replay = Replay::load(ATTRACT_REPLAY).expect("The shipped attract replay predates the header");
+        assert_eq!(replay.version, 0);
+        assert!(replay.physics.is_none());
+        assert!(!replay.frames.is_empty());
+        assert!(replay.total_ticks > replay.frames.len() as u64, "Per-tick frames are run-length encoded on load");
+    }
+
+    #[test]
+    fn test_recording_stores_only_input_changes() {
+        let mut replay = Replay::new(0, START_LEVEL, &Config::default().physics);
+        let script = [vec![], vec![], vec![InputAction::MoveRight], vec![InputAction::MoveRight], vec![InputAction::Jump, InputAction::MoveRight], vec![InputAction::MoveRight, InputAction::Jump], vec![]];
+        for (tick, actions) in script.iter().enumerate() { replay.record(tick as u64, actions.clone(), vec![]); }
+
+        assert_eq!(replay.frames.iter().map(|f| f.tick).collect::<Vec<_>>(), vec![2, 4, 6]);
+        assert_eq!(replay.total_ticks, 7);
+        for (tick, actions) in script.iter().enumerate() {
+            assert!(same_actions(replay.actions_at(tick as u64), actions), "Tick {} reconstructs its input", tick);
+        }
+        assert!(replay.actions_at(100).is_empty(), "The last input holds past the end");
+    }
+
+    #[test]
+    fn test_input_at_reconstructs_presses_for_any_tick() {
+        let mut replay = Replay::new(0, START_LEVEL, &Config::default().physics);
+        replay.record(0, vec![InputAction::Jump], vec![]);
+        replay.record(1, vec![InputAction::Jump], vec![]);
+        replay.record(2, vec![InputAction::Jump, InputAction::MoveRight], vec![]);
+
+        assert!(replay.input_at(0).is_action_just_pressed(InputAction::Jump), "The first tick presses against no input");
+        let held = replay.input_at(1);
+        assert!(held.is_action_pressed(InputAction::Jump) && !held.is_action_just_pressed(InputAction::Jump));
+        let joined = replay.input_at(2);
+        assert!(joined.is_action_just_pressed(InputAction::MoveRight) && !joined.is_action_just_pressed(InputAction::Jump));
+        assert!(!replay.input_at(50).is_action_just_pressed(InputAction::MoveRight), "Input held past the end is not pressed again");
+    }
+
+    #[test]
+    fn test_second_player_input_is_recorded_and_replayed() {
+        let mut replay = Replay::new(0, START_LEVEL, &Config::default().physics);
+        replay.record(0, vec![InputAction::MoveRight], vec![]);
+        replay.record(1, vec![InputAction::MoveRight], vec![InputAction::Jump]);
+        replay.record(2, vec![InputAction::MoveRight], vec![InputAction::Jump]);
+        assert_eq!(replay.frames.len(), 2, "A change on either player's bindings starts a frame");
+
+        let reparsed = Replay::parse(&serde_json::to_string(&replay).unwrap()).unwrap();
+        assert!(!reparsed.input_at(0).is_player_action_pressed(1, InputAction::Jump));
+        assert!(reparsed.input_at(1).is_player_action_just_pressed(1, InputAction::Jump));
+        let held = reparsed.input_at(2);
+        assert!(held.is_player_action_pressed(1, InputAction::Jump) && !held.is_player_action_just_pressed(1, InputAction::Jump));
+        assert!(!held.is_player_action_pressed(0, InputAction::Jump), "The players' inputs stay apart");
+    }
+
+    #[test]
+    fn test_legacy_per_tick_frames_are_encoded_on_parse() {
+        let legacy = r#"{ "version": 2, "seed": 3, "frames": [
+            { "tick": 0, "pressed_actions": ["MoveLeft"] },
+            { "tick": 1, "pressed_actions": ["MoveLeft"] },
+            { "tick": 2, "pressed_actions": [] },
+            { "tick": 3, "pressed_actions": [] } ] }"#;
+        let replay = Replay::parse(legacy).unwrap();
+        assert_eq!(replay.frames, vec![
+            InputFrame { tick: 0, pressed_actions: vec![InputAction::MoveLeft], player_two_actions: vec![] },
+            InputFrame { tick: 2, pressed_actions: vec![], player_two_actions: vec![] },
+        ]);
+        assert_eq!(replay.total_ticks, 4);
+
+        let mut current = replay.clone();
+        current.version = REPLAY_VERSION;
+        let reparsed = Replay::parse(&serde_json::to_string(&current).unwrap()).unwrap();
+        assert_eq!(reparsed.frames, replay.frames, "Encoded files are read as they are");
+        assert_eq!(reparsed.total_ticks, 4);
+        assert!(Replay::parse(r#"{ "version": 99, "seed": 0, "frames": [] }"#).is_err());
+    }
+
+    #[test]
+    fn test_physics_snapshot_survives_a_round_trip_and_overrides_live_tuning() {
+        let mut recorded = Config::default().physics;
+        recorded.gravity = 1234.0;
+        recorded.max_jumps = 2;
+        let replay = Replay::new(7, START_LEVEL, &recorded);
+        let loaded: Replay = serde_json::from_str(&serde_json::to_string(&replay).unwrap()).unwrap();
+        assert_eq!(loaded.version, REPLAY_VERSION);
+        assert_eq!(loaded.physics, replay.physics);
+
+        let mut live = Config::default().physics;
+        loaded.physics.unwrap().apply(&mut live);
+        assert_eq!(live.gravity, 1234.0);
+        assert_eq!(live.max_jumps, 2);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_missing_replay_level_is_an_error() {
+        let replay = Replay::new(0, "assets/levels/missing/level.tmx", &Config::default().physics);
+        let error = replay.load_level(START_LEVEL).err().expect("Missing level must not load");
+        assert!(error.contains("assets/levels/missing/level.tmx"));
+    }
+}
@@ -0,0 +1,176 @@
+//! # Concept: Scoring
+//!
+//! Points are awarded by [crate::ecs::systems::game_resolution::SystemGameResolution] for
+//! coins and stomps; stomping several enemies before landing multiplies the stomp points.
+//! A finished level adds a time bonus and is summed up in a [LevelTally] before the next
+//! level loads. The best score of every level is kept in [HIGH_SCORES_PATH].
+
+use std::collections::BTreeMap;
+use serde::{Deserialize, Serialize};
+use crate::config::ScoreConfig;
+use crate::ecs::resources::GameStats;
+
+/// Per-level high scores, next to `settings.toml` in the working directory.
+pub const HIGH_SCORES_PATH: &str = "high_scores.toml";
+
+/// Points for the `chain`-th stomp since the player last stood on the ground (1 for the first).
+pub fn stomp_points(chain: u32, config: &ScoreConfig) -> u32 {
+    config.stomp_points * chain.clamp(1, config.max_combo.max(1))
+}
+
+/// Bonus for finishing a level in `time` **seconds**: every whole second under par pays.
+pub fn time_bonus(time: f32, config: &ScoreConfig) -> u32 {
+    (config.par_time - time).max(0.0).floor() as u32 * config.time_bonus_per_second
+}
+
+/// # Resource: Level Tally
+/// The summary shown while the game is in [crate::ecs::resources::GameState::LevelComplete].
+#[derive(Debug, Clone, PartialEq)]
+pub struct LevelTally {
+    /// Path of the level that was finished, the key of its high score.
+    pub level: String,
+    pub coins: u32,
+    pub kills: u32,
+    /// Time spent in the level in **seconds**.
+    pub time: f32,
+    pub time_bonus: u32,
+    /// Points scored in this level, time bonus included.
+    pub level_score: u32,
+    /// The running score of the game, time bonus included.
+    pub total_score: u32,
+    /// Best score of this level so far, this run included.
+    pub high_score: u32,
+    pub new_high_score: bool,
+    /// The level that loads once the tally is dismissed.
+    pub next_level: String,
+    /// **Seconds** left before the tally advances by itself.
+    pub remaining: f32,
+}
+
+impl LevelTally {
+    /// Sums up the level just finished from `stats`, adding its time bonus to the score.
+    pub fn finish_level(stats: &mut GameStats, level: &str, next_level: String, config: &ScoreConfig) -> Self {
+        let time_bonus = time_bonus(stats.level_time, config);
+        stats.score += time_bonus;
+        Self {
+            level: level.to_string(),
+            coins: stats.level_coins,
+            kills: stats.level_kills,
+            time: stats.level_time,
+            time_bonus,
+            level_score: stats.score.saturating_sub(stats.level_start_score),
+            total_score: stats.score,
+            high_score: 0,
+            new_high_score: false,
+            next_level,
+            remaining: config.tally_duration,
+        }
+    }
+
+    /// The rows of the tally screen, top to bottom.
+    pub fn lines(&self) -> Vec<String> {
+        let best = if self.new_high_score { "NEW BEST!".to_string() } else { format!("Best {}", self.high_score) };
+        vec![
+            format!("Coins {}", self.coins),
+            format!("Kills {}", self.kills),
+            format!("Time {}:{:04.1}", (self.time / 60.0).floor() as u32, self.time % 60.0),
+            format!("Time bonus {}", self.time_bonus),
+            format!("Level score {}", self.level_score),
+            best,
+            format!("Total score {}", self.total_score),
+        ]
+    }
+}
+
+/// The best score reached on each level, keyed by level path.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct HighScores {
+    #[serde(default)]
+    pub levels: BTreeMap<String, u32>,
+}
+
+impl HighScores {
+    /// Reads the high score file; a missing or unreadable file yields an empty table.
+    pub fn load(path: &str) -> Self {
+        let Ok(source) = std::fs::read_to_string(path) else { return Self::default() };
+        toml::from_str(&source).unwrap_or_else(|e| {
+            eprintln!("[Score] Ignoring malformed '{}': {}", path, e);
+            Self::default()
+        })
+    }
+
+    /// Writes the high score file, replacing its previous contents.
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        let source = toml::to_string(self).map_err(|e| e.to_string())?;
+        std::fs::write(path, source).map_err(|e| e.to_string())
+    }
+
+    /// The best score of `level`, or 0 if it was never finished.
+    pub fn best(&self, level: &str) -> u32 {
+        self.levels.get(level).copied().unwrap_or(0)
+    }
+
+    /// Enters the tally's level score and fills in its high score. Returns true if it set a new best.
+    pub fn record(&mut self, tally: &mut LevelTally) -> bool {
+        tally.new_high_score = tally.level_score > self.best(&tally.level);
+        if tally.new_high_score { self.levels.insert(tally.level.clone(), tally.level_score); }
+        tally.high_score = self.best(&tally.level);
+        tally.new_high_score
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEVEL: &str = "assets/levels/world_1_level_1/level.tmx";
+
+    #[test]
+    fn test_stomp_points_multiply_along_the_chain_up_to_the_cap() {
+        let config = ScoreConfig { stomp_points: 100, max_combo: 3, ..Default::default() };
+        let points: Vec<u32> = (1..=5).map(|chain| stomp_points(chain, &config)).collect();
+        assert_eq!(points, vec![100, 200, 300, 300, 300]);
+    }
+
+    #[test]
+    fn test_time_bonus_pays_for_whole_seconds_under_par() {
+        let config = ScoreConfig { par_time: 60.0, time_bonus_per_second: 10, ..Default::default() };
+        assert_eq!(time_bonus(45.5, &config), 140);
+        assert_eq!(time_bonus(60.0, &config), 0);
+        assert_eq!(time_bonus(200.0, &config), 0, "A slow run earns nothing, it never costs points");
+    }
+
+    #[test]
+    fn test_tally_adds_the_time_bonus_and_counts_only_this_level() {
+        let config = ScoreConfig { par_time: 100.0, time_bonus_per_second: 10, ..Default::default() };
+        let mut stats = GameStats { score: 1500, level_start_score: 1000, level_coins: 7, level_kills: 2, level_time: 90.0, ..Default::default() };
+        let tally = LevelTally::finish_level(&mut stats, LEVEL, "next.tmx".to_string(), &config);
+
+        assert_eq!(stats.score, 1600);
+        assert_eq!((tally.time_bonus, tally.level_score, tally.total_score), (100, 600, 1600));
+        assert_eq!((tally.coins, tally.kills), (7, 2));
+        assert_eq!(tally.lines()[2], "Time 1:30.0");
+    }
+
+    #[test]
+    fn test_high_scores_keep_the_best_run_and_survive_a_round_trip() {
+        let config = ScoreConfig::default();
+        let mut high_scores = HighScores::default();
+        let mut run = |score| {
+            let mut stats = GameStats { score, ..Default::default() };
+            let mut tally = LevelTally::finish_level(&mut stats, LEVEL, String::new(), &ScoreConfig { par_time: 0.0, ..config.clone() });
+            let new_best = high_scores.record(&mut tally);
+            (new_best, tally.high_score)
+        };
+        assert_eq!(run(500), (true, 500));
+        assert_eq!(run(300), (false, 500));
+        assert_eq!(run(800), (true, 800));
+
+        let path = std::env::temp_dir().join("gfx_engine_high_scores_test.toml");
+        let path = path.to_str().unwrap();
+        high_scores.save(path).unwrap();
+        assert_eq!(HighScores::load(path), high_scores);
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(HighScores::load(path), HighScores::default(), "A missing file is an empty table");
+    }
+}
@@ -0,0 +1,163 @@
+//! # Manager: Simulation Tick
+//!
+//! This module owns the single per-tick path of a gameplay session: the systems,
+//! the game-over check and the follow camera. Real-time play, replay seeking and
+//! the headless harness all advance the world through [Simulation::tick], so a
+//! seek cannot drift from what playback would have shown. The camera moves inside
+//! the tick because dormancy is decided from its view.
+
+use crate::audio::{AudioEvent, GameAudioManager};
+use crate::benchmarker::Benchmarker;
+use crate::camera::Camera;
+use crate::config::{Config, GameConfig};
+use crate::ecs::component::Direction;
+use crate::ecs::event::{EventGameOver, EventLevelCompleted};
+use crate::ecs::resources::GameState;
+use crate::ecs::system_manager::SystemManager;
+use crate::ecs::systems::SystemContext;
+use crate::ecs::world::{Entity, World};
+use crate::input::InputState;
+use crate::level::Level;
+use crate::math::Vector2D;
+use crate::replay::Replay;
+use crate::timestep::FIXED_TIMESTEP;
+use std::sync::mpsc::{channel, Sender};
+
+/// The parts of a session one tick advances, borrowed from their owner.
+pub struct Simulation<'a> {
+    pub world: &'a mut World,
+    pub system_manager: &'a mut SystemManager,
+    pub camera: &'a mut Camera,
+    pub benchmarker: &'a mut Benchmarker,
+    pub next_level: &'a mut Option<String>,
+    pub level: &'a Level,
+    /// The player the single-player camera follows.
+    pub player_entity: Option<Entity>,
+}
+
+/// Per-tick settings that differ between real-time play, seeking and tests.
+pub struct TickOptions {
+    /// Length of the tick in **seconds**.
+    pub delta_time: f32,
+    pub is_paused: bool,
+    pub is_attract_mode: bool,
+    /// Whether the players drive the camera; off for the free camera and in tests that place it by hand.
+    pub follow_camera: bool,
+    pub current_soundtrack: Option<String>,
+}
+
+/// What happened during a tick that the owner of the session has to react to.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct TickOutcome {
+    /// [EventGameOver] fired; the world is already in [GameState::GameOver].
+    pub game_over: bool,
+    /// [EventLevelCompleted] fired; `next_level` holds the destination.
+    pub level_completed: bool,
+}
+
+impl Simulation<'_> {
+    /// Runs one tick of the systems and then moves the camera after the players.
+    ///
+    /// ⚠️ **Hotpath**: Called 120x per second, and back to back while seeking.
+    /// With an `audio_manager` the tick also updates the listener, music beats and playback;
+    /// without one, sounds only go to `audio_sender`.
+    ///
+    /// # Side Effects
+    /// * Switches to [GameState::GameOver] on [EventGameOver] and clears the tick's events.
+    pub fn tick(&mut self, config: &Config, game_config: &GameConfig, input_state: &InputState, audio_sender: &Sender<AudioEvent>, audio_manager: Option<&mut GameAudioManager>, options: TickOptions) -> TickOutcome {
+        // 1. Systems.
+        let mut context = SystemContext {
+            level: self.level,
+            input_state,
+            config,
+            game_config,
+            audio_sender,
+            next_level: self.next_level,
+            delta_time: options.delta_time,
+            camera: self.camera,
+            benchmarker: self.benchmarker,
+            current_soundtrack: options.current_soundtrack,
+            is_paused: options.is_paused,
+            is_attract_mode: options.is_attract_mode,
+        };
+        match audio_manager {
+            Some(audio_manager) => self.system_manager.update(self.world, &mut context, audio_manager),
+            None => self.system_manager.step(self.world, &mut context),
+        }
+
+        // 2. Facts the owner reacts to, read before the events are cleared.
+        let outcome = TickOutcome {
+            game_over: self.world.event_bus.read::<EventGameOver>().next().is_some(),
+            level_completed: self.world.event_bus.read::<EventLevelCompleted>().next().is_some(),
+        };
+        if outcome.game_over { self.world.game_state = GameState::GameOver; }
+        self.world.clear_events();
+
+        // 3. Camera.
+        if options.follow_camera { self.follow_players(config, options.delta_time); }
+        outcome
+    }
+
+    /// Re-simulates `replay` from `tick` up to `target_tick` as fast as possible and returns the tick it stopped at.
+    ///
+    /// Runs the same [Self::tick] as playback, in attract mode and without sound. Stops early on a
+    /// level change or game over, which the next real-time update handles.
+    pub fn fast_forward(&mut self, replay: &Replay, mut tick: u64, target_tick: u64, config: &Config, game_config: &GameConfig, current_soundtrack: Option<String>) -> u64 {
+        // Sounds go to a channel nobody listens to.
+        let (audio_sender, _audio_receiver) = channel();
+        let target_tick = target_tick.min(replay.total_ticks);
+        while tick < target_tick && self.next_level.is_none() && self.world.game_state != GameState::GameOver {
+            let input = replay.input_at(tick);
+            let options = TickOptions {
+                delta_time: FIXED_TIMESTEP,
+                is_paused: false,
+                is_attract_mode: true,
+                follow_camera: true,
+                current_soundtrack: current_soundtrack.clone(),
+            };
+            self.tick(config, game_config, &input, &audio_sender, None, options);
+            tick += 1;
+        }
+        tick
+    }
+
+    /// Blends camera zones and follows the players: local co-op frames everyone, a single player
+    /// keeps the cinematic follow camera.
+    fn follow_players(&mut self, config: &Config, delta_time: f32) {
+        // Camera zones follow the primary player and blend the clamp region on entry/exit.
+        if let Some(pe) = self.player_entity && let Some(pos) = self.world.positions.get(&pe) {
+            self.camera.set_zone(self.level.camera_zone_at(pos.0));
+        }
+        self.camera.update_bounds(delta_time);
+
+        let player_centers = player_centers(self.world);
+        if player_centers.len() > 1 {
+            self.camera.frame_group(&player_centers, config.window.camera_group_padding, config.window.camera_min_zoom, delta_time);
+        } else if let Some(pe) = self.player_entity
+            && let (Some(pos), Some(rend)) = (self.world.positions.get(&pe), self.world.renderables.get(&pe)) {
+                let sx = pos.0.x + rend.horizontal_offset as f32 + (rend.width as f32 / 2.0);
+                let sy = pos.0.y + rend.vertical_offset as f32 + (rend.height as f32 / 2.0);
+                let vy = self.world.velocities.get(&pe).map_or(0.0, |v| v.0.y);
+                let dir = self.world.directions.get(&pe).map_or(Direction::Right, |d| d.direction);
+                self.camera.update(Vector2D::new(sx, sy), self.world.is_grounded(pe), vy, dir, delta_time);
+            }
+    }
+}
+
+/// Collects the visual center of every player sprite, ordered by player slot.
+fn player_centers(world: &World) -> Vec<Vector2D> {
+    let mut players: Vec<_> = world.player_tags.keys().copied().collect();
+    players.sort_by_key(|e| world.player_slots.get(e).map_or(0, |s| s.0));
+    players.iter().filter_map(|e| {
+        let (pos, rend) = (world.positions.get(e)?, world.renderables.get(e)?);
+        Some(Vector2D::new(
+            pos.0.x + rend.horizontal_offset as f32 + (rend.width as f32 / 2.0),
+            pos.0.y + rend.vertical_offset as f32 + (rend.height as f32 / 2.0),
+        ))
+    }).collect()
+}
+
+/// The primary player (slot 0), if one is spawned.
+pub fn primary_player(world: &World) -> Option<Entity> {
+    world.player_tags.keys().copied().min_by_key(|e| world.player_slots.get(e).map_or(0, |s| s.0))
+}
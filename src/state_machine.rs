@@ -3,6 +3,10 @@
 //! The state machine is used to control the behavior of entities such as the
 //! player and enemies, ensuring that their logic is organized into distinct,
 //! manageable states (e.g., `Idle`, `Walking`, `Jumping`).
+//!
+//! A state may name a parent through [State::parent]. Parents run before the state
+//! itself, so behavior shared by sibling states (e.g. "grounded" vs "airborne") is
+//! written once. States without a parent behave exactly as a flat state machine.
 
 /// The `State` trait defines the behavior of a single state in the state machine.
 ///
@@ -28,43 +32,78 @@ pub trait State {
 
     /// Returns a string slice representing the name of the state, used for debugging.
     fn get_name(&self) -> &str;
+
+    /// Returns the enclosing state, or `None` for a top-level state.
+    ///
+    /// Called once when the state machine enters this state. Parents are matched by
+    /// [State::get_name], so a parent shared with the previous state is kept rather than re-entered.
+    fn parent(&self) -> Option<Box<dyn State>> { None }
 }
 
 /// The `StateMachine` struct manages the current state of an entity and handles transitions.
 pub struct StateMachine {
     /// The current active state, wrapped in a `Box` to allow for dynamic dispatch.
     pub current_state: Option<Box<dyn State>>,
+    /// The ancestors of the current state, outermost first; empty for a top-level state.
+    pub parents: Vec<Box<dyn State>>,
 }
 
 impl StateMachine {
     /// Creates a new `StateMachine` and immediately enters the given `initial_state`.
     pub fn new<S: State + 'static>(initial_state: S) -> Self {
-        let mut state_machine = StateMachine { current_state: None };
+        let mut state_machine = StateMachine { current_state: None, parents: Vec::new() };
         state_machine.set_current_state(Box::new(initial_state));
         state_machine
     }
 
     /// Updates the current state and checks for transitions.
     ///
-    /// This method first calls the `update` logic of the current state, then
-    /// checks if a transition to a new state is warranted.
+    /// Parents run before the current state, outermost first: every `update` runs,
+    /// then the first parent requesting a transition pre-empts the ones below it.
     pub fn update_with_context(&mut self, world: &mut crate::ecs::world::World, context: &mut crate::ecs::systems::SystemContext, entity: crate::ecs::world::Entity) {
-        if let Some(state) = self.current_state.as_mut() {
-            state.update_with_context(world, context, entity);
-            if let Some(next_state) = state.transition_with_context(world, context, entity) {
-                self.set_current_state(next_state);
-            }
+        let Some(state) = self.current_state.as_mut() else { return };
+        for parent in self.parents.iter_mut() {
+            parent.update_with_context(world, context, entity);
+        }
+        state.update_with_context(world, context, entity);
+
+        let next_state = self.parents.iter_mut()
+            .find_map(|parent| parent.transition_with_context(world, context, entity))
+            .or_else(|| self.current_state.as_mut()?.transition_with_context(world, context, entity));
+        if let Some(next_state) = next_state {
+            self.set_current_state(next_state);
         }
     }
 
     /// Sets the new current state, calling `exit` on the old state and `enter` on the new one.
-    fn set_current_state(&mut self, new_state: Box<dyn State>) {
+    ///
+    /// Parents shared by the old and new state stay active; the others are exited
+    /// innermost first and the new ones entered outermost first.
+    fn set_current_state(&mut self, mut new_state: Box<dyn State>) {
+        // 1. Collect the new state's ancestors, outermost first.
+        let mut chain = Vec::new();
+        let mut next_parent = new_state.parent();
+        while let Some(parent) = next_parent {
+            next_parent = parent.parent();
+            chain.push(parent);
+        }
+        chain.reverse();
+
+        // 2. Exit the old state and the ancestors it does not share with the new one.
         if let Some(mut old_state) = self.current_state.take() {
             old_state.exit();
         }
-        self.current_state = Some(new_state);
-        if let Some(state) = self.current_state.as_mut() {
-            state.enter();
+        let shared = self.parents.iter().zip(&chain).take_while(|(old, new)| old.get_name() == new.get_name()).count();
+        for mut parent in self.parents.drain(shared..).rev() {
+            parent.exit();
         }
+
+        // 3. Enter the new ancestors, then the new state.
+        for mut parent in chain.into_iter().skip(shared) {
+            parent.enter();
+            self.parents.push(parent);
+        }
+        new_state.enter();
+        self.current_state = Some(new_state);
     }
 }
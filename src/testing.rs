@@ -0,0 +1,122 @@
+//! # Manager: Headless Harness
+//!
+//! This module runs the gameplay simulation without a window, audio device or
+//! files on disk. It wires a [World] and [SystemManager] to in-memory
+//! [Config]/[GameConfig] structs and a programmatically built [Level], so
+//! integration tests and CI can step fixed ticks and assert on the world.
+
+use crate::audio::AudioEvent;
+use crate::benchmarker::Benchmarker;
+use crate::camera::Camera;
+use crate::config::{Config, GameConfig};
+use crate::ecs::resources::GameState;
+use crate::ecs::system_manager::SystemManager;
+use crate::ecs::world::{Entity, World};
+use crate::game_state_manager::GameStateManager;
+use crate::input::InputState;
+use crate::level::Level;
+use crate::math::Vector2D;
+use crate::player::factory::PlayerFactory;
+use crate::replay::Replay;
+use crate::simulation::{primary_player, Simulation, TickOptions};
+use crate::timestep::FIXED_TIMESTEP;
+use std::sync::mpsc::{channel, Receiver, Sender};
+
+/// A gameplay session that is stepped manually and never renders.
+pub struct HeadlessGame {
+    pub world: World,
+    pub config: Config,
+    pub game_config: GameConfig,
+    pub level: Level,
+    pub camera: Camera,
+    /// Input applied on every subsequent tick.
+    pub input_state: InputState,
+    /// Set when gameplay requests a level change; the harness does not load it.
+    pub next_level: Option<String>,
+    /// Whether the camera follows the players as in play; off by default so tests can place it.
+    pub follow_camera: bool,
+    system_manager: SystemManager,
+    benchmarker: Benchmarker,
+    audio_sender: Sender<AudioEvent>,
+    audio_receiver: Receiver<AudioEvent>,
+}
+
+impl HeadlessGame {
+    /// Creates a session in the `Playing` state with no entities.
+    pub fn new(config: Config, game_config: GameConfig, level: Level) -> Self {
+        let (audio_sender, audio_receiver) = channel();
+        let camera = GameStateManager::create_camera(&config, &game_config, &level);
+        let mut world = World::new();
+        world.game_state = GameState::Playing;
+        world.stats.lives = game_config.player.lives;
+        Self {
+            world, config, game_config, level, camera,
+            input_state: InputState::default(), next_level: None, follow_camera: false,
+            system_manager: SystemManager::new(), benchmarker: Benchmarker::new(),
+            audio_sender, audio_receiver,
+        }
+    }
+
+    /// Creates a session using the bundled default configuration.
+    pub fn with_level(level: Level) -> Self {
+        Self::new(Config::default(), GameConfig::default(), level)
+    }
+
+    /// Spawns the primary player at `position` (in **pixels**).
+    pub fn spawn_player(&mut self, position: Vector2D) -> Entity {
+        PlayerFactory::create_for_slot(&mut self.world, &self.game_config, 0, position)
+    }
+
+    /// Spawns an instance of a configured prefab (e.g. "GoldCoin") at `position`.
+    pub fn spawn(&mut self, prefab: &str, position: Vector2D) -> Entity {
+        let template = crate::level::Entity { r#type: prefab.to_string(), position, ..Default::default() };
+        crate::entity_spawner::spawn_entity(&mut self.world, &template, &self.game_config)
+    }
+
+    /// Advances the simulation by `ticks` fixed steps of [FIXED_TIMESTEP].
+    pub fn step(&mut self, ticks: u32) {
+        let (config, game_config, input_state, audio_sender) = (&self.config, &self.game_config, &self.input_state, &self.audio_sender);
+        let mut simulation = Simulation {
+            world: &mut self.world,
+            system_manager: &mut self.system_manager,
+            camera: &mut self.camera,
+            benchmarker: &mut self.benchmarker,
+            next_level: &mut self.next_level,
+            level: &self.level,
+            player_entity: None,
+        };
+        for _ in 0..ticks {
+            simulation.player_entity = primary_player(simulation.world);
+            let options = TickOptions {
+                delta_time: FIXED_TIMESTEP,
+                is_paused: false,
+                is_attract_mode: false,
+                follow_camera: self.follow_camera,
+                current_soundtrack: None,
+            };
+            simulation.tick(config, game_config, input_state, audio_sender, None, options);
+        }
+    }
+
+    /// Re-simulates `replay` up to `target_tick` through the same fast-forward a replay seek uses.
+    ///
+    /// Returns the tick it stopped at, which is earlier when the replay changes level or ends in a game over.
+    pub fn fast_forward(&mut self, replay: &Replay, target_tick: u64) -> u64 {
+        let player_entity = primary_player(&self.world);
+        let mut simulation = Simulation {
+            world: &mut self.world,
+            system_manager: &mut self.system_manager,
+            camera: &mut self.camera,
+            benchmarker: &mut self.benchmarker,
+            next_level: &mut self.next_level,
+            level: &self.level,
+            player_entity,
+        };
+        simulation.fast_forward(replay, 0, target_tick, &self.config, &self.game_config, None)
+    }
+
+    /// Drains every audio request sent since the last call.
+    pub fn take_audio_events(&mut self) -> Vec<AudioEvent> {
+        self.audio_receiver.try_iter().collect()
+    }
+}
@@ -3,49 +3,163 @@
 //! This module provides the central authority for graphical assets. 
 //! It handles the loading, decoding, and caching of image files, ensuring 
 //! that textures are upscaled correctly for the engine's 1:1 pixel workspace.
+//!
+//! Textures are registered up front but decoded on first use ([TextureManager::get_or_load]).
+//! A file that cannot be loaded is drawn as a magenta placeholder instead of failing the game,
+//! and textures registered for the current level are released when the level changes.
 
 use sdl3::render::{Texture, TextureCreator, BlendMode};
 use sdl3::video::WindowContext;
 use sdl3::surface::Surface;
-use image::ImageReader;
-use std::collections::HashMap;
+use sdl3::rect::Rect;
+use image::{ImageReader, RgbaImage};
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
+/// Side length of the generated placeholder in **pixels**.
+const PLACEHOLDER_SIZE: u32 = 16;
+/// Side length of one placeholder checker in **pixels**.
+const PLACEHOLDER_CHECKER: u32 = 4;
+
+/// Who holds on to a registered texture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextureScope {
+    /// Kept for the whole session (sprites, HUD, parallax layers).
+    Global,
+    /// Released by [TextureManager::unload_level_scope] when the level changes (tilesets).
+    Level,
+}
+
+/// Where a registered texture is loaded from and which scopes reference it.
+struct TextureSource {
+    path: String,
+    global: bool,
+    level: bool,
+}
+
 /// A repository for managing the lifecycle of SDL texture resources.
 pub struct TextureManager {
     textures: HashMap<String, Texture>,
+    /// Registered textures by name; unregistered names are loaded from a path equal to the name.
+    sources: HashMap<String, TextureSource>,
+    /// Names whose file could not be loaded; they are drawn with [Self::placeholder].
+    missing: HashSet<String>,
+    /// Generated on the first miss.
+    placeholder: Option<Texture>,
+    /// Named sprite rects from loaded atlases, in source-image **pixels**.
+    atlas_frames: HashMap<String, Rect>,
 }
 
 impl TextureManager {
     /// Initializes a new, empty asset manager.
     pub fn new() -> Self {
-        Self { textures: HashMap::new() }
+        Self { textures: HashMap::new(), sources: HashMap::new(), missing: HashSet::new(), placeholder: None, atlas_frames: HashMap::new() }
     }
 
-    /// Decodes an image from disk and creates a GPU texture upscaled for high-res rendering.
-    pub fn load(&mut self, path: &str, name: &str, texture_creator: &TextureCreator<WindowContext>) -> Result<(), String> {
-        // 1. Open and decode the image file using the 'image' crate.
-        let path_obj = Path::new(path);
-        let image = ImageReader::open(path_obj).map_err(|e| e.to_string())?.decode().map_err(|e| e.to_string())?;
-        
-        let scaled_image = image.to_rgba8();
-
-        // 3. Construct an SDL Surface from the raw pixel buffer.
-        let (width, height) = scaled_image.dimensions();
-        let mut surface = Surface::new(width, height, sdl3::pixels::PixelFormatEnum::ABGR8888.into()).map_err(|e| e.to_string())?;
-        surface.with_lock_mut(|pixels| {
-            pixels.copy_from_slice(&scaled_image);
-        });
-
-        // 4. Create the final GPU Texture and enforce Nearest-Neighbor scaling for the pixel-art aesthetic.
-        let mut texture = texture_creator.create_texture_from_surface(&surface).map_err(|e| e.to_string())?;
-        texture.set_blend_mode(BlendMode::Blend);
-        
-        unsafe {
-            sdl3_sys::render::SDL_SetTextureScaleMode(texture.raw(), sdl3_sys::surface::SDL_SCALEMODE_NEAREST);
+    /// Makes `name` loadable from `path` by [Self::get_or_load] without decoding it yet.
+    pub fn register(&mut self, name: &str, path: &str, scope: TextureScope) {
+        let source = self.sources.entry(name.to_string()).or_insert_with(|| TextureSource { path: path.to_string(), global: false, level: false });
+        source.path = path.to_string();
+        match scope {
+            TextureScope::Global => source.global = true,
+            TextureScope::Level => source.level = true,
+        }
+    }
+
+    /// True if `name` was registered and has not been released since.
+    pub fn is_registered(&self, name: &str) -> bool {
+        self.sources.contains_key(name)
+    }
+
+    /// Releases every texture only the previous level referred to. Call it after the next level
+    /// has registered its own, with their names in `keep`, so textures both levels share stay
+    /// decoded. Textures also registered as [TextureScope::Global] stay.
+    ///
+    /// # Returns
+    /// * The names that were released, sorted.
+    pub fn unload_level_scope(&mut self, keep: &[&str]) -> Vec<String> {
+        let mut released: Vec<String> = self.sources.iter()
+            .filter(|(name, s)| s.level && !s.global && !keep.contains(&name.as_str()))
+            .map(|(name, _)| name.clone())
+            .collect();
+        released.sort();
+        for (name, source) in self.sources.iter_mut() { source.level = keep.contains(&name.as_str()); }
+        for name in &released {
+            self.sources.remove(name);
+            self.missing.remove(name);
+            if let Some(texture) = self.textures.remove(name) { unsafe { texture.destroy(); } }
+        }
+        released
+    }
+
+    /// Returns the texture `name`, decoding it on first use.
+    ///
+    /// A file that cannot be loaded is logged once and drawn with a magenta placeholder from
+    /// then on. `None` only if the placeholder itself could not be created.
+    pub fn get_or_load(&mut self, name: &str, texture_creator: &TextureCreator<WindowContext>) -> Option<&mut Texture> {
+        if let Some(image) = self.decode_pending(name) {
+            match create_texture(&image, texture_creator) {
+                Ok(texture) => { self.textures.insert(name.to_string(), texture); },
+                Err(e) => { eprintln!("[Texture] Drawing '{}' as a placeholder: {}", name, e); self.missing.insert(name.to_string()); },
+            }
+        }
+        if self.missing.contains(name) { return self.placeholder(texture_creator); }
+        self.textures.get_mut(name)
+    }
+
+    /// Decodes `name` if it is neither loaded nor known to be missing.
+    ///
+    /// # Side Effects
+    /// * A file that cannot be decoded is logged and remembered as missing, so it is only tried once.
+    fn decode_pending(&mut self, name: &str) -> Option<RgbaImage> {
+        if self.textures.contains_key(name) || self.missing.contains(name) { return None; }
+        let path = self.sources.get(name).map_or(name, |s| s.path.as_str());
+        match decode_image(path) {
+            Ok(image) => Some(image),
+            Err(e) => {
+                eprintln!("[Texture] Drawing '{}' as a placeholder: {}", path, e);
+                self.missing.insert(name.to_string());
+                None
+            }
         }
+    }
+
+    /// True if `name` could not be loaded and is drawn with the placeholder.
+    pub fn is_missing(&self, name: &str) -> bool {
+        self.missing.contains(name)
+    }
+
+    /// The shared stand-in for missing textures, created on first use.
+    fn placeholder(&mut self, texture_creator: &TextureCreator<WindowContext>) -> Option<&mut Texture> {
+        if self.placeholder.is_none() {
+            match create_texture(&placeholder_image(), texture_creator) {
+                Ok(texture) => self.placeholder = Some(texture),
+                Err(e) => eprintln!("[Texture] Failed to create the placeholder: {}", e),
+            }
+        }
+        self.placeholder.as_mut()
+    }
+
+    /// Reads a TexturePacker-style JSON atlas and registers its named frames.
+    ///
+    /// # Side Effects
+    /// * Frames with the same name as an earlier atlas replace the old rect.
+    pub fn load_atlas(&mut self, path: &str) -> Result<(), String> {
+        let json = std::fs::read_to_string(path).map_err(|e| format!("Failed to read atlas '{}': {}", path, e))?;
+        let frames = parse_atlas(&json).map_err(|e| format!("Failed to parse atlas '{}': {}", path, e))?;
+        self.atlas_frames.extend(frames);
+        Ok(())
+    }
 
-        // 5. Store the texture in the cache, indexed by its unique identifier.
+    /// Looks up a named frame from any loaded atlas.
+    pub fn atlas_frame(&self, name: &str) -> Option<Rect> {
+        self.atlas_frames.get(name).copied()
+    }
+
+    /// Decodes an image from disk and creates a GPU texture upscaled for high-res rendering.
+    pub fn load(&mut self, path: &str, name: &str, texture_creator: &TextureCreator<WindowContext>) -> Result<(), String> {
+        let texture = create_texture(&decode_image(path)?, texture_creator)?;
         self.textures.insert(name.to_string(), texture);
         Ok(())
     }
@@ -61,8 +175,155 @@ impl TextureManager {
     }
 }
 
+/// Opens and decodes an image file using the 'image' crate.
+fn decode_image(path: &str) -> Result<RgbaImage, String> {
+    let image = ImageReader::open(Path::new(path)).map_err(|e| e.to_string())?.decode().map_err(|e| e.to_string())?;
+    Ok(image.to_rgba8())
+}
+
+/// Uploads RGBA pixels as a blended texture with Nearest-Neighbor scaling for the pixel-art aesthetic.
+fn create_texture(image: &RgbaImage, texture_creator: &TextureCreator<WindowContext>) -> Result<Texture, String> {
+    // 1. Construct an SDL Surface from the raw pixel buffer.
+    let (width, height) = image.dimensions();
+    let mut surface = Surface::new(width, height, sdl3::pixels::PixelFormatEnum::ABGR8888.into()).map_err(|e| e.to_string())?;
+    surface.with_lock_mut(|pixels| {
+        pixels.copy_from_slice(image);
+    });
+
+    // 2. Create the final GPU Texture.
+    let mut texture = texture_creator.create_texture_from_surface(&surface).map_err(|e| e.to_string())?;
+    texture.set_blend_mode(BlendMode::Blend);
+    
+    unsafe {
+        sdl3_sys::render::SDL_SetTextureScaleMode(texture.raw(), sdl3_sys::surface::SDL_SCALEMODE_NEAREST);
+    }
+    Ok(texture)
+}
+
+/// A bright magenta and black checkerboard that is hard to mistake for real art.
+pub fn placeholder_image() -> RgbaImage {
+    RgbaImage::from_fn(PLACEHOLDER_SIZE, PLACEHOLDER_SIZE, |x, y| {
+        if (x / PLACEHOLDER_CHECKER + y / PLACEHOLDER_CHECKER).is_multiple_of(2) { image::Rgba([255, 0, 255, 255]) } else { image::Rgba([0, 0, 0, 255]) }
+    })
+}
+
 impl Default for TextureManager {
     fn default() -> Self {
         Self::new()
     }
 }
+
+#[derive(Deserialize)]
+struct AtlasRect { x: i32, y: i32, w: u32, h: u32 }
+
+#[derive(Deserialize)]
+struct AtlasEntry { #[serde(default)] filename: Option<String>, frame: AtlasRect }
+
+/// TexturePacker exports frames either as a name-keyed object ("JSON Hash") or a list ("JSON Array").
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AtlasFrames { Hash(HashMap<String, AtlasEntry>), Array(Vec<AtlasEntry>) }
+
+#[derive(Deserialize)]
+struct AtlasFile { frames: AtlasFrames }
+
+/// Decodes the `frames` section of a TexturePacker JSON atlas into named rects.
+pub fn parse_atlas(json: &str) -> Result<HashMap<String, Rect>, String> {
+    let atlas: AtlasFile = serde_json::from_str(json).map_err(|e| e.to_string())?;
+    let to_rect = |f: &AtlasRect| Rect::new(f.x, f.y, f.w, f.h);
+    match atlas.frames {
+        AtlasFrames::Hash(frames) => Ok(frames.iter().map(|(name, entry)| (name.clone(), to_rect(&entry.frame))).collect()),
+        AtlasFrames::Array(frames) => frames.iter()
+            .map(|entry| entry.filename.clone().map(|name| (name, to_rect(&entry.frame))).ok_or_else(|| "array atlas entry without a filename".to_string()))
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_files_fall_back_to_the_placeholder_once() {
+        let mut textures = TextureManager::new();
+        textures.register("heart", "assets/graphics/heart_pixel_art_32x32.png", TextureScope::Global);
+        textures.register("typo", "assets/graphics/no_such_file.png", TextureScope::Global);
+
+        assert!(textures.decode_pending("heart").is_some());
+        assert!(!textures.is_missing("heart"));
+        assert!(textures.decode_pending("typo").is_none());
+        assert!(textures.is_missing("typo"), "The miss is remembered, so it is logged and retried only once");
+
+        let placeholder = placeholder_image();
+        assert_eq!(placeholder.dimensions(), (PLACEHOLDER_SIZE, PLACEHOLDER_SIZE));
+        assert_eq!(placeholder.get_pixel(0, 0).0, [255, 0, 255, 255]);
+        assert_eq!(placeholder.get_pixel(PLACEHOLDER_CHECKER, 0).0, [0, 0, 0, 255]);
+    }
+
+    #[test]
+    fn test_unloading_a_level_keeps_textures_that_are_also_global() {
+        let mut textures = TextureManager::new();
+        textures.register("cat", "assets/graphics/cat.png", TextureScope::Global);
+        textures.register("sky", "assets/graphics/sky.png", TextureScope::Global);
+        textures.register("sky", "assets/graphics/sky.png", TextureScope::Level);
+        textures.register("tiles_1", "assets/levels/tiles_1.png", TextureScope::Level);
+        textures.register("typo", "assets/levels/typo.png", TextureScope::Level);
+        assert!(textures.decode_pending("typo").is_none());
+
+        assert_eq!(textures.unload_level_scope(&[]), vec!["tiles_1".to_string(), "typo".to_string()]);
+        assert!(textures.is_registered("cat") && textures.is_registered("sky"));
+        assert!(!textures.is_registered("tiles_1"));
+        assert!(!textures.is_missing("typo"), "A later level may ship the file");
+
+        textures.register("tiles_2", "assets/levels/tiles_2.png", TextureScope::Level);
+        assert_eq!(textures.unload_level_scope(&[]), vec!["tiles_2".to_string()], "'sky' is no longer referenced by a level, but still global");
+    }
+
+    #[test]
+    fn test_textures_the_next_level_shares_stay_decoded() {
+        let mut textures = TextureManager::new();
+        textures.register("tiles_1", "assets/levels/tiles_1.png", TextureScope::Level);
+        textures.register("tiles_2", "assets/levels/tiles_2.png", TextureScope::Level);
+        assert!(textures.decode_pending("tiles_1").is_none(), "Remembered as missing, like a decoded texture it is not retried");
+
+        // Restarting the level registers its tileset again before releasing the old ones.
+        textures.register("tiles_1", "assets/levels/tiles_1.png", TextureScope::Level);
+        assert_eq!(textures.unload_level_scope(&["tiles_1"]), vec!["tiles_2".to_string()]);
+        assert!(textures.is_registered("tiles_1"));
+        assert!(textures.is_missing("tiles_1"), "The shared texture is not decoded again");
+
+        assert_eq!(textures.unload_level_scope(&[]), vec!["tiles_1".to_string()], "It still belongs to the level scope");
+    }
+
+    #[test]
+    fn test_parse_hash_and_array_atlases() {
+        let hash = r#"{ "frames": { "cat_walk_0": { "frame": { "x": 0, "y": 0, "w": 80, "h": 64 } }, "cat_walk_1": { "frame": { "x": 80, "y": 0, "w": 80, "h": 64 }, "rotated": false } }, "meta": { "image": "cat.png" } }"#;
+        let frames = parse_atlas(hash).unwrap();
+        assert_eq!(frames["cat_walk_1"], Rect::new(80, 0, 80, 64));
+
+        let array = r#"{ "frames": [ { "filename": "coin_0", "frame": { "x": 16, "y": 32, "w": 16, "h": 16 } } ] }"#;
+        let frames = parse_atlas(array).unwrap();
+        assert_eq!(frames["coin_0"], Rect::new(16, 32, 16, 16));
+
+        assert!(parse_atlas(r#"{ "frames": [ { "frame": { "x": 0, "y": 0, "w": 1, "h": 1 } } ] }"#).is_err());
+    }
+
+    #[test]
+    fn test_animation_frames_resolve_from_atlas() {
+        let path = std::env::temp_dir().join("gfx_engine_test_atlas.json");
+        std::fs::write(&path, r#"{ "frames": { "a": { "frame": { "x": 5, "y": 6, "w": 7, "h": 8 } }, "b": { "frame": { "x": 12, "y": 6, "w": 7, "h": 8 } } } }"#).unwrap();
+        let mut textures = TextureManager::new();
+        textures.load_atlas(path.to_str().unwrap()).unwrap();
+
+        let mut game_config = crate::config::GameConfig::default();
+        let anim = game_config.animation.get_mut("walk_right").unwrap();
+        anim.frames = vec!["b".to_string(), "a".to_string()];
+        game_config.resolve_atlas_frames(&textures).unwrap();
+
+        let clip = crate::animation::Animation::from_config(&game_config.animation["walk_right"]);
+        assert_eq!(clip.frames, vec![Rect::new(12, 6, 7, 8), Rect::new(5, 6, 7, 8)], "Named frames replace the grid, in listed order");
+
+        game_config.animation.get_mut("walk_right").unwrap().frames.push("missing".to_string());
+        assert!(game_config.resolve_atlas_frames(&textures).is_err());
+    }
+}
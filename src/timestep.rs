@@ -0,0 +1,44 @@
+//! # Concept: Fixed Timestep
+//!
+//! This module decouples simulation from the display rate. Wall-clock frame 
+//! time is accumulated and consumed in fixed slices, so physics behaves the 
+//! same at 30 or 240 FPS. A stall (window drag, breakpoint) is clamped to 
+//! `max_delta` so the simulation never tries to catch up in one giant leap.
+
+/// Length of one simulation tick in **seconds** (120 Hz).
+pub const FIXED_TIMESTEP: f32 = 1.0 / 120.0;
+
+/// Accumulates frame time and hands it out in fixed simulation steps.
+pub struct FixedTimestep {
+    /// Length of one simulation step in **seconds**.
+    pub step: f32,
+    /// Largest frame time in **seconds** accepted per frame; longer frames are clamped.
+    pub max_delta: f32,
+    accumulator: f32,
+}
+
+impl FixedTimestep {
+    pub fn new(step: f32, max_delta: f32) -> Self {
+        Self { step, max_delta: max_delta.max(step), accumulator: 0.0 }
+    }
+
+    /// Adds one frame of wall-clock time and returns how many fixed steps to run.
+    pub fn advance(&mut self, frame_time: f32) -> u32 {
+        self.accumulator += frame_time.clamp(0.0, self.max_delta);
+        let steps = (self.accumulator / self.step) as u32;
+        self.accumulator -= steps as f32 * self.step;
+        steps
+    }
+
+    /// The most steps a single [FixedTimestep::advance] can return: the clamped frame time plus
+    /// the leftover of less than one step. This bounds the catch-up work after a stall, so a slow
+    /// frame can never snowball into an ever longer one.
+    pub fn max_steps_per_frame(&self) -> u32 {
+        (self.max_delta / self.step).ceil() as u32
+    }
+
+    /// Fraction of a step left over, used to interpolate rendering between snapshots.
+    pub fn alpha(&self) -> f32 {
+        self.accumulator / self.step
+    }
+}
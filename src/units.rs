@@ -0,0 +1,80 @@
+//! # Concept: World Units
+//!
+//! The engine uses exactly two coordinate spaces:
+//! - **World units:** positions, velocities, collision rects, tile sizes and
+//!   the camera. One world unit is one pixel of the source art, so the
+//!   viewport is `virtual_width` x `virtual_height` world units at zoom 1.
+//! - **Screen pixels:** what SDL draws, `world * scale * zoom`, measured from the
+//!   top-left corner of the letterboxed canvas.
+//!
+//! The scale is not a constant: [ViewTransform] derives it from the output size at
+//! runtime, and every conversion goes through it so all code paths agree.
+
+use crate::config::WindowConfig;
+
+/// Conversions that only depend on configuration, not on the window.
+pub struct Units;
+
+impl Units {
+    /// The visible area in **world units** for the configured virtual resolution.
+    pub fn viewport_in_world(window: &WindowConfig, zoom: f32) -> (f32, f32) {
+        (window.virtual_width as f32 / zoom, window.virtual_height as f32 / zoom)
+    }
+}
+
+/// Maps the virtual resolution onto the actual output: the largest scale that fits,
+/// centered with letterbox (top/bottom) or pillarbox (left/right) bars.
+///
+/// Owned by [crate::renderer::Renderer] and recomputed whenever the window changes size.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ViewTransform {
+    /// Screen **pixels** per world unit at zoom 1.
+    pub scale: f32,
+    /// Top-left corner of the canvas within the output, in output **pixels**.
+    /// Negative when even a 1x canvas is larger than the output; the edges are then cropped.
+    pub offset: (i32, i32),
+    /// Size of the canvas in **pixels** (the virtual resolution times `scale`).
+    pub canvas_size: (u32, u32),
+}
+
+impl ViewTransform {
+    /// Fits a `virtual_size` canvas into `output_size`. With `integer_scaling` the scale is
+    /// rounded down to a whole number (at least 1) so every art pixel stays the same size.
+    pub fn fit(output_size: (u32, u32), virtual_size: (u32, u32), integer_scaling: bool) -> Self {
+        let (virtual_width, virtual_height) = (virtual_size.0.max(1) as f32, virtual_size.1.max(1) as f32);
+        let fit = (output_size.0 as f32 / virtual_width).min(output_size.1 as f32 / virtual_height);
+        let scale = if integer_scaling { fit.floor().max(1.0) } else { fit.max(f32::EPSILON) };
+        let canvas_size = ((virtual_width * scale).round() as u32, (virtual_height * scale).round() as u32);
+        let offset = ((output_size.0 as i32 - canvas_size.0 as i32) / 2, (output_size.1 as i32 - canvas_size.1 as i32) / 2);
+        Self { scale, offset, canvas_size }
+    }
+
+    /// Fits the configured virtual resolution into an output of `output_size`.
+    pub fn for_window(window: &WindowConfig, output_size: (u32, u32)) -> Self {
+        Self::fit(output_size, (window.virtual_width, window.virtual_height), window.integer_scaling)
+    }
+
+    /// Screen **pixels** per world unit at the given camera zoom.
+    pub fn screen_scale(&self, zoom: f32) -> f32 { self.scale * zoom }
+
+    /// Converts a length in **world units** to **screen pixels**.
+    pub fn world_to_screen(&self, length: f32, zoom: f32) -> f32 { length * self.screen_scale(zoom) }
+
+    /// Converts a length in **screen pixels** to **world units**.
+    pub fn screen_to_world(&self, length: f32, zoom: f32) -> f32 { length / self.screen_scale(zoom) }
+
+    /// The canvas area within the output, in output **pixels**.
+    pub fn canvas_rect(&self) -> sdl3::rect::Rect {
+        sdl3::rect::Rect::new(self.offset.0, self.offset.1, self.canvas_size.0, self.canvas_size.1)
+    }
+
+    /// Converts a point in output **pixels** (e.g. the mouse) to canvas **pixels**.
+    pub fn output_to_canvas(&self, x: f32, y: f32) -> (f32, f32) {
+        (x - self.offset.0 as f32, y - self.offset.1 as f32)
+    }
+}
+
+impl Default for ViewTransform {
+    /// The shipped 1920x1080 window over a 480x270 virtual screen: 4x, no bars.
+    fn default() -> Self { Self::fit((1920, 1080), (480, 270), true) }
+}
@@ -0,0 +1,48 @@
+use gfx_engine::config::{AnimationPhase, ComponentConfig};
+use gfx_engine::ecs::world::Entity;
+use gfx_engine::level::Level;
+use gfx_engine::math::Vector2D;
+use gfx_engine::testing::HeadlessGame;
+
+fn game_with_coin_phase(phase: AnimationPhase) -> HeadlessGame {
+    let mut game = HeadlessGame::with_level(Level::from_tiles(32, 32, vec![vec![0; 12]; 6]));
+    for component in &mut game.game_config.prefabs.get_mut("GoldCoin").unwrap().components {
+        if let ComponentConfig::Animation { phase: coin_phase, .. } = component { *coin_phase = phase; }
+    }
+    game
+}
+
+fn frame_x(game: &HeadlessGame, coin: Entity) -> i32 {
+    game.world.animations[&coin].controller.current_frame_rect().expect("Coin has a frame").x()
+}
+
+#[test]
+fn test_synced_coins_share_a_frame_index() {
+    let mut game = game_with_coin_phase(AnimationPhase::Synced);
+    let early = game.spawn("GoldCoin", Vector2D::new(32.0, 32.0));
+    game.step(7);
+    let late = game.spawn("GoldCoin", Vector2D::new(96.0, 32.0));
+
+    // Sample across several frame changes of the spin.
+    for _ in 0..60 {
+        game.step(1);
+        assert_eq!(frame_x(&game, early), frame_x(&game, late), "Coins spawned apart still spin together");
+    }
+}
+
+/// Frames shown by a row of eight coins spawned on the same tick.
+fn spawn_row(phase: AnimationPhase) -> Vec<i32> {
+    let mut game = game_with_coin_phase(phase);
+    let coins: Vec<_> = (0..8).map(|i| game.spawn("GoldCoin", Vector2D::new(i as f32 * 32.0, 32.0))).collect();
+    game.step(3);
+    coins.iter().map(|&coin| frame_x(&game, coin)).collect()
+}
+
+#[test]
+fn test_random_phases_differ_between_coins_and_are_reproducible() {
+    let row = spawn_row(AnimationPhase::Random);
+    assert!(row.iter().any(|&x| x != row[0]), "Coins spawned together start on different frames: {:?}", row);
+    let synced = spawn_row(AnimationPhase::Synced);
+    assert!(synced.iter().all(|&x| x == synced[0]), "Without the random phase they would all match");
+    assert_eq!(row, spawn_row(AnimationPhase::Random), "Phases come from the world's seeded RNG");
+}
@@ -32,9 +32,13 @@ mod tests {
         
         // Mock Level
         let level = gfx_engine::level::Level {
-            map: gfx_engine::level::Map { tiles: vec![] },
-            tileset: gfx_engine::level::Tileset { texture: "".to_string(), tile_width: 0, tile_height: 0 },
+            map: gfx_engine::level::Map::default(),
+            tileset: gfx_engine::level::Tileset { texture: "".to_string(), tile_width: 0, tile_height: 0, ..Default::default() },
             collision: gfx_engine::level::Collision { tiles: vec![] },
+            hazards: gfx_engine::level::Collision { tiles: vec![] },
+            camera_zones: vec![],
+            water: gfx_engine::level::Collision { tiles: vec![] },
+            water_volumes: vec![],
             entities: vec![],
         };
 
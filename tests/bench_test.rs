@@ -0,0 +1,39 @@
+use gfx_engine::app::LaunchOptions;
+use gfx_engine::bench::{scripted_input, BENCH_LEVEL, BENCH_TICKS};
+use gfx_engine::input::InputState;
+use gfx_engine::level::load_level;
+use gfx_engine::math::Vector2D;
+use gfx_engine::testing::HeadlessGame;
+
+/// Plays the whole benchmark script on the stress level and returns the player's final position and coin count.
+fn bench_run() -> (Vector2D, u32) {
+    let mut game = HeadlessGame::with_level(load_level(BENCH_LEVEL).unwrap());
+    gfx_engine::entity_spawner::spawn_entities(&mut game.world, &game.level, &game.game_config);
+    let player = game.spawn_player(game.game_config.player.start_pos);
+    assert!(game.world.gold_coins.len() >= 200 && game.world.enemy_tags.len() >= 40, "A stress level needs plenty of entities");
+
+    let mut input = InputState::default();
+    for tick in 0..BENCH_TICKS {
+        input = scripted_input(tick, &input);
+        game.input_state = input.clone();
+        game.step(1);
+    }
+    (game.world.positions[&player].0, game.world.stats.gold_coin_count)
+}
+
+#[test]
+fn test_benchmark_runs_simulate_identically() {
+    let (first_position, first_coins) = bench_run();
+    let (second_position, second_coins) = bench_run();
+
+    assert_eq!(first_position, second_position);
+    assert_eq!(first_coins, second_coins);
+    assert!(first_coins > 0, "The scripted run collects coins along the floor");
+}
+
+#[test]
+fn test_launch_options_parse_the_bench_flag() {
+    assert_eq!(LaunchOptions::from_args(Vec::new()), Ok(LaunchOptions::default()));
+    assert!(LaunchOptions::from_args(vec!["--bench".to_string()]).unwrap().bench);
+    assert!(LaunchOptions::from_args(vec!["--fast".to_string()]).is_err());
+}
@@ -0,0 +1,103 @@
+use gfx_engine::ecs::event::EventPlayerEnemyStomped;
+use gfx_engine::ecs::systems::gui_render::active_boss;
+use gfx_engine::ecs::world::Entity;
+use gfx_engine::enemy::boss::is_vulnerable;
+use gfx_engine::level::{Level, Region};
+use gfx_engine::math::Vector2D;
+use gfx_engine::testing::HeadlessGame;
+
+const TILE: u32 = 32;
+const NEXT_LEVEL: &str = "assets/levels/level_02.tmx";
+
+/// A floored arena walled on both sides, with the boss at the left wall and a player up on the
+/// left ledge (out of its path), so the boss charges right into the far wall.
+fn arena() -> (HeadlessGame, Entity, Entity) {
+    let mut tiles = vec![vec![0; 16]; 6];
+    tiles[5] = vec![1; 16];
+    for row in tiles.iter_mut().take(5) {
+        row[0] = 1;
+        row[15] = 1;
+    }
+    let mut game = HeadlessGame::with_level(Level::from_tiles(TILE, TILE, tiles));
+    game.game_config.bosses.get_mut("king_spider").unwrap().next_level = Some(NEXT_LEVEL.to_string());
+    let floor_top = (5 * TILE) as f32;
+    let boss = game.spawn("BossSpider", Vector2D::new(40.0, floor_top - 48.0));
+    let player = game.spawn_player(Vector2D::new(40.0, -64.0));
+    game.world.gravity_tags.remove(&player);
+    (game, boss, player)
+}
+
+/// Steps until the boss crashes into a wall; panics if it never does.
+fn stun(game: &mut HeadlessGame, boss: Entity) {
+    for _ in 0..600 {
+        if is_vulnerable(&game.world, boss) { return; }
+        game.step(1);
+    }
+    panic!("The boss never crashed into the wall");
+}
+
+fn stomp(game: &mut HeadlessGame, player: Entity, boss: Entity) {
+    game.world.event_bus.publish(EventPlayerEnemyStomped { player, enemy: boss });
+    game.step(1);
+}
+
+#[test]
+fn test_charge_ends_in_a_stun_at_the_wall() {
+    let (mut game, boss, _) = arena();
+    game.step(1);
+    assert!(!is_vulnerable(&game.world, boss), "The boss starts out winding up");
+
+    stun(&mut game, boss);
+    let right = game.world.positions[&boss].0.x + 96.0;
+    assert!(right > (14 * TILE) as f32 - 2.0, "It stopped at the far wall, not before it");
+    assert_eq!(game.world.velocities[&boss].0.x, 0.0);
+}
+
+#[test]
+fn test_only_a_stunned_boss_takes_damage() {
+    let (mut game, boss, player) = arena();
+    let max = game.world.healths[&boss].max;
+
+    stomp(&mut game, player, boss);
+    assert_eq!(game.world.healths[&boss].current, max, "Stomps outside the stun are shrugged off");
+    assert!(game.world.enemy_tags.contains_key(&boss), "The boss is not killed like a regular enemy");
+
+    stun(&mut game, boss);
+    stomp(&mut game, player, boss);
+    assert_eq!(game.world.healths[&boss].current, max - 1);
+    assert!(!is_vulnerable(&game.world, boss), "A hit ends the stun, so it cannot be chained");
+}
+
+#[test]
+fn test_health_thresholds_advance_the_phase() {
+    let (mut game, boss, player) = arena();
+    game.world.healths.get_mut(&boss).unwrap().current = 5;
+    assert_eq!(game.world.bosses[&boss].phase, 0);
+
+    stun(&mut game, boss);
+    stomp(&mut game, player, boss);
+    assert_eq!(game.world.bosses[&boss].phase, 1, "Dropping to 4 health enters the first phase");
+}
+
+#[test]
+fn test_defeat_leads_to_the_next_level() {
+    let (mut game, boss, player) = arena();
+    game.world.healths.get_mut(&boss).unwrap().current = 1;
+
+    stun(&mut game, boss);
+    stomp(&mut game, player, boss);
+    assert!(!game.world.enemy_tags.contains_key(&boss), "The boss is defeated");
+    assert!(game.world.level_exit.is_some(), "Its defeat starts the level transition");
+
+    game.step(240);
+    assert_eq!(game.next_level.as_deref(), Some(NEXT_LEVEL));
+}
+
+#[test]
+fn test_health_bar_only_tracks_a_boss_in_the_camera_zone() {
+    let (game, boss, _) = arena();
+    let arena_zone = Region { x: 0.0, y: 0.0, width: (16 * TILE) as f32, height: (6 * TILE) as f32 };
+    let elsewhere = Region { x: 1000.0, ..arena_zone };
+    assert_eq!(active_boss(&game.world, &arena_zone), Some(boss));
+    assert_eq!(active_boss(&game.world, &elsewhere), None);
+}
@@ -0,0 +1,92 @@
+use gfx_engine::ecs::world::Entity;
+use gfx_engine::level::Level;
+use gfx_engine::math::Vector2D;
+use gfx_engine::testing::HeadlessGame;
+
+const TILE: u32 = 32;
+
+fn state_name(game: &HeadlessGame, entity: Entity) -> String {
+    game.world.state_components[&entity].state_machine.current_state.as_ref().unwrap().get_name().to_string()
+}
+
+/// A floored room; the chaser stands at column `chaser_column`, the player at `player_column`.
+/// A wall between them is added at `wall_column` when given.
+fn room(chaser_column: u32, player_column: u32, wall_column: Option<usize>) -> (HeadlessGame, Entity, Entity) {
+    let mut tiles = vec![vec![0; 24]; 8];
+    tiles[7] = vec![1; 24];
+    if let Some(column) = wall_column {
+        for row in tiles.iter_mut().take(7) { row[column] = 1; }
+    }
+    let mut game = HeadlessGame::with_level(Level::from_tiles(TILE, TILE, tiles));
+    let floor_top = (7 * TILE) as f32;
+    let chaser = game.spawn("EnemyChaser", Vector2D::new((chaser_column * TILE) as f32, floor_top - 12.0));
+    let player = game.spawn_player(Vector2D::new((player_column * TILE) as f32, floor_top - 26.0));
+    (game, chaser, player)
+}
+
+/// Two platforms at the same height with a gap between: the chaser on the left one, the player on the right.
+fn gap() -> (HeadlessGame, Entity) {
+    let mut tiles = vec![vec![0; 16]; 8];
+    tiles[4][1..7].copy_from_slice(&[1; 6]);
+    tiles[4][10..15].copy_from_slice(&[1; 5]);
+    tiles[7] = vec![1; 16];
+    let mut game = HeadlessGame::with_level(Level::from_tiles(TILE, TILE, tiles));
+    game.game_config.enemy_behavior.get_mut("chaser").unwrap().detection_radius = 320.0;
+    let platform_top = (4 * TILE) as f32;
+    let chaser = game.spawn("EnemyChaser", Vector2D::new((2 * TILE) as f32, platform_top - 12.0));
+    game.spawn_player(Vector2D::new((11 * TILE) as f32, platform_top - 26.0));
+    (game, chaser)
+}
+
+#[test]
+fn test_chaser_turns_toward_a_visible_player_at_chase_speed() {
+    let (mut game, chaser, _) = room(10, 6, None);
+    let start = game.world.positions[&chaser].0.x;
+    game.step(60);
+
+    assert_eq!(state_name(&game, chaser), "ChaseState");
+    assert!(game.world.positions[&chaser].0.x < start, "It heads left, toward the player, despite patrolling right");
+    let chase_speed = game.game_config.enemy_behavior["chaser"].chase_speed;
+    assert!(game.world.velocities[&chaser].0.x < -game.world.patrols[&chaser].speed, "It runs faster than it patrols");
+    assert!(game.world.velocities[&chaser].0.x >= -chase_speed - 1.0);
+}
+
+#[test]
+fn test_walls_block_the_chasers_sight() {
+    let (mut game, chaser, _) = room(10, 6, Some(8));
+    game.step(60);
+    assert_eq!(state_name(&game, chaser), "PatrolState");
+}
+
+#[test]
+fn test_chaser_gives_up_once_the_player_escapes() {
+    let (mut game, chaser, player) = room(10, 6, None);
+    game.step(10);
+    assert_eq!(state_name(&game, chaser), "ChaseState");
+
+    let far = Vector2D::new((22 * TILE) as f32, game.world.positions[&player].0.y);
+    game.world.positions.get_mut(&player).unwrap().0 = far;
+    game.world.collisions.get_mut(&player).unwrap().rect.set_x(far.x as i32);
+    game.world.gravity_tags.remove(&player);
+    game.step(2);
+    assert_eq!(state_name(&game, chaser), "PatrolState");
+}
+
+#[test]
+fn test_careful_chaser_waits_at_the_edge() {
+    let (mut game, chaser) = gap();
+    game.step(240);
+
+    assert_eq!(state_name(&game, chaser), "ChaseState");
+    let x = game.world.positions[&chaser].0.x;
+    assert!(game.world.positions[&chaser].0.y < (4 * TILE) as f32, "Still standing on its platform");
+    assert!(x + 24.0 > (7 * TILE) as f32 - 8.0, "Waiting right at the edge (x = {})", x);
+}
+
+#[test]
+fn test_reckless_chaser_follows_over_the_edge() {
+    let (mut game, chaser) = gap();
+    game.game_config.enemy_behavior.get_mut("chaser").unwrap().reckless = true;
+    game.step(240);
+    assert!(game.world.positions[&chaser].0.y > (4 * TILE) as f32, "It ran off the platform into the gap");
+}
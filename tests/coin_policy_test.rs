@@ -0,0 +1,36 @@
+use gfx_engine::ecs::resources::GameStats;
+
+#[test]
+fn test_coins_carry_into_next_level() {
+    let mut stats = GameStats { gold_coin_count: 12, ..Default::default() };
+    let level_start = stats.enter_level(true, None);
+    assert_eq!(stats.gold_coin_count, 12);
+    assert_eq!(level_start, 12);
+}
+
+#[test]
+fn test_coins_reset_on_next_level() {
+    let mut stats = GameStats { gold_coin_count: 12, ..Default::default() };
+    let level_start = stats.enter_level(false, None);
+    assert_eq!(stats.gold_coin_count, 0);
+    assert_eq!(level_start, 0);
+}
+
+#[test]
+fn test_restart_rolls_back_to_level_start_under_both_policies() {
+    for carry in [true, false] {
+        let mut stats = GameStats { gold_coin_count: 20, ..Default::default() };
+        let level_start = stats.enter_level(carry, Some(5));
+        assert_eq!(stats.gold_coin_count, 5, "carry_coins_on_level = {}", carry);
+        assert_eq!(level_start, 5);
+    }
+}
+
+#[test]
+fn test_life_coin_policy() {
+    let mut stats = GameStats { gold_coin_count: 3, ..Default::default() };
+    stats.lose_life_coins(true);
+    assert_eq!(stats.gold_coin_count, 3);
+    stats.lose_life_coins(false);
+    assert_eq!(stats.gold_coin_count, 0);
+}
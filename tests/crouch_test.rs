@@ -0,0 +1,88 @@
+use gfx_engine::ecs::world::Entity;
+use gfx_engine::input::{InputAction, InputState};
+use gfx_engine::level::Level;
+use gfx_engine::math::Vector2D;
+use gfx_engine::testing::HeadlessGame;
+
+const TILE: u32 = 16;
+
+fn state_name(game: &HeadlessGame, entity: Entity) -> String {
+    game.world.state_components[&entity].state_machine.current_state.as_ref().unwrap().get_name().to_string()
+}
+
+/// The y of the player's feet in **pixels**.
+fn feet(game: &HeadlessGame, player: Entity) -> f32 {
+    game.world.positions[&player].0.y + game.world.collisions[&player].rect.height() as f32
+}
+
+/// A floored corridor whose right half has a ceiling one tile above the floor: too low to
+/// stand under, high enough to crawl with a 12 pixel crouch. Returns the settled player at `column`.
+fn corridor(column: u32) -> (HeadlessGame, Entity) {
+    let mut tiles = vec![vec![0; 32]; 20];
+    tiles[19] = vec![1; 32];
+    tiles[17][16..].copy_from_slice(&[1; 16]);
+    let mut game = HeadlessGame::with_level(Level::from_tiles(TILE, TILE, tiles));
+    game.game_config.player.crouch_height = 12;
+    let floor_top = (19 * TILE) as f32;
+    let player = game.spawn_player(Vector2D::new((column * TILE) as f32, floor_top - game.game_config.player.height as f32));
+    game.step(10);
+    assert!(game.world.is_grounded(player));
+    (game, player)
+}
+
+#[test]
+fn test_holding_down_crouches_with_the_feet_in_place() {
+    let (mut game, player) = corridor(2);
+    let standing_feet = feet(&game, player);
+    game.input_state = InputState::from_actions(vec![InputAction::Down]);
+    game.step(2);
+
+    assert_eq!(state_name(&game, player), "CrouchingState");
+    assert_eq!(game.world.collisions[&player].rect.height(), game.game_config.player.crouch_height);
+    assert!((feet(&game, player) - standing_feet).abs() < 0.5, "The box shrank from the top");
+    assert!(game.world.is_grounded(player));
+
+    game.input_state = InputState::default();
+    game.step(2);
+    assert_ne!(state_name(&game, player), "CrouchingState");
+    assert_eq!(game.world.collisions[&player].rect.height(), game.game_config.player.height);
+    assert!((feet(&game, player) - standing_feet).abs() < 0.5);
+}
+
+#[test]
+fn test_crouching_slows_the_player_down() {
+    let (mut game, player) = corridor(2);
+    game.input_state = InputState::from_actions(vec![InputAction::Down, InputAction::MoveRight]);
+    game.step(60);
+
+    let top_speed = game.config.physics.max_speed * game.game_config.player.crouch_speed_factor;
+    assert_eq!(state_name(&game, player), "CrouchingState");
+    assert!(game.world.velocities[&player].0.x <= top_speed + 0.01, "Capped at the crouch speed");
+    assert!(game.world.velocities[&player].0.x > 0.0);
+}
+
+#[test]
+fn test_low_ceiling_keeps_the_player_crouched() {
+    let (mut game, player) = corridor(12);
+    game.input_state = InputState::from_actions(vec![InputAction::Down, InputAction::MoveRight]);
+    game.step(180);
+    assert!(game.world.positions[&player].0.x > (16 * TILE) as f32, "Crawled under the ceiling");
+
+    game.input_state = InputState::default();
+    game.step(30);
+    assert_eq!(state_name(&game, player), "CrouchingState", "No headroom to stand up");
+    assert_eq!(game.world.collisions[&player].rect.height(), game.game_config.player.crouch_height);
+}
+
+#[test]
+fn test_down_and_jump_together_do_not_crouch() {
+    let (mut game, player) = corridor(2);
+    let mut input = InputState::from_actions(vec![InputAction::Down, InputAction::Jump]);
+    input.calculate_deltas(&InputState::default());
+    game.input_state = input;
+    game.step(1);
+
+    assert_ne!(state_name(&game, player), "CrouchingState");
+    assert!(!game.world.crouchings.contains_key(&player));
+    assert_eq!(game.world.collisions[&player].rect.height(), game.game_config.player.height);
+}
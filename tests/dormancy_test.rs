@@ -0,0 +1,43 @@
+use gfx_engine::level::Level;
+use gfx_engine::math::Vector2D;
+use gfx_engine::testing::HeadlessGame;
+
+/// A long floored corridor, far wider than one screen.
+fn corridor() -> HeadlessGame {
+    let mut tiles = vec![vec![0; 200]; 6];
+    tiles[5] = vec![1; 200];
+    HeadlessGame::with_level(Level::from_tiles(32, 32, tiles))
+}
+
+#[test]
+fn test_far_enemies_freeze_and_resume_in_place() {
+    let mut game = corridor();
+    let enemy = game.spawn("EnemySpider", Vector2D::new(5000.0, 100.0));
+    let player = game.spawn_player(Vector2D::new(6200.0, 100.0));
+
+    // 1. Let the enemy land and patrol while the camera is on it.
+    game.camera.snap_to(Vector2D::new(5000.0, 100.0));
+    game.step(60);
+    assert!(!game.world.is_dormant(enemy));
+    assert!(game.world.is_grounded(enemy));
+    assert!(game.world.velocities[&enemy].0.x != 0.0, "The enemy is walking when it goes dormant");
+
+    // 2. Leave it behind: it stays exactly where it was, on the ground.
+    game.camera.snap_to(Vector2D::new(0.0, 0.0));
+    game.step(1);
+    assert!(game.world.is_dormant(enemy));
+    assert!(!game.world.is_dormant(player), "Players never go dormant");
+    let frozen = game.world.positions[&enemy].0;
+    game.step(120);
+    assert_eq!(game.world.positions[&enemy].0, frozen, "Dormant entities do not move");
+    assert!(game.world.is_grounded(enemy), "Dormancy keeps the grounded state");
+    assert_eq!(game.world.frame_debug_info.dormant_count, 1);
+    assert_eq!(game.world.frame_debug_info.active_count, 1);
+
+    // 3. Bringing the camera back wakes the enemy where it was frozen.
+    game.camera.snap_to(frozen);
+    game.step(1);
+    assert!(!game.world.is_dormant(enemy));
+    let position = game.world.positions[&enemy].0;
+    assert!((position.x - frozen.x).abs() < 5.0 && (position.y - frozen.y).abs() < 5.0, "No teleport on wake: {:?}", position);
+}
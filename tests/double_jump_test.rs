@@ -0,0 +1,59 @@
+use gfx_engine::ecs::world::Entity;
+use gfx_engine::input::{InputAction, InputState};
+use gfx_engine::level::Level;
+use gfx_engine::math::Vector2D;
+use gfx_engine::testing::HeadlessGame;
+
+const TILE: u32 = 32;
+
+/// A player standing on the floor of a tall room, with `max_jumps` per airtime.
+fn standing_player(max_jumps: u32) -> (HeadlessGame, Entity) {
+    let mut tiles = vec![vec![0; 8]; 20];
+    tiles[19] = vec![1; 8];
+    let mut game = HeadlessGame::with_level(Level::from_tiles(TILE, TILE, tiles));
+    game.config.physics.max_jumps = max_jumps;
+    let floor_top = (19 * TILE) as f32;
+    let player = game.spawn_player(Vector2D::new(64.0, floor_top - game.game_config.player.height as f32));
+    game.step(10);
+    assert!(game.world.is_grounded(player));
+    (game, player)
+}
+
+/// Presses Jump for a single tick, then releases it.
+fn press_jump(game: &mut HeadlessGame) {
+    let mut input = InputState::from_actions(vec![InputAction::Jump]);
+    input.calculate_deltas(&InputState::default());
+    game.input_state = input;
+    game.step(1);
+    game.input_state = InputState::default();
+}
+
+/// Presses Jump once the player has been falling for a while; returns whether it rose again.
+fn jump_while_falling(game: &mut HeadlessGame, player: Entity) -> bool {
+    game.step(40);
+    assert!(game.world.velocities[&player].0.y > 0.0, "Past the apex");
+    press_jump(game);
+    game.world.velocities[&player].0.y < 0.0
+}
+
+#[test]
+fn test_double_jump_allows_exactly_one_air_jump() {
+    let (mut game, player) = standing_player(2);
+    press_jump(&mut game);
+    assert!(!game.world.is_grounded(player));
+
+    assert!(jump_while_falling(&mut game, player), "The air jump resets the fall into a rise");
+    assert!(!jump_while_falling(&mut game, player), "No third jump before landing");
+
+    game.step(240);
+    assert!(game.world.is_grounded(player));
+    press_jump(&mut game);
+    assert!(jump_while_falling(&mut game, player), "Landing refills the air jump");
+}
+
+#[test]
+fn test_single_jump_is_the_default() {
+    let (mut game, player) = standing_player(gfx_engine::config::Config::default().physics.max_jumps);
+    press_jump(&mut game);
+    assert!(!jump_while_falling(&mut game, player));
+}
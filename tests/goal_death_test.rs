@@ -0,0 +1,51 @@
+use gfx_engine::config::GoalDeathPrecedence;
+use gfx_engine::level::{Entity, Level};
+use gfx_engine::math::Vector2D;
+use gfx_engine::testing::HeadlessGame;
+
+const DEATH_PLANE_Y: f32 = 100.0;
+
+/// A player standing in a goal that hangs below the death plane: both facts fire on the first tick.
+fn goal_over_pit(precedence: GoalDeathPrecedence) -> HeadlessGame {
+    let mut game = HeadlessGame::with_level(Level::from_tiles(32, 32, vec![vec![0; 12]; 6]));
+    game.game_config.world.death_plane_y = DEATH_PLANE_Y;
+    game.game_config.gameplay.goal_death_precedence = precedence;
+
+    let spot = Vector2D::new(64.0, DEATH_PLANE_Y + 10.0);
+    let goal = Entity {
+        r#type: "Goal".to_string(),
+        position: spot,
+        properties: [("next_level".to_string(), "assets/levels/level_02.tmx".to_string())].into(),
+        ..Default::default()
+    };
+    gfx_engine::entity_spawner::spawn_entity(&mut game.world, &goal, &game.game_config);
+    game.spawn_player(spot);
+    game
+}
+
+#[test]
+fn test_goal_precedence_completes_level_without_losing_a_life() {
+    let mut game = goal_over_pit(GoalDeathPrecedence::Goal);
+    let lives = game.world.stats.lives;
+
+    game.step(1);
+    assert!(game.world.level_exit.is_some(), "The goal starts the level transition");
+    assert!(game.world.respawn_timers.is_empty(), "No respawn is queued");
+
+    game.step(240);
+    assert_eq!(game.world.stats.lives, lives, "Falling after the goal never costs a life");
+    assert_eq!(game.next_level.as_deref(), Some("assets/levels/level_02.tmx"));
+}
+
+#[test]
+fn test_death_precedence_loses_a_life_and_stays_in_level() {
+    let mut game = goal_over_pit(GoalDeathPrecedence::Death);
+    let lives = game.world.stats.lives;
+
+    game.step(1);
+    assert_eq!(game.world.stats.lives, lives - 1);
+    assert!(game.world.level_exit.is_none(), "A dead player cannot complete the level");
+
+    game.step(240);
+    assert_eq!(game.next_level, None);
+}
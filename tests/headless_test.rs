@@ -0,0 +1,43 @@
+use gfx_engine::input::{InputAction, InputState};
+use gfx_engine::level::Level;
+use gfx_engine::math::Vector2D;
+use gfx_engine::testing::HeadlessGame;
+
+const TILE: u32 = 32;
+
+/// A 12x6 room with a solid floor on the bottom row.
+fn floor_level() -> Level {
+    let mut tiles = vec![vec![0; 12]; 6];
+    tiles[5] = vec![1; 12];
+    Level::from_tiles(TILE, TILE, tiles)
+}
+
+#[test]
+fn test_player_falls_onto_floor_and_becomes_grounded() {
+    let mut game = HeadlessGame::with_level(floor_level());
+    let player = game.spawn_player(Vector2D::new(64.0, 32.0));
+    assert!(!game.world.is_grounded(player));
+
+    game.step(120);
+
+    let floor_top = (5 * TILE) as f32;
+    let feet = game.world.positions[&player].0.y + game.game_config.player.height as f32;
+    assert!(game.world.is_grounded(player), "Player should land within one second");
+    assert!((feet - floor_top).abs() <= 1.0, "Feet at {} should rest on the floor at {}", feet, floor_top);
+}
+
+#[test]
+fn test_walking_into_coin_increments_count() {
+    let mut game = HeadlessGame::with_level(floor_level());
+    let floor_top = (5 * TILE) as f32;
+    let player = game.spawn_player(Vector2D::new(32.0, floor_top - game.game_config.player.height as f32));
+    let coin = game.spawn("GoldCoin", Vector2D::new(160.0, floor_top - 20.0));
+    assert_eq!(game.world.stats.gold_coin_count, 0);
+
+    game.input_state = InputState::from_actions(vec![InputAction::MoveRight]);
+    game.step(120);
+
+    assert_eq!(game.world.stats.gold_coin_count, 1);
+    assert!(!game.world.gold_coins.contains_key(&coin), "Collected coin is removed");
+    assert!(game.world.positions[&player].0.x > 160.0);
+}
@@ -0,0 +1,41 @@
+use gfx_engine::ecs::world::Entity;
+use gfx_engine::input::{InputAction, InputState};
+use gfx_engine::level::Level;
+use gfx_engine::math::Vector2D;
+use gfx_engine::testing::HeadlessGame;
+
+const TILE: u32 = 32;
+
+/// A floored room with a player, missing `damage` health, walking right into a heart.
+fn walk_into_heart(damage: u32) -> (HeadlessGame, Entity, Entity) {
+    let mut tiles = vec![vec![0; 12]; 6];
+    tiles[5] = vec![1; 12];
+    let mut game = HeadlessGame::with_level(Level::from_tiles(TILE, TILE, tiles));
+    let floor_top = (5 * TILE) as f32;
+    let player = game.spawn_player(Vector2D::new(32.0, floor_top - game.game_config.player.height as f32));
+    let heart = game.spawn("HeartRefill", Vector2D::new(160.0, floor_top - 20.0));
+    let health = game.world.healths.get_mut(&player).unwrap();
+    health.current = health.max - damage;
+
+    game.input_state = InputState::from_actions(vec![InputAction::MoveRight]);
+    game.step(120);
+    (game, player, heart)
+}
+
+#[test]
+fn test_heart_refills_a_damaged_player() {
+    let (game, player, heart) = walk_into_heart(1);
+    let health = game.world.healths[&player];
+    assert_eq!(health.current, health.max, "One heart restores one point, clamped to max");
+    assert!(!game.world.health_pickups.contains_key(&heart), "Collected heart is removed");
+    assert_eq!(game.world.ui_state.display_health, health.current, "HUD follows the refill");
+}
+
+#[test]
+fn test_full_health_player_leaves_the_heart() {
+    let (game, player, heart) = walk_into_heart(0);
+    let health = game.world.healths[&player];
+    assert_eq!(health.current, health.max);
+    assert!(game.world.health_pickups.contains_key(&heart), "The heart stays for later");
+    assert!(game.world.positions[&player].0.x > 160.0, "The player walked through it");
+}
@@ -29,9 +29,13 @@ fn test_entity_cleanup_integrity() {
     
     // Mock Level (Empty)
     let level = gfx_engine::level::Level {
-        map: gfx_engine::level::Map { tiles: vec![] },
-        tileset: gfx_engine::level::Tileset { texture: "".to_string(), tile_width: 0, tile_height: 0 },
+        map: gfx_engine::level::Map::default(),
+        tileset: gfx_engine::level::Tileset { texture: "".to_string(), tile_width: 0, tile_height: 0, ..Default::default() },
         collision: gfx_engine::level::Collision { tiles: vec![] },
+        hazards: gfx_engine::level::Collision { tiles: vec![] },
+        camera_zones: vec![],
+        water: gfx_engine::level::Collision { tiles: vec![] },
+        water_volumes: vec![],
         entities: vec![],
     };
 
@@ -57,26 +61,39 @@ fn test_entity_cleanup_integrity() {
     world.add_velocity(entity, Velocity(Vector2D::default()));
     world.add_acceleration(entity, Acceleration(Vector2D::default()));
     world.add_movement_intention(entity, MovementIntention::default());
-    world.add_renderable(entity, Renderable { width: 0, height: 0, horizontal_offset: 0, vertical_offset: 0, z_index: 0, rotation: 0.0, flip_horizontal: false, flip_vertical: false });
+    world.add_renderable(entity, Renderable { width: 0, height: 0, horizontal_offset: 0, vertical_offset: 0, z_index: 0, rotation: 0.0, flip_horizontal: false, flip_vertical: false, tint: sdl3::pixels::Color::WHITE, alpha: 255 });
     world.add_animation(entity, Animation { controller: AnimationController::new() });
     world.add_player_tag(entity, PlayerTag);
     world.add_gold_coin(entity, GoldCoin);
+    world.add_health_pickup(entity, HealthPickup { amount: 1 });
+    world.add_melee_hitbox(entity, MeleeHitbox { owner: entity });
+    world.add_attack_cooldown(entity, AttackCooldown { timer: 1.0 });
     world.add_enemy_tag(entity, EnemyTag);
-    world.add_patrol(entity, Patrol { speed: 0.0, anim_prefix: "".to_string(), direction: 0.0 });
+    world.add_patrol(entity, Patrol { speed: 0.0, anim_prefix: "".to_string(), direction: 0.0, turn_at_ledge: true, behavior: None });
     world.add_gravity(entity, Gravity);
     world.add_collision(entity, Collision { rect: sdl3::rect::Rect::new(0,0,0,0) });
     world.add_grounded(entity, Grounded);
     world.add_wall_hit(entity, WallHit { normal_x: 0.0 });
     world.add_state_component(entity, StateComponent { state_machine: StateMachine::new(IdleState) });
     world.add_respawn_tag(entity, RespawnTag);
-    world.add_respawn_timer(entity, RespawnTimer { timer: 0.0, transition_started: false });
+    world.add_respawn_timer(entity, RespawnTimer { timer: 0.0, transition_started: false, transition_complete: false });
     world.add_health(entity, Health { current: 0, max: 0 });
     world.add_invincibility(entity, Invincibility { timer: 0.0 });
+    world.add_knockback(entity, Knockback { timer: 0.0 });
     world.add_lifetime(entity, Lifetime { timer: 0.0 });
     world.add_direction(entity, Directional { direction: Direction::Right });
     world.add_goal(entity, Goal);
     world.add_next_level(entity, NextLevel("".to_string()));
+    world.add_exit_spawn_point(entity, ExitSpawnPoint("".to_string()));
+    world.add_checkpoint(entity, Checkpoint::default());
+    world.add_trigger(entity, Trigger { event_name: "test".to_string(), once: true, ..Default::default() });
+    world.add_projectile(entity, Projectile { owner: None, from_player: true, damage: 1, speed: 100.0, lifetime: 1.0, gravity: false });
+    world.add_particle_emitter(entity, ParticleEmitter { spec: game_config.particles.presets["dust"].clone(), rate: 1.0, accumulator: 0.0 });
+    world.add_jump_assist(entity, JumpAssist::default());
+    world.add_player_slot(entity, PlayerSlot(1));
     world.add_dormant_tag(entity, DormantTag);
+    world.add_submerged(entity, Submerged);
+    world.add_boss(entity, Boss { config_key: "test".to_string(), ..Default::default() });
 
     // 3. Mark the entity for death
     world.add_dead_tag(entity, DeadTag);
@@ -94,6 +111,9 @@ fn test_entity_cleanup_integrity() {
     assert!(!world.animations.contains_key(&entity), "Leaked Animation");
     assert!(!world.player_tags.contains_key(&entity), "Leaked PlayerTag");
     assert!(!world.gold_coins.contains_key(&entity), "Leaked GoldCoin");
+    assert!(!world.health_pickups.contains_key(&entity), "Leaked HealthPickup");
+    assert!(!world.melee_hitboxes.contains_key(&entity), "Leaked MeleeHitbox");
+    assert!(!world.attack_cooldowns.contains_key(&entity), "Leaked AttackCooldown");
     assert!(!world.enemy_tags.contains_key(&entity), "Leaked EnemyTag");
     assert!(!world.dead_tags.contains_key(&entity), "Leaked DeadTag");
     assert!(!world.patrols.contains_key(&entity), "Leaked Patrol");
@@ -101,15 +121,78 @@ fn test_entity_cleanup_integrity() {
     assert!(!world.collisions.contains_key(&entity), "Leaked Collision");
     assert!(!world.grounded_tags.contains_key(&entity), "Leaked Grounded");
     assert!(!world.wall_hits.contains_key(&entity), "Leaked WallHit"); 
+    assert!(!world.submerged_tags.contains_key(&entity), "Leaked Submerged");
     
     assert!(!world.state_components.contains_key(&entity), "Leaked StateComponent");
     assert!(!world.respawn_tags.contains_key(&entity), "Leaked RespawnTag");
     assert!(!world.respawn_timers.contains_key(&entity), "Leaked RespawnTimer");
     assert!(!world.healths.contains_key(&entity), "Leaked Health");
     assert!(!world.invincibilities.contains_key(&entity), "Leaked Invincibility");
+    assert!(!world.knockbacks.contains_key(&entity), "Leaked Knockback");
     assert!(!world.lifetimes.contains_key(&entity), "Leaked Lifetime");
     assert!(!world.directions.contains_key(&entity), "Leaked Direction");
     assert!(!world.goals.contains_key(&entity), "Leaked Goal");
     assert!(!world.next_levels.contains_key(&entity), "Leaked NextLevel");
+    assert!(!world.exit_spawn_points.contains_key(&entity), "Leaked ExitSpawnPoint");
+    assert!(!world.checkpoints.contains_key(&entity), "Leaked Checkpoint");
+    assert!(!world.triggers.contains_key(&entity), "Leaked Trigger");
+    assert!(!world.particle_emitters.contains_key(&entity), "Leaked ParticleEmitter");
+    assert!(!world.projectiles.contains_key(&entity), "Leaked Projectile");
+    assert!(!world.jump_assists.contains_key(&entity), "Leaked JumpAssist");
+    assert!(!world.bosses.contains_key(&entity), "Leaked Boss");
+    assert!(!world.player_slots.contains_key(&entity), "Leaked PlayerSlot");
     assert!(!world.dormant_tags.contains_key(&entity), "Leaked DormantTag");
+    assert!(!world.has_components(entity), "Leaked a registered component");
+    assert!(!world.is_alive(entity), "The handle went stale");
+}
+
+#[test]
+fn test_clear_level_despawns_everything_and_invalidates_old_handles() {
+    let mut world = World::new();
+    let old = world.create_entity();
+    world.add_position(old, Position(Vector2D::new(10.0, 20.0)));
+    world.add_player_tag(old, PlayerTag);
+    world.add_health(old, Health { current: 3, max: 3 });
+    world.stats.lives = 2;
+
+    world.clear_level();
+
+    assert!(!world.is_alive(old));
+    assert_eq!(world.stats.lives, 2, "Stats carry over a level swap");
+    assert_ne!(world.create_entity(), old, "Handles from the old level never match new entities");
+}
+
+#[test]
+fn test_recycled_slots_never_answer_to_stale_handles() {
+    let mut world = World::new();
+    let coin = world.create_entity();
+    world.despawn(coin);
+    let player = world.create_entity();
+    world.add_health(player, Health { current: 3, max: 3 });
+
+    assert_eq!(player.index(), coin.index(), "The freed slot is reused");
+    assert_ne!(player, coin);
+    assert!(!world.healths.contains_key(&coin), "A stale handle matches none of the new occupant's components");
+
+    world.despawn(coin);
+    assert!(world.is_alive(player), "Despawning a stale handle leaves the new occupant alone");
+    assert!(world.healths.contains_key(&player));
+}
+
+#[test]
+fn test_clear_life_state_only_removes_per_life_components() {
+    let mut world = World::new();
+    let player = world.create_entity();
+    world.add_position(player, Position(Vector2D::new(10.0, 20.0)));
+    world.add_health(player, Health { current: 1, max: 3 });
+    world.add_invincibility(player, Invincibility { timer: 999.0 });
+    world.add_knockback(player, Knockback { timer: 0.2 });
+    world.add_crouching(player, Crouching { standing_height: 26 });
+
+    world.clear_life_state(player);
+
+    assert!(!world.invincibilities.contains_key(&player));
+    assert!(!world.knockbacks.contains_key(&player));
+    assert!(!world.crouchings.contains_key(&player));
+    assert!(world.positions.contains_key(&player) && world.healths.contains_key(&player), "The entity itself lives on");
 }
@@ -0,0 +1,85 @@
+use gfx_engine::audio::AudioEvent;
+use gfx_engine::ecs::world::Entity;
+use gfx_engine::input::{InputAction, InputState};
+use gfx_engine::level::Level;
+use gfx_engine::math::Vector2D;
+use gfx_engine::testing::HeadlessGame;
+
+const TILE: u32 = 32;
+
+/// A tall room with a ledge spanning the first `ledge_columns` columns halfway up, the player standing
+/// at its left end, and a floor far below.
+fn room(ledge_columns: usize) -> (HeadlessGame, Entity) {
+    let mut tiles = vec![vec![0; 12]; 20];
+    for tile in tiles[10].iter_mut().take(ledge_columns) { *tile = 1; }
+    tiles[19] = vec![1; 12];
+    let mut game = HeadlessGame::with_level(Level::from_tiles(TILE, TILE, tiles));
+    game.game_config.sound_events.insert("player_jump".to_string(), "test_jump".to_string());
+    game.game_config.sound_events.remove("player_air_jump");
+    let ledge_top = (10 * TILE) as f32;
+    let player = game.spawn_player(Vector2D::new(32.0, ledge_top - game.game_config.player.height as f32));
+    game.step(10);
+    assert!(game.world.is_grounded(player));
+    (game, player)
+}
+
+/// Presses Jump for a single tick on top of the `held` actions, then keeps holding only those.
+fn press_jump(game: &mut HeadlessGame, held: &[InputAction]) {
+    let previous = InputState::from_actions(held.to_vec());
+    let mut input = InputState::from_actions(held.iter().copied().chain([InputAction::Jump]).collect());
+    input.calculate_deltas(&previous);
+    game.input_state = input;
+    game.step(1);
+    game.input_state = previous;
+}
+
+/// Steps until `done` holds and returns how many ticks that took.
+fn ticks_until(game: &mut HeadlessGame, done: impl Fn(&HeadlessGame) -> bool) -> u32 {
+    for tick in 0..600 {
+        if done(game) { return tick; }
+        game.step(1);
+    }
+    panic!("Condition never reached");
+}
+
+fn jump_sounds(game: &mut HeadlessGame) -> usize {
+    game.take_audio_events().into_iter()
+        .filter(|event| matches!(event, AudioEvent::PlaySound(name, _) if name == "test_jump"))
+        .count()
+}
+
+/// Walks the player off the ledge and presses Jump `late_ticks` after it lost the ground; returns whether it rose.
+fn jump_after_walking_off(late_ticks: u32) -> bool {
+    let (mut game, player) = room(4);
+    game.input_state = InputState::from_actions(vec![InputAction::MoveRight]);
+    ticks_until(&mut game, |game| !game.world.is_grounded(player));
+    game.step(late_ticks);
+    press_jump(&mut game, &[InputAction::MoveRight]);
+    game.world.velocities[&player].0.y < 0.0
+}
+
+#[test]
+fn test_jump_just_after_walking_off_a_ledge_still_works() {
+    assert!(jump_after_walking_off(3), "Pressed within the coyote time");
+    assert!(!jump_after_walking_off(30), "Pressed long after the coyote time, still above the floor");
+}
+
+#[test]
+fn test_jump_pressed_just_before_landing_fires_on_touchdown_once() {
+    // 1. Measure how long a jump stays in the air.
+    let (mut game, player) = room(12);
+    press_jump(&mut game, &[]);
+    let airtime = ticks_until(&mut game, |game| game.world.is_grounded(player));
+
+    // 2. Jump again and press a few ticks before that landing.
+    let (mut game, player) = room(12);
+    press_jump(&mut game, &[]);
+    game.step(airtime - 5);
+    assert!(!game.world.is_grounded(player), "Still in the air when pressing");
+    press_jump(&mut game, &[]);
+    ticks_until(&mut game, |game| game.world.velocities[&player].0.y < 0.0);
+    game.step(10);
+
+    assert!(!game.world.is_grounded(player), "The buffered press jumped off the landing");
+    assert_eq!(jump_sounds(&mut game), 2, "One sound per jump, not one per buffered tick");
+}
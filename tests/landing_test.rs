@@ -0,0 +1,83 @@
+use gfx_engine::audio::AudioEvent;
+use gfx_engine::ecs::component::RespawnTag;
+use gfx_engine::level::Level;
+use gfx_engine::math::Vector2D;
+use gfx_engine::testing::HeadlessGame;
+
+const TILE: u32 = 32;
+
+/// A tall floored shaft with a distinct landing sound, so footsteps never get mistaken for it.
+fn shaft() -> HeadlessGame {
+    let mut tiles = vec![vec![0; 8]; 20];
+    tiles[19] = vec![1; 8];
+    let mut game = HeadlessGame::with_level(Level::from_tiles(TILE, TILE, tiles));
+    game.game_config.sound_events.insert("player_land".to_string(), "test_land".to_string());
+    game
+}
+
+/// Drops the player from `height` **pixels** above the floor and returns the volumes of every landing
+/// sound, plus the largest screen shake offset in **pixels** seen along the way.
+fn drop_from(height: f32) -> (Vec<f64>, f32) {
+    let mut game = shaft();
+    let floor_top = (19 * TILE) as f32;
+    game.spawn_player(Vector2D::new((3 * TILE) as f32, floor_top - 26.0 - height));
+    let mut max_shake: f32 = 0.0;
+    for _ in 0..240 {
+        game.step(1);
+        let offset = game.camera.shake_offset;
+        max_shake = max_shake.max(offset.x.abs().max(offset.y.abs()));
+    }
+    (landing_volumes(&mut game), max_shake)
+}
+
+fn landing_volumes(game: &mut HeadlessGame) -> Vec<f64> {
+    game.take_audio_events().into_iter()
+        .filter_map(|event| match event {
+            AudioEvent::PlaySound(name, params) if name == "test_land" => Some(params.volume),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn test_long_falls_land_loudly_once() {
+    let (volumes, _) = drop_from((12 * TILE) as f32);
+    assert_eq!(volumes.len(), 1, "One landing, not one per grounded tick");
+    assert!(volumes[0] > 0.9, "A fall at terminal speed is full volume (got {})", volumes[0]);
+}
+
+#[test]
+fn test_short_drops_land_quieter() {
+    let (long, _) = drop_from((12 * TILE) as f32);
+    let (short, _) = drop_from(TILE as f32);
+    assert_eq!(short.len(), 1);
+    assert!(short[0] < long[0]);
+}
+
+#[test]
+fn test_only_heavy_landings_shake_the_camera() {
+    let (_, heavy) = drop_from((12 * TILE) as f32);
+    let (_, light) = drop_from(TILE as f32);
+    assert!(heavy > 0.0, "A landing at terminal speed publishes a screen shake");
+    assert_eq!(light, 0.0);
+}
+
+#[test]
+fn test_respawn_teleport_of_a_falling_player_stays_silent() {
+    let mut game = shaft();
+    let floor_top = (19 * TILE) as f32;
+    game.game_config.player.respawn_pos = Vector2D::new((5 * TILE) as f32, floor_top - 26.0);
+    let player = game.spawn_player(Vector2D::new((3 * TILE) as f32, 0.0));
+
+    // Half a second into the fall the player is fast, but still far above the floor.
+    game.step(60);
+    assert!(!game.world.is_grounded(player));
+    assert!(game.world.velocities[&player].0.y > game.game_config.gameplay.landing.min_speed);
+
+    // RuleRespawn puts the player down on the floor with its velocity zeroed.
+    game.world.add_respawn_tag(player, RespawnTag);
+    game.step(120);
+
+    assert!(game.world.is_grounded(player), "The respawn point is on the floor");
+    assert!(landing_volumes(&mut game).is_empty());
+}
@@ -0,0 +1,28 @@
+use gfx_engine::level::{load_level, TileKind};
+
+const FIXTURE: &str = "tests/fixtures/infinite_map.tmx";
+
+/// The fixture has a chunk at tile (-4, 0) and one at (4, 4), both 4x4, so the stitched
+/// grid spans columns -4..8 and rows 0..8 and is shifted right by 4 tiles.
+#[test]
+fn test_chunks_are_stitched_into_one_grid() {
+    let level = load_level(FIXTURE).unwrap();
+    assert_eq!((level.map.width, level.map.height), (12, 8));
+
+    let tiles = &level.collision.tiles;
+    assert_eq!(tiles.len(), 8);
+    assert!(tiles.iter().all(|row| row.len() == 12));
+    let solid: Vec<Vec<bool>> = tiles.iter().map(|row| row.iter().map(|t| *t == TileKind::Solid).collect()).collect();
+    assert!(solid[0][0], "Top-left tile of the negative chunk lands at the grid origin");
+    assert_eq!(solid[3][0..4], [true; 4]);
+    assert_eq!(solid[7][8..12], [true; 4], "Second chunk sits at columns 8..12, rows 4..8");
+    assert!(solid[0..4].iter().all(|row| row[4..12].iter().all(|&t| !t)), "Gaps between chunks are empty");
+    assert!(solid[4..8].iter().all(|row| row[0..8].iter().all(|&t| !t)));
+}
+
+#[test]
+fn test_objects_are_shifted_with_the_map() {
+    let level = load_level(FIXTURE).unwrap();
+    let coin = level.entities.iter().find(|e| e.r#type == "GoldCoin").unwrap();
+    assert_eq!((coin.position.x, coin.position.y), (32.0, 64.0), "x = -96 + 4 tiles * 32");
+}
@@ -0,0 +1,61 @@
+use gfx_engine::config::load_game_config;
+use gfx_engine::ecs::world::World;
+use gfx_engine::entity_spawner::spawn_entity;
+use gfx_engine::level::{load_level, Level};
+
+const FIXTURE: &str = "tests/fixtures/object_map.tmx";
+
+fn object<'a>(level: &'a Level, name: Option<&str>, x: f32) -> &'a gfx_engine::level::Entity {
+    level.entities.iter().find(|e| e.name.as_deref() == name && (e.position.x - x).abs() < 1.0).unwrap()
+}
+
+#[test]
+fn test_template_instances_inherit_and_override() {
+    let level = load_level(FIXTURE).unwrap();
+
+    let plain = object(&level, None, 32.0);
+    assert_eq!(plain.r#type, "GoldCoin");
+    assert!(plain.is_tile, "The template's gid makes it a tile object");
+    assert_eq!(plain.size, Some((16.0, 16.0)));
+    assert_eq!(plain.position.y, 80.0, "Tile objects are anchored bottom-left");
+    assert_eq!(plain.properties["value"], "1");
+
+    let bonus = object(&level, Some("bonus"), 64.0);
+    assert_eq!(bonus.size, Some((32.0, 32.0)), "Instance attributes win");
+    assert_eq!(bonus.properties["value"], "5", "Instance properties win");
+    assert_eq!(bonus.properties["sparkle"], "true", "Other template properties are kept");
+}
+
+#[test]
+fn test_rotated_objects_keep_their_drawn_bounds() {
+    let level = load_level(FIXTURE).unwrap();
+    let door = level.entities.iter().find(|e| e.name.as_deref() == Some("door")).unwrap();
+    assert_eq!(door.rotation, 90.0);
+
+    // 64x32 rotated a quarter turn clockwise about its top-left corner at (96, 32).
+    let (corner, size) = door.bounds().unwrap();
+    assert_eq!((corner.x, corner.y, size.0, size.1), (64.0, 32.0, 32.0, 64.0));
+
+    let game_config = load_game_config("assets/game_config.toml").unwrap();
+    let mut world = World::new();
+    let entity = spawn_entity(&mut world, door, &game_config);
+    let rect = world.collisions[&entity].rect;
+    assert_eq!((rect.x(), rect.y(), rect.width(), rect.height()), (64, 32, 32, 64));
+    assert_eq!(world.positions[&entity].0.x, 64.0, "Anchored at the collision corner");
+}
+
+#[test]
+fn test_objects_without_the_new_attributes_spawn_as_before() {
+    let level = load_level(FIXTURE).unwrap();
+    let coin = object(&level, None, 0.0);
+    assert!(!coin.is_tile);
+    assert_eq!(coin.rotation, 0.0);
+
+    let game_config = load_game_config("assets/game_config.toml").unwrap();
+    let mut world = World::new();
+    let entity = spawn_entity(&mut world, coin, &game_config);
+    let rect = world.collisions[&entity].rect;
+    assert_eq!((rect.x(), rect.y(), rect.width(), rect.height()), (0, 0, 16, 16), "The prefab's collision size is kept");
+    assert_eq!(world.renderables[&entity].rotation, 0.0);
+    assert_eq!((world.renderables[&entity].horizontal_offset, world.renderables[&entity].vertical_offset), (0, 0));
+}
@@ -0,0 +1,60 @@
+use gfx_engine::ecs::component::Knockback;
+use gfx_engine::ecs::world::Entity;
+use gfx_engine::input::{InputAction, InputState};
+use gfx_engine::level::Level;
+use gfx_engine::math::Vector2D;
+use gfx_engine::testing::HeadlessGame;
+
+const TILE: u32 = 32;
+
+/// A player standing on a floor, facing right (the default).
+fn standing_player() -> (HeadlessGame, Entity) {
+    let mut tiles = vec![vec![0; 12]; 6];
+    tiles[5] = vec![1; 12];
+    let mut game = HeadlessGame::with_level(Level::from_tiles(TILE, TILE, tiles));
+    let floor_top = (5 * TILE) as f32;
+    let player = game.spawn_player(Vector2D::new(64.0, floor_top - game.game_config.player.height as f32));
+    game.step(2);
+    (game, player)
+}
+
+/// Presses Attack for a single tick, then releases it.
+fn swing(game: &mut HeadlessGame) {
+    let mut input = InputState::from_actions(vec![InputAction::Attack]);
+    input.calculate_deltas(&InputState::default());
+    game.input_state = input;
+    game.step(1);
+    game.input_state = InputState::default();
+}
+
+#[test]
+fn test_swing_kills_enemy_in_front() {
+    let (mut game, player) = standing_player();
+    let body_right = game.world.positions[&player].0.x + game.game_config.player.width as f32;
+    let floor_top = (5 * TILE) as f32;
+    let enemy = game.spawn("EnemySpider", Vector2D::new(body_right + 8.0, floor_top - 12.0));
+    let behind = game.spawn("EnemySpider", Vector2D::new(8.0, floor_top - 12.0));
+
+    swing(&mut game);
+    assert_eq!(game.world.melee_hitboxes.len(), 1, "The swing spawns one hitbox");
+    game.step(2);
+
+    assert!(!game.world.enemy_tags.contains_key(&enemy), "The enemy in front is defeated");
+    assert!(game.world.enemy_tags.contains_key(&behind), "The hitbox only covers the facing side");
+}
+
+#[test]
+fn test_cooldown_and_knockback_block_new_swings() {
+    let (mut game, player) = standing_player();
+    swing(&mut game);
+    game.step(1);
+    swing(&mut game);
+    assert_eq!(game.world.melee_hitboxes.len(), 1, "A second press during the cooldown is ignored");
+
+    game.step(120);
+    assert!(game.world.attack_cooldowns.is_empty(), "The cooldown runs out");
+    let hitboxes = game.world.melee_hitboxes.len();
+    game.world.add_knockback(player, Knockback { timer: 1.0 });
+    swing(&mut game);
+    assert_eq!(game.world.melee_hitboxes.len(), hitboxes, "A knocked-back player cannot attack");
+}
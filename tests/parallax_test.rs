@@ -0,0 +1,39 @@
+use gfx_engine::config::{ParallaxConfig, ParallaxLayerConfig};
+use gfx_engine::math::Vector2D;
+use gfx_engine::renderer::{parallax_origin, parallax_pass};
+
+fn layer(texture: &str, z_index: u8, scroll_speed: f32, repeat_y: bool) -> ParallaxLayerConfig {
+    ParallaxLayerConfig { texture: texture.to_string(), z_index, scroll_speed_x: scroll_speed, scroll_speed_y: scroll_speed, repeat_y }
+}
+
+#[test]
+fn test_layers_split_around_the_tile_map_back_to_front() {
+    let config = ParallaxConfig {
+        layers: vec![layer("hills", 210, 0.5, false), layer("fog", 150, 1.2, false), layer("sky", 250, 0.1, false), layer("mist", 200, 1.5, false)],
+        tile_z_index: 200,
+    };
+    let names = |behind| parallax_pass(&config, behind).iter().map(|l| l.texture.as_str()).collect::<Vec<_>>();
+    assert_eq!(names(true), vec!["sky", "hills"], "Layers above tile_z_index are drawn behind the tiles, furthest first");
+    assert_eq!(names(false), vec!["mist", "fog"], "A layer at tile_z_index is drawn in front of the tiles");
+}
+
+#[test]
+fn test_origin_scrolls_by_speed_and_wraps_seamlessly() {
+    let hills = layer("hills", 210, 0.5, false);
+    // 100px texture at 2x: the camera at x = 300 scrolls the layer 150px, i.e. 50px into the second copy.
+    let (x, y) = parallax_origin(&hills, Vector2D::new(300.0, 40.0), (100, 60), 2.0);
+    assert_eq!(x, -100.0);
+    assert_eq!(y, -40.0, "Without repeat_y the layer scrolls vertically without wrapping");
+
+    for camera_x in [0.0, 37.0, 199.0, 200.0, 1234.5] {
+        let (x, _) = parallax_origin(&hills, Vector2D::new(camera_x, 0.0), (100, 60), 2.0);
+        assert!(x <= 0.0 && x > -200.0, "Camera x {}: first copy starts at {}", camera_x, x);
+    }
+}
+
+#[test]
+fn test_repeat_y_wraps_vertically_for_tall_maps() {
+    let clouds = layer("clouds", 250, 0.5, true);
+    let (_, y) = parallax_origin(&clouds, Vector2D::new(0.0, 1000.0), (100, 60), 1.0);
+    assert_eq!(y, -20.0, "500px of scroll is 8 copies of 60px plus 20px");
+}
@@ -0,0 +1,45 @@
+use gfx_engine::level::Level;
+use gfx_engine::math::Vector2D;
+use gfx_engine::testing::HeadlessGame;
+
+const TILE: f32 = 32.0;
+
+/// A 3-tile platform (columns 3..6) floating in the middle of an empty room.
+fn platform_level() -> Level {
+    let mut tiles = vec![vec![0; 10]; 8];
+    tiles[4][3..6].copy_from_slice(&[1, 1, 1]);
+    Level::from_tiles(32, 32, tiles)
+}
+
+#[test]
+fn test_patrol_ping_pongs_on_a_small_platform_without_falling() {
+    let mut game = HeadlessGame::with_level(platform_level());
+    let platform_top = 4.0 * TILE;
+    let enemy = game.spawn("EnemySpider", Vector2D::new(4.0 * TILE, platform_top - 12.0));
+
+    let (mut min_x, mut max_x, mut turns) = (f32::MAX, f32::MIN, 0);
+    let mut heading = game.world.patrols[&enemy].direction;
+    for _ in 0..600 {
+        game.step(1);
+        let x = game.world.positions[&enemy].0.x;
+        min_x = min_x.min(x);
+        max_x = max_x.max(x);
+        let direction = game.world.patrols[&enemy].direction;
+        if direction != heading { turns += 1; heading = direction; }
+    }
+
+    let y = game.world.positions[&enemy].0.y;
+    assert!((y + 12.0 - platform_top).abs() <= 1.0, "Spider still stands on the platform (y = {})", y);
+    assert!(turns >= 4, "Five seconds at 60 px/s cover the 96px platform several times, turned {} times", turns);
+    assert!(min_x >= 3.0 * TILE - 1.0 && max_x + 24.0 <= 6.0 * TILE + 1.0, "Stayed within {}..{}", min_x, max_x);
+}
+
+#[test]
+fn test_patrol_without_ledge_turning_walks_off() {
+    let mut game = HeadlessGame::with_level(platform_level());
+    let enemy = game.spawn("EnemySpider", Vector2D::new(4.0 * TILE, 4.0 * TILE - 12.0));
+    game.world.patrols.get_mut(&enemy).unwrap().turn_at_ledge = false;
+
+    game.step(240);
+    assert!(game.world.positions[&enemy].0.y > 4.0 * TILE, "Without ledge turning the spider falls off");
+}
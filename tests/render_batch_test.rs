@@ -0,0 +1,78 @@
+use gfx_engine::camera::Camera;
+use gfx_engine::renderer::{flipped_source, scanline_rows, visible_tile_range, QuadBatch};
+use sdl3::render::FRect;
+
+fn camera_at(x: f32, y: f32) -> Camera {
+    Camera::new(x, y, 0.1, 480.0, 270.0, 10000.0, 10000.0, 0.1, 0.1, 0.1, 0.1, 0.1, 0.1, 1000.0, 100.0, 0.1)
+}
+
+#[test]
+fn test_tile_range_covers_only_the_view() {
+    // 480x270 view over 32px tiles starting mid-tile.
+    let camera = camera_at(48.0, 40.0);
+    let (rows, cols) = visible_tile_range(&camera, 32, 32, 1000, 1000);
+    assert_eq!(cols, 1..17, "Partially visible edge columns are included");
+    assert_eq!(rows, 1..10);
+}
+
+#[test]
+fn test_tile_range_is_clamped_to_the_grid() {
+    let camera = camera_at(-100.0, -100.0);
+    let (rows, cols) = visible_tile_range(&camera, 32, 32, 4, 6);
+    assert_eq!((rows, cols), (0..4, 0..6));
+
+    let beyond = camera_at(5000.0, 5000.0);
+    let (rows, cols) = visible_tile_range(&beyond, 32, 32, 4, 6);
+    assert!(rows.is_empty() && cols.is_empty(), "A camera past the map visits no tiles");
+}
+
+#[test]
+fn test_zoom_widens_the_range() {
+    let mut camera = camera_at(0.0, 0.0);
+    let (_, normal) = visible_tile_range(&camera, 32, 32, 1000, 1000);
+    camera.zoom = 0.5;
+    let (_, zoomed_out) = visible_tile_range(&camera, 32, 32, 1000, 1000);
+    assert_eq!(zoomed_out.len(), normal.len() * 2);
+}
+
+#[test]
+fn test_quad_batch_maps_source_to_texture_coordinates() {
+    let mut batch = QuadBatch::default();
+    batch.push(FRect::new(32.0, 0.0, 32.0, 32.0), FRect::new(10.0, 20.0, 64.0, 64.0), (128.0, 64.0), 0.5);
+    batch.push(FRect::new(0.0, 32.0, 32.0, 32.0), FRect::new(74.0, 20.0, 64.0, 64.0), (128.0, 64.0), 0.5);
+    assert_eq!(batch.len(), 2);
+    assert_eq!(batch.indices(), &[0, 1, 2, 0, 2, 3, 4, 5, 6, 4, 6, 7]);
+
+    let first = &batch.vertices()[0];
+    assert_eq!((first.position.x, first.position.y), (10.0, 20.0));
+    assert_eq!((first.tex_coord.x, first.tex_coord.y), (0.25, 0.0));
+    let opposite = &batch.vertices()[2];
+    assert_eq!((opposite.position.x, opposite.position.y), (74.0, 84.0));
+    assert_eq!((opposite.tex_coord.x, opposite.tex_coord.y), (0.5, 0.5));
+    assert_eq!(first.color.a, 0.5, "Layer opacity is carried per vertex");
+
+    batch.clear();
+    assert!(batch.is_empty());
+}
+
+#[test]
+fn test_flipped_sources_mirror_the_texture_coordinates() {
+    let frame = sdl3::rect::Rect::new(32, 0, 32, 16);
+    assert_eq!(flipped_source(&frame, false, false), FRect::new(32.0, 0.0, 32.0, 16.0));
+    assert_eq!(flipped_source(&frame, true, true), FRect::new(64.0, 16.0, -32.0, -16.0));
+
+    let mut batch = QuadBatch::default();
+    let tint = sdl3_sys::pixels::SDL_FColor { r: 1.0, g: 0.5, b: 0.5, a: 1.0 };
+    batch.push_tinted(flipped_source(&frame, true, false), FRect::new(0.0, 0.0, 32.0, 16.0), (128.0, 16.0), tint);
+    let (left, right) = (&batch.vertices()[0], &batch.vertices()[1]);
+    assert!(left.position.x < right.position.x, "The quad itself is not mirrored");
+    assert_eq!((left.tex_coord.x, right.tex_coord.x), (0.5, 0.25), "Only its texture coordinates are");
+    assert_eq!(left.color.g, 0.5, "The tint is carried per vertex");
+}
+
+#[test]
+fn test_scanlines_dim_the_lower_half_of_each_virtual_row() {
+    assert_eq!(scanline_rows(2, 0.5), vec![255, 128, 255, 128]);
+    assert_eq!(scanline_rows(1, 0.0), vec![255, 255], "Zero strength leaves the frame untouched");
+    assert_eq!(scanline_rows(1, 3.0), vec![255, 0], "Strength is clamped to 1");
+}
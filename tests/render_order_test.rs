@@ -0,0 +1,46 @@
+use gfx_engine::renderer::{sort_back_to_front, sort_for_batching, ENTITY_LAYER_Z};
+use gfx_engine::config::load_game_config;
+use gfx_engine::ecs::world::{Entity, World};
+
+/// Handles `e[0]..e[7]`, ordered by index.
+fn entities() -> Vec<Entity> {
+    let mut world = World::new();
+    (0..8).map(|_| world.create_entity()).collect()
+}
+
+#[test]
+fn test_higher_z_is_drawn_first() {
+    // (z_index, entity): background prop, player, effect behind, effect in front.
+    let e = entities();
+    let mut items = vec![(99, e[4]), (100, e[2]), (200, e[1]), (101, e[3])];
+    sort_back_to_front(&mut items);
+    let order: Vec<_> = items.iter().map(|(_, e)| *e).collect();
+    assert_eq!(order, vec![e[1], e[3], e[2], e[4]], "Higher z must be drawn first (further back)");
+}
+
+#[test]
+fn test_equal_depth_order_is_stable() {
+    let e = entities();
+    let mut a = vec![(100, e[7]), (100, e[3]), (100, e[5])];
+    let mut b = vec![(100, e[5]), (100, e[7]), (100, e[3])];
+    sort_back_to_front(&mut a);
+    sort_back_to_front(&mut b);
+    assert_eq!(a, b, "Ties must not depend on query order");
+}
+
+#[test]
+fn test_batching_groups_textures_without_breaking_depth() {
+    let e = entities();
+    let mut items = vec![(100, "coin", e[0]), (100, "enemy", e[1]), (200, "coin", e[2]), (100, "coin", e[3]), (99, "coin", e[4])];
+    sort_for_batching(&mut items);
+    let order: Vec<_> = items.iter().map(|(z, texture, _)| (*z, *texture)).collect();
+    assert_eq!(order, vec![(200, "coin"), (100, "coin"), (100, "coin"), (100, "enemy"), (99, "coin")], "Same-texture sprites are adjacent only within a depth");
+    assert_eq!(items[1].2, e[0], "Ties within a texture still break by entity");
+}
+
+#[test]
+fn test_default_effect_depth_is_behind_player() {
+    let game_config = load_game_config("assets/game_config.toml").unwrap();
+    assert_eq!(game_config.player.z_index, ENTITY_LAYER_Z);
+    assert!(game_config.gameplay.explosion.z_index > game_config.player.z_index, "Explosions are configured to render behind the player");
+}
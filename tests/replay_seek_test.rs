@@ -0,0 +1,95 @@
+use gfx_engine::bench::{scripted_input, BENCH_LEVEL};
+use gfx_engine::config::Config;
+use gfx_engine::ecs::world::Entity;
+use gfx_engine::input::InputState;
+use gfx_engine::level::load_level;
+use gfx_engine::math::Vector2D;
+use gfx_engine::replay::Replay;
+use gfx_engine::testing::HeadlessGame;
+
+const SEEK_TICK: u64 = 1500;
+
+/// A fresh stress level with its entities and the player, as a replay starts.
+fn fresh_game() -> HeadlessGame {
+    let mut game = HeadlessGame::with_level(load_level(BENCH_LEVEL).unwrap());
+    gfx_engine::entity_spawner::spawn_entities(&mut game.world, &game.level, &game.game_config);
+    game.spawn_player(game.game_config.player.start_pos);
+    game
+}
+
+/// Records the bench script for `ticks` ticks without simulating it.
+fn scripted_replay(ticks: u64) -> Replay {
+    let mut replay = Replay::new(0, BENCH_LEVEL, &Config::default().physics);
+    let mut previous = InputState::default();
+    for tick in 0..ticks {
+        previous = scripted_input(tick, &previous);
+        replay.record(tick, previous.get_pressed_actions(), previous.get_player_two_actions());
+    }
+    replay
+}
+
+/// Everything a seek has to reproduce: every entity's position, which ones are dormant, and the camera.
+fn snapshot(game: &HeadlessGame) -> (Vec<(Entity, Vector2D, bool)>, Vector2D) {
+    let mut entities: Vec<_> = game.world.positions.iter().map(|(e, p)| (*e, p.0, game.world.is_dormant(*e))).collect();
+    entities.sort_by_key(|(entity, _, _)| *entity);
+    (entities, game.camera.position)
+}
+
+/// Plays `ticks` ticks on a fresh stress level and returns the player's final position, velocity and coin count.
+fn play(ticks: u64, mut input_for: impl FnMut(u64) -> InputState) -> (Vector2D, Vector2D, u32) {
+    let mut game = fresh_game();
+    let player = gfx_engine::simulation::primary_player(&game.world).unwrap();
+    for tick in 0..ticks {
+        game.input_state = input_for(tick);
+        game.step(1);
+    }
+    (game.world.positions[&player].0, game.world.velocities[&player].0, game.world.stats.gold_coin_count)
+}
+
+#[test]
+fn test_input_rebuilt_per_tick_simulates_like_the_recorded_session() {
+    // 1. Play live, recording the input the way the game state manager does.
+    let mut replay = Replay::new(0, BENCH_LEVEL, &Config::default().physics);
+    let mut previous = InputState::default();
+    let live = play(SEEK_TICK, |tick| {
+        previous = scripted_input(tick, &previous);
+        replay.record(tick, previous.get_pressed_actions(), previous.get_player_two_actions());
+        previous.clone()
+    });
+
+    // 2. Re-simulate from the start with nothing but the replay, as a seek does.
+    let seeked = play(SEEK_TICK, |tick| replay.input_at(tick));
+
+    assert_eq!(live, seeked);
+    assert!(live.2 > 0, "The session collects coins, so the comparison covers more than idle physics");
+}
+
+#[test]
+fn test_fast_forward_matches_tick_by_tick_playback_across_a_wide_level() {
+    let replay = scripted_replay(SEEK_TICK);
+
+    // 1. Real-time playback: one tick per update, the camera following the player.
+    let mut played = fresh_game();
+    played.follow_camera = true;
+    let start_camera = played.camera.position;
+    let mut camera_travel: f32 = 0.0;
+    for tick in 0..SEEK_TICK {
+        played.input_state = replay.input_at(tick);
+        played.step(1);
+        camera_travel = camera_travel.max((played.camera.position.x - start_camera.x).abs());
+    }
+
+    // 2. The seek path.
+    let mut seeked = fresh_game();
+    assert_eq!(seeked.fast_forward(&replay, SEEK_TICK), SEEK_TICK);
+    assert_eq!(snapshot(&seeked), snapshot(&played));
+
+    // 3. The run crosses the level, so a camera left behind would freeze different entities.
+    assert!(camera_travel > played.camera.view_width(), "The camera travels more than a screen");
+    let mut left_behind = fresh_game();
+    for tick in 0..SEEK_TICK {
+        left_behind.input_state = replay.input_at(tick);
+        left_behind.step(1);
+    }
+    assert_ne!(snapshot(&left_behind).0, snapshot(&played).0, "Dormancy depends on where the camera is");
+}
@@ -0,0 +1,81 @@
+use gfx_engine::ecs::component::Direction;
+use gfx_engine::ecs::event::{EventCoinCollected, EventPlayerDamaged, EventPlayerEnemyStomped};
+use gfx_engine::ecs::world::Entity;
+use gfx_engine::level::Level;
+use gfx_engine::math::Vector2D;
+use gfx_engine::player::factory::PlayerFactory;
+use gfx_engine::testing::HeadlessGame;
+
+const TILE: u32 = 32;
+
+/// An empty room with a weightless player in the middle, away from everything spawned in the tests.
+fn room() -> (HeadlessGame, Entity) {
+    let mut game = HeadlessGame::with_level(Level::from_tiles(TILE, TILE, vec![vec![0; 16]; 8]));
+    let player = game.spawn_player(Vector2D::new(40.0, 40.0));
+    game.world.gravity_tags.remove(&player);
+    (game, player)
+}
+
+fn damage(player: Entity, amount: u32) -> EventPlayerDamaged {
+    EventPlayerDamaged { player, knockback_x: -200.0, direction: Direction::Left, position: Vector2D::new(40.0, 40.0), amount }
+}
+
+#[test]
+fn test_collected_coin_is_counted_and_removed() {
+    let (mut game, _) = room();
+    let coin = game.spawn("GoldCoin", Vector2D::new(400.0, 100.0));
+    game.world.event_bus.publish(EventCoinCollected { coin });
+    game.step(1);
+
+    assert_eq!(game.world.stats.gold_coin_count, 1);
+    assert!(!game.world.positions.contains_key(&coin), "The coin is despawned");
+}
+
+#[test]
+fn test_coin_touched_by_both_players_counts_once() {
+    let (mut game, _) = room();
+    let second = PlayerFactory::create_for_slot(&mut game.world, &game.game_config, 1, Vector2D::new(40.0, 40.0));
+    game.world.gravity_tags.remove(&second);
+    game.spawn("GoldCoin", Vector2D::new(44.0, 44.0));
+    game.step(2);
+
+    assert_eq!(game.world.stats.gold_coin_count, 1);
+    assert_eq!(game.world.stats.level_coins, 1);
+    assert_eq!(game.world.stats.score, game.game_config.gameplay.score.coin_points);
+}
+
+#[test]
+fn test_stomped_enemy_dies_and_bounces_the_player() {
+    let (mut game, player) = room();
+    let enemy = game.spawn("EnemySpider", Vector2D::new(400.0, 100.0));
+    game.world.event_bus.publish(EventPlayerEnemyStomped { player, enemy });
+    game.step(1);
+
+    assert!(!game.world.positions.contains_key(&enemy), "The enemy is despawned");
+    assert!(game.world.velocities[&player].0.y < 0.0, "The player bounces upwards");
+}
+
+#[test]
+fn test_enemy_stomped_twice_in_one_tick_scores_once() {
+    let (mut game, player) = room();
+    let enemy = game.spawn("EnemySpider", Vector2D::new(400.0, 100.0));
+    game.world.event_bus.publish(EventPlayerEnemyStomped { player, enemy });
+    game.world.event_bus.publish(EventPlayerEnemyStomped { player, enemy });
+    game.step(1);
+
+    assert_eq!(game.world.stats.score, game.game_config.gameplay.score.stomp_points);
+    assert_eq!(game.world.stomp_combos[&player].chain, 1);
+}
+
+#[test]
+fn test_damage_costs_health_once_per_invincibility_window() {
+    let (mut game, player) = room();
+    let start = game.world.healths[&player].current;
+    game.world.event_bus.publish(damage(player, 1));
+    game.world.event_bus.publish(damage(player, 1));
+    game.step(1);
+
+    assert_eq!(game.world.healths[&player].current, start - 1, "The first hit makes the player invincible");
+    assert!(game.world.invincibilities.contains_key(&player));
+    assert!(game.world.knockbacks.contains_key(&player), "The hit knocks the player back");
+}
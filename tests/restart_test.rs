@@ -0,0 +1,81 @@
+use gfx_engine::ecs::event::{EventCoinCollected, EventLevelRestartRequested};
+use gfx_engine::level::{Entity, Level};
+use gfx_engine::math::Vector2D;
+use gfx_engine::testing::HeadlessGame;
+
+const LEVEL: &str = "assets/levels/world_1_level_1/level.tmx";
+
+/// A floored room with the player standing on it, entered the way the game state manager enters a level.
+fn room() -> (HeadlessGame, u32) {
+    let mut tiles = vec![vec![0; 12]; 6];
+    tiles[5] = vec![1; 12];
+    let mut game = HeadlessGame::with_level(Level::from_tiles(32, 32, tiles));
+    game.world.stats.score = 500;
+    game.world.stats.gold_coin_count = 3;
+    let level_start = game.world.stats.enter_level(true, None);
+    game.spawn_player(Vector2D::new(64.0, 100.0));
+    (game, level_start)
+}
+
+fn request_restart(game: &mut HeadlessGame) {
+    game.world.event_bus.publish(EventLevelRestartRequested { level: LEVEL.to_string() });
+}
+
+/// Steps until gameplay asks for a level load, returning how many ticks that took.
+fn ticks_until_load(game: &mut HeadlessGame) -> u32 {
+    for tick in 0..600 {
+        if game.next_level.is_some() { return tick; }
+        game.step(1);
+    }
+    panic!("No level load was requested");
+}
+
+#[test]
+fn test_restart_irises_out_before_reloading_the_level() {
+    let (mut game, _) = room();
+    request_restart(&mut game);
+    assert!(ticks_until_load(&mut game) > 1, "The old level is not cut away on the spot");
+    assert_eq!(game.next_level.as_deref(), Some(LEVEL));
+    assert!(game.world.level_exit.is_none(), "A restart is not a level exit");
+}
+
+#[test]
+fn test_restart_during_a_goal_iris_out_drops_the_exit() {
+    let (mut game, _) = room();
+    let goal = Entity {
+        r#type: "Goal".to_string(),
+        position: Vector2D::new(64.0, 100.0),
+        properties: [("next_level".to_string(), "assets/levels/level_02.tmx".to_string())].into(),
+        ..Default::default()
+    };
+    gfx_engine::entity_spawner::spawn_entity(&mut game.world, &goal, &game.game_config);
+    game.step(2);
+    assert!(game.world.level_exit.is_some(), "The goal starts the level transition");
+
+    request_restart(&mut game);
+    game.step(1);
+    assert!(game.world.level_exit.is_none(), "The exit's spawn point and carried health are dropped");
+    ticks_until_load(&mut game);
+    assert_eq!(game.next_level.as_deref(), Some(LEVEL), "The restart wins over the goal");
+}
+
+#[test]
+fn test_restart_keeps_lives_and_rolls_coins_and_score_back() {
+    let (mut game, level_start) = room();
+    let lives = game.world.stats.lives;
+    let coin = game.spawn("GoldCoin", Vector2D::new(300.0, 100.0));
+    game.world.event_bus.publish(EventCoinCollected { coin });
+    game.step(1);
+    assert_eq!(game.world.stats.gold_coin_count, 4);
+    assert!(game.world.stats.score > 500);
+
+    request_restart(&mut game);
+    ticks_until_load(&mut game);
+    // The game state manager reloads the level with the count it had on entry.
+    game.world.stats.enter_level(true, Some(level_start));
+
+    assert_eq!(game.world.stats.lives, lives);
+    assert_eq!(game.world.stats.gold_coin_count, 3);
+    assert_eq!(game.world.stats.score, 500);
+    assert_eq!(game.world.stats.level_coins, 0);
+}
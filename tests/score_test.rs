@@ -0,0 +1,66 @@
+use gfx_engine::ecs::component::Gravity;
+use gfx_engine::ecs::event::{EventCoinCollected, EventPlayerEnemyStomped};
+use gfx_engine::ecs::resources::GameStats;
+use gfx_engine::ecs::world::Entity;
+use gfx_engine::level::Level;
+use gfx_engine::math::Vector2D;
+use gfx_engine::testing::HeadlessGame;
+
+const TILE: u32 = 32;
+
+/// A floored room with the player held in the air above it, out of reach of the enemies.
+fn room() -> (HeadlessGame, Entity) {
+    let mut tiles = vec![vec![0; 16]; 6];
+    tiles[5] = vec![1; 16];
+    let mut game = HeadlessGame::with_level(Level::from_tiles(TILE, TILE, tiles));
+    let player = game.spawn_player(Vector2D::new(40.0, -64.0));
+    game.world.gravity_tags.remove(&player);
+    (game, player)
+}
+
+fn stomp_new_enemy(game: &mut HeadlessGame, player: Entity) {
+    let enemy = game.spawn("EnemySpider", Vector2D::new(400.0, 100.0));
+    game.world.event_bus.publish(EventPlayerEnemyStomped { player, enemy });
+    game.step(1);
+}
+
+#[test]
+fn test_coins_score_their_points_and_count_for_the_level() {
+    let (mut game, _) = room();
+    let coin = game.spawn("GoldCoin", Vector2D::new(200.0, 100.0));
+    game.world.event_bus.publish(EventCoinCollected { coin });
+    game.step(1);
+
+    assert_eq!(game.world.stats.score, game.game_config.gameplay.score.coin_points);
+    assert_eq!(game.world.stats.level_coins, 1);
+}
+
+#[test]
+fn test_stomps_before_landing_chain_into_a_combo() {
+    let (mut game, player) = room();
+    let points = game.game_config.gameplay.score.stomp_points;
+    stomp_new_enemy(&mut game, player);
+    stomp_new_enemy(&mut game, player);
+    assert_eq!(game.world.stats.score, points + 2 * points);
+    assert_eq!(game.world.stats.level_kills, 2);
+
+    // Landing ends the chain; the next stomp is worth the base points again.
+    game.world.add_gravity(player, Gravity);
+    game.step(240);
+    assert!(game.world.is_grounded(player));
+    assert!(!game.world.stomp_combos.contains_key(&player));
+    let before = game.world.stats.score;
+    stomp_new_enemy(&mut game, player);
+    assert_eq!(game.world.stats.score - before, points);
+}
+
+#[test]
+fn test_restart_takes_back_the_points_scored_in_the_level() {
+    let mut stats = GameStats { score: 300, ..Default::default() };
+    stats.enter_level(true, None);
+    stats.score = 900;
+    stats.level_coins = 4;
+    stats.enter_level(true, Some(0));
+    assert_eq!(stats.score, 300);
+    assert_eq!(stats.level_coins, 0);
+}
@@ -0,0 +1,71 @@
+use gfx_engine::ecs::component::Invincibility;
+use gfx_engine::ecs::world::Entity;
+use gfx_engine::level::Level;
+use gfx_engine::math::Vector2D;
+use gfx_engine::testing::HeadlessGame;
+
+const TILE: u32 = 32;
+
+fn state_name(game: &HeadlessGame, entity: Entity) -> String {
+    game.world.state_components[&entity].state_machine.current_state.as_ref().unwrap().get_name().to_string()
+}
+
+/// A floored room with the shooter at column 4 and the player five tiles to its right,
+/// either on the floor or hovering high above it when `player_above`.
+fn range(player_above: bool) -> (HeadlessGame, Entity, Entity) {
+    let mut tiles = vec![vec![0; 20]; 10];
+    tiles[9] = vec![1; 20];
+    let mut game = HeadlessGame::with_level(Level::from_tiles(TILE, TILE, tiles));
+    let floor_top = (9 * TILE) as f32;
+    let shooter = game.spawn("EnemyShooter", Vector2D::new((4 * TILE) as f32, floor_top - 12.0));
+    let player_y = if player_above { (4 * TILE) as f32 - 26.0 } else { floor_top - 26.0 };
+    let player = game.spawn_player(Vector2D::new((9 * TILE) as f32, player_y));
+    if player_above { game.world.gravity_tags.remove(&player); }
+    (game, shooter, player)
+}
+
+/// Steps until the shooter's first shot is in the air and returns it.
+fn first_shot(game: &mut HeadlessGame, shooter: Entity) -> Entity {
+    for _ in 0..600 {
+        game.step(1);
+        if let Some((&shot, _)) = game.world.projectiles.iter().find(|(_, p)| p.owner == Some(shooter)) { return shot; }
+    }
+    panic!("The shooter never fired");
+}
+
+#[test]
+fn test_shooter_halts_and_fires_aimed_shots() {
+    let (mut game, shooter, _) = range(true);
+    let shot = first_shot(&mut game, shooter);
+
+    assert_eq!(state_name(&game, shooter), "ShooterState");
+    assert!(game.world.velocities[&shooter].0.x.abs() < 1.0, "It stands still to shoot");
+    let velocity = game.world.velocities[&shot].0;
+    assert!(velocity.x > 0.0 && velocity.y < 0.0, "Aimed up and right at the player above");
+    let speed = game.game_config.enemy_behavior["shooter"].projectile_speed;
+    assert!(((velocity.x * velocity.x + velocity.y * velocity.y).sqrt() - speed).abs() < 1.0);
+    assert!(!game.world.projectiles[&shot].from_player);
+}
+
+#[test]
+fn test_shots_hurt_the_player_but_never_their_shooter() {
+    let (mut game, shooter, player) = range(false);
+    let max = game.world.healths[&player].max;
+    first_shot(&mut game, shooter);
+    game.step(240);
+
+    assert!(game.world.healths[&player].current < max, "The shot reached the player");
+    assert!(game.world.enemy_tags.contains_key(&shooter), "The shooter survived standing inside its own shot");
+}
+
+#[test]
+fn test_invincible_players_shrug_off_shots() {
+    let (mut game, shooter, player) = range(false);
+    let max = game.world.healths[&player].max;
+    game.world.add_invincibility(player, Invincibility { timer: 999.0 });
+    let shot = first_shot(&mut game, shooter);
+    game.step(240);
+
+    assert_eq!(game.world.healths[&player].current, max);
+    assert!(!game.world.projectiles.contains_key(&shot), "The shot is still spent on the player");
+}
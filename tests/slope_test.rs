@@ -0,0 +1,68 @@
+use gfx_engine::ecs::world::Entity;
+use gfx_engine::input::{InputAction, InputState};
+use gfx_engine::level::{Level, TileKind};
+use gfx_engine::math::Vector2D;
+use gfx_engine::testing::HeadlessGame;
+
+const TILE: f32 = 32.0;
+const SLOPE_COLUMN: usize = 8;
+
+/// Ground along row 9, a 45° ramp at column 8 and a plateau one tile higher from column 9 on.
+fn ramp_level() -> Level {
+    let mut tiles = vec![vec![0; 20]; 10];
+    tiles[9] = vec![1; 20];
+    for tile in &mut tiles[8][SLOPE_COLUMN + 1..] { *tile = 1; }
+    let mut level = Level::from_tiles(32, 32, tiles);
+    level.collision.tiles[8][SLOPE_COLUMN] = TileKind::Slope { rises_right: true, base: 0.0, height: 1.0 };
+    level
+}
+
+fn center_on_slope(game: &HeadlessGame, player: Entity) -> bool {
+    let center = game.world.positions[&player].0.x + game.game_config.player.width as f32 / 2.0;
+    (center / TILE) as usize == SLOPE_COLUMN
+}
+
+#[test]
+fn test_walking_up_a_slope_keeps_momentum_and_reaches_the_plateau() {
+    let mut game = HeadlessGame::with_level(ramp_level());
+    let height = game.game_config.player.height as f32;
+    let player = game.spawn_player(Vector2D::new(64.0, 9.0 * TILE - height));
+    game.step(30);
+    game.input_state = InputState::from_actions(vec![InputAction::MoveRight]);
+
+    let mut last_x = game.world.positions[&player].0.x;
+    for _ in 0..240 {
+        game.step(1);
+        let x = game.world.positions[&player].0.x;
+        if center_on_slope(&game, player) {
+            assert!(x > last_x, "Horizontal movement never stalls on the ramp");
+            assert!(game.world.wall_hits.get(&player).is_none(), "The slope face is not a wall");
+        }
+        last_x = x;
+    }
+
+    let feet = game.world.positions[&player].0.y + height;
+    assert!(last_x > 12.0 * TILE, "Player crossed the ramp (x = {})", last_x);
+    assert!((feet - 8.0 * TILE).abs() <= 1.0, "Feet rest on the plateau at {} (got {})", 8.0 * TILE, feet);
+}
+
+#[test]
+fn test_walking_down_a_slope_stays_grounded() {
+    let mut game = HeadlessGame::with_level(ramp_level());
+    let height = game.game_config.player.height as f32;
+    let player = game.spawn_player(Vector2D::new(12.0 * TILE, 8.0 * TILE - height));
+    game.step(30);
+    game.input_state = InputState::from_actions(vec![InputAction::MoveLeft]);
+
+    let mut ticks_on_slope = 0;
+    for _ in 0..240 {
+        game.step(1);
+        if center_on_slope(&game, player) {
+            ticks_on_slope += 1;
+            assert!(game.world.is_grounded(player), "No fall-land stutter while descending");
+        }
+    }
+    assert!(ticks_on_slope > 0, "The walk passed over the slope");
+    let feet = game.world.positions[&player].0.y + height;
+    assert!((feet - 9.0 * TILE).abs() <= 1.0, "Player ends on the lower floor");
+}
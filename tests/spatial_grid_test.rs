@@ -0,0 +1,59 @@
+use gfx_engine::ecs::resources::SpatialGrid;
+use gfx_engine::ecs::world::{Entity, World};
+use sdl3::rect::Rect;
+
+fn near(grid: &SpatialGrid, rect: Rect) -> Vec<Entity> {
+    let mut found: Vec<Entity> = grid.query_near(rect).collect();
+    found.sort();
+    found
+}
+
+#[test]
+fn test_only_nearby_entities_are_returned() {
+    let mut world = World::new();
+    let mut grid = SpatialGrid::new(32.0);
+    let cluster: Vec<Entity> = (0..3).map(|i| {
+        let entity = world.create_entity();
+        grid.insert(entity, Rect::new(100 + i * 8, 100, 16, 16));
+        entity
+    }).collect();
+    let distant = world.create_entity();
+    grid.insert(distant, Rect::new(2000, 1500, 16, 16));
+
+    assert_eq!(near(&grid, Rect::new(104, 104, 8, 8)), cluster);
+    assert_eq!(near(&grid, Rect::new(1990, 1490, 32, 32)), vec![distant]);
+    assert!(near(&grid, Rect::new(600, 600, 16, 16)).is_empty(), "Empty space has no candidates");
+}
+
+#[test]
+fn test_entities_spanning_cells_are_reported_once() {
+    let mut world = World::new();
+    let mut grid = SpatialGrid::new(32.0);
+    let wide = world.create_entity();
+    grid.insert(wide, Rect::new(10, 10, 100, 70));
+
+    assert_eq!(near(&grid, Rect::new(0, 0, 200, 200)), vec![wide]);
+    assert_eq!(near(&grid, Rect::new(90, 60, 4, 4)), vec![wide], "Found from a cell it only partly covers");
+}
+
+#[test]
+fn test_cell_edges_are_exclusive() {
+    let mut world = World::new();
+    let mut grid = SpatialGrid::new(32.0);
+    let entity = world.create_entity();
+    grid.insert(entity, Rect::new(0, 0, 32, 32));
+
+    assert!(near(&grid, Rect::new(32, 0, 8, 8)).is_empty(), "Touching the next cell's edge is not overlapping it");
+    assert_eq!(near(&grid, Rect::new(-8, -8, 10, 10)), vec![entity], "Negative coordinates hash correctly");
+}
+
+#[test]
+fn test_clear_empties_the_grid() {
+    let mut world = World::new();
+    let mut grid = SpatialGrid::new(32.0);
+    grid.insert(world.create_entity(), Rect::new(0, 0, 16, 16));
+    assert_eq!(grid.len(), 1);
+    grid.clear();
+    assert!(grid.is_empty());
+    assert!(near(&grid, Rect::new(0, 0, 16, 16)).is_empty());
+}
@@ -0,0 +1,85 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use gfx_engine::ecs::component::StateComponent;
+use gfx_engine::ecs::systems::SystemContext;
+use gfx_engine::ecs::world::{Entity, World};
+use gfx_engine::input::{InputAction, InputState};
+use gfx_engine::level::Level;
+use gfx_engine::math::Vector2D;
+use gfx_engine::state_machine::{State, StateMachine};
+use gfx_engine::testing::HeadlessGame;
+
+const TILE: u32 = 32;
+
+type Log = Rc<RefCell<Vec<String>>>;
+
+/// A state that records its calls; it moves on every tick along A -> B -> C, where A and B share the parent P.
+struct Logged { name: &'static str, parent: Option<&'static str>, log: Log }
+
+impl Logged {
+    fn boxed(name: &'static str, parent: Option<&'static str>, log: &Log) -> Box<dyn State> {
+        Box::new(Logged { name, parent, log: log.clone() })
+    }
+}
+
+impl State for Logged {
+    fn enter(&mut self) { self.log.borrow_mut().push(format!("enter {}", self.name)); }
+    fn exit(&mut self) { self.log.borrow_mut().push(format!("exit {}", self.name)); }
+    fn update_with_context(&mut self, _world: &mut World, _context: &mut SystemContext, _entity: Entity) {
+        self.log.borrow_mut().push(format!("update {}", self.name));
+    }
+    fn transition_with_context(&mut self, _world: &mut World, _context: &mut SystemContext, _entity: Entity) -> Option<Box<dyn State>> {
+        match self.name {
+            "A" => Some(Logged::boxed("B", Some("P"), &self.log)),
+            "B" => Some(Logged::boxed("C", None, &self.log)),
+            _ => None,
+        }
+    }
+    fn get_name(&self) -> &str { self.name }
+    fn parent(&self) -> Option<Box<dyn State>> { self.parent.map(|name| Logged::boxed(name, None, &self.log)) }
+}
+
+fn state_names(game: &HeadlessGame, entity: Entity) -> (Vec<String>, String) {
+    let machine = &game.world.state_components[&entity].state_machine;
+    let parents = machine.parents.iter().map(|p| p.get_name().to_string()).collect();
+    (parents, machine.current_state.as_ref().unwrap().get_name().to_string())
+}
+
+#[test]
+fn test_parents_run_first_and_stay_active_between_siblings() {
+    let mut game = HeadlessGame::with_level(Level::from_tiles(TILE, TILE, vec![vec![0; 4]; 4]));
+    let log = Log::default();
+    let entity = game.world.create_entity();
+    let state_machine = StateMachine::new(Logged { name: "A", parent: Some("P"), log: log.clone() });
+    game.world.add_state_component(entity, StateComponent { state_machine });
+    assert_eq!(*log.borrow(), ["enter P", "enter A"]);
+
+    log.borrow_mut().clear();
+    game.step(1);
+    assert_eq!(*log.borrow(), ["update P", "update A", "exit A", "enter B"], "The shared parent is not re-entered");
+
+    log.borrow_mut().clear();
+    game.step(1);
+    assert_eq!(*log.borrow(), ["update P", "update B", "exit B", "exit P", "enter C"]);
+    assert_eq!(state_names(&game, entity), (vec![], "C".to_string()));
+}
+
+#[test]
+fn test_player_states_sit_under_grounded_and_airborne_parents() {
+    let mut tiles = vec![vec![0; 8]; 8];
+    tiles[7] = vec![1; 8];
+    let mut game = HeadlessGame::with_level(Level::from_tiles(TILE, TILE, tiles));
+    let player = game.spawn_player(Vector2D::new(64.0, (7 * TILE) as f32 - game.game_config.player.height as f32));
+    game.step(10);
+    assert_eq!(state_names(&game, player), (vec!["GroundedState".to_string()], "IdleState".to_string()));
+
+    game.input_state = InputState::from_actions(vec![InputAction::MoveRight]);
+    game.step(10);
+    assert_eq!(state_names(&game, player), (vec!["GroundedState".to_string()], "WalkingState".to_string()));
+
+    let mut jump = InputState::from_actions(vec![InputAction::Jump]);
+    jump.calculate_deltas(&game.input_state);
+    game.input_state = jump;
+    game.step(2);
+    assert_eq!(state_names(&game, player), (vec!["AirborneState".to_string()], "JumpingState".to_string()));
+}
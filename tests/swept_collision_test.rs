@@ -0,0 +1,59 @@
+use gfx_engine::ecs::component::{Collision, Position, Velocity};
+use gfx_engine::level::Level;
+use gfx_engine::math::Vector2D;
+use gfx_engine::testing::HeadlessGame;
+
+const TILE: u32 = 32;
+
+/// An open shaft with a one-tile-thick floor at row 8 and the real ground far below it.
+fn thin_floor() -> HeadlessGame {
+    let mut tiles = vec![vec![0; 10]; 20];
+    tiles[8] = vec![1; 10];
+    tiles[19] = vec![1; 10];
+    HeadlessGame::with_level(Level::from_tiles(TILE, TILE, tiles))
+}
+
+#[test]
+fn test_fast_falls_stop_on_a_thin_floor() {
+    let mut game = thin_floor();
+    // Far more than one tile per tick: 6000 px/s is 50 px at 120 Hz.
+    game.config.physics.entity_max_fall_speed = 6000.0;
+    let height = game.game_config.player.height as f32;
+    let player = game.spawn_player(Vector2D::new((4 * TILE) as f32, 40.0));
+    game.world.velocities.get_mut(&player).unwrap().0.y = 6000.0;
+    game.step(10);
+
+    let feet = game.world.positions[&player].0.y + height;
+    assert_eq!(feet, (8 * TILE) as f32, "Landed on top of the thin floor instead of falling through it");
+    assert!(game.world.grounded_tags.contains_key(&player));
+}
+
+#[test]
+fn test_fast_jumps_bonk_on_a_thin_ceiling() {
+    let mut game = thin_floor();
+    let player = game.spawn_player(Vector2D::new((4 * TILE) as f32, (12 * TILE) as f32));
+    game.world.velocities.get_mut(&player).unwrap().0.y = -6000.0;
+    // The second tick would carry the head from row 10 to row 8, past the gap in row 9.
+    game.step(2);
+
+    assert_eq!(game.world.positions[&player].0.y, (9 * TILE) as f32, "Stopped just below the ceiling");
+    assert!(game.world.velocities[&player].0.y >= 0.0);
+}
+
+#[test]
+fn test_fast_dashes_stop_at_a_thin_wall() {
+    let mut tiles = vec![vec![0; 20]; 6];
+    for row in tiles.iter_mut() { row[10] = 1; }
+    let mut game = HeadlessGame::with_level(Level::from_tiles(TILE, TILE, tiles));
+
+    // A bare moving box: no gravity or input, just velocity against the tiles.
+    let dart = game.world.create_entity();
+    game.world.add_position(dart, Position(Vector2D::new(64.0, 64.0)));
+    game.world.add_velocity(dart, Velocity(Vector2D::new(6000.0, 0.0)));
+    game.world.add_collision(dart, Collision { rect: sdl3::rect::Rect::new(64, 64, 8, 8) });
+    game.step(10);
+
+    let right = game.world.positions[&dart].0.x + 8.0;
+    assert!(right <= (10 * TILE) as f32 && right > (10 * TILE) as f32 - 1.0, "Stopped at the wall's face (right edge at {})", right);
+    assert_eq!(game.world.velocities[&dart].0.x, 0.0, "The wall absorbed the dash");
+}
@@ -0,0 +1,36 @@
+use gfx_engine::renderer::{aligned_x, TextAlign, TextCache, TextKey};
+
+fn key(text: &str) -> TextKey {
+    TextKey { font: "debug".to_string(), size_bits: 24.0f32.to_bits(), text: text.to_string() }
+}
+
+#[test]
+fn test_text_cache_evicts_the_least_recently_used_line() {
+    let mut cache = TextCache::new(2);
+    assert_eq!(cache.insert(key("Frame"), 1), None);
+    assert_eq!(cache.insert(key("FPS"), 2), None);
+
+    // Touching "Frame" makes "FPS" the oldest entry.
+    assert_eq!(cache.get(&key("Frame")).copied(), Some(1));
+    assert_eq!(cache.insert(key("Score"), 3), Some(2), "The evicted value is handed back for cleanup");
+    assert_eq!(cache.len(), 2);
+    assert!(cache.get(&key("FPS")).is_none());
+}
+
+#[test]
+fn test_text_cache_keys_include_the_size() {
+    let mut cache = TextCache::new(4);
+    cache.insert(key("HUD"), 1);
+    let larger = TextKey { size_bits: 32.0f32.to_bits(), ..key("HUD") };
+    assert!(cache.get(&larger).is_none());
+    assert_eq!(cache.insert(key("HUD"), 5), Some(1), "Replacing a line returns the old texture");
+}
+
+#[test]
+fn test_each_line_is_aligned_on_the_anchor() {
+    // A two-line centered block at x = 100: both lines straddle the anchor.
+    assert_eq!(aligned_x(TextAlign::Center, 100, 80), 60);
+    assert_eq!(aligned_x(TextAlign::Center, 100, 40), 80);
+    assert_eq!(aligned_x(TextAlign::Right, 100, 40), 60);
+    assert_eq!(aligned_x(TextAlign::Left, 100, 40), 100);
+}
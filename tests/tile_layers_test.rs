@@ -0,0 +1,33 @@
+use gfx_engine::level::{load_level, TileKind, BACKGROUND_LAYER_Z, FOREGROUND_LAYER_Z};
+
+const FIXTURE: &str = "tests/fixtures/layered_map.tmx";
+
+/// Background, Main (flagged `collision = true`), Foreground and an Overlay with an explicit `z_index`.
+#[test]
+fn test_layers_keep_document_order_depth_and_opacity() {
+    let level = load_level(FIXTURE).unwrap();
+    let layers: Vec<(&str, u8, f32)> = level.map.layers.iter().map(|l| (l.name.as_str(), l.z_index, l.opacity)).collect();
+    assert_eq!(layers, vec![
+        ("Background", BACKGROUND_LAYER_Z, 0.5),
+        ("Main", BACKGROUND_LAYER_Z, 1.0),
+        ("Foreground", FOREGROUND_LAYER_Z, 1.0),
+        ("Overlay", 120, 1.0),
+    ], "Layers after the collision layer default to the foreground; a z_index property wins");
+}
+
+#[test]
+fn test_collision_comes_only_from_the_collision_layer() {
+    let level = load_level(FIXTURE).unwrap();
+    assert_eq!(level.map.collision_layer, 1);
+    let solid: Vec<Vec<bool>> = level.collision.tiles.iter().map(|row| row.iter().map(|t| *t == TileKind::Solid).collect()).collect();
+    assert_eq!(solid[3], [true; 4], "The Main layer's floor is solid");
+    assert!(solid[0..3].iter().all(|row| row.iter().all(|&t| !t)), "Tiles on the other layers are decoration only");
+}
+
+#[test]
+fn test_single_layer_level_collides_and_draws_behind_entities() {
+    let level = load_level("tests/fixtures/object_map.tmx").unwrap();
+    assert_eq!(level.map.layers.len(), 1);
+    assert_eq!((level.map.layers[0].z_index, level.map.layers[0].opacity), (BACKGROUND_LAYER_Z, 1.0));
+    assert!(level.collision.tiles[3].iter().all(|t| *t == TileKind::Solid));
+}
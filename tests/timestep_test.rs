@@ -0,0 +1,68 @@
+use gfx_engine::timestep::FixedTimestep;
+use gfx_engine::config::load_config;
+use gfx_engine::level::Level;
+use gfx_engine::math::Vector2D;
+use gfx_engine::testing::HeadlessGame;
+
+const STEP: f32 = 1.0 / 120.0;
+const TILE: u32 = 32;
+
+#[test]
+fn test_large_spike_is_clamped() {
+    let config = load_config().unwrap();
+    let mut timestep = FixedTimestep::new(STEP, config.physics.max_delta_time);
+
+    // A 2-second stall must only advance the simulation by `max_delta_time`.
+    let steps = timestep.advance(2.0);
+    let simulated = steps as f32 * STEP;
+    assert!(simulated <= config.physics.max_delta_time + 1e-4, "Simulated {}s after a 2s spike", simulated);
+    assert!(timestep.alpha() < 1.0);
+}
+
+#[test]
+fn test_spike_does_not_tunnel_player() {
+    // A single-tile floor with nothing below it, and a player just above it at terminal velocity.
+    let mut tiles = vec![vec![0; 8]; 12];
+    tiles[6] = vec![1; 8];
+    let mut game = HeadlessGame::with_level(Level::from_tiles(TILE, TILE, tiles));
+    let floor_top = (6 * TILE) as f32;
+    let height = game.game_config.player.height as f32;
+    let player = game.spawn_player(Vector2D::new(64.0, floor_top - height - 8.0));
+    game.world.velocities.get_mut(&player).unwrap().0.y = game.config.physics.entity_max_fall_speed;
+
+    // A 2-second stall, then a few normal frames to settle.
+    let mut timestep = FixedTimestep::new(STEP, game.config.physics.max_delta_time);
+    let steps = timestep.advance(2.0);
+    game.step(steps);
+    game.step(timestep.advance(1.0 / 60.0));
+
+    let bottom = game.world.positions[&player].0.y + height;
+    assert!((bottom - floor_top).abs() < 1.0, "The player rests on the floor, bottom at {}px", bottom);
+    assert!(game.world.is_grounded(player));
+}
+
+#[test]
+fn test_steady_frames_consume_accumulator() {
+    let mut timestep = FixedTimestep::new(STEP, 0.25);
+    let total: u32 = (0..120).map(|_| timestep.advance(1.0 / 60.0)).sum();
+    assert!((239..=240).contains(&total), "60 FPS for 2s yields ~240 steps, got {}", total);
+}
+
+#[test]
+fn test_repeated_stalls_never_exceed_the_step_cap() {
+    let config = load_config().unwrap();
+    let mut timestep = FixedTimestep::new(STEP, config.physics.max_delta_time);
+    let cap = timestep.max_steps_per_frame();
+    assert!(cap >= 1);
+    for _ in 0..50 {
+        let steps = timestep.advance(0.5);
+        assert!(steps <= cap, "{} steps after a stall, cap is {}", steps, cap);
+    }
+}
+
+#[test]
+fn test_alpha_is_the_leftover_fraction_of_a_step() {
+    let mut timestep = FixedTimestep::new(STEP, 0.25);
+    assert_eq!(timestep.advance(STEP * 2.5), 2);
+    assert!((timestep.alpha() - 0.5).abs() < 1e-3, "Half a step left over, alpha = {}", timestep.alpha());
+}
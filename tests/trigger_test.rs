@@ -0,0 +1,60 @@
+use gfx_engine::level::{Entity, Level};
+use gfx_engine::math::Vector2D;
+use gfx_engine::testing::HeadlessGame;
+
+const TILE: u32 = 32;
+
+/// A floored room with a one-shot ambush trigger that spawns a spider at a marker.
+fn ambush_level() -> Level {
+    let mut tiles = vec![vec![0; 16]; 6];
+    tiles[5] = vec![1; 16];
+    let mut level = Level::from_tiles(TILE, TILE, tiles);
+    level.entities.push(Entity {
+        r#type: "Trigger".to_string(),
+        position: Vector2D::new(128.0, 0.0),
+        size: Some((32.0, 160.0)),
+        properties: [
+            ("event", "ambush"), ("once", "true"), ("shake", "0.4"),
+            ("spawn", "EnemySpider"), ("spawn_at", "ambush_drop"),
+        ].into_iter().map(|(k, v)| (k.to_string(), v.to_string())).collect(),
+        ..Default::default()
+    });
+    level.entities.push(Entity {
+        r#type: "Marker".to_string(),
+        name: Some("ambush_drop".to_string()),
+        position: Vector2D::new(448.0, 64.0),
+        ..Default::default()
+    });
+    level
+}
+
+/// Spawns the level's objects and a player, then walks the player into, out of and back into the trigger.
+fn play_through(game: &mut HeadlessGame) -> usize {
+    let level = game.level.clone();
+    gfx_engine::entity_spawner::spawn_entities(&mut game.world, &level, &game.game_config);
+    let floor_top = (5 * TILE) as f32;
+    let player = game.spawn_player(Vector2D::new(32.0, floor_top - game.game_config.player.height as f32));
+    for x in [136.0, 32.0, 136.0, 32.0, 136.0] {
+        game.world.positions.get_mut(&player).unwrap().0.x = x;
+        game.step(2);
+    }
+    game.world.enemy_tags.len()
+}
+
+#[test]
+fn test_once_trigger_fires_exactly_once() {
+    let mut game = HeadlessGame::with_level(ambush_level());
+    assert_eq!(play_through(&mut game), 1, "Re-entering a consumed trigger spawns nothing");
+    assert!(game.world.triggers.values().all(|t| t.consumed));
+}
+
+#[test]
+fn test_once_trigger_rearms_on_level_reload() {
+    let mut game = HeadlessGame::with_level(ambush_level());
+    play_through(&mut game);
+
+    // Reloading rebuilds the world from the level, so the trigger starts fresh.
+    game.world = gfx_engine::ecs::world::World::new();
+    game.world.game_state = gfx_engine::ecs::resources::GameState::Playing;
+    assert_eq!(play_through(&mut game), 1);
+}
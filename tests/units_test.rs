@@ -0,0 +1,83 @@
+use gfx_engine::config::Config;
+use gfx_engine::level::Level;
+use gfx_engine::math::Vector2D;
+use gfx_engine::testing::HeadlessGame;
+use gfx_engine::units::{Units, ViewTransform};
+
+#[test]
+fn test_world_screen_round_trip() {
+    let view = ViewTransform::default();
+    let world = 123.5;
+    let screen = view.world_to_screen(world, 0.5);
+    assert_eq!(screen, world * view.scale * 0.5);
+    assert_eq!(view.screen_to_world(screen, 0.5), world);
+}
+
+#[test]
+fn test_shipped_window_fills_the_screen_at_4x() {
+    let config = Config::default();
+    let view = ViewTransform::for_window(&config.window, (config.window.width, config.window.height));
+    assert_eq!(view.scale, 4.0);
+    assert_eq!(view.offset, (0, 0), "No bars when the window is an exact multiple");
+    assert_eq!(view.canvas_size, (config.window.width, config.window.height));
+}
+
+#[test]
+fn test_integer_scale_letterboxes_the_remainder() {
+    // 16:10 at 4x leaves 60px bars top and bottom.
+    let tall = ViewTransform::fit((1920, 1200), (480, 270), true);
+    assert_eq!((tall.scale, tall.offset, tall.canvas_size), (4.0, (0, 60), (1920, 1080)));
+
+    // 1366x768 fits 2x; the rest is split evenly on every side.
+    let laptop = ViewTransform::fit((1366, 768), (480, 270), true);
+    assert_eq!((laptop.scale, laptop.offset, laptop.canvas_size), (2.0, (203, 114), (960, 540)));
+
+    // Ultrawide: pillarbox bars left and right.
+    let wide = ViewTransform::fit((3440, 1440), (480, 270), true);
+    assert_eq!((wide.scale, wide.offset), (5.0, (520, 45)));
+}
+
+#[test]
+fn test_fractional_scaling_fills_one_axis() {
+    let view = ViewTransform::fit((1366, 768), (480, 270), false);
+    assert!((view.scale - 768.0 / 270.0).abs() < 1e-4);
+    assert_eq!(view.canvas_size.1, 768, "The limiting axis has no bars");
+    assert_eq!(view.offset.1, 0);
+}
+
+#[test]
+fn test_tiny_windows_still_draw_at_1x() {
+    let view = ViewTransform::fit((320, 200), (480, 270), true);
+    assert_eq!(view.scale, 1.0);
+    assert_eq!(view.offset, (-80, -35), "The canvas stays centered and its edges are cropped");
+}
+
+#[test]
+fn test_pointer_maps_onto_the_canvas() {
+    let view = ViewTransform::fit((1920, 1200), (480, 270), true);
+    assert_eq!(view.output_to_canvas(100.0, 60.0), (100.0, 0.0));
+    assert_eq!(view.output_to_canvas(960.0, 10.0), (960.0, -50.0), "Clicks on a bar land outside the canvas");
+}
+
+#[test]
+fn test_camera_and_units_agree_on_viewport() {
+    let mut game = HeadlessGame::with_level(Level::default());
+    let expected = Units::viewport_in_world(&game.config.window, 1.0);
+    assert_eq!((game.camera.view_width(), game.camera.view_height()), expected);
+
+    game.camera.zoom = 0.5;
+    let expected = Units::viewport_in_world(&game.config.window, 0.5);
+    assert_eq!((game.camera.view_width(), game.camera.view_height()), expected);
+}
+
+#[test]
+fn test_dormancy_uses_zoomed_viewport() {
+    let mut game = HeadlessGame::with_level(Level::default());
+    game.camera.zoom = 0.5;
+    // Inside the 1.5-viewport margin of the zoomed-out view, but outside it for an unzoomed view.
+    let x = game.camera.position.x + game.camera.view_width() * 2.4;
+    let coin = game.spawn("GoldCoin", Vector2D::new(x, 0.0));
+
+    game.step(1);
+    assert!(!game.world.is_dormant(coin));
+}